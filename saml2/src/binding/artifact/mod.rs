@@ -0,0 +1,366 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc, sync::Mutex};
+
+use aes_gcm::aead::{rand_core::RngCore, OsRng};
+use base64::{prelude::BASE64_STANDARD, Engine};
+use chrono::{DateTime, Duration, Utc};
+use sha1::{Digest, Sha1};
+
+use crate::{
+    core::{
+        artifact_resolve::ArtifactResolve, artifact_response::ArtifactResponse,
+        request_abstract_type::RequestAbstractType, saml_version::SAMLVersion, status::Status,
+        status_code::StatusCode, status_response_type::StatusResponseType,
+    },
+    error::SAMLError,
+    util::{generate_id, InputStream},
+    xml::XmlObject,
+};
+
+/// the URI identifying the HTTP-Artifact binding, e.g. in metadata endpoints
+pub const HTTP_ARTIFACT_BINDING: &str = "urn:oasis:names:tc:SAML:2.0:bindings:HTTP-Artifact";
+
+/// A SAML 2.0 type 0x0004 artifact: the type code, the index of the artifact resolution
+/// endpoint, the SHA-1 of the issuer entity ID and a random message handle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtifactType0004 {
+    endpoint_index: u16,
+    source_id: [u8; 20],
+    message_handle: [u8; 20],
+}
+
+impl ArtifactType0004 {
+    pub const TYPE_CODE: [u8; 2] = [0x00, 0x04];
+    const LENGTH: usize = 44;
+
+    /// a new artifact of `entity_id` with a random message handle
+    pub fn new(entity_id: &str, endpoint_index: u16) -> Self {
+        let mut message_handle = [0u8; 20];
+        OsRng.fill_bytes(&mut message_handle);
+        ArtifactType0004 {
+            endpoint_index,
+            source_id: ArtifactType0004::source_id_of(entity_id),
+            message_handle,
+        }
+    }
+
+    /// the SourceID of an issuer, the SHA-1 of its entity ID
+    pub fn source_id_of(entity_id: &str) -> [u8; 20] {
+        Sha1::digest(entity_id.as_bytes()).into()
+    }
+
+    #[inline]
+    pub fn endpoint_index(&self) -> u16 {
+        self.endpoint_index
+    }
+
+    #[inline]
+    pub fn source_id(&self) -> &[u8; 20] {
+        &self.source_id
+    }
+
+    #[inline]
+    pub fn message_handle(&self) -> &[u8; 20] {
+        &self.message_handle
+    }
+
+    /// the base64 form carried by `SAMLart` and `saml2p:Artifact`
+    pub fn encode(&self) -> String {
+        let mut octets = Vec::with_capacity(ArtifactType0004::LENGTH);
+        octets.extend_from_slice(&ArtifactType0004::TYPE_CODE);
+        octets.extend_from_slice(&self.endpoint_index.to_be_bytes());
+        octets.extend_from_slice(&self.source_id);
+        octets.extend_from_slice(&self.message_handle);
+        BASE64_STANDARD.encode(octets)
+    }
+
+    pub fn decode(artifact: &str) -> Result<Self, SAMLError> {
+        let octets = BASE64_STANDARD.decode(artifact.trim()).map_err(|_| {
+            SAMLError::MessageDecodingError("invalid artifact encoding".to_string())
+        })?;
+        if octets.len() != ArtifactType0004::LENGTH {
+            return Err(SAMLError::MessageDecodingError(format!(
+                "artifact must be {} bytes",
+                ArtifactType0004::LENGTH
+            )));
+        }
+        if octets[..2] != ArtifactType0004::TYPE_CODE {
+            return Err(SAMLError::MessageDecodingError(format!(
+                "unsupported artifact type 0x{:02x}{:02x}",
+                octets[0], octets[1]
+            )));
+        }
+        let mut source_id = [0u8; 20];
+        source_id.copy_from_slice(&octets[4..24]);
+        let mut message_handle = [0u8; 20];
+        message_handle.copy_from_slice(&octets[24..44]);
+        Ok(ArtifactType0004 {
+            endpoint_index: u16::from_be_bytes([octets[2], octets[3]]),
+            source_id,
+            message_handle,
+        })
+    }
+}
+
+/// a message waiting to be resolved, kept serialized
+#[derive(Debug, Clone)]
+pub struct ArtifactEntry {
+    message: String,
+    relying_party: Option<String>,
+    expires_at: DateTime<Utc>,
+}
+
+impl ArtifactEntry {
+    pub fn new(message: String, relying_party: Option<String>, expires_at: DateTime<Utc>) -> Self {
+        ArtifactEntry {
+            message,
+            relying_party,
+            expires_at,
+        }
+    }
+
+    #[inline]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// the entity the message was issued to, only it may resolve the artifact
+    #[inline]
+    pub fn relying_party(&self) -> Option<&String> {
+        self.relying_party.as_ref()
+    }
+
+    #[inline]
+    pub fn expires_at(&self) -> &DateTime<Utc> {
+        &self.expires_at
+    }
+
+    /// whether `requester` may resolve the artifact, any requester may if the message was
+    /// not issued to a particular relying party
+    pub fn may_be_resolved_by(&self, requester: Option<&str>) -> bool {
+        self.relying_party.is_none() || self.relying_party.as_deref() == requester
+    }
+}
+
+/// Keeps the messages of issued artifacts until they are resolved.
+///
+/// An artifact resolves at most once, `take` removes the entry it returns.
+pub trait ArtifactStore {
+    fn put(&self, artifact: String, entry: ArtifactEntry) -> Result<(), SAMLError>;
+
+    /// Remove and return the entry of `artifact` if `requester` may resolve it, see
+    /// [`ArtifactEntry::may_be_resolved_by`].
+    ///
+    /// The entry is kept for its relying party when another requester asks for it, so
+    /// that guessing an artifact cannot spend it. An expired entry is never returned.
+    fn take(&self, artifact: &str, requester: Option<&str>) -> Option<ArtifactEntry>;
+}
+
+/// an [`ArtifactStore`] local to the process
+#[derive(Debug, Default)]
+pub struct InMemoryArtifactStore {
+    entries: Mutex<HashMap<String, ArtifactEntry>>,
+}
+
+impl ArtifactStore for InMemoryArtifactStore {
+    fn put(&self, artifact: String, entry: ArtifactEntry) -> Result<(), SAMLError> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Utc::now();
+        entries.retain(|_, entry| entry.expires_at > now);
+        if entries.contains_key(&artifact) {
+            return Err(SAMLError::MessageEncodingError(
+                "artifact is already issued".to_string(),
+            ));
+        }
+        entries.insert(artifact, entry);
+        Ok(())
+    }
+
+    fn take(&self, artifact: &str, requester: Option<&str>) -> Option<ArtifactEntry> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let entry = entries.get(artifact)?;
+        if entry.expires_at <= Utc::now() {
+            entries.remove(artifact);
+            return None;
+        }
+        if !entry.may_be_resolved_by(requester) {
+            return None;
+        }
+        entries.remove(artifact)
+    }
+}
+
+/// Issue an artifact of `entity_id` standing for `message` for `lifetime`, to be resolved
+/// at the artifact resolution endpoint of `endpoint_index`.
+pub fn issue_artifact(
+    store: &dyn ArtifactStore,
+    entity_id: &str,
+    endpoint_index: u16,
+    message: XmlObject,
+    relying_party: Option<String>,
+    lifetime: Duration,
+) -> Result<String, SAMLError> {
+    let message = XmlObject::write_xml(Rc::new(RefCell::new(message)))
+        .map_err(|_| SAMLError::MessageEncodingError("invalid xml format!".to_string()))?;
+    let artifact = ArtifactType0004::new(entity_id, endpoint_index).encode();
+    store.put(
+        artifact.clone(),
+        ArtifactEntry::new(message, relying_party, Utc::now() + lifetime),
+    )?;
+    Ok(artifact)
+}
+
+/// Answer `artifact_resolve` from `store` for `requester`.
+///
+/// `requester` is the entityID the requester has been authenticated as, e.g. by its TLS
+/// client certificate or by the signature of `artifact_resolve`, and `None` when it has
+/// not been authenticated. The `Issuer` of `artifact_resolve` is not used in its place,
+/// as anyone can put any entityID there.
+///
+/// An unknown, expired or already resolved artifact, as well as one issued to another
+/// relying party, yields a successful response without a message, as the binding requires.
+pub fn resolve_artifact(
+    store: &dyn ArtifactStore,
+    artifact_resolve: &ArtifactResolve,
+    requester: Option<&str>,
+) -> Result<ArtifactResponse, SAMLError> {
+    let mut artifact_response = ArtifactResponse::default();
+    artifact_response.set_id(generate_id());
+    artifact_response.set_version(SAMLVersion::new(2, 0));
+    artifact_response.set_issue_instant(Utc::now());
+    artifact_response.set_in_response_to(Some(artifact_resolve.id().to_string()));
+    let mut status_code = StatusCode::default();
+    status_code.set_value(StatusCode::SUCCESS.to_string());
+    let mut status = Status::default();
+    status.set_status_code(status_code);
+    artifact_response.set_status(status);
+
+    if let Some(entry) = store.take(artifact_resolve.artifact().value().trim(), requester) {
+        let message = XmlObject::parse_xml(InputStream::new(entry.message.into_bytes()))
+            .map_err(|_| SAMLError::MessageDecodingError("invalid xml format!".to_string()))?;
+        artifact_response.set_message(Some(message));
+    }
+    Ok(artifact_response)
+}
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    use base64::{prelude::BASE64_STANDARD, Engine};
+    use chrono::Duration;
+
+    use crate::{
+        binding::decoding::{decode_message, SAMLMessage},
+        core::{
+            artifact::Artifact, artifact_resolve::ArtifactResolve, issuer::Issuer,
+            request_abstract_type::RequestAbstractType, status_response_type::StatusResponseType,
+        },
+        error::SAMLError,
+        util::InputStream,
+        xml::XmlObject,
+    };
+
+    use super::{issue_artifact, resolve_artifact, ArtifactType0004, InMemoryArtifactStore};
+
+    const IDP: &str = "https://idp.example.org/metadata";
+    const SP: &str = "https://sp.example.org/metadata";
+
+    fn response() -> XmlObject {
+        let root = XmlObject::parse_xml(InputStream::new(
+            br#"<saml2p:Response xmlns:saml2p="urn:oasis:names:tc:SAML:2.0:protocol" ID="_5f4e3d2c1b0a9f8e7d6c5b4a3f2e1d0c9b8a7f6e5d" Version="2.0" IssueInstant="2024-03-04T10:15:30Z"><saml2p:Status><saml2p:StatusCode Value="urn:oasis:names:tc:SAML:2.0:status:Success"/></saml2p:Status></saml2p:Response>"#
+                .to_vec(),
+        ))
+        .unwrap();
+        Rc::try_unwrap(root).unwrap().into_inner()
+    }
+
+    fn artifact_resolve(artifact: &str, requester: &str) -> ArtifactResolve {
+        let mut artifact_resolve = ArtifactResolve::default();
+        artifact_resolve.set_id("_resolve".to_string());
+        let mut issuer = Issuer::new();
+        issuer.set_value(Some(requester.to_string()));
+        artifact_resolve.set_issuer(Some(issuer));
+        artifact_resolve.set_artifact(Artifact::new(artifact.to_string()));
+        artifact_resolve
+    }
+
+    #[test]
+    fn we_can_encode_and_decode_type_0004_artifact() {
+        let artifact = ArtifactType0004::new(IDP, 3);
+        let encoded = artifact.encode();
+        let decoded = ArtifactType0004::decode(&encoded).unwrap();
+        assert_eq!(decoded, artifact);
+        assert_eq!(decoded.endpoint_index(), 3);
+        assert_eq!(decoded.source_id(), &ArtifactType0004::source_id_of(IDP));
+
+        let mut octets = vec![0x00, 0x04, 0x01, 0x02];
+        octets.extend_from_slice(&ArtifactType0004::source_id_of(SP));
+        octets.extend_from_slice(&[0x5a; 20]);
+        let decoded = ArtifactType0004::decode(&BASE64_STANDARD.encode(&octets)).unwrap();
+        assert_eq!(decoded.endpoint_index(), 0x0102);
+        assert_eq!(decoded.source_id(), &ArtifactType0004::source_id_of(SP));
+        assert_eq!(decoded.message_handle(), &[0x5a; 20]);
+
+        octets[1] = 0x02;
+        assert!(matches!(
+            ArtifactType0004::decode(&BASE64_STANDARD.encode(&octets)),
+            Err(SAMLError::MessageDecodingError(_))
+        ));
+    }
+
+    #[test]
+    fn we_can_resolve_artifact_only_once() {
+        let store = InMemoryArtifactStore::default();
+        let artifact = issue_artifact(
+            &store,
+            IDP,
+            0,
+            response(),
+            Some(SP.to_string()),
+            Duration::minutes(1),
+        )
+        .unwrap();
+
+        // another relying party does not get the message, nor does it spend the artifact,
+        // even when it claims to be the relying party in the Issuer
+        let artifact_response = resolve_artifact(
+            &store,
+            &artifact_resolve(&artifact, SP),
+            Some("https://other.example.org"),
+        )
+        .unwrap();
+        assert!(artifact_response.message().is_none());
+        let artifact_response =
+            resolve_artifact(&store, &artifact_resolve(&artifact, SP), None).unwrap();
+        assert!(artifact_response.message().is_none());
+
+        // the artifact may be surrounded by whitespace
+        let artifact_response = resolve_artifact(
+            &store,
+            &artifact_resolve(&format!("\n  {}\n", artifact), SP),
+            Some(SP),
+        )
+        .unwrap();
+        assert_eq!(artifact_response.in_response_to().unwrap(), "_resolve");
+        let SAMLMessage::Response(response) =
+            decode_message(artifact_response.message().unwrap()).unwrap()
+        else {
+            panic!("expected a response");
+        };
+        assert_eq!(response.id(), "_5f4e3d2c1b0a9f8e7d6c5b4a3f2e1d0c9b8a7f6e5d");
+
+        let artifact_response =
+            resolve_artifact(&store, &artifact_resolve(&artifact, SP), Some(SP)).unwrap();
+        assert!(artifact_response.message().is_none());
+    }
+
+    #[test]
+    fn we_cannot_resolve_expired_artifact() {
+        let store = InMemoryArtifactStore::default();
+        let artifact =
+            issue_artifact(&store, IDP, 0, response(), None, Duration::seconds(-1)).unwrap();
+        let artifact_response =
+            resolve_artifact(&store, &artifact_resolve(&artifact, SP), Some(SP)).unwrap();
+        assert!(artifact_response.message().is_none());
+    }
+}
@@ -1,90 +1,240 @@
-use std::collections::HashMap;
+pub mod post;
+pub mod registry;
+pub mod simple_sign;
+
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
 use base64::{prelude::BASE64_STANDARD, Engine};
 use inflate::inflate_bytes;
+use percent_encoding::percent_decode_str;
 
-use crate::core::authn_request::AuthnRequest;
 use crate::core::request_abstract_type::RequestAbstractType;
-use crate::core::response::Response;
 use crate::core::status_response_type::StatusResponseType;
+use crate::signature::{algorithm::SignatureAlgorithm, credential::Credential};
 use crate::{error::SAMLError, util::InputStream, xml::XmlObject};
 
+use super::encoding::{RELAY_STATE, SAML_REQUEST, SAML_RESPONSE, SIGNATURE, SIG_ALG};
+use registry::MessageRegistry;
+
+/// a decoded protocol message, by the kind of its root element
+pub enum SAMLMessage {
+    Request(Box<dyn RequestAbstractType>),
+    Response(Box<dyn StatusResponseType>),
+}
+
+/// Unmarshall the protocol message rooted at `root` with the default [`MessageRegistry`].
+pub fn decode_message(root: &Rc<RefCell<XmlObject>>) -> Result<SAMLMessage, SAMLError> {
+    MessageRegistry::global().unmarshall(root)
+}
+
 pub fn decode_request(
     params: &HashMap<String, String>,
 ) -> Result<Box<dyn RequestAbstractType>, SAMLError> {
-    if let Some(saml_encoding) = params.get("SAMLEncoding") {
-        if saml_encoding.trim() != "urn:oasis:names:to:SAML:2.0:bindings:URL-Encoding:DEFLATE" {
-            // todo throw error
-        }
-    }
-    if let Some(saml_message_encoded) = params
-        .get("SAMLRequest")
-        .or_else(|| params.get("SAMLResponse"))
-    {
-        let saml_message =
-            inflate_bytes(&BASE64_STANDARD.decode(saml_message_encoded).unwrap()).unwrap();
-        println!(
-            "saml message is {:?}",
-            String::from_utf8(saml_message.clone())
-        );
-        match XmlObject::parse_xml(InputStream::new(saml_message)) {
-            Ok(xml_object) => {
-                let result = XmlObject::write_xml(xml_object.clone());
-                println!("xml object is {:?}", result);
-                let authn_request = AuthnRequest::try_from(xml_object.borrow())?;
-                Ok(Box::new(authn_request))
-            }
-            Err(_) => Err(SAMLError::MessageDecodingError(
-                "invalid xml format!".to_string(),
-            )),
-        }
-    } else {
-        Err(SAMLError::MessageDecodingError(
-            "saml message cannot be null!".to_string(),
-        ))
-    }
+    MessageRegistry::global().unmarshall_request(&inflate_message(params)?)
 }
 
 pub fn decode_response(
     params: &HashMap<String, String>,
 ) -> Result<Box<dyn StatusResponseType>, SAMLError> {
+    MessageRegistry::global().unmarshall_response(&inflate_message(params)?)
+}
+
+/// a message received through the HTTP-Redirect binding
+pub struct RedirectMessage {
+    message: SAMLMessage,
+    relay_state: Option<String>,
+}
+
+impl RedirectMessage {
+    #[inline]
+    pub fn message(&self) -> &SAMLMessage {
+        &self.message
+    }
+
+    #[inline]
+    pub fn into_message(self) -> SAMLMessage {
+        self.message
+    }
+
+    #[inline]
+    pub fn relay_state(&self) -> Option<&String> {
+        self.relay_state.as_ref()
+    }
+}
+
+/// the query parameters an HTTP-Redirect binding defines, none of them may appear twice
+const BINDING_PARAMETERS: [&str; 5] =
+    [SAML_REQUEST, SAML_RESPONSE, RELAY_STATE, SIG_ALG, SIGNATURE];
+
+/// base64-decode, inflate and parse the message of an HTTP-Redirect binding
+fn inflate_message(params: &HashMap<String, String>) -> Result<Rc<RefCell<XmlObject>>, SAMLError> {
     if let Some(saml_encoding) = params.get("SAMLEncoding") {
         if saml_encoding.trim() != "urn:oasis:names:to:SAML:2.0:bindings:URL-Encoding:DEFLATE" {
             // todo throw error
         }
     }
-    if let Some(saml_message_encoded) = params
-        .get("SAMLRequest")
-        .or_else(|| params.get("SAMLResponse"))
-    {
-        let saml_message =
-            inflate_bytes(&BASE64_STANDARD.decode(saml_message_encoded).unwrap()).unwrap();
-        println!(
-            "saml message is {:?}",
-            String::from_utf8(saml_message.clone())
-        );
-        match XmlObject::parse_xml(InputStream::new(saml_message)) {
-            Ok(xml_object) => {
-                let response = Response::try_from(xml_object.borrow())?;
-                println!("response is {:?}", response);
-                Ok(Box::new(response))
-            }
-            Err(_) => Err(SAMLError::MessageDecodingError(
-                "invalid xml format!".to_string(),
-            )),
+    let saml_message_encoded = params
+        .get(SAML_REQUEST)
+        .or_else(|| params.get(SAML_RESPONSE))
+        .ok_or_else(|| {
+            SAMLError::MessageDecodingError("saml message cannot be null!".to_string())
+        })?;
+    inflate(saml_message_encoded)
+}
+
+fn inflate(saml_message_encoded: &str) -> Result<Rc<RefCell<XmlObject>>, SAMLError> {
+    let saml_message = BASE64_STANDARD
+        .decode(saml_message_encoded)
+        .map_err(|_| SAMLError::MessageDecodingError("invalid base64 encoding!".to_string()))
+        .and_then(|deflated| {
+            inflate_bytes(&deflated).map_err(|_| {
+                SAMLError::MessageDecodingError("invalid deflate encoding!".to_string())
+            })
+        })?;
+    XmlObject::parse_xml(InputStream::new(saml_message))
+        .map_err(|_| SAMLError::MessageDecodingError("invalid xml format!".to_string()))
+}
+
+/// Parse the raw query string of an HTTP-Redirect binding into its URL-decoded parameters.
+///
+/// A query in which a parameter of the binding appears more than once is rejected, as
+/// the occurrence a signature was verified over could differ from the one decoded.
+pub fn parse_query(query: &str) -> Result<HashMap<String, String>, SAMLError> {
+    raw_parameters(query)?
+        .into_iter()
+        .map(|(name, value)| Ok((name, url_decode(value)?)))
+        .collect()
+}
+
+/// the parameters of `query` by URL-decoded name, with their values as received
+fn raw_parameters(query: &str) -> Result<HashMap<String, &str>, SAMLError> {
+    let mut parameters = HashMap::new();
+    for parameter in query.split('&').filter(|parameter| !parameter.is_empty()) {
+        let (name, value) = parameter.split_once('=').unwrap_or((parameter, ""));
+        let name = url_decode(name)?;
+        if BINDING_PARAMETERS.contains(&name.as_str()) && parameters.contains_key(&name) {
+            return Err(SAMLError::MessageDecodingError(format!(
+                "duplicate {} parameter",
+                name
+            )));
         }
-    } else {
-        Err(SAMLError::MessageDecodingError(
-            "saml message cannot be null!".to_string(),
-        ))
+        parameters.insert(name, value);
     }
+    Ok(parameters)
+}
+
+/// Verify the `Signature` of an HTTP-Redirect binding with the public key of `credential`.
+///
+/// `query` is the query string as received. The signed content is rebuilt from the
+/// original URL-encoded octets of the message, `RelayState` and `SigAlg` parameters in
+/// the order the binding defines, since re-encoding the decoded values does not have to
+/// reproduce the octets the sender signed.
+pub fn verify_redirect_signature(query: &str, credential: &Credential) -> Result<(), SAMLError> {
+    verify_raw_parameters(&raw_parameters(query)?, credential).map(|_| ())
+}
+
+/// Verify the `Signature` of an HTTP-Redirect binding and decode the message it covers.
+///
+/// The message is inflated from the same octets the signature was verified over, prefer
+/// this to [`verify_redirect_signature`] followed by [`decode_request`] or
+/// [`decode_response`].
+pub fn decode_signed_redirect(
+    query: &str,
+    credential: &Credential,
+) -> Result<RedirectMessage, SAMLError> {
+    let parameters = raw_parameters(query)?;
+    let message = verify_raw_parameters(&parameters, credential)?;
+    let root = inflate(&url_decode(message)?)?;
+    Ok(RedirectMessage {
+        message: decode_message(&root)?,
+        relay_state: parameters
+            .get(RELAY_STATE)
+            .map(|relay_state| url_decode(relay_state))
+            .transpose()?,
+    })
+}
+
+/// verify the signature over the raw `parameters`, returns the raw message it covers
+fn verify_raw_parameters<'a>(
+    parameters: &HashMap<String, &'a str>,
+    credential: &Credential,
+) -> Result<&'a str, SAMLError> {
+    let raw_parameter = |name: &str| parameters.get(name).copied();
+    let (message_parameter, message) = raw_parameter(SAML_REQUEST)
+        .map(|message| (SAML_REQUEST, message))
+        .or_else(|| raw_parameter(SAML_RESPONSE).map(|message| (SAML_RESPONSE, message)))
+        .ok_or_else(|| {
+            SAMLError::MessageDecodingError("saml message cannot be null!".to_string())
+        })?;
+    let (sig_alg, signature) = raw_parameter(SIG_ALG)
+        .zip(raw_parameter(SIGNATURE))
+        .ok_or_else(|| SAMLError::SignatureError("message is not signed".to_string()))?;
+
+    let mut signed_content = format!("{}={}", message_parameter, message);
+    if let Some(relay_state) = raw_parameter(RELAY_STATE) {
+        signed_content.push_str(&format!("&{}={}", RELAY_STATE, relay_state));
+    }
+    signed_content.push_str(&format!("&{}={}", SIG_ALG, sig_alg));
+
+    let signature_algorithm = SignatureAlgorithm::from_uri(&url_decode(sig_alg)?)?;
+    let signature = BASE64_STANDARD
+        .decode(url_decode(signature)?)
+        .map_err(|_| SAMLError::SignatureError("invalid Signature encoding".to_string()))?;
+    signature_algorithm.verify(
+        credential.public_key(),
+        signed_content.as_bytes(),
+        &signature,
+    )?;
+    Ok(message)
+}
+
+/// decode a form URL-encoded query value, `+` stands for a space
+fn url_decode(value: &str) -> Result<String, SAMLError> {
+    percent_decode_str(&value.replace('+', " "))
+        .decode_utf8()
+        .map(|value| value.into_owned())
+        .map_err(|_| SAMLError::MessageDecodingError("invalid query string encoding".to_string()))
 }
 
 #[cfg(test)]
 mod test {
-    use std::collections::HashMap;
+    use std::{collections::HashMap, rc::Rc};
+
+    use base64::{prelude::BASE64_STANDARD, Engine};
+
+    use crate::{
+        binding::encoding::{encode_signed_redirect, MessageParameter},
+        error::SAMLError,
+        signature::{algorithm::SignatureAlgorithm, credential::Credential},
+        util::InputStream,
+        xml::XmlObject,
+    };
+
+    use super::{
+        decode_request, decode_response, decode_signed_redirect, parse_query,
+        verify_redirect_signature, SAMLMessage,
+    };
 
-    use super::{decode_request, decode_response};
+    const PRIVATE_KEY: &str = include_str!("../../../tests/resources/rsa_private_key.pem");
+    const CERTIFICATE: &str = include_str!("../../../tests/resources/rsa_certificate.pem");
+    const LOGOUT_REQUEST: &str = r#"<saml2p:LogoutRequest xmlns:saml2p="urn:oasis:names:tc:SAML:2.0:protocol" xmlns:saml2="urn:oasis:names:tc:SAML:2.0:assertion" ID="_9a8b7c6d5e4f3a2b1c0d9e8f7a6b5c4d3e2f1a0b9c" Version="2.0" IssueInstant="2024-03-04T10:15:30Z"><saml2:Issuer>https://sp.example.org/metadata</saml2:Issuer><saml2:NameID>alice</saml2:NameID></saml2p:LogoutRequest>"#;
+
+    fn signed_logout_request(relay_state: &str) -> String {
+        let credential = Credential::from_private_key_pem(PRIVATE_KEY).unwrap();
+        let request =
+            XmlObject::parse_xml(InputStream::new(LOGOUT_REQUEST.as_bytes().to_vec())).unwrap();
+        let location = encode_signed_redirect(
+            "https://idp.example.org/slo",
+            MessageParameter::SAMLRequest,
+            Rc::try_unwrap(request).unwrap().into_inner(),
+            Some(relay_state),
+            &credential,
+            SignatureAlgorithm::RSA_SHA256,
+        )
+        .unwrap();
+        let (_, query) = location.split_once('?').unwrap();
+        query.to_string()
+    }
 
     #[test]
     fn we_can_decode_base64_encode_inflate_saml_message() {
@@ -97,10 +247,133 @@ mod test {
 
     #[test]
     fn we_can_decode_base64_encode_inflate_saml_resposne() {
-        let message = "";
+        let message = "fVHLboMwEPwV5HvAmGesQFQ1qhSpvTRpDr1Ui72kSGAj1lT5/NI0qE0OOe5oZ+exq/Wpa70vHKixpmChz5mHRlndmGPB3vZPi5ytyxVB14peviL11hB6E8mQ/EULNg5GWqCGpIEOSToldw8vz1L4XPaDdVbZlv3n3KcAEQ5u8sO87aZgH7yOtFBhFUOCaZ1BXi0V1yGKOoK4SlSqM8zrJfCKeYc5ifhJsiUacWvIgXETxEW84NGCx/uQyzCREX9n3gbJNQbcmfXpXE8yCKj38QRd36Jvh2MAiqZjZo6/t5MrCCuhIh1jUqeQVblaao5hLSCqYpXoFLM6h2XFVcgu/cmznaGcRRp9rdKhAw0OVsHV+lz+zoEb6WZ8tBq9A7Qj3u+UzttyNyqFRCwoLyJ/Z4ObH5ff";
         let mut params: HashMap<String, String> = HashMap::new();
         params.insert("SAMLResponse".to_string(), message.to_string());
         let result = decode_response(&params);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn we_can_verify_signed_redirect() {
+        let credential = Credential::from_private_key_pem(PRIVATE_KEY).unwrap();
+        let response = XmlObject::parse_xml(InputStream::new(
+            r#"<saml2p:LogoutResponse xmlns:saml2p="urn:oasis:names:tc:SAML:2.0:protocol" ID="_4d3c2b1a0f9e8d7c6b5a4f3e2d1c0b9a8f7e6d5c4b" Version="2.0" IssueInstant="2024-03-04T10:15:30Z"><saml2p:Status><saml2p:StatusCode Value="urn:oasis:names:tc:SAML:2.0:status:Success"/></saml2p:Status></saml2p:LogoutResponse>"#
+                .as_bytes()
+                .to_vec(),
+        ))
+        .unwrap();
+        let location = encode_signed_redirect(
+            "https://sp.example.org/slo?tenant=a",
+            MessageParameter::SAMLResponse,
+            Rc::try_unwrap(response).unwrap().into_inner(),
+            Some("https://sp.example.org/home?page=1"),
+            &credential,
+            SignatureAlgorithm::RSA_SHA256,
+        )
+        .unwrap();
+        let (_, query) = location.split_once('?').unwrap();
+        let verifier = Credential::from_certificate_pem(CERTIFICATE).unwrap();
+        verify_redirect_signature(query, &verifier).unwrap();
+        let params = parse_query(query).unwrap();
+        assert_eq!(params["SigAlg"], SignatureAlgorithm::RSA_SHA256);
+        assert_eq!(params["RelayState"], "https://sp.example.org/home?page=1");
+
+        let tampered = query.replace("page%3D1", "page%3D2");
+        assert!(matches!(
+            verify_redirect_signature(&tampered, &verifier),
+            Err(SAMLError::SignatureError(_))
+        ));
+        let unsigned = &query[..query.find("&SigAlg=").unwrap()];
+        assert!(matches!(
+            verify_redirect_signature(unsigned, &verifier),
+            Err(SAMLError::SignatureError(_))
+        ));
+    }
+
+    #[test]
+    fn we_can_verify_redirect_signature_over_original_encoding() {
+        // lower case percent-encoding, which re-encoding the decoded values would not reproduce
+        let encode = |value: &str| {
+            value
+                .bytes()
+                .map(|octet| match octet {
+                    b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' => {
+                        (octet as char).to_string()
+                    }
+                    _ => format!("%{:02x}", octet),
+                })
+                .collect::<String>()
+        };
+        let message = "fVNLj5swEL6vtP8BcQ8YNo9iJanSpI9IaYIC7aGXyrWHxhK2qW1203/fgc1uUqnlhGx/j/lmhrljqm7oqvUnfYRfLTgfnFWtHe0fFmFrNTXMSUc1U+Co57RYfd7RNCK0scYbburw/u7KGaYw58B6aTRytptFeNi/3x0+bvff35BsRmYVIQ+ETQQh05TwTGSiymbVNJ2xCjLBeTpG3lewDhUWIQriMbfmUQqwezRbhEUeeAzRyTvXwlY7z7RHLEnGIzIbJdMyfaCTlI4n3xC0QazUzPd6J+8bGsdSNBGcmWpqiLhRcVEcCrCPkkPUnJpnxz73O6mF1D+HE/94Bjn6qSzzUX4oSlRYvbRhbbRrFdiLwZfj7rUM93cVApRJYhSDc1fGW8ZduLy/m3c9p31WuxxiKvBMMM868jy+ZV1EGtp1cLvJTS357+CDsYr5/2dLoqS/kWJU9VAKisl6JYQF57qMdW2e1haYx7F420IYxFevy7KB6FcP2+Dh7IO1UQ2z0nXTwAjcXyPe4tY17tERquXgqnHKOxxe5/h5MlZ0gwOOpqVl2jXG+ksn/imOzvFAsTfvt7/P8g8=";
+        let mut query = format!(
+            "SAMLRequest={}&RelayState={}&SigAlg={}",
+            encode(message),
+            encode("state/1"),
+            encode(SignatureAlgorithm::RSA_SHA1)
+        );
+        let credential = Credential::from_private_key_pem(PRIVATE_KEY).unwrap();
+        let signature = SignatureAlgorithm::RsaSha1
+            .sign(credential.private_key().unwrap(), query.as_bytes())
+            .unwrap();
+        query.push_str(&format!(
+            "&Signature={}",
+            encode(&BASE64_STANDARD.encode(signature))
+        ));
+
+        verify_redirect_signature(&query, &credential).unwrap();
+        let params = parse_query(&query).unwrap();
+        assert_eq!(params["RelayState"], "state/1");
+        assert!(decode_request(&params).is_ok());
+    }
+
+    #[test]
+    fn we_can_decode_signed_redirect() {
+        let query = signed_logout_request("state/1");
+        let verifier = Credential::from_certificate_pem(CERTIFICATE).unwrap();
+        let redirect = decode_signed_redirect(&query, &verifier).unwrap();
+        assert_eq!(redirect.relay_state().unwrap(), "state/1");
+        match redirect.message() {
+            SAMLMessage::Request(request) => {
+                assert_eq!(request.id(), "_9a8b7c6d5e4f3a2b1c0d9e8f7a6b5c4d3e2f1a0b9c")
+            }
+            SAMLMessage::Response(_) => panic!("expected a request"),
+        }
+
+        let tampered = query.replace("state%2F1", "state%2F2");
+        assert!(matches!(
+            decode_signed_redirect(&tampered, &verifier),
+            Err(SAMLError::SignatureError(_))
+        ));
+    }
+
+    #[test]
+    fn we_cannot_verify_redirect_with_duplicate_parameters() {
+        let query = signed_logout_request("state/1");
+        let verifier = Credential::from_certificate_pem(CERTIFICATE).unwrap();
+        let other_request = BASE64_STANDARD.encode(b"forged");
+        let duplicates = [
+            format!("SAMLRequest={}&{}", other_request, query),
+            format!("{}&SAMLRequest={}", query, other_request),
+            // the duplicate is only recognised after decoding its name
+            format!("{}&SAML%52equest={}", query, other_request),
+            format!("RelayState=evil&{}", query),
+            format!("{}&RelayState=evil", query),
+        ];
+        for duplicate in duplicates {
+            assert!(matches!(
+                verify_redirect_signature(&duplicate, &verifier),
+                Err(SAMLError::MessageDecodingError(_))
+            ));
+            assert!(matches!(
+                decode_signed_redirect(&duplicate, &verifier),
+                Err(SAMLError::MessageDecodingError(_))
+            ));
+            assert!(matches!(
+                parse_query(&duplicate),
+                Err(SAMLError::MessageDecodingError(_))
+            ));
+        }
+        verify_redirect_signature(&format!("{}&tenant=a&tenant=b", query), &verifier).unwrap();
+    }
 }
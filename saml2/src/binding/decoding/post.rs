@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+
+use base64::{prelude::BASE64_STANDARD, Engine};
+
+use crate::{
+    binding::encoding::{RELAY_STATE, SAML_REQUEST, SAML_RESPONSE},
+    error::SAMLError,
+    util::InputStream,
+    xml::XmlObject,
+};
+
+use super::{decode_message, SAMLMessage};
+
+/// a message received through the HTTP-POST binding
+pub struct PostMessage {
+    message: SAMLMessage,
+    relay_state: Option<String>,
+    decoded_message: Vec<u8>,
+}
+
+impl PostMessage {
+    #[inline]
+    pub fn message(&self) -> &SAMLMessage {
+        &self.message
+    }
+
+    #[inline]
+    pub fn into_message(self) -> SAMLMessage {
+        self.message
+    }
+
+    #[inline]
+    pub fn relay_state(&self) -> Option<&String> {
+        self.relay_state.as_ref()
+    }
+
+    /// the base64-decoded octets of the message as sent, signatures are verified over them
+    #[inline]
+    pub fn decoded_message(&self) -> &[u8] {
+        &self.decoded_message
+    }
+}
+
+/// Decode the form fields of an HTTP-POST binding.
+///
+/// Unlike the HTTP-Redirect binding the message is only base64-encoded, without DEFLATE.
+pub fn decode_post(form: &HashMap<String, String>) -> Result<PostMessage, SAMLError> {
+    let encoded_message = form
+        .get(SAML_REQUEST)
+        .or_else(|| form.get(SAML_RESPONSE))
+        .ok_or_else(|| {
+            SAMLError::MessageDecodingError("saml message cannot be null!".to_string())
+        })?;
+    // the value may be wrapped over several lines
+    let encoded_message: String = encoded_message
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    let decoded_message = BASE64_STANDARD
+        .decode(encoded_message)
+        .map_err(|_| SAMLError::MessageDecodingError("invalid base64 encoding!".to_string()))?;
+    let root = XmlObject::parse_xml(InputStream::new(decoded_message.clone()))
+        .map_err(|_| SAMLError::MessageDecodingError("invalid xml format!".to_string()))?;
+    Ok(PostMessage {
+        message: decode_message(&root)?,
+        relay_state: form.get(RELAY_STATE).cloned(),
+        decoded_message,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashMap, rc::Rc};
+
+    use crate::{
+        binding::{
+            decoding::SAMLMessage,
+            encoding::{post::post_form, MessageParameter},
+        },
+        error::SAMLError,
+        util::InputStream,
+        xml::XmlObject,
+    };
+
+    use super::decode_post;
+
+    const LOGOUT_REQUEST: &str = r#"<saml2p:LogoutRequest xmlns:saml2p="urn:oasis:names:tc:SAML:2.0:protocol" xmlns:saml2="urn:oasis:names:tc:SAML:2.0:assertion" ID="_9a8b7c6d5e4f3a2b1c0d9e8f7a6b5c4d3e2f1a0b9c" Version="2.0" IssueInstant="2024-03-04T10:15:30Z"><saml2:Issuer>https://sp.example.org/metadata</saml2:Issuer><saml2:NameID>alice</saml2:NameID></saml2p:LogoutRequest>"#;
+
+    fn form(xml: &str, relay_state: Option<&str>) -> HashMap<String, String> {
+        let root = XmlObject::parse_xml(InputStream::new(xml.as_bytes().to_vec())).unwrap();
+        post_form(
+            "https://idp.example.org/slo",
+            MessageParameter::SAMLRequest,
+            Rc::try_unwrap(root).unwrap().into_inner(),
+            relay_state,
+        )
+        .unwrap()
+        .parameters()
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.clone()))
+        .collect()
+    }
+
+    #[test]
+    fn we_can_decode_post_message() {
+        let post_message = decode_post(&form(LOGOUT_REQUEST, Some("state"))).unwrap();
+        assert_eq!(post_message.relay_state().unwrap(), "state");
+        assert!(String::from_utf8_lossy(post_message.decoded_message())
+            .contains("_9a8b7c6d5e4f3a2b1c0d9e8f7a6b5c4d3e2f1a0b9c"));
+        let SAMLMessage::Request(request) = post_message.into_message() else {
+            panic!("expected a request");
+        };
+        assert_eq!(request.id(), "_9a8b7c6d5e4f3a2b1c0d9e8f7a6b5c4d3e2f1a0b9c");
+    }
+
+    #[test]
+    fn we_cannot_decode_post_message_of_unknown_root() {
+        let form = form(
+            r#"<saml2p:Unknown xmlns:saml2p="urn:oasis:names:tc:SAML:2.0:protocol"/>"#,
+            None,
+        );
+        assert!(matches!(
+            decode_post(&form),
+            Err(SAMLError::UnknownMessageError { local_name, .. }) if local_name == "Unknown"
+        ));
+    }
+}
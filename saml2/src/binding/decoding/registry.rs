@@ -0,0 +1,299 @@
+use std::{
+    cell::{Ref, RefCell},
+    collections::HashMap,
+    rc::Rc,
+    sync::OnceLock,
+};
+
+use crate::{
+    core::{
+        artifact_resolve::ArtifactResolve, artifact_response::ArtifactResponse,
+        attribute_query::AttributeQuery, authn_request::AuthnRequest,
+        logout_request::LogoutRequest, logout_response::LogoutResponse,
+        manage_name_id_request::ManageNameIDRequest, manage_name_id_response::ManageNameIDResponse,
+        request_abstract_type::RequestAbstractType, response::Response,
+        status_response_type::StatusResponseType,
+    },
+    error::SAMLError,
+    xml::XmlObject,
+};
+
+use super::SAMLMessage;
+
+pub type RequestUnmarshaller =
+    fn(Ref<'_, XmlObject>) -> Result<Box<dyn RequestAbstractType>, SAMLError>;
+pub type ResponseUnmarshaller =
+    fn(Ref<'_, XmlObject>) -> Result<Box<dyn StatusResponseType>, SAMLError>;
+
+/// Message types by the namespace and local name of their root element.
+///
+/// The default registry knows every protocol message of this crate, further types can be
+/// registered for extension protocols.
+#[derive(Debug, Clone)]
+pub struct MessageRegistry {
+    requests: HashMap<(String, String), RequestUnmarshaller>,
+    responses: HashMap<(String, String), ResponseUnmarshaller>,
+}
+
+fn unmarshall_request<T>(
+    element: Ref<'_, XmlObject>,
+) -> Result<Box<dyn RequestAbstractType>, SAMLError>
+where
+    T: RequestAbstractType + for<'a> TryFrom<Ref<'a, XmlObject>, Error = SAMLError>,
+{
+    Ok(Box::new(T::try_from(element)?))
+}
+
+fn unmarshall_response<T>(
+    element: Ref<'_, XmlObject>,
+) -> Result<Box<dyn StatusResponseType>, SAMLError>
+where
+    T: StatusResponseType + for<'a> TryFrom<Ref<'a, XmlObject>, Error = SAMLError>,
+{
+    Ok(Box::new(T::try_from(element)?))
+}
+
+impl Default for MessageRegistry {
+    fn default() -> Self {
+        let mut registry = MessageRegistry::empty();
+        registry.register_request(
+            AuthnRequest::NS_URI,
+            AuthnRequest::ELEMENT_NAME,
+            unmarshall_request::<AuthnRequest>,
+        );
+        registry.register_request(
+            LogoutRequest::NS_URI,
+            LogoutRequest::ELEMENT_NAME,
+            unmarshall_request::<LogoutRequest>,
+        );
+        registry.register_request(
+            ArtifactResolve::NS_URI,
+            ArtifactResolve::ELEMENT_NAME,
+            unmarshall_request::<ArtifactResolve>,
+        );
+        registry.register_request(
+            AttributeQuery::NS_URI,
+            AttributeQuery::ELEMENT_NAME,
+            unmarshall_request::<AttributeQuery>,
+        );
+        registry.register_request(
+            ManageNameIDRequest::NS_URI,
+            ManageNameIDRequest::ELEMENT_NAME,
+            unmarshall_request::<ManageNameIDRequest>,
+        );
+        registry.register_response(
+            Response::NS_URI,
+            Response::ELEMENT_NAME,
+            unmarshall_response::<Response>,
+        );
+        registry.register_response(
+            ArtifactResponse::NS_URI,
+            ArtifactResponse::ELEMENT_NAME,
+            unmarshall_response::<ArtifactResponse>,
+        );
+        registry.register_response(
+            LogoutResponse::NS_URI,
+            LogoutResponse::ELEMENT_NAME,
+            unmarshall_response::<LogoutResponse>,
+        );
+        registry.register_response(
+            ManageNameIDResponse::NS_URI,
+            ManageNameIDResponse::ELEMENT_NAME,
+            unmarshall_response::<ManageNameIDResponse>,
+        );
+        registry
+    }
+}
+
+impl MessageRegistry {
+    /// a registry without any message type
+    pub fn empty() -> Self {
+        MessageRegistry {
+            requests: HashMap::new(),
+            responses: HashMap::new(),
+        }
+    }
+
+    /// the shared default registry
+    pub fn global() -> &'static MessageRegistry {
+        static REGISTRY: OnceLock<MessageRegistry> = OnceLock::new();
+        REGISTRY.get_or_init(MessageRegistry::default)
+    }
+
+    pub fn register_request(
+        &mut self,
+        namespace_uri: &str,
+        local_name: &str,
+        unmarshaller: RequestUnmarshaller,
+    ) {
+        self.requests.insert(
+            (namespace_uri.to_string(), local_name.to_string()),
+            unmarshaller,
+        );
+    }
+
+    pub fn register_response(
+        &mut self,
+        namespace_uri: &str,
+        local_name: &str,
+        unmarshaller: ResponseUnmarshaller,
+    ) {
+        self.responses.insert(
+            (namespace_uri.to_string(), local_name.to_string()),
+            unmarshaller,
+        );
+    }
+
+    /// Unmarshall the request or response rooted at `root`.
+    pub fn unmarshall(&self, root: &Rc<RefCell<XmlObject>>) -> Result<SAMLMessage, SAMLError> {
+        let key = root_key(&root.borrow());
+        if let Some(unmarshaller) = self.requests.get(&key) {
+            return Ok(SAMLMessage::Request(unmarshaller(root.borrow())?));
+        }
+        if let Some(unmarshaller) = self.responses.get(&key) {
+            return Ok(SAMLMessage::Response(unmarshaller(root.borrow())?));
+        }
+        Err(unknown_message(&root.borrow()))
+    }
+
+    /// Unmarshall the request rooted at `root`, any other root is an unknown message.
+    pub fn unmarshall_request(
+        &self,
+        root: &Rc<RefCell<XmlObject>>,
+    ) -> Result<Box<dyn RequestAbstractType>, SAMLError> {
+        match self.requests.get(&root_key(&root.borrow())) {
+            Some(unmarshaller) => unmarshaller(root.borrow()),
+            None => Err(unknown_message(&root.borrow())),
+        }
+    }
+
+    /// Unmarshall the response rooted at `root`, any other root is an unknown message.
+    pub fn unmarshall_response(
+        &self,
+        root: &Rc<RefCell<XmlObject>>,
+    ) -> Result<Box<dyn StatusResponseType>, SAMLError> {
+        match self.responses.get(&root_key(&root.borrow())) {
+            Some(unmarshaller) => unmarshaller(root.borrow()),
+            None => Err(unknown_message(&root.borrow())),
+        }
+    }
+}
+
+fn root_key(root: &XmlObject) -> (String, String) {
+    (
+        root.q_name()
+            .namespace_uri()
+            .unwrap_or_default()
+            .to_string(),
+        root.q_name().local_name().to_string(),
+    )
+}
+
+fn unknown_message(root: &XmlObject) -> SAMLError {
+    SAMLError::UnknownMessageError {
+        namespace_uri: root.q_name().namespace_uri().map(|uri| uri.to_string()),
+        local_name: root.q_name().local_name().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        binding::decoding::SAMLMessage,
+        core::{
+            artifact_resolve::ArtifactResolve, attribute_query::AttributeQuery,
+            logout_response::LogoutResponse, manage_name_id_request::ManageNameIDRequest,
+            request_abstract_type::RequestAbstractType,
+        },
+        error::SAMLError,
+        util::InputStream,
+        xml::XmlObject,
+    };
+
+    use super::MessageRegistry;
+
+    fn parse(xml: &str) -> Rc<RefCell<XmlObject>> {
+        XmlObject::parse_xml(InputStream::new(xml.as_bytes().to_vec())).unwrap()
+    }
+
+    fn request(xml: &str) -> Box<dyn RequestAbstractType> {
+        MessageRegistry::global()
+            .unmarshall_request(&parse(xml))
+            .unwrap()
+    }
+
+    #[test]
+    fn we_can_dispatch_requests_on_root_element() {
+        let artifact_resolve = request(
+            r#"<samlp:ArtifactResolve xmlns:samlp="urn:oasis:names:tc:SAML:2.0:protocol" ID="_a1" Version="2.0" IssueInstant="2024-03-04T10:15:30Z"><samlp:Artifact>AAQAAMh48/1oXIM+sDo7Dh2qMp1HM4IF5DaRNmDj6RdUmllwn9jJHyEgIi8=</samlp:Artifact></samlp:ArtifactResolve>"#,
+        );
+        let artifact_resolve = artifact_resolve
+            .as_any()
+            .downcast_ref::<ArtifactResolve>()
+            .unwrap();
+        assert_eq!(
+            artifact_resolve.artifact().value(),
+            "AAQAAMh48/1oXIM+sDo7Dh2qMp1HM4IF5DaRNmDj6RdUmllwn9jJHyEgIi8="
+        );
+
+        let attribute_query = request(
+            r#"<samlp:AttributeQuery xmlns:samlp="urn:oasis:names:tc:SAML:2.0:protocol" xmlns:saml="urn:oasis:names:tc:SAML:2.0:assertion" ID="_a2" Version="2.0" IssueInstant="2024-03-04T10:15:30Z"><saml:Subject><saml:NameID>alice</saml:NameID></saml:Subject><saml:Attribute Name="urn:oid:2.5.4.42"/></samlp:AttributeQuery>"#,
+        );
+        let attribute_query = attribute_query
+            .as_any()
+            .downcast_ref::<AttributeQuery>()
+            .unwrap();
+        assert_eq!(attribute_query.attributes()[0].name(), "urn:oid:2.5.4.42");
+
+        let manage_name_id_request = request(
+            r#"<samlp:ManageNameIDRequest xmlns:samlp="urn:oasis:names:tc:SAML:2.0:protocol" xmlns:saml="urn:oasis:names:tc:SAML:2.0:assertion" ID="_a3" Version="2.0" IssueInstant="2024-03-04T10:15:30Z"><saml:NameID>alice</saml:NameID><samlp:Terminate/></samlp:ManageNameIDRequest>"#,
+        );
+        let manage_name_id_request = manage_name_id_request
+            .as_any()
+            .downcast_ref::<ManageNameIDRequest>()
+            .unwrap();
+        assert_eq!(manage_name_id_request.id(), "_a3");
+        assert!(manage_name_id_request.terminate());
+        assert!(manage_name_id_request.new_id().is_none());
+    }
+
+    #[test]
+    fn we_can_dispatch_responses_on_root_element() {
+        let message = MessageRegistry::global()
+            .unmarshall(&parse(
+                r#"<samlp:LogoutResponse xmlns:samlp="urn:oasis:names:tc:SAML:2.0:protocol" ID="_r1" InResponseTo="_a1" Version="2.0" IssueInstant="2024-03-04T10:15:30Z"><samlp:Status><samlp:StatusCode Value="urn:oasis:names:tc:SAML:2.0:status:Success"/></samlp:Status></samlp:LogoutResponse>"#,
+            ))
+            .unwrap();
+        let SAMLMessage::Response(response) = message else {
+            panic!("expected a response");
+        };
+        assert_eq!(response.in_response_to().unwrap(), "_a1");
+        assert!(response.as_any().is::<LogoutResponse>());
+    }
+
+    #[test]
+    fn we_cannot_dispatch_unknown_root_element() {
+        let root = parse(
+            r#"<samlp:LogoutResponse xmlns:samlp="urn:oasis:names:tc:SAML:2.0:protocol" ID="_r1" Version="2.0" IssueInstant="2024-03-04T10:15:30Z"/>"#,
+        );
+        assert!(matches!(
+            MessageRegistry::global().unmarshall_request(&root),
+            Err(SAMLError::UnknownMessageError { local_name, .. }) if local_name == "LogoutResponse"
+        ));
+        assert!(matches!(
+            MessageRegistry::empty().unmarshall(&root),
+            Err(SAMLError::UnknownMessageError { namespace_uri: Some(namespace_uri), .. })
+                if namespace_uri == "urn:oasis:names:tc:SAML:2.0:protocol"
+        ));
+
+        let mut registry = MessageRegistry::empty();
+        registry.register_response(
+            "urn:oasis:names:tc:SAML:2.0:protocol",
+            "LogoutResponse",
+            |element| Ok(Box::new(LogoutResponse::try_from(element)?)),
+        );
+        assert!(registry.unmarshall_response(&root).is_ok());
+    }
+}
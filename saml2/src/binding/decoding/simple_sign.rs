@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+
+use base64::{prelude::BASE64_STANDARD, Engine};
+
+use crate::{
+    binding::encoding::{RELAY_STATE, SAML_REQUEST, SAML_RESPONSE, SIGNATURE, SIG_ALG},
+    error::SAMLError,
+    signature::{algorithm::SignatureAlgorithm, credential::Credential},
+};
+
+use super::post::{decode_post, PostMessage};
+
+/// Verify the `Signature` field of an HTTP-POST-SimpleSign binding with the public key of
+/// `credential`.
+///
+/// The signed content is rebuilt from the message, `RelayState` and `SigAlg` fields as
+/// they were posted, without URL-encoding.
+pub fn verify_simple_sign_signature(
+    form: &HashMap<String, String>,
+    credential: &Credential,
+) -> Result<(), SAMLError> {
+    let (message_parameter, message) = form
+        .get_key_value(SAML_REQUEST)
+        .or_else(|| form.get_key_value(SAML_RESPONSE))
+        .ok_or_else(|| {
+            SAMLError::MessageDecodingError("saml message cannot be null!".to_string())
+        })?;
+    let (sig_alg, signature) = form
+        .get(SIG_ALG)
+        .zip(form.get(SIGNATURE))
+        .ok_or_else(|| SAMLError::SignatureError("message is not signed".to_string()))?;
+
+    let mut signed_content = format!("{}={}", message_parameter, message);
+    if let Some(relay_state) = form.get(RELAY_STATE) {
+        signed_content.push_str(&format!("&{}={}", RELAY_STATE, relay_state));
+    }
+    signed_content.push_str(&format!("&{}={}", SIG_ALG, sig_alg));
+
+    let signature_algorithm = SignatureAlgorithm::from_uri(sig_alg)?;
+    let signature: String = signature.chars().filter(|c| !c.is_whitespace()).collect();
+    let signature = BASE64_STANDARD
+        .decode(signature)
+        .map_err(|_| SAMLError::SignatureError("invalid Signature encoding".to_string()))?;
+    signature_algorithm.verify(
+        credential.public_key(),
+        signed_content.as_bytes(),
+        &signature,
+    )
+}
+
+/// Verify and decode the form fields of an HTTP-POST-SimpleSign binding.
+pub fn decode_simple_sign(
+    form: &HashMap<String, String>,
+    credential: &Credential,
+) -> Result<PostMessage, SAMLError> {
+    verify_simple_sign_signature(form, credential)?;
+    decode_post(form)
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashMap, rc::Rc};
+
+    use crate::{
+        binding::{
+            decoding::SAMLMessage,
+            encoding::{
+                post::html_escape,
+                simple_sign::{encode_simple_sign, simple_sign_form},
+                MessageParameter,
+            },
+        },
+        error::SAMLError,
+        signature::{algorithm::SignatureAlgorithm, credential::Credential},
+        util::InputStream,
+        xml::XmlObject,
+    };
+
+    use super::{decode_simple_sign, verify_simple_sign_signature};
+
+    const PRIVATE_KEY: &str = include_str!("../../../tests/resources/rsa_private_key.pem");
+    const CERTIFICATE: &str = include_str!("../../../tests/resources/rsa_certificate.pem");
+
+    fn authn_request() -> XmlObject {
+        let root = XmlObject::parse_xml(InputStream::new(
+            br#"<saml2p:AuthnRequest xmlns:saml2p="urn:oasis:names:tc:SAML:2.0:protocol" xmlns:saml2="urn:oasis:names:tc:SAML:2.0:assertion" ID="_3e2d1c0b9a8f7e6d5c4b3a2f1e0d9c8b7a6f5e4d3c" Version="2.0" IssueInstant="2024-03-04T10:15:30Z" AssertionConsumerServiceURL="https://sp.example.org/acs"><saml2:Issuer>https://sp.example.org/metadata</saml2:Issuer></saml2p:AuthnRequest>"#
+                .to_vec(),
+        ))
+        .unwrap();
+        Rc::try_unwrap(root).unwrap().into_inner()
+    }
+
+    fn form(relay_state: Option<&str>) -> HashMap<String, String> {
+        let credential = Credential::from_private_key_pem(PRIVATE_KEY).unwrap();
+        simple_sign_form(
+            "https://idp.example.org/sso",
+            MessageParameter::SAMLRequest,
+            authn_request(),
+            relay_state,
+            &credential,
+            SignatureAlgorithm::RSA_SHA256,
+        )
+        .unwrap()
+        .parameters()
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.clone()))
+        .collect()
+    }
+
+    #[test]
+    fn we_can_verify_and_decode_simple_sign_form() {
+        let verifier = Credential::from_certificate_pem(CERTIFICATE).unwrap();
+        let form = form(Some("a&b=c"));
+        let post_message = decode_simple_sign(&form, &verifier).unwrap();
+        assert_eq!(post_message.relay_state().unwrap(), "a&b=c");
+        let SAMLMessage::Request(request) = post_message.message() else {
+            panic!("expected a request");
+        };
+        assert_eq!(request.id(), "_3e2d1c0b9a8f7e6d5c4b3a2f1e0d9c8b7a6f5e4d3c");
+
+        let mut tampered = form.clone();
+        tampered.insert("RelayState".to_string(), "a&b=d".to_string());
+        assert!(matches!(
+            verify_simple_sign_signature(&tampered, &verifier),
+            Err(SAMLError::SignatureError(_))
+        ));
+        let mut unsigned = form.clone();
+        unsigned.remove("Signature");
+        assert!(matches!(
+            decode_simple_sign(&unsigned, &verifier),
+            Err(SAMLError::SignatureError(_))
+        ));
+    }
+
+    #[test]
+    fn we_can_render_simple_sign_form() {
+        let credential = Credential::from_private_key_pem(PRIVATE_KEY).unwrap();
+        let html = encode_simple_sign(
+            "https://idp.example.org/sso",
+            MessageParameter::SAMLRequest,
+            authn_request(),
+            None,
+            &credential,
+            SignatureAlgorithm::RSA_SHA256,
+        )
+        .unwrap();
+        assert!(html.contains(&format!(
+            r#"name="SigAlg" value="{}""#,
+            html_escape(SignatureAlgorithm::RSA_SHA256)
+        )));
+        assert!(html.contains(r#"name="Signature" value=""#));
+        assert!(!html.contains("RelayState"));
+
+        let verifier = Credential::from_certificate_pem(CERTIFICATE).unwrap();
+        verify_simple_sign_signature(&form(None), &verifier).unwrap();
+    }
+}
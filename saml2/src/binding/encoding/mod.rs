@@ -0,0 +1,237 @@
+pub mod post;
+pub mod simple_sign;
+
+use std::{cell::RefCell, rc::Rc};
+
+use base64::{prelude::BASE64_STANDARD, Engine};
+use deflate::deflate_bytes;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+use crate::{
+    error::SAMLError,
+    signature::{algorithm::SignatureAlgorithm, credential::Credential},
+    xml::XmlObject,
+};
+
+/// the URI identifying the HTTP-Redirect binding, e.g. in metadata endpoints
+pub const HTTP_REDIRECT_BINDING: &str = "urn:oasis:names:tc:SAML:2.0:bindings:HTTP-Redirect";
+
+pub const SAML_REQUEST: &str = "SAMLRequest";
+pub const SAML_RESPONSE: &str = "SAMLResponse";
+pub const RELAY_STATE: &str = "RelayState";
+pub const SIG_ALG: &str = "SigAlg";
+pub const SIGNATURE: &str = "Signature";
+
+const RELAY_STATE_MAX_LENGTH: usize = 80;
+
+/// everything but the unreserved characters of RFC 3986 is percent-encoded
+const QUERY_VALUE: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// the parameter carrying the message of an HTTP-Redirect or HTTP-POST binding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageParameter {
+    SAMLRequest,
+    SAMLResponse,
+}
+
+impl MessageParameter {
+    #[inline]
+    pub fn name(&self) -> &'static str {
+        match self {
+            MessageParameter::SAMLRequest => SAML_REQUEST,
+            MessageParameter::SAMLResponse => SAML_RESPONSE,
+        }
+    }
+}
+
+/// DEFLATE-compress and base64-encode `message`, the value of the message parameter
+/// before URL-encoding
+pub fn deflate_encode(message: XmlObject) -> Result<String, SAMLError> {
+    let xml = XmlObject::write_xml(Rc::new(RefCell::new(message)))
+        .map_err(|_| SAMLError::MessageEncodingError("invalid xml format!".to_string()))?;
+    Ok(BASE64_STANDARD.encode(deflate_bytes(xml.as_bytes())))
+}
+
+/// Build the `Location` of an HTTP-Redirect binding delivering `message` to `endpoint`.
+///
+/// The query string of the endpoint is kept, except for the parameters the binding
+/// defines itself, which are replaced.
+pub fn encode_redirect(
+    endpoint: &str,
+    parameter: MessageParameter,
+    message: XmlObject,
+    relay_state: Option<&str>,
+) -> Result<String, SAMLError> {
+    let query = redirect_query(parameter, &deflate_encode(message)?, relay_state)?;
+    Ok(redirect_location(endpoint, &query))
+}
+
+/// Build the `Location` of an HTTP-Redirect binding delivering `message` to `endpoint`,
+/// signed with the private key of `credential`.
+///
+/// The signature covers the URL-encoded message, `RelayState` and `SigAlg` parameters
+/// exactly as they appear in the query string and is appended as `Signature`.
+pub fn encode_signed_redirect(
+    endpoint: &str,
+    parameter: MessageParameter,
+    message: XmlObject,
+    relay_state: Option<&str>,
+    credential: &Credential,
+    algorithm: &str,
+) -> Result<String, SAMLError> {
+    let signature_algorithm = SignatureAlgorithm::from_uri(algorithm)?;
+    let private_key = credential
+        .private_key()
+        .ok_or_else(|| SAMLError::CredentialError("signing requires a private key".to_string()))?;
+    let mut query = redirect_query(parameter, &deflate_encode(message)?, relay_state)?;
+    query.push_str(&format!("&{}={}", SIG_ALG, url_encode(algorithm)));
+    let signature = signature_algorithm.sign(private_key, query.as_bytes())?;
+    query.push_str(&format!(
+        "&{}={}",
+        SIGNATURE,
+        url_encode(&BASE64_STANDARD.encode(signature))
+    ));
+    Ok(redirect_location(endpoint, &query))
+}
+
+/// the URL-encoded message and relay state parameters, in the order of the binding
+fn redirect_query(
+    parameter: MessageParameter,
+    encoded_message: &str,
+    relay_state: Option<&str>,
+) -> Result<String, SAMLError> {
+    let mut query = format!("{}={}", parameter.name(), url_encode(encoded_message));
+    if let Some(relay_state) = relay_state {
+        check_relay_state(relay_state)?;
+        query.push_str(&format!("&{}={}", RELAY_STATE, url_encode(relay_state)));
+    }
+    Ok(query)
+}
+
+/// append `query` to the query string of `endpoint`, ahead of any fragment
+fn redirect_location(endpoint: &str, query: &str) -> String {
+    let (endpoint, fragment) = match endpoint.split_once('#') {
+        Some((endpoint, fragment)) => (endpoint, Some(fragment)),
+        None => (endpoint, None),
+    };
+    let (base, existing_query) = endpoint.split_once('?').unwrap_or((endpoint, ""));
+    let mut parameters: Vec<&str> = existing_query
+        .split('&')
+        .filter(|parameter| {
+            let name = parameter
+                .split_once('=')
+                .map_or(*parameter, |(name, _)| name);
+            !name.is_empty()
+                && ![SAML_REQUEST, SAML_RESPONSE, RELAY_STATE, SIG_ALG, SIGNATURE].contains(&name)
+        })
+        .collect();
+    parameters.push(query);
+    let mut location = format!("{}?{}", base, parameters.join("&"));
+    if let Some(fragment) = fragment {
+        location.push('#');
+        location.push_str(fragment);
+    }
+    location
+}
+
+/// the binding restricts `RelayState` to 80 bytes
+fn check_relay_state(relay_state: &str) -> Result<(), SAMLError> {
+    if relay_state.len() > RELAY_STATE_MAX_LENGTH {
+        return Err(SAMLError::MessageEncodingError(format!(
+            "RelayState must not exceed {} bytes",
+            RELAY_STATE_MAX_LENGTH
+        )));
+    }
+    Ok(())
+}
+
+#[inline]
+pub(crate) fn url_encode(value: &str) -> String {
+    utf8_percent_encode(value, QUERY_VALUE).to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashMap, rc::Rc};
+
+    use percent_encoding::percent_decode_str;
+
+    use crate::{
+        binding::decoding::decode_response, error::SAMLError, util::InputStream, xml::XmlObject,
+    };
+
+    use super::{encode_redirect, MessageParameter};
+
+    const RESPONSE: &str = r#"<saml2p:Response xmlns:saml2p="urn:oasis:names:tc:SAML:2.0:protocol" ID="_6c3a4f8b5d2e1f0a9b8c7d6e5f4a3b2c1d0e9f8a7b" Version="2.0" IssueInstant="2024-03-04T10:15:30Z" Destination="https://sp.example.org/acs"><saml2p:Status><saml2p:StatusCode Value="urn:oasis:names:tc:SAML:2.0:status:Success"/></saml2p:Status></saml2p:Response>"#;
+
+    fn query_parameters(location: &str) -> HashMap<String, String> {
+        let (_, query) = location.split_once('?').unwrap();
+        query
+            .split('&')
+            .map(|parameter| {
+                let (name, value) = parameter.split_once('=').unwrap();
+                (
+                    name.to_string(),
+                    percent_decode_str(value).decode_utf8().unwrap().to_string(),
+                )
+            })
+            .collect()
+    }
+
+    fn response() -> XmlObject {
+        let root = XmlObject::parse_xml(InputStream::new(RESPONSE.as_bytes().to_vec())).unwrap();
+        Rc::try_unwrap(root).unwrap().into_inner()
+    }
+
+    #[test]
+    fn we_can_encode_redirect_and_decode_it_back() {
+        let location = encode_redirect(
+            "https://sp.example.org/acs",
+            MessageParameter::SAMLResponse,
+            response(),
+            Some("state & more/=?"),
+        )
+        .unwrap();
+        assert!(location.starts_with("https://sp.example.org/acs?SAMLResponse="));
+        assert!(location.ends_with("&RelayState=state%20%26%20more%2F%3D%3F"));
+
+        let params = query_parameters(&location);
+        assert_eq!(params["RelayState"], "state & more/=?");
+        let response = decode_response(&params).unwrap();
+        assert_eq!(response.id(), "_6c3a4f8b5d2e1f0a9b8c7d6e5f4a3b2c1d0e9f8a7b");
+    }
+
+    #[test]
+    fn we_can_keep_query_string_of_endpoint() {
+        let location = encode_redirect(
+            "https://idp.example.org/sso?tenant=a%20b&RelayState=stale&lang=en#top",
+            MessageParameter::SAMLRequest,
+            response(),
+            None,
+        )
+        .unwrap();
+        assert!(
+            location.starts_with("https://idp.example.org/sso?tenant=a%20b&lang=en&SAMLRequest=")
+        );
+        assert!(location.ends_with("#top"));
+        assert!(!location.contains("RelayState"));
+    }
+
+    #[test]
+    fn we_cannot_encode_relay_state_over_80_bytes() {
+        let relay_state = "r".repeat(81);
+        assert!(matches!(
+            encode_redirect(
+                "https://sp.example.org/acs",
+                MessageParameter::SAMLResponse,
+                response(),
+                Some(&relay_state),
+            ),
+            Err(SAMLError::MessageEncodingError(_))
+        ));
+    }
+}
@@ -0,0 +1,230 @@
+use std::{cell::RefCell, rc::Rc};
+
+use base64::{prelude::BASE64_STANDARD, Engine};
+
+use crate::{error::SAMLError, xml::XmlObject};
+
+use super::{check_relay_state, MessageParameter, RELAY_STATE};
+
+/// the URI identifying the HTTP-POST binding, e.g. in metadata endpoints
+pub const HTTP_POST_BINDING: &str = "urn:oasis:names:tc:SAML:2.0:bindings:HTTP-POST";
+
+/// the form of an HTTP-POST binding, every value is kept as is and escaped on rendering
+#[derive(Debug)]
+pub struct PostForm {
+    action: String,
+    parameters: Vec<(&'static str, String)>,
+}
+
+impl PostForm {
+    #[inline]
+    pub fn action(&self) -> &str {
+        &self.action
+    }
+
+    /// the message parameter followed by `RelayState`, if any
+    #[inline]
+    pub fn parameters(&self) -> &Vec<(&'static str, String)> {
+        &self.parameters
+    }
+
+    #[inline]
+    pub(crate) fn add_parameter(&mut self, name: &'static str, value: String) {
+        self.parameters.push((name, value));
+    }
+
+    /// the HTML-escaped `action` attribute value
+    pub fn escaped_action(&self) -> String {
+        html_escape(&self.action)
+    }
+
+    /// one hidden `input` per parameter, with HTML-escaped names and values
+    pub fn hidden_inputs(&self) -> String {
+        self.parameters
+            .iter()
+            .map(|(name, value)| {
+                format!(
+                    "<input type=\"hidden\" name=\"{}\" value=\"{}\"/>",
+                    html_escape(name),
+                    html_escape(value)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Renders a [`PostForm`] into the HTML page returned to the user agent.
+///
+/// Implementations are responsible for escaping, [`PostForm::escaped_action`] and
+/// [`PostForm::hidden_inputs`] provide the escaped markup.
+pub trait PostTemplate {
+    fn render(&self, form: &PostForm) -> String;
+}
+
+/// the default page, submitting itself on load with a button for user agents without
+/// JavaScript
+#[derive(Debug, Default)]
+pub struct AutoSubmitTemplate;
+
+impl PostTemplate for AutoSubmitTemplate {
+    fn render(&self, form: &PostForm) -> String {
+        format!(
+            r#"<!DOCTYPE html>
+<html>
+<body onload="document.forms[0].submit()">
+<noscript>
+<p>Since your browser does not support JavaScript, you must press the Continue button once to proceed.</p>
+</noscript>
+<form method="post" action="{}">
+{}
+<noscript>
+<input type="submit" value="Continue"/>
+</noscript>
+</form>
+</body>
+</html>
+"#,
+            form.escaped_action(),
+            form.hidden_inputs()
+        )
+    }
+}
+
+/// base64-encode `message`, the value of the message parameter of an HTTP-POST binding
+pub fn base64_encode(message: XmlObject) -> Result<String, SAMLError> {
+    let xml = XmlObject::write_xml(Rc::new(RefCell::new(message)))
+        .map_err(|_| SAMLError::MessageEncodingError("invalid xml format!".to_string()))?;
+    Ok(BASE64_STANDARD.encode(xml))
+}
+
+/// Build the [`PostForm`] of an HTTP-POST binding delivering `message` to `endpoint`.
+pub fn post_form(
+    endpoint: &str,
+    parameter: MessageParameter,
+    message: XmlObject,
+    relay_state: Option<&str>,
+) -> Result<PostForm, SAMLError> {
+    let mut parameters = vec![(parameter.name(), base64_encode(message)?)];
+    if let Some(relay_state) = relay_state {
+        check_relay_state(relay_state)?;
+        parameters.push((RELAY_STATE, relay_state.to_string()));
+    }
+    Ok(PostForm {
+        action: endpoint.to_string(),
+        parameters,
+    })
+}
+
+/// Render the self-submitting HTML page of an HTTP-POST binding delivering `message` to
+/// `endpoint`.
+pub fn encode_post(
+    endpoint: &str,
+    parameter: MessageParameter,
+    message: XmlObject,
+    relay_state: Option<&str>,
+) -> Result<String, SAMLError> {
+    encode_post_with_template(
+        endpoint,
+        parameter,
+        message,
+        relay_state,
+        &AutoSubmitTemplate,
+    )
+}
+
+/// [`encode_post`] with a custom page
+pub fn encode_post_with_template(
+    endpoint: &str,
+    parameter: MessageParameter,
+    message: XmlObject,
+    relay_state: Option<&str>,
+    template: &dyn PostTemplate,
+) -> Result<String, SAMLError> {
+    Ok(template.render(&post_form(endpoint, parameter, message, relay_state)?))
+}
+
+/// escape `value` for HTML text and double quoted attribute values
+pub fn html_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#x27;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    use base64::{prelude::BASE64_STANDARD, Engine};
+
+    use crate::{binding::encoding::MessageParameter, util::InputStream, xml::XmlObject};
+
+    use super::{encode_post, encode_post_with_template, PostForm, PostTemplate};
+
+    const RESPONSE: &str = r#"<saml2p:Response xmlns:saml2p="urn:oasis:names:tc:SAML:2.0:protocol" ID="_6c3a4f8b5d2e1f0a9b8c7d6e5f4a3b2c1d0e9f8a7b" Version="2.0" IssueInstant="2024-03-04T10:15:30Z"><saml2p:Status><saml2p:StatusCode Value="urn:oasis:names:tc:SAML:2.0:status:Success"/></saml2p:Status></saml2p:Response>"#;
+
+    fn response() -> XmlObject {
+        let root = XmlObject::parse_xml(InputStream::new(RESPONSE.as_bytes().to_vec())).unwrap();
+        Rc::try_unwrap(root).unwrap().into_inner()
+    }
+
+    #[test]
+    fn we_can_render_auto_submit_post_form() {
+        let html = encode_post(
+            "https://sp.example.org/acs?a=1&b=\"2\"",
+            MessageParameter::SAMLResponse,
+            response(),
+            Some("<script>alert('x')</script>"),
+        )
+        .unwrap();
+        assert!(html.contains(r#"action="https://sp.example.org/acs?a=1&amp;b=&quot;2&quot;""#));
+        assert!(html.contains(
+            r#"name="RelayState" value="&lt;script&gt;alert(&#x27;x&#x27;)&lt;/script&gt;""#
+        ));
+        assert!(!html.contains("<script>"));
+        assert!(html.contains(r#"<input type="submit" value="Continue"/>"#));
+
+        let start = html.find(r#"name="SAMLResponse" value=""#).unwrap()
+            + r#"name="SAMLResponse" value=""#.len();
+        let end = start + html[start..].find('"').unwrap();
+        let xml = String::from_utf8(BASE64_STANDARD.decode(&html[start..end]).unwrap()).unwrap();
+        assert!(xml.contains("_6c3a4f8b5d2e1f0a9b8c7d6e5f4a3b2c1d0e9f8a7b"));
+    }
+
+    #[test]
+    fn we_can_render_post_form_with_custom_template() {
+        struct MinimalTemplate;
+
+        impl PostTemplate for MinimalTemplate {
+            fn render(&self, form: &PostForm) -> String {
+                format!(
+                    "<form action=\"{}\">{}</form>",
+                    form.escaped_action(),
+                    form.hidden_inputs()
+                )
+            }
+        }
+
+        let html = encode_post_with_template(
+            "https://idp.example.org/sso",
+            MessageParameter::SAMLRequest,
+            response(),
+            None,
+            &MinimalTemplate,
+        )
+        .unwrap();
+        assert!(html.starts_with(
+            "<form action=\"https://idp.example.org/sso\"><input type=\"hidden\" name=\"SAMLRequest\" value=\""
+        ));
+        assert!(!html.contains("RelayState"));
+    }
+}
@@ -0,0 +1,89 @@
+use base64::{prelude::BASE64_STANDARD, Engine};
+
+use crate::{
+    error::SAMLError,
+    signature::{algorithm::SignatureAlgorithm, credential::Credential},
+    xml::XmlObject,
+};
+
+use super::{
+    post::{post_form, AutoSubmitTemplate, PostForm, PostTemplate},
+    MessageParameter, SIGNATURE, SIG_ALG,
+};
+
+/// the URI identifying the HTTP-POST-SimpleSign binding, e.g. in metadata endpoints
+pub const POST_SIMPLE_SIGN_BINDING: &str =
+    "urn:oasis:names:tc:SAML:2.0:bindings:HTTP-POST-SimpleSign";
+
+/// Build the [`PostForm`] of an HTTP-POST-SimpleSign binding delivering `message` to
+/// `endpoint`, signed with the private key of `credential`.
+///
+/// The signature covers `name=value` of the message, `RelayState` and `SigAlg` fields
+/// joined by `&`, the values as they are posted, without URL-encoding.
+pub fn simple_sign_form(
+    endpoint: &str,
+    parameter: MessageParameter,
+    message: XmlObject,
+    relay_state: Option<&str>,
+    credential: &Credential,
+    algorithm: &str,
+) -> Result<PostForm, SAMLError> {
+    let signature_algorithm = SignatureAlgorithm::from_uri(algorithm)?;
+    let private_key = credential
+        .private_key()
+        .ok_or_else(|| SAMLError::CredentialError("signing requires a private key".to_string()))?;
+    let mut form = post_form(endpoint, parameter, message, relay_state)?;
+    form.add_parameter(SIG_ALG, algorithm.to_string());
+    let signature = signature_algorithm.sign(private_key, signed_content(&form).as_bytes())?;
+    form.add_parameter(SIGNATURE, BASE64_STANDARD.encode(signature));
+    Ok(form)
+}
+
+/// Render the self-submitting HTML page of an HTTP-POST-SimpleSign binding.
+pub fn encode_simple_sign(
+    endpoint: &str,
+    parameter: MessageParameter,
+    message: XmlObject,
+    relay_state: Option<&str>,
+    credential: &Credential,
+    algorithm: &str,
+) -> Result<String, SAMLError> {
+    encode_simple_sign_with_template(
+        endpoint,
+        parameter,
+        message,
+        relay_state,
+        credential,
+        algorithm,
+        &AutoSubmitTemplate,
+    )
+}
+
+/// [`encode_simple_sign`] with a custom page
+pub fn encode_simple_sign_with_template(
+    endpoint: &str,
+    parameter: MessageParameter,
+    message: XmlObject,
+    relay_state: Option<&str>,
+    credential: &Credential,
+    algorithm: &str,
+    template: &dyn PostTemplate,
+) -> Result<String, SAMLError> {
+    Ok(template.render(&simple_sign_form(
+        endpoint,
+        parameter,
+        message,
+        relay_state,
+        credential,
+        algorithm,
+    )?))
+}
+
+/// the message, `RelayState` and `SigAlg` fields, in the order the form holds them
+fn signed_content(form: &PostForm) -> String {
+    form.parameters()
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect::<Vec<_>>()
+        .join("&")
+}
@@ -1 +1,5 @@
-mod decoding;
+pub mod artifact;
+pub mod decoding;
+pub mod encoding;
+pub mod paos;
+pub mod soap;
@@ -0,0 +1,57 @@
+use std::cell::Ref;
+
+use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+
+use super::{add_header_attributes, ECP_NS_PREFIX, ECP_NS_URI};
+
+/// `ecp:RelayState` header, the SP state the client hands back with the response
+#[derive(Debug, Default)]
+pub struct EcpRelayState {
+    value: String,
+}
+
+impl SAML2Obj for EcpRelayState {}
+
+impl EcpRelayState {
+    pub const ELEMENT_NAME: &'static str = "RelayState";
+
+    pub fn new(value: String) -> Self {
+        EcpRelayState { value }
+    }
+
+    #[inline]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    #[inline]
+    pub fn set_value(&mut self, value: String) {
+        self.value = value;
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for EcpRelayState {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        Ok(EcpRelayState::new(
+            element.text().cloned().unwrap_or_default(),
+        ))
+    }
+}
+
+impl TryFrom<EcpRelayState> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(ecp_relay_state: EcpRelayState) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(ECP_NS_URI.to_string()),
+            EcpRelayState::ELEMENT_NAME.to_string(),
+            Some(ECP_NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(ECP_NS_PREFIX.to_string(), ECP_NS_URI.to_string());
+        add_header_attributes(&mut xml_object);
+        xml_object.set_text(Some(ecp_relay_state.value));
+        Ok(xml_object)
+    }
+}
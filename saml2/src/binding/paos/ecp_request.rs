@@ -0,0 +1,140 @@
+use std::cell::{Ref, RefCell};
+use std::rc::Rc;
+
+use crate::{
+    common::SAML2Obj,
+    core::{idp_list::IDPList, issuer::Issuer},
+    error::SAMLError,
+    xml::XmlObject,
+};
+
+use super::{add_header_attributes, ECP_NS_PREFIX, ECP_NS_URI};
+
+/// `ecp:Request` header of the SP, telling the client who asks for authentication
+#[derive(Debug, Default)]
+pub struct EcpRequest {
+    issuer: Issuer,
+    idp_list: Option<IDPList>,
+    is_passive: Option<bool>,
+    provider_name: Option<String>,
+}
+
+impl SAML2Obj for EcpRequest {}
+
+impl EcpRequest {
+    const ATTRIB_IS_PASSIVE: &'static str = "IsPassive";
+    const ATTRIB_PROVIDER_NAME: &'static str = "ProviderName";
+
+    const CHILD_ISSUER: &'static str = "Issuer";
+    const CHILD_IDP_LIST: &'static str = "IDPList";
+
+    pub const ELEMENT_NAME: &'static str = "Request";
+
+    pub fn new(issuer: Issuer) -> Self {
+        EcpRequest {
+            issuer,
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    pub fn issuer(&self) -> &Issuer {
+        &self.issuer
+    }
+
+    #[inline]
+    pub fn set_issuer(&mut self, issuer: Issuer) {
+        self.issuer = issuer;
+    }
+
+    /// the identity providers the SP trusts, for the client to choose from
+    #[inline]
+    pub fn idp_list(&self) -> Option<&IDPList> {
+        self.idp_list.as_ref()
+    }
+
+    #[inline]
+    pub fn set_idp_list(&mut self, idp_list: Option<IDPList>) {
+        self.idp_list = idp_list;
+    }
+
+    #[inline]
+    pub fn is_passive(&self) -> Option<bool> {
+        self.is_passive
+    }
+
+    #[inline]
+    pub fn set_is_passive(&mut self, is_passive: Option<bool>) {
+        self.is_passive = is_passive;
+    }
+
+    #[inline]
+    pub fn provider_name(&self) -> Option<&String> {
+        self.provider_name.as_ref()
+    }
+
+    #[inline]
+    pub fn set_provider_name(&mut self, provider_name: Option<String>) {
+        self.provider_name = provider_name;
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for EcpRequest {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut ecp_request = EcpRequest::default();
+        for (key, value) in element.attributes() {
+            match key.as_str() {
+                EcpRequest::ATTRIB_IS_PASSIVE => {
+                    ecp_request.set_is_passive(Some(value == "true" || value == "1"));
+                }
+                EcpRequest::ATTRIB_PROVIDER_NAME => {
+                    ecp_request.set_provider_name(Some(value.to_string()));
+                }
+                _ => {}
+            }
+        }
+        for child in element.children() {
+            let child = child.borrow();
+            match child.q_name().local_name() {
+                EcpRequest::CHILD_ISSUER => ecp_request.set_issuer(Issuer::try_from(child)?),
+                EcpRequest::CHILD_IDP_LIST => {
+                    ecp_request.set_idp_list(Some(IDPList::try_from(child)?));
+                }
+                _ => {}
+            }
+        }
+        Ok(ecp_request)
+    }
+}
+
+impl TryFrom<EcpRequest> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(ecp_request: EcpRequest) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(ECP_NS_URI.to_string()),
+            EcpRequest::ELEMENT_NAME.to_string(),
+            Some(ECP_NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(ECP_NS_PREFIX.to_string(), ECP_NS_URI.to_string());
+        add_header_attributes(&mut xml_object);
+        if let Some(is_passive) = ecp_request.is_passive {
+            xml_object.add_attribute(
+                EcpRequest::ATTRIB_IS_PASSIVE.to_string(),
+                is_passive.to_string(),
+            );
+        }
+        if let Some(provider_name) = ecp_request.provider_name {
+            xml_object.add_attribute(EcpRequest::ATTRIB_PROVIDER_NAME.to_string(), provider_name);
+        }
+        xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(
+            ecp_request.issuer,
+        )?)));
+        if let Some(idp_list) = ecp_request.idp_list {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(idp_list)?)));
+        }
+        Ok(xml_object)
+    }
+}
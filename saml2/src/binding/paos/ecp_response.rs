@@ -0,0 +1,67 @@
+use std::cell::Ref;
+
+use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+
+use super::{add_header_attributes, ECP_NS_PREFIX, ECP_NS_URI};
+
+/// `ecp:Response` header of the IdP, where the enclosed response is meant to be delivered
+#[derive(Debug, Default)]
+pub struct EcpResponse {
+    assertion_consumer_service_url: String,
+}
+
+impl SAML2Obj for EcpResponse {}
+
+impl EcpResponse {
+    const ATTRIB_ASSERTION_CONSUMER_SERVICE_URL: &'static str = "AssertionConsumerServiceURL";
+
+    pub const ELEMENT_NAME: &'static str = "Response";
+
+    pub fn new(assertion_consumer_service_url: String) -> Self {
+        EcpResponse {
+            assertion_consumer_service_url,
+        }
+    }
+
+    #[inline]
+    pub fn assertion_consumer_service_url(&self) -> &str {
+        &self.assertion_consumer_service_url
+    }
+
+    #[inline]
+    pub fn set_assertion_consumer_service_url(&mut self, assertion_consumer_service_url: String) {
+        self.assertion_consumer_service_url = assertion_consumer_service_url;
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for EcpResponse {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        match element.attribute(EcpResponse::ATTRIB_ASSERTION_CONSUMER_SERVICE_URL) {
+            Some(url) => Ok(EcpResponse::new(url.to_string())),
+            None => Err(SAMLError::UnmarshallingError(
+                "ecp:Response must have an AssertionConsumerServiceURL".to_string(),
+            )),
+        }
+    }
+}
+
+impl TryFrom<EcpResponse> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(ecp_response: EcpResponse) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(ECP_NS_URI.to_string()),
+            EcpResponse::ELEMENT_NAME.to_string(),
+            Some(ECP_NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(ECP_NS_PREFIX.to_string(), ECP_NS_URI.to_string());
+        add_header_attributes(&mut xml_object);
+        xml_object.add_attribute(
+            EcpResponse::ATTRIB_ASSERTION_CONSUMER_SERVICE_URL.to_string(),
+            ecp_response.assertion_consumer_service_url,
+        );
+        Ok(xml_object)
+    }
+}
@@ -0,0 +1,318 @@
+pub mod ecp_relay_state;
+pub mod ecp_request;
+pub mod ecp_response;
+pub mod paos_request;
+pub mod paos_response;
+
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    core::{
+        authn_request::AuthnRequest, idp_list::IDPList, request_abstract_type::RequestAbstractType,
+    },
+    error::SAMLError,
+    xml::XmlObject,
+};
+
+use super::soap::{
+    decode_envelope, encode_envelope, envelope::Envelope, fault::Fault, SOAPTransport,
+    SOAP11_NS_PREFIX, SOAP11_NS_URI,
+};
+
+use ecp_relay_state::EcpRelayState;
+use ecp_request::EcpRequest;
+use ecp_response::EcpResponse;
+use paos_request::PaosRequest;
+use paos_response::PaosResponse;
+
+pub const PAOS_NS_PREFIX: &str = "paos";
+pub const PAOS_NS_URI: &str = "urn:liberty:paos:2003-08";
+pub const ECP_NS_PREFIX: &str = "ecp";
+pub const ECP_NS_URI: &str = "urn:oasis:names:tc:SAML:2.0:profiles:SSO:ecp";
+
+/// the `ProtocolBinding` of an AuthnRequest answered through the ECP client
+pub const PAOS_BINDING: &str = "urn:oasis:names:tc:SAML:2.0:bindings:PAOS";
+
+/// the media type a client accepts and the SP answers PAOS requests with
+pub const PAOS_CONTENT_TYPE: &str = "application/vnd.paos+xml";
+
+/// the `PAOS` HTTP header value of a client advertising the ECP service
+pub const PAOS_HEADER: &str =
+    "ver=\"urn:liberty:paos:2003-08\";\"urn:oasis:names:tc:SAML:2.0:profiles:SSO:ecp\"";
+
+const SOAP_ACTOR_NEXT: &str = "http://schemas.xmlsoap.org/soap/actor/next";
+const ATTRIB_MUST_UNDERSTAND: &str = "soap11:mustUnderstand";
+const ATTRIB_ACTOR: &str = "soap11:actor";
+
+/// PAOS and ECP header blocks must be understood by, and are addressed to, the next hop
+pub(crate) fn add_header_attributes(xml_object: &mut XmlObject) {
+    xml_object.add_namespace(SOAP11_NS_PREFIX.to_string(), SOAP11_NS_URI.to_string());
+    xml_object.add_attribute(ATTRIB_MUST_UNDERSTAND.to_string(), "1".to_string());
+    xml_object.add_attribute(ATTRIB_ACTOR.to_string(), SOAP_ACTOR_NEXT.to_string());
+}
+
+fn find_header<'a>(
+    envelope: &'a Envelope,
+    namespace_uri: &str,
+    local_name: &str,
+) -> Option<&'a Rc<RefCell<XmlObject>>> {
+    envelope.headers().iter().find(|header| {
+        let header = header.borrow();
+        header.q_name().namespace_uri() == Some(namespace_uri)
+            && header.q_name().local_name() == local_name
+    })
+}
+
+fn header(header: XmlObject) -> Rc<RefCell<XmlObject>> {
+    Rc::new(RefCell::new(header))
+}
+
+/// Build the PAOS request of the SP: `authn_request` is bound to PAOS with the response
+/// consumed at `assertion_consumer_service_url`, the `ecp:Request` header repeats its
+/// issuer for the client and `idp_list` names the identity providers the SP trusts.
+pub fn ecp_authn_request_envelope(
+    mut authn_request: AuthnRequest,
+    assertion_consumer_service_url: &str,
+    idp_list: Option<IDPList>,
+    relay_state: Option<&str>,
+) -> Result<Envelope, SAMLError> {
+    let issuer = authn_request.issuer().cloned().ok_or_else(|| {
+        SAMLError::MessageEncodingError("ECP AuthnRequest must have an Issuer".to_string())
+    })?;
+    authn_request.set_protocol_binding(Some(PAOS_BINDING.to_string()));
+    authn_request
+        .set_assertion_consumer_service_url(Some(assertion_consumer_service_url.to_string()));
+
+    let mut paos_request = PaosRequest::new(assertion_consumer_service_url.to_string());
+    paos_request.set_message_id(Some(authn_request.id().to_string()));
+    let mut ecp_request = EcpRequest::new(issuer);
+    ecp_request.set_is_passive(authn_request.is_passive());
+    ecp_request.set_provider_name(authn_request.provider_name().cloned());
+    ecp_request.set_idp_list(idp_list);
+
+    let mut envelope = Envelope::new(XmlObject::try_from(authn_request)?);
+    envelope.add_header(header(XmlObject::try_from(paos_request)?));
+    envelope.add_header(header(XmlObject::try_from(ecp_request)?));
+    if let Some(relay_state) = relay_state {
+        let relay_state = EcpRelayState::new(relay_state.to_string());
+        envelope.add_header(header(XmlObject::try_from(relay_state)?));
+    }
+    Ok(envelope)
+}
+
+/// Build the answer of the IdP: `response` is delivered by the client to
+/// `assertion_consumer_service_url`.
+pub fn ecp_response_envelope(
+    response: XmlObject,
+    assertion_consumer_service_url: &str,
+) -> Result<Envelope, SAMLError> {
+    let ecp_response = EcpResponse::new(assertion_consumer_service_url.to_string());
+    let mut envelope = Envelope::new(response);
+    envelope.add_header(header(XmlObject::try_from(ecp_response)?));
+    Ok(envelope)
+}
+
+/// The enhanced client of the ECP profile, relaying the AuthnRequest of an SP to the IdP
+/// and the Response back, both over `transport`.
+pub struct EcpClient<'a> {
+    transport: &'a dyn SOAPTransport,
+}
+
+impl<'a> EcpClient<'a> {
+    pub fn new(transport: &'a dyn SOAPTransport) -> Self {
+        EcpClient { transport }
+    }
+
+    /// Run the client side of the profile on `sp_envelope`, the PAOS request of the SP:
+    /// its AuthnRequest goes to the SSO service of the IdP at `idp_sso_url` and the
+    /// Response answered to the `responseConsumerURL` of the SP, whose answer is returned.
+    pub fn authenticate(&self, sp_envelope: &str, idp_sso_url: &str) -> Result<String, SAMLError> {
+        let sp_envelope = decode_envelope(sp_envelope)?;
+        let paos_request = find_header(&sp_envelope, PAOS_NS_URI, PaosRequest::ELEMENT_NAME)
+            .ok_or_else(|| {
+                SAMLError::MessageDecodingError("missing paos:Request header".to_string())
+            })?;
+        let paos_request = PaosRequest::try_from(paos_request.borrow())?;
+        if find_header(&sp_envelope, ECP_NS_URI, EcpRequest::ELEMENT_NAME).is_none() {
+            return Err(SAMLError::MessageDecodingError(
+                "missing ecp:Request header".to_string(),
+            ));
+        }
+        let relay_state = match find_header(&sp_envelope, ECP_NS_URI, EcpRelayState::ELEMENT_NAME) {
+            Some(relay_state) => Some(EcpRelayState::try_from(relay_state.borrow())?),
+            None => None,
+        };
+
+        // the IdP gets the bare AuthnRequest, the headers are for the client only
+        let mut idp_request = Envelope::default();
+        idp_request.set_body(sp_envelope.body().cloned());
+        let idp_envelope = self
+            .transport
+            .send(idp_sso_url, &encode_envelope(idp_request)?)?;
+        self.forward_response(&idp_envelope, &paos_request, relay_state)
+    }
+
+    /// Deliver the Response in `idp_envelope` to the SP that sent `paos_request`, unless
+    /// the IdP meant it for another `AssertionConsumerServiceURL`: then the SP gets a
+    /// SOAP fault and the Response is withheld.
+    pub fn forward_response(
+        &self,
+        idp_envelope: &str,
+        paos_request: &PaosRequest,
+        relay_state: Option<EcpRelayState>,
+    ) -> Result<String, SAMLError> {
+        let idp_envelope = decode_envelope(idp_envelope)?;
+        let ecp_response = find_header(&idp_envelope, ECP_NS_URI, EcpResponse::ELEMENT_NAME)
+            .ok_or_else(|| {
+                SAMLError::MessageDecodingError("missing ecp:Response header".to_string())
+            })?;
+        let ecp_response = EcpResponse::try_from(ecp_response.borrow())?;
+        let response = idp_envelope.into_message()?;
+
+        let mut paos_response = PaosResponse::default();
+        paos_response.set_ref_to_message_id(paos_request.message_id().cloned());
+        let consumer_url = paos_request.response_consumer_url();
+        if ecp_response.assertion_consumer_service_url() != consumer_url {
+            let fault = Fault::new(
+                Fault::CLIENT.to_string(),
+                "AssertionConsumerServiceURL does not match responseConsumerURL".to_string(),
+            );
+            let mut envelope = Envelope::new(XmlObject::try_from(fault)?);
+            envelope.add_header(header(XmlObject::try_from(paos_response)?));
+            self.transport
+                .send(consumer_url, &encode_envelope(envelope)?)?;
+            return Err(SAMLError::MessageDecodingError(format!(
+                "IdP AssertionConsumerServiceURL {} does not match SP responseConsumerURL {}",
+                ecp_response.assertion_consumer_service_url(),
+                consumer_url
+            )));
+        }
+
+        let mut envelope = Envelope::default();
+        envelope.set_body(Some(response));
+        envelope.add_header(header(XmlObject::try_from(paos_response)?));
+        if let Some(relay_state) = relay_state {
+            envelope.add_header(header(XmlObject::try_from(relay_state)?));
+        }
+        self.transport
+            .send(consumer_url, &encode_envelope(envelope)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        binding::{
+            decoding::registry::MessageRegistry,
+            soap::{decode_envelope, encode_envelope, InProcessTransport},
+        },
+        core::{
+            authn_request::AuthnRequest, issuer::Issuer,
+            request_abstract_type::RequestAbstractType, saml_version::SAMLVersion,
+        },
+        error::SAMLError,
+        util::InputStream,
+        xml::XmlObject,
+    };
+
+    use super::{
+        ecp_authn_request_envelope, ecp_relay_state::EcpRelayState, ecp_response_envelope,
+        find_header, paos_response::PaosResponse, EcpClient, ECP_NS_URI, PAOS_BINDING, PAOS_NS_URI,
+    };
+
+    const IDP_SSO: &str = "https://idp.example.org/SAML2/ECP";
+    const SP_ACS: &str = "https://sp.example.org/SAML2/ECP/ACS";
+    const REQUEST_ID: &str = "_3c39bc0fe7b13769cab2f6f45eba801b1245264310738";
+
+    fn sp_envelope() -> String {
+        let mut authn_request = AuthnRequest::default();
+        authn_request.set_id(REQUEST_ID.to_string());
+        authn_request.set_version(SAMLVersion::new(2, 0));
+        let mut issuer = Issuer::new();
+        issuer.set_value(Some("https://sp.example.org/metadata".to_string()));
+        authn_request.set_issuer(Some(issuer));
+        let envelope =
+            ecp_authn_request_envelope(authn_request, SP_ACS, None, Some("state-0042")).unwrap();
+        encode_envelope(envelope).unwrap()
+    }
+
+    fn response() -> XmlObject {
+        let root = XmlObject::parse_xml(InputStream::new(
+            format!(
+                r#"<saml2p:Response xmlns:saml2p="urn:oasis:names:tc:SAML:2.0:protocol" ID="_2b4e8f1a6c3d5e7f9a0b1c2d3e4f5a6b7c8d9e0f" InResponseTo="{}" Version="2.0" IssueInstant="2024-03-04T10:15:30Z"><saml2p:Status><saml2p:StatusCode Value="urn:oasis:names:tc:SAML:2.0:status:Success"/></saml2p:Status></saml2p:Response>"#,
+                REQUEST_ID
+            )
+            .into_bytes(),
+        ))
+        .unwrap();
+        Rc::try_unwrap(root).unwrap().into_inner()
+    }
+
+    /// an IdP answering at `IDP_SSO` for `acs` and an SP consuming at `SP_ACS`
+    fn stub<'a>(
+        acs: &'static str,
+        sp_calls: &'a RefCell<Vec<String>>,
+    ) -> impl Fn(&str, &str) -> Result<String, SAMLError> + 'a {
+        move |endpoint: &str, envelope: &str| {
+            let envelope = decode_envelope(envelope)?;
+            match endpoint {
+                IDP_SSO => {
+                    assert!(envelope.headers().is_empty());
+                    let request = envelope.into_message()?;
+                    let request = MessageRegistry::global().unmarshall_request(&request)?;
+                    let authn_request = request.as_any().downcast_ref::<AuthnRequest>().unwrap();
+                    assert_eq!(authn_request.protocol_binding().unwrap(), PAOS_BINDING);
+                    assert_eq!(
+                        authn_request.assertion_consumer_service_url().unwrap(),
+                        SP_ACS
+                    );
+                    encode_envelope(ecp_response_envelope(response(), acs)?)
+                }
+                SP_ACS => {
+                    let paos_response = find_header(&envelope, PAOS_NS_URI, "Response").unwrap();
+                    let paos_response = PaosResponse::try_from(paos_response.borrow())?;
+                    assert_eq!(paos_response.ref_to_message_id().unwrap(), REQUEST_ID);
+                    if let Some(fault) = envelope.fault()? {
+                        sp_calls.borrow_mut().push(fault.fault_code().to_string());
+                        return Ok(String::new());
+                    }
+                    let relay_state = find_header(&envelope, ECP_NS_URI, "RelayState").unwrap();
+                    let relay_state = EcpRelayState::try_from(relay_state.borrow())?;
+                    let response = envelope.into_message()?;
+                    let response = MessageRegistry::global().unmarshall_response(&response)?;
+                    sp_calls
+                        .borrow_mut()
+                        .push(response.in_response_to().unwrap().to_string());
+                    Ok(format!("welcome back, {}", relay_state.value()))
+                }
+                _ => Err(SAMLError::TransportError(format!(
+                    "unknown endpoint {}",
+                    endpoint
+                ))),
+            }
+        }
+    }
+
+    #[test]
+    fn we_can_forward_response_to_assertion_consumer_service() {
+        let sp_calls = RefCell::new(Vec::new());
+        let transport = InProcessTransport::new(stub(SP_ACS, &sp_calls));
+        let answer = EcpClient::new(&transport)
+            .authenticate(&sp_envelope(), IDP_SSO)
+            .unwrap();
+        assert_eq!(answer, "welcome back, state-0042");
+        assert_eq!(*sp_calls.borrow(), vec![REQUEST_ID.to_string()]);
+    }
+
+    #[test]
+    fn we_cannot_forward_response_to_another_assertion_consumer_service() {
+        let sp_calls = RefCell::new(Vec::new());
+        let transport =
+            InProcessTransport::new(stub("https://attacker.example.org/ACS", &sp_calls));
+        let result = EcpClient::new(&transport).authenticate(&sp_envelope(), IDP_SSO);
+        assert!(matches!(result, Err(SAMLError::MessageDecodingError(_))));
+        assert_eq!(*sp_calls.borrow(), vec!["soap11:Client".to_string()]);
+    }
+}
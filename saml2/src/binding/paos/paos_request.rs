@@ -0,0 +1,115 @@
+use std::cell::Ref;
+
+use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+
+use super::{add_header_attributes, ECP_NS_URI, PAOS_NS_PREFIX, PAOS_NS_URI};
+
+/// `paos:Request` header of the SP, where the client posts the answer to the enclosed
+/// request
+#[derive(Debug, Default)]
+pub struct PaosRequest {
+    response_consumer_url: String,
+    service: String,
+    message_id: Option<String>,
+}
+
+impl SAML2Obj for PaosRequest {}
+
+impl PaosRequest {
+    const ATTRIB_RESPONSE_CONSUMER_URL: &'static str = "responseConsumerURL";
+    const ATTRIB_SERVICE: &'static str = "service";
+    const ATTRIB_MESSAGE_ID: &'static str = "messageID";
+
+    pub const ELEMENT_NAME: &'static str = "Request";
+
+    /// a request for the ECP service
+    pub fn new(response_consumer_url: String) -> Self {
+        PaosRequest {
+            response_consumer_url,
+            service: ECP_NS_URI.to_string(),
+            message_id: None,
+        }
+    }
+
+    #[inline]
+    pub fn response_consumer_url(&self) -> &str {
+        &self.response_consumer_url
+    }
+
+    #[inline]
+    pub fn set_response_consumer_url(&mut self, response_consumer_url: String) {
+        self.response_consumer_url = response_consumer_url;
+    }
+
+    #[inline]
+    pub fn service(&self) -> &str {
+        &self.service
+    }
+
+    #[inline]
+    pub fn set_service(&mut self, service: String) {
+        self.service = service;
+    }
+
+    #[inline]
+    pub fn message_id(&self) -> Option<&String> {
+        self.message_id.as_ref()
+    }
+
+    #[inline]
+    pub fn set_message_id(&mut self, message_id: Option<String>) {
+        self.message_id = message_id;
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for PaosRequest {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut paos_request = PaosRequest::default();
+        for (key, value) in element.attributes() {
+            match key.as_str() {
+                PaosRequest::ATTRIB_RESPONSE_CONSUMER_URL => {
+                    paos_request.set_response_consumer_url(value.to_string());
+                }
+                PaosRequest::ATTRIB_SERVICE => paos_request.set_service(value.to_string()),
+                PaosRequest::ATTRIB_MESSAGE_ID => {
+                    paos_request.set_message_id(Some(value.to_string()));
+                }
+                _ => {}
+            }
+        }
+        if paos_request.response_consumer_url.is_empty() {
+            return Err(SAMLError::UnmarshallingError(
+                "paos:Request must have a responseConsumerURL".to_string(),
+            ));
+        }
+        Ok(paos_request)
+    }
+}
+
+impl TryFrom<PaosRequest> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(paos_request: PaosRequest) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(PAOS_NS_URI.to_string()),
+            PaosRequest::ELEMENT_NAME.to_string(),
+            Some(PAOS_NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(PAOS_NS_PREFIX.to_string(), PAOS_NS_URI.to_string());
+        add_header_attributes(&mut xml_object);
+        xml_object.add_attribute(
+            PaosRequest::ATTRIB_RESPONSE_CONSUMER_URL.to_string(),
+            paos_request.response_consumer_url,
+        );
+        xml_object.add_attribute(
+            PaosRequest::ATTRIB_SERVICE.to_string(),
+            paos_request.service,
+        );
+        if let Some(message_id) = paos_request.message_id {
+            xml_object.add_attribute(PaosRequest::ATTRIB_MESSAGE_ID.to_string(), message_id);
+        }
+        Ok(xml_object)
+    }
+}
@@ -0,0 +1,65 @@
+use std::cell::Ref;
+
+use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+
+use super::{add_header_attributes, PAOS_NS_PREFIX, PAOS_NS_URI};
+
+/// `paos:Response` header of the client, answering a `paos:Request`
+#[derive(Debug, Default)]
+pub struct PaosResponse {
+    ref_to_message_id: Option<String>,
+}
+
+impl SAML2Obj for PaosResponse {}
+
+impl PaosResponse {
+    const ATTRIB_REF_TO_MESSAGE_ID: &'static str = "refToMessageID";
+
+    pub const ELEMENT_NAME: &'static str = "Response";
+
+    /// the `messageID` of the answered `paos:Request`
+    #[inline]
+    pub fn ref_to_message_id(&self) -> Option<&String> {
+        self.ref_to_message_id.as_ref()
+    }
+
+    #[inline]
+    pub fn set_ref_to_message_id(&mut self, ref_to_message_id: Option<String>) {
+        self.ref_to_message_id = ref_to_message_id;
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for PaosResponse {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut paos_response = PaosResponse::default();
+        paos_response.set_ref_to_message_id(
+            element
+                .attribute(PaosResponse::ATTRIB_REF_TO_MESSAGE_ID)
+                .map(|value| value.to_string()),
+        );
+        Ok(paos_response)
+    }
+}
+
+impl TryFrom<PaosResponse> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(paos_response: PaosResponse) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(PAOS_NS_URI.to_string()),
+            PaosResponse::ELEMENT_NAME.to_string(),
+            Some(PAOS_NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(PAOS_NS_PREFIX.to_string(), PAOS_NS_URI.to_string());
+        add_header_attributes(&mut xml_object);
+        if let Some(ref_to_message_id) = paos_response.ref_to_message_id {
+            xml_object.add_attribute(
+                PaosResponse::ATTRIB_REF_TO_MESSAGE_ID.to_string(),
+                ref_to_message_id,
+            );
+        }
+        Ok(xml_object)
+    }
+}
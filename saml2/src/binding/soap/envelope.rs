@@ -0,0 +1,135 @@
+use std::cell::{Ref, RefCell};
+use std::rc::Rc;
+
+use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+
+use super::{fault::Fault, SOAP11_NS_PREFIX, SOAP11_NS_URI};
+
+/// `soap11:Envelope` with its `Header` blocks and the single element of its `Body`
+#[derive(Debug, Default)]
+pub struct Envelope {
+    headers: Vec<Rc<RefCell<XmlObject>>>,
+    body: Option<Rc<RefCell<XmlObject>>>,
+}
+
+impl SAML2Obj for Envelope {}
+
+impl Envelope {
+    const CHILD_HEADER: &'static str = "Header";
+    const CHILD_BODY: &'static str = "Body";
+
+    pub const ELEMENT_NAME: &'static str = "Envelope";
+
+    pub fn new(body: XmlObject) -> Self {
+        Envelope {
+            headers: Vec::new(),
+            body: Some(Rc::new(RefCell::new(body))),
+        }
+    }
+
+    #[inline]
+    pub fn headers(&self) -> &Vec<Rc<RefCell<XmlObject>>> {
+        &self.headers
+    }
+
+    #[inline]
+    pub fn add_header(&mut self, header: Rc<RefCell<XmlObject>>) {
+        self.headers.push(header);
+    }
+
+    #[inline]
+    pub fn body(&self) -> Option<&Rc<RefCell<XmlObject>>> {
+        self.body.as_ref()
+    }
+
+    #[inline]
+    pub fn set_body(&mut self, body: Option<Rc<RefCell<XmlObject>>>) {
+        self.body = body;
+    }
+
+    /// the `Fault` carried by the body, if any
+    pub fn fault(&self) -> Result<Option<Fault>, SAMLError> {
+        match &self.body {
+            Some(body) if is_fault(&body.borrow()) => Ok(Some(Fault::try_from(body.borrow())?)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Take the message out of the body, a `Fault` becomes [`SAMLError::SOAPFaultError`].
+    pub fn into_message(self) -> Result<Rc<RefCell<XmlObject>>, SAMLError> {
+        if let Some(fault) = self.fault()? {
+            return Err(fault.into());
+        }
+        self.body
+            .ok_or_else(|| SAMLError::MessageDecodingError("SOAP Body is empty".to_string()))
+    }
+}
+
+fn is_fault(element: &XmlObject) -> bool {
+    element.q_name().namespace_uri() == Some(SOAP11_NS_URI)
+        && element.q_name().local_name() == Fault::ELEMENT_NAME
+}
+
+fn soap_element(local_name: &str) -> XmlObject {
+    let mut xml_object = XmlObject::new(
+        Some(SOAP11_NS_URI.to_string()),
+        local_name.to_string(),
+        Some(SOAP11_NS_PREFIX.to_string()),
+    );
+    xml_object.add_namespace(SOAP11_NS_PREFIX.to_string(), SOAP11_NS_URI.to_string());
+    xml_object
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for Envelope {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        if element.q_name().namespace_uri() != Some(SOAP11_NS_URI)
+            || element.q_name().local_name() != Envelope::ELEMENT_NAME
+        {
+            return Err(SAMLError::UnmarshallingError(
+                "expected a SOAP 1.1 Envelope".to_string(),
+            ));
+        }
+        let mut envelope = Envelope::default();
+        for child in element.children() {
+            let child = child.borrow();
+            if child.q_name().namespace_uri() != Some(SOAP11_NS_URI) {
+                continue;
+            }
+            match child.q_name().local_name() {
+                Envelope::CHILD_HEADER => {
+                    for header in child.children() {
+                        envelope.add_header(Rc::clone(header));
+                    }
+                }
+                Envelope::CHILD_BODY => {
+                    envelope.set_body(child.children().first().cloned());
+                }
+                _ => {}
+            }
+        }
+        Ok(envelope)
+    }
+}
+
+impl TryFrom<Envelope> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(envelope: Envelope) -> Result<Self, Self::Error> {
+        let mut xml_object = soap_element(Envelope::ELEMENT_NAME);
+        if !envelope.headers.is_empty() {
+            let mut header = soap_element(Envelope::CHILD_HEADER);
+            for header_block in envelope.headers {
+                header.add_child(header_block);
+            }
+            xml_object.add_child(Rc::new(RefCell::new(header)));
+        }
+        let mut body = soap_element(Envelope::CHILD_BODY);
+        if let Some(message) = envelope.body {
+            body.add_child(message);
+        }
+        xml_object.add_child(Rc::new(RefCell::new(body)));
+        Ok(xml_object)
+    }
+}
@@ -0,0 +1,142 @@
+use std::cell::{Ref, RefCell};
+use std::rc::Rc;
+
+use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+
+use super::{SOAP11_NS_PREFIX, SOAP11_NS_URI};
+
+/// `soap11:Fault`, the error answer of a SOAP peer
+#[derive(Debug, Default)]
+pub struct Fault {
+    fault_code: String,
+    fault_string: String,
+    fault_actor: Option<String>,
+    detail: Option<Rc<RefCell<XmlObject>>>,
+}
+
+impl SAML2Obj for Fault {}
+
+impl Fault {
+    const CHILD_FAULT_CODE: &'static str = "faultcode";
+    const CHILD_FAULT_STRING: &'static str = "faultstring";
+    const CHILD_FAULT_ACTOR: &'static str = "faultactor";
+    const CHILD_DETAIL: &'static str = "detail";
+
+    /// the sender of the message is at fault
+    pub const CLIENT: &'static str = "soap11:Client";
+    /// the receiver of the message failed to process it
+    pub const SERVER: &'static str = "soap11:Server";
+
+    pub const ELEMENT_NAME: &'static str = "Fault";
+
+    pub fn new(fault_code: String, fault_string: String) -> Self {
+        Fault {
+            fault_code,
+            fault_string,
+            ..Default::default()
+        }
+    }
+
+    /// the qualified name of the fault, e.g. [`Fault::CLIENT`]
+    #[inline]
+    pub fn fault_code(&self) -> &str {
+        &self.fault_code
+    }
+
+    #[inline]
+    pub fn set_fault_code(&mut self, fault_code: String) {
+        self.fault_code = fault_code;
+    }
+
+    #[inline]
+    pub fn fault_string(&self) -> &str {
+        &self.fault_string
+    }
+
+    #[inline]
+    pub fn set_fault_string(&mut self, fault_string: String) {
+        self.fault_string = fault_string;
+    }
+
+    #[inline]
+    pub fn fault_actor(&self) -> Option<&String> {
+        self.fault_actor.as_ref()
+    }
+
+    #[inline]
+    pub fn set_fault_actor(&mut self, fault_actor: Option<String>) {
+        self.fault_actor = fault_actor;
+    }
+
+    #[inline]
+    pub fn detail(&self) -> Option<&Rc<RefCell<XmlObject>>> {
+        self.detail.as_ref()
+    }
+
+    #[inline]
+    pub fn set_detail(&mut self, detail: Option<Rc<RefCell<XmlObject>>>) {
+        self.detail = detail;
+    }
+}
+
+impl From<Fault> for SAMLError {
+    fn from(fault: Fault) -> Self {
+        SAMLError::SOAPFaultError {
+            fault_code: fault.fault_code,
+            fault_string: fault.fault_string,
+        }
+    }
+}
+
+/// an unqualified child element with text content, as the children of a fault are
+fn text_element(local_name: &str, text: String) -> Rc<RefCell<XmlObject>> {
+    let mut xml_object = XmlObject::new(None, local_name.to_string(), None);
+    xml_object.set_text(Some(text));
+    Rc::new(RefCell::new(xml_object))
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for Fault {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut fault = Fault::default();
+        for child in element.children() {
+            let text = child.borrow().text().map(|text| text.trim().to_string());
+            match child.borrow().q_name().local_name() {
+                Fault::CHILD_FAULT_CODE => fault.set_fault_code(text.unwrap_or_default()),
+                Fault::CHILD_FAULT_STRING => fault.set_fault_string(text.unwrap_or_default()),
+                Fault::CHILD_FAULT_ACTOR => fault.set_fault_actor(text),
+                Fault::CHILD_DETAIL => fault.set_detail(Some(Rc::clone(child))),
+                _ => {}
+            }
+        }
+        if fault.fault_code.is_empty() {
+            return Err(SAMLError::UnmarshallingError(
+                "Fault must have a faultcode".to_string(),
+            ));
+        }
+        Ok(fault)
+    }
+}
+
+impl TryFrom<Fault> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(fault: Fault) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(SOAP11_NS_URI.to_string()),
+            Fault::ELEMENT_NAME.to_string(),
+            Some(SOAP11_NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(SOAP11_NS_PREFIX.to_string(), SOAP11_NS_URI.to_string());
+        xml_object.add_child(text_element(Fault::CHILD_FAULT_CODE, fault.fault_code));
+        xml_object.add_child(text_element(Fault::CHILD_FAULT_STRING, fault.fault_string));
+        if let Some(fault_actor) = fault.fault_actor {
+            xml_object.add_child(text_element(Fault::CHILD_FAULT_ACTOR, fault_actor));
+        }
+        if let Some(detail) = fault.detail {
+            xml_object.add_child(detail);
+        }
+        Ok(xml_object)
+    }
+}
@@ -0,0 +1,183 @@
+pub mod envelope;
+pub mod fault;
+
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{error::SAMLError, util::InputStream, xml::XmlObject};
+
+use envelope::Envelope;
+
+pub const SOAP11_NS_PREFIX: &str = "soap11";
+pub const SOAP11_NS_URI: &str = "http://schemas.xmlsoap.org/soap/envelope/";
+
+/// the URI identifying the SAML SOAP binding, e.g. in metadata endpoints
+pub const SOAP_BINDING: &str = "urn:oasis:names:tc:SAML:2.0:bindings:SOAP";
+
+/// the `SOAPAction` HTTP header value of the SAML SOAP binding
+pub const SOAP_ACTION: &str = "http://www.oasis-open.org/committees/security";
+
+/// Carries a serialized SOAP envelope to `endpoint` and returns the envelope answered.
+pub trait SOAPTransport {
+    fn send(&self, endpoint: &str, envelope: &str) -> Result<String, SAMLError>;
+}
+
+/// A [`SOAPTransport`] answering in-process through `handler`, a stand-in for a live peer.
+pub struct InProcessTransport<F>
+where
+    F: Fn(&str, &str) -> Result<String, SAMLError>,
+{
+    handler: F,
+}
+
+impl<F> InProcessTransport<F>
+where
+    F: Fn(&str, &str) -> Result<String, SAMLError>,
+{
+    /// `handler` receives the endpoint and the serialized request envelope
+    pub fn new(handler: F) -> Self {
+        InProcessTransport { handler }
+    }
+}
+
+impl<F> SOAPTransport for InProcessTransport<F>
+where
+    F: Fn(&str, &str) -> Result<String, SAMLError>,
+{
+    fn send(&self, endpoint: &str, envelope: &str) -> Result<String, SAMLError> {
+        (self.handler)(endpoint, envelope)
+    }
+}
+
+/// serialize `envelope` for the wire
+pub fn encode_envelope(envelope: Envelope) -> Result<String, SAMLError> {
+    XmlObject::write_xml(Rc::new(RefCell::new(XmlObject::try_from(envelope)?)))
+        .map_err(|_| SAMLError::MessageEncodingError("invalid xml format!".to_string()))
+}
+
+/// parse an envelope received from the wire
+pub fn decode_envelope(envelope: &str) -> Result<Envelope, SAMLError> {
+    let root = XmlObject::parse_xml(InputStream::new(envelope.as_bytes().to_vec()))
+        .map_err(|_| SAMLError::MessageDecodingError("invalid xml format!".to_string()))?;
+    let envelope = Envelope::try_from(root.borrow())?;
+    Ok(envelope)
+}
+
+/// Send `message` to `endpoint` in a SOAP envelope and return the message answered, a
+/// `Fault` answer becomes [`SAMLError::SOAPFaultError`].
+pub fn send_message(
+    transport: &dyn SOAPTransport,
+    endpoint: &str,
+    message: XmlObject,
+) -> Result<Rc<RefCell<XmlObject>>, SAMLError> {
+    let answer = transport.send(endpoint, &encode_envelope(Envelope::new(message))?)?;
+    decode_envelope(&answer)?.into_message()
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use chrono::Duration;
+
+    use crate::{
+        binding::{
+            artifact::{issue_artifact, resolve_artifact, InMemoryArtifactStore},
+            decoding::registry::MessageRegistry,
+        },
+        core::{
+            artifact::Artifact, artifact_resolve::ArtifactResolve,
+            artifact_response::ArtifactResponse, request_abstract_type::RequestAbstractType,
+            saml_version::SAMLVersion,
+        },
+        error::SAMLError,
+        util::InputStream,
+        xml::XmlObject,
+    };
+
+    use super::{
+        decode_envelope, encode_envelope, envelope::Envelope, fault::Fault, send_message,
+        InProcessTransport,
+    };
+
+    const ARS: &str = "https://idp.example.org/artifact";
+
+    fn logout_response() -> XmlObject {
+        let root = XmlObject::parse_xml(InputStream::new(
+            br#"<saml2p:LogoutResponse xmlns:saml2p="urn:oasis:names:tc:SAML:2.0:protocol" ID="_7e6d5c4b3a2f1e0d9c8b7a6f5e4d3c2b1a0f9e8d7c" Version="2.0" IssueInstant="2024-03-04T10:15:30Z"><saml2p:Status><saml2p:StatusCode Value="urn:oasis:names:tc:SAML:2.0:status:Success"/></saml2p:Status></saml2p:LogoutResponse>"#
+                .to_vec(),
+        ))
+        .unwrap();
+        Rc::try_unwrap(root).unwrap().into_inner()
+    }
+
+    #[test]
+    fn we_can_resolve_artifact_over_soap() {
+        let store = InMemoryArtifactStore::default();
+        let artifact = issue_artifact(
+            &store,
+            "https://idp.example.org/metadata",
+            0,
+            logout_response(),
+            None,
+            Duration::minutes(1),
+        )
+        .unwrap();
+
+        let transport = InProcessTransport::new(|endpoint: &str, envelope: &str| {
+            assert_eq!(endpoint, ARS);
+            assert!(envelope.contains("soap11:Body"));
+            let request = decode_envelope(envelope)?.into_message()?;
+            let request = MessageRegistry::global().unmarshall_request(&request)?;
+            let artifact_resolve = request.as_any().downcast_ref::<ArtifactResolve>().unwrap();
+            let artifact_response = resolve_artifact(&store, artifact_resolve, None)?;
+            encode_envelope(Envelope::new(XmlObject::try_from(artifact_response)?))
+        });
+
+        let mut artifact_resolve = ArtifactResolve::default();
+        artifact_resolve.set_id("_resolve".to_string());
+        artifact_resolve.set_version(SAMLVersion::new(2, 0));
+        artifact_resolve.set_artifact(Artifact::new(artifact));
+        let answer = send_message(
+            &transport,
+            ARS,
+            XmlObject::try_from(artifact_resolve).unwrap(),
+        )
+        .unwrap();
+        let artifact_response = ArtifactResponse::try_from(answer.borrow()).unwrap();
+        let message = artifact_response.message().unwrap();
+        let logout_response = MessageRegistry::global()
+            .unmarshall_response(message)
+            .unwrap();
+        assert_eq!(
+            logout_response.id(),
+            "_7e6d5c4b3a2f1e0d9c8b7a6f5e4d3c2b1a0f9e8d7c"
+        );
+    }
+
+    #[test]
+    fn we_can_map_soap_fault_to_error() {
+        let transport = InProcessTransport::new(|_: &str, _: &str| {
+            let mut envelope = Envelope::default();
+            let fault = Fault::new(
+                Fault::SERVER.to_string(),
+                "resolver unavailable".to_string(),
+            );
+            envelope.set_body(Some(Rc::new(RefCell::new(XmlObject::try_from(fault)?))));
+            encode_envelope(envelope)
+        });
+        let result = send_message(&transport, ARS, logout_response());
+        assert!(matches!(
+            result,
+            Err(SAMLError::SOAPFaultError { fault_code, fault_string })
+                if fault_code == "soap11:Server" && fault_string == "resolver unavailable"
+        ));
+
+        let transport = InProcessTransport::new(|_: &str, _: &str| {
+            Err(SAMLError::TransportError("connection refused".to_string()))
+        });
+        assert!(matches!(
+            send_message(&transport, ARS, logout_response()),
+            Err(SAMLError::TransportError(_))
+        ));
+    }
+}
@@ -0,0 +1,62 @@
+use std::cell::Ref;
+
+use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+
+/// `saml2p:Artifact`, the base64-encoded artifact of the HTTP-Artifact binding
+#[derive(Debug, Default)]
+pub struct Artifact {
+    value: String,
+}
+
+impl SAML2Obj for Artifact {}
+
+impl Artifact {
+    pub const ELEMENT_NAME: &'static str = "Artifact";
+    pub const NS_PREFIX: &'static str = "saml2p";
+    pub const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:protocol";
+
+    pub fn new(value: String) -> Self {
+        Artifact { value }
+    }
+
+    #[inline]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    #[inline]
+    pub fn set_value(&mut self, value: String) {
+        self.value = value;
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for Artifact {
+    type Error = SAMLError;
+
+    fn try_from(object: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        match object.text() {
+            Some(value) => Ok(Artifact {
+                value: value.to_string(),
+            }),
+            None => Err(SAMLError::UnmarshallingError("Invalid XML".to_string())),
+        }
+    }
+}
+
+impl TryFrom<Artifact> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(artifact: Artifact) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(Artifact::NS_URI.to_string()),
+            Artifact::ELEMENT_NAME.to_string(),
+            Some(Artifact::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            Artifact::NS_PREFIX.to_string(),
+            Artifact::NS_URI.to_string(),
+        );
+        xml_object.set_text(Some(artifact.value.to_string()));
+        Ok(xml_object)
+    }
+}
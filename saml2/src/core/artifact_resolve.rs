@@ -0,0 +1,237 @@
+use std::any::Any;
+use std::cell::{Ref, RefCell};
+use std::rc::Rc;
+
+use chrono::{DateTime, Utc};
+
+use crate::common::SAML2Obj;
+use crate::core::parse_from_string;
+use crate::signature::signature::Signature;
+use crate::{error::SAMLError, xml::XmlObject};
+
+use super::{
+    artifact::Artifact, extensions::Extensions, issuer::Issuer,
+    request_abstract_type::RequestAbstractType, saml_version::SAMLVersion,
+};
+
+/// `saml2p:ArtifactResolve`, asks the issuer of an artifact for the message it stands for
+#[derive(Debug, Default)]
+pub struct ArtifactResolve {
+    id: String,
+    version: SAMLVersion,
+    issue_instant: DateTime<Utc>,
+    destination: Option<String>,
+    consent: Option<String>,
+    issuer: Option<Issuer>,
+    extensions: Option<Extensions>,
+    signature: Option<Signature>,
+    artifact: Artifact,
+}
+
+impl SAML2Obj for ArtifactResolve {}
+
+impl ArtifactResolve {
+    const ATTRIB_VERSION: &'static str = "Version";
+    const ATTRIB_ID: &'static str = "ID";
+    const ATTRIB_ISSUE_INSTANT: &'static str = "IssueInstant";
+    const ATTRIB_DESTINATION: &'static str = "Destination";
+    const ATTRIB_CONSENT: &'static str = "Consent";
+
+    const CHILD_ISSUER: &'static str = "Issuer";
+    const CHILD_SIGNATURE: &'static str = "Signature";
+    const CHILD_EXTENSIONS: &'static str = "Extensions";
+    const CHILD_ARTIFACT: &'static str = "Artifact";
+
+    pub const ELEMENT_NAME: &'static str = "ArtifactResolve";
+    pub const NS_PREFIX: &'static str = "saml2p";
+    pub const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:protocol";
+
+    #[inline]
+    pub fn artifact(&self) -> &Artifact {
+        &self.artifact
+    }
+
+    #[inline]
+    pub fn set_artifact(&mut self, artifact: Artifact) {
+        self.artifact = artifact;
+    }
+}
+
+impl RequestAbstractType for ArtifactResolve {
+    #[inline]
+    fn version(&self) -> &SAMLVersion {
+        &self.version
+    }
+
+    #[inline]
+    fn set_version(&mut self, saml_version: SAMLVersion) {
+        self.version = saml_version
+    }
+
+    #[inline]
+    fn id(&self) -> &String {
+        &self.id
+    }
+
+    #[inline]
+    fn set_id(&mut self, id: String) {
+        self.id = id;
+    }
+
+    #[inline]
+    fn issue_instant(&self) -> &DateTime<Utc> {
+        &self.issue_instant
+    }
+
+    #[inline]
+    fn set_issue_instant(&mut self, issue_instant: DateTime<Utc>) {
+        self.issue_instant = issue_instant
+    }
+
+    #[inline]
+    fn destination(&self) -> Option<&String> {
+        self.destination.as_ref()
+    }
+
+    #[inline]
+    fn set_destination(&mut self, destination: Option<String>) {
+        self.destination = destination
+    }
+
+    #[inline]
+    fn consent(&self) -> Option<&String> {
+        self.consent.as_ref()
+    }
+
+    #[inline]
+    fn set_consent(&mut self, consent: Option<String>) {
+        self.consent = consent
+    }
+
+    #[inline]
+    fn issuer(&self) -> Option<&Issuer> {
+        self.issuer.as_ref()
+    }
+
+    #[inline]
+    fn set_issuer(&mut self, issuer: Option<Issuer>) {
+        self.issuer = issuer
+    }
+
+    #[inline]
+    fn extensions(&self) -> Option<&Extensions> {
+        self.extensions.as_ref()
+    }
+
+    #[inline]
+    fn set_extensions(&mut self, extensions: Option<Extensions>) {
+        self.extensions = extensions
+    }
+
+    #[inline]
+    fn signature(&self) -> Option<&Signature> {
+        self.signature.as_ref()
+    }
+
+    #[inline]
+    fn set_signature(&mut self, signature: Option<Signature>) {
+        self.signature = signature
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for ArtifactResolve {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut artifact_resolve = ArtifactResolve::default();
+        for attribute in element.attributes() {
+            let (key, value) = (attribute.0.as_str(), attribute.1.as_str());
+            match key {
+                ArtifactResolve::ATTRIB_VERSION => {
+                    artifact_resolve.set_version(SAMLVersion::from_string(value)?);
+                }
+                ArtifactResolve::ATTRIB_ID => {
+                    artifact_resolve.set_id(value.to_string());
+                }
+                ArtifactResolve::ATTRIB_ISSUE_INSTANT => {
+                    artifact_resolve.set_issue_instant(parse_from_string(value)?);
+                }
+                ArtifactResolve::ATTRIB_DESTINATION => {
+                    artifact_resolve.set_destination(Some(value.to_string()));
+                }
+                ArtifactResolve::ATTRIB_CONSENT => {
+                    artifact_resolve.set_consent(Some(value.to_string()));
+                }
+                _ => {}
+            }
+        }
+        for child in element.children() {
+            let child = child.borrow();
+            match child.q_name().local_name() {
+                ArtifactResolve::CHILD_ISSUER => {
+                    artifact_resolve.set_issuer(Some(Issuer::try_from(child)?));
+                }
+                ArtifactResolve::CHILD_SIGNATURE => {
+                    artifact_resolve.set_signature(Some(Signature::try_from(child)?));
+                }
+                ArtifactResolve::CHILD_EXTENSIONS => {
+                    artifact_resolve.set_extensions(Some(Extensions::try_from(child)?));
+                }
+                ArtifactResolve::CHILD_ARTIFACT => {
+                    artifact_resolve.set_artifact(Artifact::try_from(child)?);
+                }
+                _ => {}
+            }
+        }
+        Ok(artifact_resolve)
+    }
+}
+
+impl TryFrom<ArtifactResolve> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(artifact_resolve: ArtifactResolve) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(ArtifactResolve::NS_URI.to_string()),
+            ArtifactResolve::ELEMENT_NAME.to_string(),
+            Some(ArtifactResolve::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            ArtifactResolve::NS_PREFIX.to_string(),
+            ArtifactResolve::NS_URI.to_string(),
+        );
+        xml_object.add_attribute(
+            ArtifactResolve::ATTRIB_VERSION.to_string(),
+            artifact_resolve.version.to_string(),
+        );
+        xml_object.add_attribute(ArtifactResolve::ATTRIB_ID.to_string(), artifact_resolve.id);
+        xml_object.add_attribute(
+            ArtifactResolve::ATTRIB_ISSUE_INSTANT.to_string(),
+            artifact_resolve.issue_instant.to_rfc3339(),
+        );
+        if let Some(destination) = artifact_resolve.destination {
+            xml_object.add_attribute(ArtifactResolve::ATTRIB_DESTINATION.to_string(), destination);
+        }
+        if let Some(consent) = artifact_resolve.consent {
+            xml_object.add_attribute(ArtifactResolve::ATTRIB_CONSENT.to_string(), consent);
+        }
+
+        if let Some(issuer) = artifact_resolve.issuer {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(issuer)?)));
+        }
+        if let Some(signature) = artifact_resolve.signature {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(signature)?)));
+        }
+        if let Some(extensions) = artifact_resolve.extensions {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(extensions)?)));
+        }
+        xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(
+            artifact_resolve.artifact,
+        )?)));
+        Ok(xml_object)
+    }
+}
@@ -0,0 +1,290 @@
+use std::any::Any;
+use std::cell::{Ref, RefCell};
+use std::rc::Rc;
+
+use chrono::{DateTime, Utc};
+
+use crate::common::SAML2Obj;
+use crate::core::parse_from_string;
+use crate::signature::signature::Signature;
+use crate::{error::SAMLError, xml::XmlObject};
+
+use super::{
+    extensions::Extensions, issuer::Issuer, saml_version::SAMLVersion, status::Status,
+    status_response_type::StatusResponseType,
+};
+
+/// `saml2p:ArtifactResponse`, carries the message an artifact stands for, if the artifact
+/// was known
+#[derive(Debug, Default)]
+pub struct ArtifactResponse {
+    id: String,
+    in_response_to: Option<String>,
+    version: SAMLVersion,
+    issue_instant: DateTime<Utc>,
+    destination: Option<String>,
+    consent: Option<String>,
+    issuer: Option<Issuer>,
+    signature: Option<Signature>,
+    extensions: Option<Extensions>,
+    status: Status,
+    message: Option<Rc<RefCell<XmlObject>>>,
+}
+
+impl SAML2Obj for ArtifactResponse {}
+
+impl ArtifactResponse {
+    const ATTRIB_ID: &'static str = "ID";
+    const ATTRIB_IN_RESPONSE_TO: &'static str = "InResponseTo";
+    const ATTRIB_VERSION: &'static str = "Version";
+    const ATTRIB_ISSUE_INSTANT: &'static str = "IssueInstant";
+    const ATTRIB_DESTINATION: &'static str = "Destination";
+    const ATTRIB_CONSENT: &'static str = "Consent";
+
+    const CHILD_ISSUER: &'static str = "Issuer";
+    const CHILD_SIGNATURE: &'static str = "Signature";
+    const CHILD_EXTENSIONS: &'static str = "Extensions";
+    const CHILD_STATUS: &'static str = "Status";
+
+    pub const ELEMENT_NAME: &'static str = "ArtifactResponse";
+    pub const NS_PREFIX: &'static str = "saml2p";
+    pub const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:protocol";
+
+    /// the resolved protocol message, see `binding::decoding::decode_message`
+    #[inline]
+    pub fn message(&self) -> Option<&Rc<RefCell<XmlObject>>> {
+        self.message.as_ref()
+    }
+
+    #[inline]
+    pub fn set_message(&mut self, message: Option<Rc<RefCell<XmlObject>>>) {
+        self.message = message;
+    }
+}
+
+impl StatusResponseType for ArtifactResponse {
+    #[inline]
+    fn id(&self) -> &String {
+        &self.id
+    }
+
+    #[inline]
+    fn set_id(&mut self, id: String) {
+        self.id = id;
+    }
+
+    #[inline]
+    fn in_response_to(&self) -> Option<&String> {
+        self.in_response_to.as_ref()
+    }
+
+    #[inline]
+    fn set_in_response_to(&mut self, in_response_to: Option<String>) {
+        self.in_response_to = in_response_to
+    }
+
+    #[inline]
+    fn version(&self) -> &SAMLVersion {
+        &self.version
+    }
+
+    #[inline]
+    fn set_version(&mut self, version: SAMLVersion) {
+        self.version = version
+    }
+
+    #[inline]
+    fn issue_instant(&self) -> DateTime<Utc> {
+        self.issue_instant
+    }
+
+    #[inline]
+    fn set_issue_instant(&mut self, issue_instant: DateTime<Utc>) {
+        self.issue_instant = issue_instant
+    }
+
+    #[inline]
+    fn destination(&self) -> Option<&String> {
+        self.destination.as_ref()
+    }
+
+    #[inline]
+    fn set_destination(&mut self, destination: Option<String>) {
+        self.destination = destination
+    }
+
+    #[inline]
+    fn consent(&self) -> Option<&String> {
+        self.consent.as_ref()
+    }
+
+    #[inline]
+    fn set_consent(&mut self, consent: Option<String>) {
+        self.consent = consent
+    }
+
+    #[inline]
+    fn issuer(&self) -> Option<&Issuer> {
+        self.issuer.as_ref()
+    }
+
+    #[inline]
+    fn set_issuer(&mut self, issuer: Option<Issuer>) {
+        self.issuer = issuer
+    }
+
+    #[inline]
+    fn signature(&self) -> Option<&Signature> {
+        self.signature.as_ref()
+    }
+
+    #[inline]
+    fn set_signature(&mut self, signature: Option<Signature>) {
+        self.signature = signature
+    }
+
+    #[inline]
+    fn extensions(&self) -> Option<&Extensions> {
+        self.extensions.as_ref()
+    }
+
+    #[inline]
+    fn set_extensions(&mut self, extensions: Option<Extensions>) {
+        self.extensions = extensions
+    }
+
+    #[inline]
+    fn status(&self) -> &Status {
+        &self.status
+    }
+
+    #[inline]
+    fn set_status(&mut self, status: Status) {
+        self.status = status
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for ArtifactResponse {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut artifact_response = ArtifactResponse::default();
+        for attribute in element.attributes() {
+            let (key, value) = (attribute.0.as_str(), attribute.1.as_str());
+            match key {
+                ArtifactResponse::ATTRIB_ID => {
+                    artifact_response.set_id(value.to_string());
+                }
+                ArtifactResponse::ATTRIB_IN_RESPONSE_TO => {
+                    artifact_response.set_in_response_to(Some(value.to_string()));
+                }
+                ArtifactResponse::ATTRIB_VERSION => {
+                    artifact_response.set_version(SAMLVersion::from_string(value)?);
+                }
+                ArtifactResponse::ATTRIB_ISSUE_INSTANT => {
+                    artifact_response.set_issue_instant(parse_from_string(value)?);
+                }
+                ArtifactResponse::ATTRIB_DESTINATION => {
+                    artifact_response.set_destination(Some(value.to_string()));
+                }
+                ArtifactResponse::ATTRIB_CONSENT => {
+                    artifact_response.set_consent(Some(value.to_string()));
+                }
+                _ => {}
+            }
+        }
+        for child in element.children() {
+            let child = child.borrow();
+            match child.q_name().local_name() {
+                ArtifactResponse::CHILD_ISSUER => {
+                    artifact_response.set_issuer(Some(Issuer::try_from(child)?));
+                }
+                ArtifactResponse::CHILD_SIGNATURE => {
+                    artifact_response.set_signature(Some(Signature::try_from(child)?));
+                }
+                ArtifactResponse::CHILD_EXTENSIONS => {
+                    artifact_response.set_extensions(Some(Extensions::try_from(child)?));
+                }
+                ArtifactResponse::CHILD_STATUS => {
+                    artifact_response.set_status(Status::try_from(child)?);
+                }
+                _ => {}
+            }
+        }
+        // the message is whatever element follows the status
+        artifact_response.set_message(
+            element
+                .children()
+                .iter()
+                .skip_while(|child| {
+                    child.borrow().q_name().local_name() != ArtifactResponse::CHILD_STATUS
+                })
+                .nth(1)
+                .cloned(),
+        );
+        Ok(artifact_response)
+    }
+}
+
+impl TryFrom<ArtifactResponse> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(artifact_response: ArtifactResponse) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(ArtifactResponse::NS_URI.to_string()),
+            ArtifactResponse::ELEMENT_NAME.to_string(),
+            Some(ArtifactResponse::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            ArtifactResponse::NS_PREFIX.to_string(),
+            ArtifactResponse::NS_URI.to_string(),
+        );
+        xml_object.add_attribute(
+            ArtifactResponse::ATTRIB_ID.to_string(),
+            artifact_response.id,
+        );
+        if let Some(in_response_to) = artifact_response.in_response_to {
+            xml_object.add_attribute(
+                ArtifactResponse::ATTRIB_IN_RESPONSE_TO.to_string(),
+                in_response_to,
+            );
+        }
+        xml_object.add_attribute(
+            ArtifactResponse::ATTRIB_VERSION.to_string(),
+            artifact_response.version.to_string(),
+        );
+        xml_object.add_attribute(
+            ArtifactResponse::ATTRIB_ISSUE_INSTANT.to_string(),
+            artifact_response.issue_instant.to_rfc3339(),
+        );
+        if let Some(destination) = artifact_response.destination {
+            xml_object.add_attribute(
+                ArtifactResponse::ATTRIB_DESTINATION.to_string(),
+                destination,
+            );
+        }
+        if let Some(consent) = artifact_response.consent {
+            xml_object.add_attribute(ArtifactResponse::ATTRIB_CONSENT.to_string(), consent);
+        }
+        if let Some(issuer) = artifact_response.issuer {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(issuer)?)));
+        }
+        if let Some(signature) = artifact_response.signature {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(signature)?)));
+        }
+        if let Some(extensions) = artifact_response.extensions {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(extensions)?)));
+        }
+        xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(
+            artifact_response.status,
+        )?)));
+        if let Some(message) = artifact_response.message {
+            xml_object.add_child(message);
+        }
+        Ok(xml_object)
+    }
+}
@@ -1,11 +1,15 @@
-use std::cell::Ref;
+use std::{
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
 
 use chrono::{DateTime, Utc};
 
-use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+use crate::{common::SAML2Obj, error::SAMLError, signature::signature::Signature, xml::XmlObject};
 
 use super::{
-    advice::Advice, authn_statement::AuthnStatement, conditions::Conditions, issuer::Issuer,
+    advice::Advice, attribute_statement::AttributeStatement, authn_statement::AuthnStatement,
+    conditions::Conditions, issuer::Issuer,
     parse_from_string, saml_version::SAMLVersion, statement::Statement, subject::Subject,
 };
 
@@ -15,7 +19,7 @@ pub struct Assertion {
     version: SAMLVersion,
     issue_instant: DateTime<Utc>,
     issuer: Issuer,
-    signature: Option<String>,
+    signature: Option<Signature>,
     subject: Option<Subject>,
     conditions: Option<Conditions>,
     advice: Option<Advice>,
@@ -37,6 +41,10 @@ impl Assertion {
     const CHILD_AUTHN_STATEMENT: &'static str = "AuthnStatement";
     const CHILD_ATTRIBUTE_STATEMENT: &'static str = "AttributeStatement";
 
+    pub const ELEMENT_NAME: &'static str = "Assertion";
+    pub const NS_PREFIX: &'static str = "saml2";
+    pub const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:assertion";
+
     pub fn id(&self) -> &String {
         &self.id
     }
@@ -69,11 +77,11 @@ impl Assertion {
         self.issuer = issuer
     }
 
-    pub fn signature(&self) -> Option<&String> {
+    pub fn signature(&self) -> Option<&Signature> {
         self.signature.as_ref()
     }
 
-    pub fn set_signature(&mut self, signature: Option<String>) {
+    pub fn set_signature(&mut self, signature: Option<Signature>) {
         self.signature = signature
     }
 
@@ -112,6 +120,13 @@ impl Assertion {
     pub fn add_statement(&mut self, statement: Box<dyn Statement>) {
         self.statements.push(statement)
     }
+
+    /// the `AttributeStatement`s among the statements of the assertion
+    pub fn attribute_statements(&self) -> impl Iterator<Item = &AttributeStatement> {
+        self.statements
+            .iter()
+            .filter_map(|statement| statement.as_any().downcast_ref::<AttributeStatement>())
+    }
 }
 
 impl TryFrom<Ref<'_, XmlObject>> for Assertion {
@@ -143,7 +158,7 @@ impl TryFrom<Ref<'_, XmlObject>> for Assertion {
                     assertion.set_issuer(Issuer::try_from(child)?);
                 }
                 Self::CHILD_SIGNATURE => {
-                    assertion.set_signature(Some(child.to_string()));
+                    assertion.set_signature(Some(Signature::try_from(child)?));
                 }
                 Self::CHILD_SUBJECT => {
                     assertion.set_subject(Some(Subject::try_from(child)?));
@@ -160,7 +175,9 @@ impl TryFrom<Ref<'_, XmlObject>> for Assertion {
                     );
                 }
                 Self::CHILD_ATTRIBUTE_STATEMENT => {
-                    // todo
+                    assertion.add_statement(
+                        Box::new(AttributeStatement::try_from(child)?) as Box<dyn Statement>
+                    );
                 }
                 _ => {}
             }
@@ -168,3 +185,44 @@ impl TryFrom<Ref<'_, XmlObject>> for Assertion {
         Ok(assertion)
     }
 }
+
+impl TryFrom<Assertion> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(assertion: Assertion) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(Assertion::NS_URI.to_string()),
+            Assertion::ELEMENT_NAME.to_string(),
+            Some(Assertion::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            Assertion::NS_PREFIX.to_string(),
+            Assertion::NS_URI.to_string(),
+        );
+        xml_object.add_attribute(
+            Assertion::ATTRIB_VERSION.to_string(),
+            assertion.version.to_string(),
+        );
+        xml_object.add_attribute(Assertion::ATTRIB_ID.to_string(), assertion.id);
+        xml_object.add_attribute(
+            Assertion::ATTRIB_ISSUE_INSTANT.to_string(),
+            assertion.issue_instant.to_rfc3339(),
+        );
+        xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(
+            assertion.issuer,
+        )?)));
+        if let Some(signature) = assertion.signature {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(signature)?)));
+        }
+        if let Some(subject) = assertion.subject {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(subject)?)));
+        }
+        if let Some(conditions) = assertion.conditions {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(conditions)?)));
+        }
+        for statement in assertion.statements {
+            xml_object.add_child(Rc::new(RefCell::new(statement.into_xml_object()?)));
+        }
+        Ok(xml_object)
+    }
+}
@@ -0,0 +1,160 @@
+use std::{
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
+
+use crate::{common::SAML2Obj, error::SAMLError, util::AttributeMap, xml::XmlObject};
+
+use super::attribute_value::AttributeValue;
+
+#[derive(Debug, Default)]
+pub struct Attribute {
+    name: String,
+    name_format: Option<String>,
+    friendly_name: Option<String>,
+    unknown_attributes: AttributeMap,
+    attribute_values: Vec<AttributeValue>,
+}
+
+impl SAML2Obj for Attribute {}
+
+impl Attribute {
+    const ATTRIB_NAME: &'static str = "Name";
+    const ATTRIB_NAME_FORMAT: &'static str = "NameFormat";
+    const ATTRIB_FRIENDLY_NAME: &'static str = "FriendlyName";
+
+    const CHILD_ATTRIBUTE_VALUE: &'static str = "AttributeValue";
+
+    pub const UNSPECIFIED: &'static str = "urn:oasis:names:tc:SAML:2.0:attrname-format:unspecified";
+    pub const URI_REFERENCE: &'static str = "urn:oasis:names:tc:SAML:2.0:attrname-format:uri";
+    pub const BASIC: &'static str = "urn:oasis:names:tc:SAML:2.0:attrname-format:basic";
+
+    pub const ELEMENT_NAME: &'static str = "Attribute";
+    pub const NS_PREFIX: &'static str = "saml2";
+    pub const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:assertion";
+
+    pub fn new(name: String) -> Self {
+        Attribute {
+            name,
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[inline]
+    pub fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    #[inline]
+    pub fn name_format(&self) -> Option<&String> {
+        self.name_format.as_ref()
+    }
+
+    #[inline]
+    pub fn set_name_format(&mut self, name_format: Option<String>) {
+        self.name_format = name_format;
+    }
+
+    #[inline]
+    pub fn friendly_name(&self) -> Option<&String> {
+        self.friendly_name.as_ref()
+    }
+
+    #[inline]
+    pub fn set_friendly_name(&mut self, friendly_name: Option<String>) {
+        self.friendly_name = friendly_name;
+    }
+
+    #[inline]
+    pub fn unknown_attributes(&self) -> &AttributeMap {
+        &self.unknown_attributes
+    }
+
+    #[inline]
+    pub fn unknown_attributes_mut(&mut self) -> &mut AttributeMap {
+        &mut self.unknown_attributes
+    }
+
+    #[inline]
+    pub fn attribute_values(&self) -> &Vec<AttributeValue> {
+        &self.attribute_values
+    }
+
+    #[inline]
+    pub fn add_attribute_value(&mut self, attribute_value: AttributeValue) {
+        self.attribute_values.push(attribute_value);
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for Attribute {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut attribute = Attribute::default();
+        let mut has_name = false;
+        for (key, value) in element.attributes() {
+            match key.as_str() {
+                Attribute::ATTRIB_NAME => {
+                    attribute.set_name(value.to_string());
+                    has_name = true;
+                }
+                Attribute::ATTRIB_NAME_FORMAT => {
+                    attribute.set_name_format(Some(value.to_string()));
+                }
+                Attribute::ATTRIB_FRIENDLY_NAME => {
+                    attribute.set_friendly_name(Some(value.to_string()));
+                }
+                _ => {
+                    attribute
+                        .unknown_attributes
+                        .add_attribute_of(&element, key, value);
+                }
+            }
+        }
+        if !has_name {
+            return Err(SAMLError::UnmarshallingError(
+                "Attribute must have a Name".to_string(),
+            ));
+        }
+        for child in element.children() {
+            let child = child.borrow();
+            if child.q_name().local_name() == Attribute::CHILD_ATTRIBUTE_VALUE {
+                attribute.add_attribute_value(AttributeValue::try_from(child)?);
+            }
+        }
+        Ok(attribute)
+    }
+}
+
+impl TryFrom<Attribute> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(attribute: Attribute) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(Attribute::NS_URI.to_string()),
+            Attribute::ELEMENT_NAME.to_string(),
+            Some(Attribute::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            Attribute::NS_PREFIX.to_string(),
+            Attribute::NS_URI.to_string(),
+        );
+        xml_object.add_attribute(Attribute::ATTRIB_NAME.to_string(), attribute.name);
+        if let Some(name_format) = attribute.name_format {
+            xml_object.add_attribute(Attribute::ATTRIB_NAME_FORMAT.to_string(), name_format);
+        }
+        if let Some(friendly_name) = attribute.friendly_name {
+            xml_object.add_attribute(Attribute::ATTRIB_FRIENDLY_NAME.to_string(), friendly_name);
+        }
+        attribute.unknown_attributes.write_to(&mut xml_object);
+        for attribute_value in attribute.attribute_values {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(attribute_value)?)));
+        }
+        Ok(xml_object)
+    }
+}
@@ -0,0 +1,256 @@
+use std::any::Any;
+use std::cell::{Ref, RefCell};
+use std::rc::Rc;
+
+use chrono::{DateTime, Utc};
+
+use crate::common::SAML2Obj;
+use crate::core::parse_from_string;
+use crate::signature::signature::Signature;
+use crate::{error::SAMLError, xml::XmlObject};
+
+use super::{
+    attribute::Attribute, extensions::Extensions, issuer::Issuer,
+    request_abstract_type::RequestAbstractType, saml_version::SAMLVersion, subject::Subject,
+};
+
+/// `saml2p:AttributeQuery`, asks for attributes of the subject, all of them when no
+/// `Attribute` is given
+#[derive(Debug, Default)]
+pub struct AttributeQuery {
+    id: String,
+    version: SAMLVersion,
+    issue_instant: DateTime<Utc>,
+    destination: Option<String>,
+    consent: Option<String>,
+    issuer: Option<Issuer>,
+    extensions: Option<Extensions>,
+    signature: Option<Signature>,
+    subject: Subject,
+    attributes: Vec<Attribute>,
+}
+
+impl SAML2Obj for AttributeQuery {}
+
+impl AttributeQuery {
+    const ATTRIB_VERSION: &'static str = "Version";
+    const ATTRIB_ID: &'static str = "ID";
+    const ATTRIB_ISSUE_INSTANT: &'static str = "IssueInstant";
+    const ATTRIB_DESTINATION: &'static str = "Destination";
+    const ATTRIB_CONSENT: &'static str = "Consent";
+
+    const CHILD_ISSUER: &'static str = "Issuer";
+    const CHILD_SIGNATURE: &'static str = "Signature";
+    const CHILD_EXTENSIONS: &'static str = "Extensions";
+    const CHILD_SUBJECT: &'static str = "Subject";
+    const CHILD_ATTRIBUTE: &'static str = "Attribute";
+
+    pub const ELEMENT_NAME: &'static str = "AttributeQuery";
+    pub const NS_PREFIX: &'static str = "saml2p";
+    pub const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:protocol";
+
+    #[inline]
+    pub fn subject(&self) -> &Subject {
+        &self.subject
+    }
+
+    #[inline]
+    pub fn set_subject(&mut self, subject: Subject) {
+        self.subject = subject;
+    }
+
+    #[inline]
+    pub fn attributes(&self) -> &Vec<Attribute> {
+        &self.attributes
+    }
+
+    #[inline]
+    pub fn add_attribute(&mut self, attribute: Attribute) {
+        self.attributes.push(attribute);
+    }
+}
+
+impl RequestAbstractType for AttributeQuery {
+    #[inline]
+    fn version(&self) -> &SAMLVersion {
+        &self.version
+    }
+
+    #[inline]
+    fn set_version(&mut self, saml_version: SAMLVersion) {
+        self.version = saml_version
+    }
+
+    #[inline]
+    fn id(&self) -> &String {
+        &self.id
+    }
+
+    #[inline]
+    fn set_id(&mut self, id: String) {
+        self.id = id;
+    }
+
+    #[inline]
+    fn issue_instant(&self) -> &DateTime<Utc> {
+        &self.issue_instant
+    }
+
+    #[inline]
+    fn set_issue_instant(&mut self, issue_instant: DateTime<Utc>) {
+        self.issue_instant = issue_instant
+    }
+
+    #[inline]
+    fn destination(&self) -> Option<&String> {
+        self.destination.as_ref()
+    }
+
+    #[inline]
+    fn set_destination(&mut self, destination: Option<String>) {
+        self.destination = destination
+    }
+
+    #[inline]
+    fn consent(&self) -> Option<&String> {
+        self.consent.as_ref()
+    }
+
+    #[inline]
+    fn set_consent(&mut self, consent: Option<String>) {
+        self.consent = consent
+    }
+
+    #[inline]
+    fn issuer(&self) -> Option<&Issuer> {
+        self.issuer.as_ref()
+    }
+
+    #[inline]
+    fn set_issuer(&mut self, issuer: Option<Issuer>) {
+        self.issuer = issuer
+    }
+
+    #[inline]
+    fn extensions(&self) -> Option<&Extensions> {
+        self.extensions.as_ref()
+    }
+
+    #[inline]
+    fn set_extensions(&mut self, extensions: Option<Extensions>) {
+        self.extensions = extensions
+    }
+
+    #[inline]
+    fn signature(&self) -> Option<&Signature> {
+        self.signature.as_ref()
+    }
+
+    #[inline]
+    fn set_signature(&mut self, signature: Option<Signature>) {
+        self.signature = signature
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for AttributeQuery {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut attribute_query = AttributeQuery::default();
+        for attribute in element.attributes() {
+            let (key, value) = (attribute.0.as_str(), attribute.1.as_str());
+            match key {
+                AttributeQuery::ATTRIB_VERSION => {
+                    attribute_query.set_version(SAMLVersion::from_string(value)?);
+                }
+                AttributeQuery::ATTRIB_ID => {
+                    attribute_query.set_id(value.to_string());
+                }
+                AttributeQuery::ATTRIB_ISSUE_INSTANT => {
+                    attribute_query.set_issue_instant(parse_from_string(value)?);
+                }
+                AttributeQuery::ATTRIB_DESTINATION => {
+                    attribute_query.set_destination(Some(value.to_string()));
+                }
+                AttributeQuery::ATTRIB_CONSENT => {
+                    attribute_query.set_consent(Some(value.to_string()));
+                }
+                _ => {}
+            }
+        }
+        for child in element.children() {
+            let child = child.borrow();
+            match child.q_name().local_name() {
+                AttributeQuery::CHILD_ISSUER => {
+                    attribute_query.set_issuer(Some(Issuer::try_from(child)?));
+                }
+                AttributeQuery::CHILD_SIGNATURE => {
+                    attribute_query.set_signature(Some(Signature::try_from(child)?));
+                }
+                AttributeQuery::CHILD_EXTENSIONS => {
+                    attribute_query.set_extensions(Some(Extensions::try_from(child)?));
+                }
+                AttributeQuery::CHILD_SUBJECT => {
+                    attribute_query.set_subject(Subject::try_from(child)?);
+                }
+                AttributeQuery::CHILD_ATTRIBUTE => {
+                    attribute_query.add_attribute(Attribute::try_from(child)?);
+                }
+                _ => {}
+            }
+        }
+        Ok(attribute_query)
+    }
+}
+
+impl TryFrom<AttributeQuery> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(attribute_query: AttributeQuery) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(AttributeQuery::NS_URI.to_string()),
+            AttributeQuery::ELEMENT_NAME.to_string(),
+            Some(AttributeQuery::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            AttributeQuery::NS_PREFIX.to_string(),
+            AttributeQuery::NS_URI.to_string(),
+        );
+        xml_object.add_attribute(
+            AttributeQuery::ATTRIB_VERSION.to_string(),
+            attribute_query.version.to_string(),
+        );
+        xml_object.add_attribute(AttributeQuery::ATTRIB_ID.to_string(), attribute_query.id);
+        xml_object.add_attribute(
+            AttributeQuery::ATTRIB_ISSUE_INSTANT.to_string(),
+            attribute_query.issue_instant.to_rfc3339(),
+        );
+        if let Some(destination) = attribute_query.destination {
+            xml_object.add_attribute(AttributeQuery::ATTRIB_DESTINATION.to_string(), destination);
+        }
+        if let Some(consent) = attribute_query.consent {
+            xml_object.add_attribute(AttributeQuery::ATTRIB_CONSENT.to_string(), consent);
+        }
+
+        if let Some(issuer) = attribute_query.issuer {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(issuer)?)));
+        }
+        if let Some(signature) = attribute_query.signature {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(signature)?)));
+        }
+        if let Some(extensions) = attribute_query.extensions {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(extensions)?)));
+        }
+        xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(
+            attribute_query.subject,
+        )?)));
+        for attribute in attribute_query.attributes {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(attribute)?)));
+        }
+        Ok(xml_object)
+    }
+}
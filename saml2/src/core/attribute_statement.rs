@@ -0,0 +1,213 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
+
+use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+
+use super::{attribute::Attribute, encrypted_attribute::EncryptedAttribute, statement::Statement};
+
+/// a child of an `AttributeStatement`, plain and encrypted attributes may be mixed
+#[derive(Debug)]
+pub enum AttributeStatementChild {
+    Attribute(Attribute),
+    EncryptedAttribute(Box<EncryptedAttribute>),
+}
+
+#[derive(Debug, Default)]
+pub struct AttributeStatement {
+    children: Vec<AttributeStatementChild>,
+}
+
+impl SAML2Obj for AttributeStatement {}
+
+impl Statement for AttributeStatement {
+    fn into_xml_object(self: Box<Self>) -> Result<XmlObject, SAMLError> {
+        XmlObject::try_from(*self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl AttributeStatement {
+    const CHILD_ATTRIBUTE: &'static str = "Attribute";
+    const CHILD_ENCRYPTED_ATTRIBUTE: &'static str = "EncryptedAttribute";
+
+    pub const ELEMENT_NAME: &'static str = "AttributeStatement";
+    pub const NS_PREFIX: &'static str = "saml2";
+    pub const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:assertion";
+
+    /// the attributes and encrypted attributes, in document order
+    #[inline]
+    pub fn children(&self) -> &Vec<AttributeStatementChild> {
+        &self.children
+    }
+
+    #[inline]
+    pub fn add_attribute(&mut self, attribute: Attribute) {
+        self.children
+            .push(AttributeStatementChild::Attribute(attribute));
+    }
+
+    #[inline]
+    pub fn add_encrypted_attribute(&mut self, encrypted_attribute: EncryptedAttribute) {
+        self.children
+            .push(AttributeStatementChild::EncryptedAttribute(Box::new(
+                encrypted_attribute,
+            )));
+    }
+
+    /// the attributes that are not encrypted
+    pub fn attributes(&self) -> Vec<&Attribute> {
+        self.children
+            .iter()
+            .filter_map(|child| match child {
+                AttributeStatementChild::Attribute(attribute) => Some(attribute),
+                AttributeStatementChild::EncryptedAttribute(_) => None,
+            })
+            .collect()
+    }
+
+    pub fn encrypted_attributes(&self) -> Vec<&EncryptedAttribute> {
+        self.children
+            .iter()
+            .filter_map(|child| match child {
+                AttributeStatementChild::Attribute(_) => None,
+                AttributeStatementChild::EncryptedAttribute(encrypted_attribute) => {
+                    Some(encrypted_attribute.as_ref())
+                }
+            })
+            .collect()
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for AttributeStatement {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut attribute_statement = AttributeStatement::default();
+        for child in element.children() {
+            let child = child.borrow();
+            match child.q_name().local_name() {
+                AttributeStatement::CHILD_ATTRIBUTE => {
+                    attribute_statement.add_attribute(Attribute::try_from(child)?);
+                }
+                AttributeStatement::CHILD_ENCRYPTED_ATTRIBUTE => {
+                    attribute_statement
+                        .add_encrypted_attribute(EncryptedAttribute::try_from(child)?);
+                }
+                _ => {}
+            }
+        }
+        Ok(attribute_statement)
+    }
+}
+
+impl TryFrom<AttributeStatement> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(attribute_statement: AttributeStatement) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(AttributeStatement::NS_URI.to_string()),
+            AttributeStatement::ELEMENT_NAME.to_string(),
+            Some(AttributeStatement::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            AttributeStatement::NS_PREFIX.to_string(),
+            AttributeStatement::NS_URI.to_string(),
+        );
+        for child in attribute_statement.children {
+            let child = match child {
+                AttributeStatementChild::Attribute(attribute) => XmlObject::try_from(attribute)?,
+                AttributeStatementChild::EncryptedAttribute(encrypted_attribute) => {
+                    XmlObject::try_from(*encrypted_attribute)?
+                }
+            };
+            xml_object.add_child(Rc::new(RefCell::new(child)));
+        }
+        Ok(xml_object)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        core::{assertion::Assertion, attribute_value::AttributeValue},
+        util::InputStream,
+        xml::XmlObject,
+    };
+
+    const ASSERTION: &str = r#"<saml2:Assertion xmlns:saml2="urn:oasis:names:tc:SAML:2.0:assertion" xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" ID="_a1" IssueInstant="2024-01-30T09:12:44Z" Version="2.0">
+  <saml2:Issuer>https://idp.example.org</saml2:Issuer>
+  <saml2:AttributeStatement>
+    <saml2:Attribute xmlns:x500="urn:oasis:names:tc:SAML:2.0:profiles:attribute:X500" x500:Encoding="LDAP" Name="urn:oid:0.9.2342.19200300.100.1.3" NameFormat="urn:oasis:names:tc:SAML:2.0:attrname-format:uri" FriendlyName="mail">
+      <saml2:AttributeValue xsi:type="xs:string">alice@example.org</saml2:AttributeValue>
+      <saml2:AttributeValue>alice@example.com</saml2:AttributeValue>
+    </saml2:Attribute>
+    <saml2:Attribute Name="age">
+      <saml2:AttributeValue xsi:type="xs:integer">42</saml2:AttributeValue>
+      <saml2:AttributeValue xsi:nil="true"/>
+    </saml2:Attribute>
+    <saml2:Attribute Name="address">
+      <saml2:AttributeValue><addr:Street xmlns:addr="urn:example:address">Main Street</addr:Street></saml2:AttributeValue>
+    </saml2:Attribute>
+  </saml2:AttributeStatement>
+</saml2:Assertion>"#;
+
+    fn parse(xml: String) -> Assertion {
+        let root = XmlObject::parse_xml(InputStream::new(xml.into_bytes())).unwrap();
+        let assertion = Assertion::try_from(root.borrow()).unwrap();
+        assertion
+    }
+
+    fn check(assertion: &Assertion) {
+        let attribute_statement = assertion.attribute_statements().next().unwrap();
+        let attributes = attribute_statement.attributes();
+        assert_eq!(attributes.len(), 3);
+
+        let mail = &attributes[0];
+        assert_eq!(mail.name(), "urn:oid:0.9.2342.19200300.100.1.3");
+        assert_eq!(mail.friendly_name().unwrap(), "mail");
+        assert_eq!(mail.unknown_attributes().get("x500:Encoding"), Some("LDAP"));
+        assert!(matches!(
+            &mail.attribute_values()[0],
+            AttributeValue::String(value) if value == "alice@example.org"
+        ));
+        assert!(matches!(
+            &mail.attribute_values()[1],
+            AttributeValue::AnyType(value) if value == "alice@example.com"
+        ));
+
+        let age = &attributes[1];
+        assert!(matches!(
+            &age.attribute_values()[0],
+            AttributeValue::Typed { schema_type, value } if schema_type == "integer" && value == "42"
+        ));
+        assert!(age.attribute_values()[1].is_nil());
+
+        match &attributes[2].attribute_values()[0] {
+            AttributeValue::Xml(children) => {
+                let street = children[0].borrow();
+                assert_eq!(street.q_name().local_name(), "Street");
+                assert_eq!(street.q_name().namespace_uri(), Some("urn:example:address"));
+                assert_eq!(street.text().unwrap(), "Main Street");
+            }
+            value => panic!("unexpected attribute value {:?}", value),
+        }
+    }
+
+    #[test]
+    fn we_can_parse_and_serialize_attribute_statement() {
+        let assertion = parse(ASSERTION.to_string());
+        check(&assertion);
+
+        let element = XmlObject::try_from(assertion).unwrap();
+        let xml = XmlObject::write_xml(Rc::new(RefCell::new(element))).unwrap();
+        check(&parse(xml));
+    }
+}
@@ -0,0 +1,165 @@
+use std::{
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
+
+use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+
+/// content of a `saml2:AttributeValue`, typed by its `xsi:type` and `xsi:nil`
+#[derive(Debug)]
+pub enum AttributeValue {
+    /// `xsi:type="xs:string"`
+    String(String),
+    /// simple content without a schema type or of `xs:anyType`
+    AnyType(String),
+    /// simple content of another built-in schema type, e.g. `integer` for `xs:integer`
+    Typed { schema_type: String, value: String },
+    /// element content
+    Xml(Vec<Rc<RefCell<XmlObject>>>),
+    /// `xsi:nil="true"`
+    Nil,
+}
+
+impl SAML2Obj for AttributeValue {}
+
+impl Default for AttributeValue {
+    fn default() -> Self {
+        AttributeValue::AnyType(String::new())
+    }
+}
+
+impl AttributeValue {
+    const ATTRIB_TYPE: &'static str = "type";
+    const ATTRIB_NIL: &'static str = "nil";
+
+    const TYPE_STRING: &'static str = "string";
+    const TYPE_ANY_TYPE: &'static str = "anyType";
+
+    pub const ELEMENT_NAME: &'static str = "AttributeValue";
+    pub const NS_PREFIX: &'static str = "saml2";
+    pub const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:assertion";
+
+    pub const XSI_PREFIX: &'static str = "xsi";
+    pub const XSI_NS_URI: &'static str = "http://www.w3.org/2001/XMLSchema-instance";
+    pub const XS_PREFIX: &'static str = "xs";
+    pub const XS_NS_URI: &'static str = "http://www.w3.org/2001/XMLSchema";
+
+    /// the simple content of the value, `None` for element content and nil values
+    pub fn text(&self) -> Option<&str> {
+        match self {
+            AttributeValue::String(value)
+            | AttributeValue::AnyType(value)
+            | AttributeValue::Typed { value, .. } => Some(value),
+            AttributeValue::Xml(_) | AttributeValue::Nil => None,
+        }
+    }
+
+    #[inline]
+    pub fn is_nil(&self) -> bool {
+        matches!(self, AttributeValue::Nil)
+    }
+}
+
+/// value of the attribute of `element` named `local_name` in the `xsi` namespace
+fn xsi_attribute<'a>(element: &'a XmlObject, local_name: &str) -> Option<&'a str> {
+    element.attributes().iter().find_map(|(key, value)| {
+        let (prefix, name) = key.split_once(':')?;
+        (name == local_name
+            && element.namespace().get(prefix).map(|uri| uri.as_str())
+                == Some(AttributeValue::XSI_NS_URI))
+        .then_some(value.as_str())
+    })
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for AttributeValue {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        if let Some("true" | "1") = xsi_attribute(&element, AttributeValue::ATTRIB_NIL) {
+            return Ok(AttributeValue::Nil);
+        }
+        if !element.children().is_empty() {
+            return Ok(AttributeValue::Xml(element.children().clone()));
+        }
+        let value = element.text().cloned().unwrap_or_default();
+        // the type is only kept for the built-in types of XML Schema
+        let schema_type =
+            xsi_attribute(&element, AttributeValue::ATTRIB_TYPE).and_then(|xsi_type| {
+                let (prefix, local_name) = xsi_type.split_once(':').unwrap_or(("", xsi_type));
+                (element.namespace().get(prefix).map(|uri| uri.as_str())
+                    == Some(AttributeValue::XS_NS_URI))
+                .then_some(local_name)
+            });
+        Ok(match schema_type {
+            Some(AttributeValue::TYPE_STRING) => AttributeValue::String(value),
+            None | Some(AttributeValue::TYPE_ANY_TYPE) => AttributeValue::AnyType(value),
+            Some(schema_type) => AttributeValue::Typed {
+                schema_type: schema_type.to_string(),
+                value,
+            },
+        })
+    }
+}
+
+impl TryFrom<AttributeValue> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(attribute_value: AttributeValue) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(AttributeValue::NS_URI.to_string()),
+            AttributeValue::ELEMENT_NAME.to_string(),
+            Some(AttributeValue::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            AttributeValue::NS_PREFIX.to_string(),
+            AttributeValue::NS_URI.to_string(),
+        );
+        let xsi_type = |schema_type: &str| format!("{}:{}", AttributeValue::XS_PREFIX, schema_type);
+        let (schema_type, value) = match attribute_value {
+            AttributeValue::String(value) => (Some(xsi_type(AttributeValue::TYPE_STRING)), value),
+            AttributeValue::AnyType(value) => (None, value),
+            AttributeValue::Typed { schema_type, value } => (Some(xsi_type(&schema_type)), value),
+            AttributeValue::Xml(children) => {
+                for child in children {
+                    xml_object.add_child(child);
+                }
+                return Ok(xml_object);
+            }
+            AttributeValue::Nil => {
+                xml_object.add_namespace(
+                    AttributeValue::XSI_PREFIX.to_string(),
+                    AttributeValue::XSI_NS_URI.to_string(),
+                );
+                xml_object.add_attribute(
+                    format!(
+                        "{}:{}",
+                        AttributeValue::XSI_PREFIX,
+                        AttributeValue::ATTRIB_NIL
+                    ),
+                    "true".to_string(),
+                );
+                return Ok(xml_object);
+            }
+        };
+        if let Some(schema_type) = schema_type {
+            xml_object.add_namespace(
+                AttributeValue::XSI_PREFIX.to_string(),
+                AttributeValue::XSI_NS_URI.to_string(),
+            );
+            xml_object.add_namespace(
+                AttributeValue::XS_PREFIX.to_string(),
+                AttributeValue::XS_NS_URI.to_string(),
+            );
+            xml_object.add_attribute(
+                format!(
+                    "{}:{}",
+                    AttributeValue::XSI_PREFIX,
+                    AttributeValue::ATTRIB_TYPE
+                ),
+                schema_type,
+            );
+        }
+        xml_object.set_text(Some(value));
+        Ok(xml_object)
+    }
+}
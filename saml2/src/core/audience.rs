@@ -43,9 +43,9 @@ impl TryFrom<Audience> for XmlObject {
 
     fn try_from(audience: Audience) -> Result<Self, Self::Error> {
         let mut xml_object = XmlObject::new(
-            Some(Audience::NS_PREFIX.to_string()),
-            Audience::ELEMENT_NAME.to_string(),
             Some(Audience::NS_URI.to_string()),
+            Audience::ELEMENT_NAME.to_string(),
+            Some(Audience::NS_PREFIX.to_string()),
         );
         xml_object.add_namespace(
             Audience::NS_PREFIX.to_string(),
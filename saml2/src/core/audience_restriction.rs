@@ -39,11 +39,8 @@ impl TryFrom<Ref<'_, XmlObject>> for AudienceRestriction {
         let mut audience_restriction = AudienceRestriction::default();
         for child in object.children() {
             let child = child.borrow();
-            match child.q_name().local_name() {
-                Self::CHILD_AUDIENCE => {
-                    audience_restriction.add_audiences(Audience::try_from(child)?);
-                }
-                _ => {}
+            if child.q_name().local_name() == Self::CHILD_AUDIENCE {
+                audience_restriction.add_audiences(Audience::try_from(child)?);
             }
         }
         Ok(audience_restriction)
@@ -55,9 +52,9 @@ impl TryFrom<AudienceRestriction> for XmlObject {
 
     fn try_from(audience_restriction: AudienceRestriction) -> Result<Self, Self::Error> {
         let mut xml_obj = XmlObject::new(
-            Some(AudienceRestriction::NS_PREFIX.to_string()),
-            AudienceRestriction::ELEMENT_NAME.to_string(),
             Some(AudienceRestriction::NS_URI.to_string()),
+            AudienceRestriction::ELEMENT_NAME.to_string(),
+            Some(AudienceRestriction::NS_PREFIX.to_string()),
         );
         xml_obj.add_namespace(
             AudienceRestriction::NS_PREFIX.to_string(),
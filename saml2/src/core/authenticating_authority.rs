@@ -44,9 +44,9 @@ impl TryFrom<AuthenticatingAuthority> for XmlObject {
 
     fn try_from(authenticating_authority: AuthenticatingAuthority) -> Result<Self, Self::Error> {
         let mut xml_object = XmlObject::new(
-            Some(AuthenticatingAuthority::NS_PREFIX.to_string()),
-            AuthenticatingAuthority::ELEMENT_NAME.to_string(),
             Some(AuthenticatingAuthority::NS_URI.to_string()),
+            AuthenticatingAuthority::ELEMENT_NAME.to_string(),
+            Some(AuthenticatingAuthority::NS_PREFIX.to_string()),
         );
         xml_object.add_namespace(
             AuthenticatingAuthority::NS_PREFIX.to_string(),
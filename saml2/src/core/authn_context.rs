@@ -117,9 +117,9 @@ impl TryFrom<AuthnContext> for XmlObject {
 
     fn try_from(authn_context: AuthnContext) -> Result<Self, Self::Error> {
         let mut xml_object = XmlObject::new(
-            Some(AuthnContext::NS_PREFIX.to_string()),
-            AuthnContext::ELEMENT_NAME.to_string(),
             Some(AuthnContext::NS_URI.to_string()),
+            AuthnContext::ELEMENT_NAME.to_string(),
+            Some(AuthnContext::NS_PREFIX.to_string()),
         );
         xml_object.add_namespace(
             AuthnContext::NS_PREFIX.to_string(),
@@ -43,9 +43,9 @@ impl TryFrom<AuthnContextClassRef> for XmlObject {
 
     fn try_from(authn_context_class_ref: AuthnContextClassRef) -> Result<Self, Self::Error> {
         let mut xml_object = XmlObject::new(
-            Some(AuthnContextClassRef::NS_PREFIX.to_string()),
-            AuthnContextClassRef::ELEMENT_NAME.to_string(),
             Some(AuthnContextClassRef::NS_URI.to_string()),
+            AuthnContextClassRef::ELEMENT_NAME.to_string(),
+            Some(AuthnContextClassRef::NS_PREFIX.to_string()),
         );
         xml_object.add_namespace(
             AuthnContextClassRef::NS_PREFIX.to_string(),
@@ -1,4 +1,4 @@
-use std::str::FromStr;
+use std::{fmt::Display, str::FromStr};
 
 use crate::{common::SAML2Obj, error::SAMLError};
 
@@ -33,13 +33,13 @@ impl FromStr for AuthnContextComparisonTypeEnumeration {
     }
 }
 
-impl ToString for AuthnContextComparisonTypeEnumeration {
-    fn to_string(&self) -> String {
+impl Display for AuthnContextComparisonTypeEnumeration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            AuthnContextComparisonTypeEnumeration::EXACT(s) => s.to_string(),
-            AuthnContextComparisonTypeEnumeration::MINIMUM(s) => s.to_string(),
-            AuthnContextComparisonTypeEnumeration::MAXIMUM(s) => s.to_string(),
-            AuthnContextComparisonTypeEnumeration::BETTER(s) => s.to_string(),
+            AuthnContextComparisonTypeEnumeration::EXACT(s) => write!(f, "{}", s),
+            AuthnContextComparisonTypeEnumeration::MINIMUM(s) => write!(f, "{}", s),
+            AuthnContextComparisonTypeEnumeration::MAXIMUM(s) => write!(f, "{}", s),
+            AuthnContextComparisonTypeEnumeration::BETTER(s) => write!(f, "{}", s),
         }
     }
 }
@@ -1,11 +1,14 @@
-use std::cell::Ref;
+use std::{
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
 
 use crate::{common::SAML2Obj, error::SAMLError, util::AttributeMap, xml::XmlObject};
 
 #[derive(Debug, Default)]
 pub struct AuthnContextDecl {
     text_content: Option<String>,
-    unknown_xml_objects: Vec<Box<dyn SAML2Obj>>,
+    unknown_xml_objects: Vec<Rc<RefCell<XmlObject>>>,
     unknown_attributes: AttributeMap,
 }
 
@@ -27,10 +30,15 @@ impl AuthnContextDecl {
     }
 
     #[inline]
-    pub fn unknown_xml_objects(&self) -> &Vec<Box<dyn SAML2Obj>> {
+    pub fn unknown_xml_objects(&self) -> &Vec<Rc<RefCell<XmlObject>>> {
         &self.unknown_xml_objects
     }
 
+    #[inline]
+    pub fn add_unknown_xml_object(&mut self, unknown_xml_object: Rc<RefCell<XmlObject>>) {
+        self.unknown_xml_objects.push(unknown_xml_object);
+    }
+
     #[inline]
     pub fn unknown_attributes(&self) -> &AttributeMap {
         &self.unknown_attributes
@@ -43,9 +51,13 @@ impl TryFrom<Ref<'_, XmlObject>> for AuthnContextDecl {
     fn try_from(object: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
         let mut authn_context_decl = AuthnContextDecl::default();
         for attrib in object.attributes() {
-            todo!("Handle attribute: {:?}", attrib);
+            authn_context_decl
+                .unknown_attributes
+                .add_attribute_of(&object, &attrib.0, &attrib.1);
+        }
+        for child in object.children() {
+            authn_context_decl.add_unknown_xml_object(Rc::clone(child));
         }
-        // TODO: Handle unknown XML objects
         authn_context_decl.set_text_content(object.text().map(|s| s.to_string()));
         Ok(authn_context_decl)
     }
@@ -56,9 +68,9 @@ impl TryFrom<AuthnContextDecl> for XmlObject {
 
     fn try_from(authn_context_decl: AuthnContextDecl) -> Result<XmlObject, Self::Error> {
         let mut xml_object = XmlObject::new(
-            Some(AuthnContextDecl::NS_PREFIX.to_string()),
-            AuthnContextDecl::ELEMENT_NAME.to_string(),
             Some(AuthnContextDecl::NS_URI.to_string()),
+            AuthnContextDecl::ELEMENT_NAME.to_string(),
+            Some(AuthnContextDecl::NS_PREFIX.to_string()),
         );
         xml_object.add_namespace(
             AuthnContextDecl::NS_PREFIX.to_string(),
@@ -67,7 +79,12 @@ impl TryFrom<AuthnContextDecl> for XmlObject {
         if let Some(text_content) = authn_context_decl.text_content() {
             xml_object.set_text(Some(text_content.to_string()));
         }
-        // TODO: Add unknown attributes and unknown XML objects
+        for child in authn_context_decl.unknown_xml_objects {
+            xml_object.add_child(child);
+        }
+        authn_context_decl
+            .unknown_attributes
+            .write_to(&mut xml_object);
         Ok(xml_object)
     }
 }
@@ -43,9 +43,9 @@ impl TryFrom<AuthnContextDeclRef> for XmlObject {
 
     fn try_from(authn_context_decl_ref: AuthnContextDeclRef) -> Result<Self, Self::Error> {
         let mut xml_object = XmlObject::new(
-            Some(AuthnContextDeclRef::NS_PREFIX.to_string()),
-            AuthnContextDeclRef::ELEMENT_NAME.to_string(),
             Some(AuthnContextDeclRef::NS_URI.to_string()),
+            AuthnContextDeclRef::ELEMENT_NAME.to_string(),
+            Some(AuthnContextDeclRef::NS_PREFIX.to_string()),
         );
         xml_object.add_namespace(
             AuthnContextDeclRef::NS_PREFIX.to_string(),
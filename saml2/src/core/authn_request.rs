@@ -5,6 +5,7 @@ use chrono::{DateTime, Utc};
 
 use crate::common::SAML2Obj;
 use crate::core::parse_from_string;
+use crate::signature::signature::Signature;
 use crate::{error::SAMLError, xml::XmlObject};
 
 use super::{
@@ -22,7 +23,7 @@ pub struct AuthnRequest {
     consent: Option<String>,
     issuer: Option<Issuer>,
     extensions: Option<Extensions>,
-    signature: Option<String>,
+    signature: Option<Signature>,
     subject: Option<Subject>,
     name_id_policy: Option<NameIDPolicy>,
     conditions: Option<Conditions>,
@@ -62,9 +63,9 @@ impl AuthnRequest {
     const CHILD_SCOPING: &'static str = "Scoping";
     const CHILD_SIGNATURE: &'static str = "Signature";
 
-    const ELEMENT_NAME: &'static str = "AuthnRequest";
-    const NS_PREFIX: &'static str = "saml2p";
-    const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:protocol";
+    pub const ELEMENT_NAME: &'static str = "AuthnRequest";
+    pub const NS_PREFIX: &'static str = "saml2p";
+    pub const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:protocol";
 
     #[inline]
     pub fn subject(&self) -> Option<&Subject> {
@@ -271,12 +272,12 @@ impl RequestAbstractType for AuthnRequest {
     }
 
     #[inline]
-    fn signature(&self) -> Option<&String> {
+    fn signature(&self) -> Option<&Signature> {
         self.signature.as_ref()
     }
 
     #[inline]
-    fn set_signature(&mut self, signature: Option<String>) {
+    fn set_signature(&mut self, signature: Option<Signature>) {
         self.signature = signature
     }
 
@@ -359,7 +360,9 @@ impl TryFrom<Ref<'_, XmlObject>> for AuthnRequest {
                 AuthnRequest::CHILD_EXTENSIONS => {
                     authn_request.set_extensions(Some(Extensions::try_from(child)?));
                 }
-                AuthnRequest::CHILD_SIGNATURE => {}
+                AuthnRequest::CHILD_SIGNATURE => {
+                    authn_request.set_signature(Some(Signature::try_from(child)?));
+                }
                 _ => {}
             }
         }
@@ -445,6 +448,12 @@ impl TryFrom<AuthnRequest> for XmlObject {
         if let Some(issuer) = authn_request.issuer {
             xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(issuer)?)));
         }
+        if let Some(signature) = authn_request.signature {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(signature)?)));
+        }
+        if let Some(extensions) = authn_request.extensions {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(extensions)?)));
+        }
         if let Some(subject) = authn_request.subject {
             xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(subject)?)));
         }
@@ -462,9 +471,6 @@ impl TryFrom<AuthnRequest> for XmlObject {
         if let Some(scoping) = authn_request.scoping {
             xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(scoping)?)));
         }
-        if let Some(extensions) = authn_request.extensions {
-            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(extensions)?)));
-        }
         Ok(xml_object)
     }
 }
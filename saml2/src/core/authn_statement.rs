@@ -1,4 +1,5 @@
 use std::{
+    any::Any,
     cell::{Ref, RefCell},
     rc::Rc,
 };
@@ -23,7 +24,15 @@ pub struct AuthnStatement {
 
 impl SAML2Obj for AuthnStatement {}
 
-impl Statement for AuthnStatement {}
+impl Statement for AuthnStatement {
+    fn into_xml_object(self: Box<Self>) -> Result<XmlObject, SAMLError> {
+        XmlObject::try_from(*self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
 
 impl AuthnStatement {
     const ATTRIB_SESSION_INDEX: &'static str = "SessionIndex";
@@ -123,3 +132,44 @@ impl TryFrom<Ref<'_, XmlObject>> for AuthnStatement {
         Ok(authn_statement)
     }
 }
+
+impl TryFrom<AuthnStatement> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(authn_statement: AuthnStatement) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(AuthnStatement::NS_URI.to_string()),
+            AuthnStatement::ELEMENT_NAME.to_string(),
+            Some(AuthnStatement::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            AuthnStatement::NS_PREFIX.to_string(),
+            AuthnStatement::NS_URI.to_string(),
+        );
+        xml_object.add_attribute(
+            AuthnStatement::ATTRIB_AUTHN_INSTANT.to_string(),
+            authn_statement.authn_instant.to_rfc3339(),
+        );
+        if let Some(session_index) = authn_statement.session_index {
+            xml_object.add_attribute(
+                AuthnStatement::ATTRIB_SESSION_INDEX.to_string(),
+                session_index,
+            );
+        }
+        if let Some(session_not_on_or_after) = authn_statement.session_not_on_or_after {
+            xml_object.add_attribute(
+                AuthnStatement::ATTRIB_SESSION_NOT_ON_OR_AFTER.to_string(),
+                session_not_on_or_after.to_rfc3339(),
+            );
+        }
+        if let Some(subject_locality) = authn_statement.subject_locality {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(
+                subject_locality,
+            )?)));
+        }
+        xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(
+            authn_statement.authn_context,
+        )?)));
+        Ok(xml_object)
+    }
+}
@@ -1,11 +1,12 @@
 use std::cell::Ref;
 
-use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+use crate::{common::SAML2Obj, error::SAMLError, util::AttributeMap, xml::XmlObject};
 
 #[derive(Clone, Debug)]
 pub struct BaseID {
     name_qualifier: Option<String>,
     sp_name_qualifier: Option<String>,
+    unknown_attributes: AttributeMap,
 }
 
 impl SAML2Obj for BaseID {}
@@ -14,14 +15,18 @@ impl BaseID {
     const ATTRIB_NAME_QUALIFIER: &'static str = "NameQualifier";
     const ATTRIB_SP_NAME_QUALIFIER: &'static str = "SPNameQualifier";
 
-    const ELEMENT_NAME: &'static str = "BaseID";
-    const NS_PREFIX: &'static str = "saml2";
-    const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:assertion";
+    const XSI_TYPE: &'static str = "type";
+    const XSI_NS_URI: &'static str = "http://www.w3.org/2001/XMLSchema-instance";
+
+    pub const ELEMENT_NAME: &'static str = "BaseID";
+    pub const NS_PREFIX: &'static str = "saml2";
+    pub const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:assertion";
 
     pub fn new(name_qualifier: Option<String>, sp_name_qualifier: Option<String>) -> Self {
         BaseID {
             name_qualifier,
             sp_name_qualifier,
+            unknown_attributes: AttributeMap::default(),
         }
     }
 
@@ -40,6 +45,30 @@ impl BaseID {
     pub fn set_sp_name_qualifier(&mut self, sp_name_qualifier: Option<String>) {
         self.sp_name_qualifier = sp_name_qualifier;
     }
+
+    /// the attributes of the concrete subtype, among them its `xsi:type`
+    pub fn unknown_attributes(&self) -> &AttributeMap {
+        &self.unknown_attributes
+    }
+
+    pub fn unknown_attributes_mut(&mut self) -> &mut AttributeMap {
+        &mut self.unknown_attributes
+    }
+
+    /// the `xsi:type` naming the concrete subtype, BaseID itself is abstract
+    pub fn xsi_type(&self) -> Option<&str> {
+        self.unknown_attributes.iter().find_map(|(name, value)| {
+            let (prefix, local_name) = name.split_once(':')?;
+            (local_name == BaseID::XSI_TYPE
+                && self
+                    .unknown_attributes
+                    .namespaces()
+                    .get(prefix)
+                    .map(|uri| uri.as_str())
+                    == Some(BaseID::XSI_NS_URI))
+            .then_some(value)
+        })
+    }
 }
 
 impl TryFrom<Ref<'_, XmlObject>> for BaseID {
@@ -55,9 +84,25 @@ impl TryFrom<Ref<'_, XmlObject>> for BaseID {
                 Self::ATTRIB_SP_NAME_QUALIFIER => {
                     base_id.set_sp_name_qualifier(Some(attribute.1.to_string()));
                 }
-                _ => {}
+                _ => {
+                    base_id.unknown_attributes.add_attribute_of(
+                        &element,
+                        &attribute.0,
+                        &attribute.1,
+                    );
+                }
             }
         }
+        // the prefix of the subtype name has to stay bound as well
+        let type_binding = base_id
+            .xsi_type()
+            .and_then(|xsi_type| xsi_type.split_once(':'))
+            .and_then(|(prefix, _)| Some((prefix.to_string(), element.namespace().get(prefix)?)));
+        if let Some((prefix, uri)) = type_binding {
+            base_id
+                .unknown_attributes
+                .add_namespace(prefix, uri.to_string());
+        }
         Ok(base_id)
     }
 }
@@ -83,6 +128,7 @@ impl TryFrom<BaseID> for XmlObject {
                 sp_name_qualifier.to_string(),
             );
         }
+        value.unknown_attributes.write_to(&mut object);
         Ok(object)
     }
 }
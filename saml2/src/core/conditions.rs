@@ -130,11 +130,8 @@ impl TryFrom<Ref<'_, XmlObject>> for Conditions {
         }
         for child in element.children() {
             let child = child.borrow();
-            match child.q_name().local_name() {
-                Conditions::CHILD_AUDIENCE_RESTRICTION => {
-                    conditions.audience_restriction = AudienceRestriction::try_from(child)?;
-                }
-                _ => {}
+            if child.q_name().local_name() == Conditions::CHILD_AUDIENCE_RESTRICTION {
+                conditions.audience_restriction = AudienceRestriction::try_from(child)?;
             }
         }
         Ok(conditions)
@@ -145,9 +142,9 @@ impl TryFrom<Conditions> for XmlObject {
     type Error = SAMLError;
     fn try_from(conditions: Conditions) -> Result<Self, Self::Error> {
         let mut xml_object = XmlObject::new(
-            Some(Conditions::NS_PREFIX.to_string()),
-            Conditions::ELEMENT_NAME.to_string(),
             Some(Conditions::NS_URI.to_string()),
+            Conditions::ELEMENT_NAME.to_string(),
+            Some(Conditions::NS_PREFIX.to_string()),
         );
         xml_object.add_namespace(
             Conditions::NS_PREFIX.to_string(),
@@ -1,6 +1,26 @@
-use crate::common::SAML2Obj;
+use std::cell::Ref;
 
-#[derive(Debug, Default, Clone)]
-pub struct EncryptedAssertion {}
+use crate::{error::SAMLError, xml::XmlObject};
 
-impl SAML2Obj for EncryptedAssertion {}
+use super::{
+    assertion::Assertion,
+    encrypted_element_type::{EncryptedContent, EncryptedElement},
+};
+
+/// `saml2:EncryptedAssertion`, the encrypted form of an `Assertion`
+pub type EncryptedAssertion = EncryptedElement<Assertion>;
+
+impl EncryptedContent for Assertion {
+    const ENCRYPTED_ELEMENT_NAME: &'static str = "EncryptedAssertion";
+    const ENCRYPTED_NS_PREFIX: &'static str = "saml2";
+    const ENCRYPTED_NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:assertion";
+
+    fn from_decrypted(element: Ref<'_, XmlObject>) -> Result<Self, SAMLError> {
+        if element.q_name().local_name() != Assertion::ELEMENT_NAME {
+            return Err(SAMLError::UnmarshallingError(
+                "decrypted element is not an Assertion".to_string(),
+            ));
+        }
+        Assertion::try_from(element)
+    }
+}
@@ -0,0 +1,99 @@
+use std::cell::Ref;
+
+use crate::{error::SAMLError, xml::XmlObject};
+
+use super::{
+    attribute::Attribute,
+    encrypted_element_type::{EncryptedContent, EncryptedElement},
+};
+
+/// `saml2:EncryptedAttribute`, the encrypted form of an `Attribute`
+pub type EncryptedAttribute = EncryptedElement<Attribute>;
+
+impl EncryptedContent for Attribute {
+    const ENCRYPTED_ELEMENT_NAME: &'static str = "EncryptedAttribute";
+    const ENCRYPTED_NS_PREFIX: &'static str = "saml2";
+    const ENCRYPTED_NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:assertion";
+
+    fn from_decrypted(element: Ref<'_, XmlObject>) -> Result<Self, SAMLError> {
+        if element.q_name().local_name() != Attribute::ELEMENT_NAME {
+            return Err(SAMLError::UnmarshallingError(
+                "decrypted element is not an Attribute".to_string(),
+            ));
+        }
+        Attribute::try_from(element)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        core::{
+            assertion::Assertion, attribute_statement::AttributeStatementChild,
+            attribute_value::AttributeValue,
+        },
+        signature::credential::Credential,
+        util::InputStream,
+        xml::XmlObject,
+    };
+
+    const PRIVATE_KEY: &str = include_str!("../../tests/resources/rsa_private_key.pem");
+    const ENCRYPTED_ATTRIBUTE: &str = include_str!("../../tests/resources/encrypted_attribute.xml");
+
+    #[test]
+    fn we_can_decrypt_encrypted_attribute_of_attribute_statement() {
+        let root = XmlObject::parse_xml(InputStream::new(ENCRYPTED_ATTRIBUTE.as_bytes().to_vec()))
+            .unwrap();
+        let assertion = Assertion::try_from(root.borrow()).unwrap();
+        let attribute_statement = assertion.attribute_statements().next().unwrap();
+        assert_eq!(attribute_statement.attributes().len(), 1);
+        assert_eq!(attribute_statement.encrypted_attributes().len(), 1);
+
+        let credential = Credential::from_private_key_pem(PRIVATE_KEY).unwrap();
+        let attribute = attribute_statement.encrypted_attributes()[0]
+            .decrypt(&credential)
+            .unwrap();
+        assert_eq!(attribute.name(), "urn:oid:2.5.4.42");
+        assert_eq!(attribute.friendly_name().unwrap(), "givenName");
+        assert!(matches!(
+            &attribute.attribute_values()[0],
+            AttributeValue::String(value) if value == "Alice"
+        ));
+    }
+
+    #[test]
+    fn we_can_serialize_attribute_statement_in_document_order() {
+        let xml = ENCRYPTED_ATTRIBUTE.replacen(
+            "</saml2:EncryptedAttribute>",
+            r#"</saml2:EncryptedAttribute>
+    <saml2:Attribute Name="urn:oid:2.5.4.4" FriendlyName="sn">
+      <saml2:AttributeValue>Smith</saml2:AttributeValue>
+    </saml2:Attribute>"#,
+            1,
+        );
+        let root = XmlObject::parse_xml(InputStream::new(xml.into_bytes())).unwrap();
+        let assertion = Assertion::try_from(root.borrow()).unwrap();
+        let element = XmlObject::try_from(assertion).unwrap();
+        let xml = XmlObject::write_xml(Rc::new(RefCell::new(element))).unwrap();
+
+        let root = XmlObject::parse_xml(InputStream::new(xml.into_bytes())).unwrap();
+        let assertion = Assertion::try_from(root.borrow()).unwrap();
+        let attribute_statement = assertion.attribute_statements().next().unwrap();
+        let children = attribute_statement.children();
+        assert_eq!(children.len(), 3);
+        assert!(matches!(
+            &children[0],
+            AttributeStatementChild::Attribute(attribute) if attribute.name() == "urn:oid:0.9.2342.19200300.100.1.1"
+        ));
+        assert!(matches!(
+            &children[1],
+            AttributeStatementChild::EncryptedAttribute(_)
+        ));
+        assert!(matches!(
+            &children[2],
+            AttributeStatementChild::Attribute(attribute) if attribute.name() == "urn:oid:2.5.4.4"
+        ));
+    }
+}
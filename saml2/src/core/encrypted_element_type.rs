@@ -1,11 +1,189 @@
-use crate::encryption::{encrypted_data::EncryptedData, encrypted_key::EncryptedKey};
+use std::{
+    cell::{Ref, RefCell},
+    fmt::Debug,
+    marker::PhantomData,
+    rc::Rc,
+};
 
+use crate::{
+    common::SAML2Obj,
+    encryption::{
+        algorithm::decryption_failed,
+        decrypter::decrypt_element,
+        encrypted_data::EncryptedData,
+        encrypted_key::EncryptedKey,
+        encrypter::{encrypt_element, KeyPlacement},
+    },
+    error::SAMLError,
+    signature::credential::Credential,
+    xml::XmlObject,
+};
+
+/// saml elements of `EncryptedElementType`: the encrypted content together with the
+/// `EncryptedKey` elements it may be decrypted with
 pub trait EncryptedElementType {
-    fn encrypted_data(&self) -> EncryptedData;
+    fn encrypted_data(&self) -> &EncryptedData;
 
     fn set_encrypted_data(&mut self, value: EncryptedData);
 
-    fn encrypted_keys(&self) -> Vec<EncryptedKey>;
+    fn encrypted_keys(&self) -> &Vec<EncryptedKey>;
 
     fn set_encrypted_keys(&mut self, value: Vec<EncryptedKey>);
 }
+
+/// what an [`EncryptedElement`] carries: the name of the encrypted element and how the
+/// decrypted element is parsed
+pub trait EncryptedContent: Sized {
+    /// the name of the encrypted element, e.g. `EncryptedAssertion` for an `Assertion`
+    const ENCRYPTED_ELEMENT_NAME: &'static str;
+    const ENCRYPTED_NS_PREFIX: &'static str;
+    const ENCRYPTED_NS_URI: &'static str;
+
+    /// parse the decrypted element, which may be of an unexpected type
+    fn from_decrypted(element: Ref<'_, XmlObject>) -> Result<Self, SAMLError>;
+}
+
+/// An element of `EncryptedElementType` whose content decrypts to a `T`, such as the
+/// `EncryptedAssertion` of an `Assertion`.
+pub struct EncryptedElement<T> {
+    encrypted_data: EncryptedData,
+    encrypted_keys: Vec<EncryptedKey>,
+    content: PhantomData<T>,
+}
+
+impl<T> Debug for EncryptedElement<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EncryptedElement")
+            .field("encrypted_data", &self.encrypted_data)
+            .field("encrypted_keys", &self.encrypted_keys)
+            .finish()
+    }
+}
+
+impl<T> Default for EncryptedElement<T> {
+    fn default() -> Self {
+        EncryptedElement {
+            encrypted_data: EncryptedData::default(),
+            encrypted_keys: Vec::new(),
+            content: PhantomData,
+        }
+    }
+}
+
+impl<T> SAML2Obj for EncryptedElement<T> {}
+
+impl<T: EncryptedContent> EncryptedElement<T> {
+    const CHILD_ENCRYPTED_DATA: &'static str = "EncryptedData";
+    const CHILD_ENCRYPTED_KEY: &'static str = "EncryptedKey";
+
+    pub const ELEMENT_NAME: &'static str = T::ENCRYPTED_ELEMENT_NAME;
+    pub const NS_PREFIX: &'static str = T::ENCRYPTED_NS_PREFIX;
+    pub const NS_URI: &'static str = T::ENCRYPTED_NS_URI;
+
+    #[inline]
+    pub fn add_encrypted_key(&mut self, encrypted_key: EncryptedKey) {
+        self.encrypted_keys.push(encrypted_key);
+    }
+
+    /// decrypt the content with the private key of `credential`, content that does not
+    /// parse is an opaque failure like any other, see `decryption_failed`
+    pub fn decrypt(&self, credential: &Credential) -> Result<T, SAMLError> {
+        let element = decrypt_element(self, credential)?;
+        let content = T::from_decrypted(element.borrow()).map_err(|_| decryption_failed());
+        content
+    }
+}
+
+impl<T: EncryptedContent> EncryptedElement<T>
+where
+    XmlObject: TryFrom<T, Error = SAMLError>,
+{
+    /// encrypt `content` for the owner of `credential`, see [`encrypt_element`]
+    pub fn encrypt(
+        content: T,
+        credential: &Credential,
+        key_placement: KeyPlacement,
+    ) -> Result<Self, SAMLError> {
+        let (encrypted_data, encrypted_keys) =
+            encrypt_element(XmlObject::try_from(content)?, credential, key_placement)?;
+        Ok(EncryptedElement {
+            encrypted_data,
+            encrypted_keys,
+            content: PhantomData,
+        })
+    }
+}
+
+impl<T> EncryptedElementType for EncryptedElement<T> {
+    #[inline]
+    fn encrypted_data(&self) -> &EncryptedData {
+        &self.encrypted_data
+    }
+
+    #[inline]
+    fn set_encrypted_data(&mut self, encrypted_data: EncryptedData) {
+        self.encrypted_data = encrypted_data;
+    }
+
+    #[inline]
+    fn encrypted_keys(&self) -> &Vec<EncryptedKey> {
+        &self.encrypted_keys
+    }
+
+    #[inline]
+    fn set_encrypted_keys(&mut self, encrypted_keys: Vec<EncryptedKey>) {
+        self.encrypted_keys = encrypted_keys;
+    }
+}
+
+impl<T: EncryptedContent> TryFrom<Ref<'_, XmlObject>> for EncryptedElement<T> {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut encrypted_element = EncryptedElement::default();
+        let mut has_encrypted_data = false;
+        for child in element.children() {
+            let child = child.borrow();
+            match child.q_name().local_name() {
+                Self::CHILD_ENCRYPTED_DATA => {
+                    encrypted_element.set_encrypted_data(EncryptedData::try_from(child)?);
+                    has_encrypted_data = true;
+                }
+                Self::CHILD_ENCRYPTED_KEY => {
+                    encrypted_element.add_encrypted_key(EncryptedKey::try_from(child)?);
+                }
+                _ => {}
+            }
+        }
+        if !has_encrypted_data {
+            return Err(SAMLError::UnmarshallingError(format!(
+                "{} must have an EncryptedData",
+                T::ENCRYPTED_ELEMENT_NAME
+            )));
+        }
+        Ok(encrypted_element)
+    }
+}
+
+impl<T: EncryptedContent> TryFrom<EncryptedElement<T>> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(encrypted_element: EncryptedElement<T>) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(T::ENCRYPTED_NS_URI.to_string()),
+            T::ENCRYPTED_ELEMENT_NAME.to_string(),
+            Some(T::ENCRYPTED_NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            T::ENCRYPTED_NS_PREFIX.to_string(),
+            T::ENCRYPTED_NS_URI.to_string(),
+        );
+        xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(
+            encrypted_element.encrypted_data,
+        )?)));
+        for encrypted_key in encrypted_element.encrypted_keys {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(encrypted_key)?)));
+        }
+        Ok(xml_object)
+    }
+}
@@ -1,92 +1,145 @@
-use std::{
-    cell::{Ref, RefCell},
-    rc::Rc,
-};
+use std::cell::Ref;
+
+use crate::{error::SAMLError, xml::XmlObject};
 
-use crate::{
-    common::SAML2Obj,
-    encryption::{encrypted_data::EncryptedData, encrypted_key::EncryptedKey},
-    error::SAMLError,
-    xml::XmlObject,
+use super::{
+    base_id::BaseID,
+    encrypted_element_type::{EncryptedContent, EncryptedElement},
+    name_id::NameID,
 };
 
-#[derive(Debug, Default)]
-pub struct EncryptedID {
-    encrypted_data: EncryptedData,
-    encrypted_keys: Vec<EncryptedKey>,
+/// the identifier an `EncryptedID` decrypts to
+#[derive(Debug)]
+pub enum DecryptedID {
+    NameID(NameID),
+    /// a subtype of the abstract `BaseID`, see `BaseID::xsi_type`
+    BaseID(BaseID),
 }
 
-impl SAML2Obj for EncryptedID {}
+/// `saml2:EncryptedID`, the encrypted form of a `NameID` or `BaseID`
+pub type EncryptedID = EncryptedElement<DecryptedID>;
 
-impl EncryptedID {
-    const CHILD_ENCRYPTED_DATA: &'static str = "EncryptedData";
-    const CHILD_ENCRYPTED_KEY: &'static str = "EncryptedKey";
+impl EncryptedContent for DecryptedID {
+    const ENCRYPTED_ELEMENT_NAME: &'static str = "EncryptedID";
+    const ENCRYPTED_NS_PREFIX: &'static str = "saml2";
+    const ENCRYPTED_NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:assertion";
 
-    const ELEMENT_NAME: &'static str = "EncryptedID";
-    const NS_PREFIX: &'static str = "saml2";
-    const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:assertion";
-
-    #[inline]
-    pub fn encrypted_data(&self) -> &EncryptedData {
-        &self.encrypted_data
+    fn from_decrypted(element: Ref<'_, XmlObject>) -> Result<Self, SAMLError> {
+        match element.q_name().local_name() {
+            NameID::ELEMENT_NAME => NameID::try_from(element).map(DecryptedID::NameID),
+            BaseID::ELEMENT_NAME => BaseID::try_from(element).map(DecryptedID::BaseID),
+            _ => Err(SAMLError::UnmarshallingError(
+                "decrypted element is not a NameID or BaseID".to_string(),
+            )),
+        }
     }
+}
+
+impl TryFrom<DecryptedID> for XmlObject {
+    type Error = SAMLError;
 
-    #[inline]
-    pub fn set_encrypted_data(&mut self, encrypted_data: EncryptedData) {
-        self.encrypted_data = encrypted_data;
+    fn try_from(decrypted_id: DecryptedID) -> Result<Self, Self::Error> {
+        match decrypted_id {
+            DecryptedID::NameID(name_id) => XmlObject::try_from(name_id),
+            DecryptedID::BaseID(base_id) => XmlObject::try_from(base_id),
+        }
     }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            abstract_name_id_type::AbstractNameIDType, base_id::BaseID,
+            encrypted_element_type::EncryptedElementType, logout_request::LogoutRequest,
+        },
+        encryption::encrypter::{encrypt_element, KeyPlacement},
+        error::SAMLError,
+        signature::credential::Credential,
+        util::InputStream,
+        xml::XmlObject,
+    };
+
+    use super::{DecryptedID, EncryptedID};
+
+    const PRIVATE_KEY: &str = include_str!("../../tests/resources/rsa_private_key.pem");
+    const ENCRYPTED_ID: &str = include_str!("../../tests/resources/encrypted_id.xml");
+
+    #[test]
+    fn we_can_decrypt_encrypted_id_to_name_id() {
+        let root =
+            XmlObject::parse_xml(InputStream::new(ENCRYPTED_ID.as_bytes().to_vec())).unwrap();
+        let logout_request = LogoutRequest::try_from(root.borrow()).unwrap();
+        assert!(logout_request.name_id().is_none());
 
-    #[inline]
-    pub fn encrypted_keys(&self) -> &Vec<EncryptedKey> {
-        self.encrypted_keys.as_ref()
+        let credential = Credential::from_private_key_pem(PRIVATE_KEY).unwrap();
+        let decrypted = logout_request
+            .encrypted_id()
+            .unwrap()
+            .decrypt(&credential)
+            .unwrap();
+        let DecryptedID::NameID(name_id) = decrypted else {
+            panic!("expected a NameID but got {:?}", decrypted);
+        };
+        assert_eq!(
+            name_id.value().unwrap(),
+            "a7f3c52e-4b1d-4e0a-9c3f-6d2b8e1f0a94"
+        );
+        assert_eq!(
+            name_id.format().unwrap(),
+            "urn:oasis:names:tc:SAML:2.0:nameid-format:persistent"
+        );
     }
 
-    #[inline]
-    pub fn add_encrypted_key(&mut self, encrypted_key: EncryptedKey) {
-        self.encrypted_keys.push(encrypted_key)
+    #[test]
+    fn we_cannot_parse_encrypted_id_without_encrypted_data() {
+        let xml = concat!(
+            r#"<saml2:EncryptedID xmlns:saml2="urn:oasis:names:tc:SAML:2.0:assertion">"#,
+            r#"<xenc:EncryptedKey xmlns:xenc="http://www.w3.org/2001/04/xmlenc#">"#,
+            "<xenc:CipherData><xenc:CipherValue>AAAA</xenc:CipherValue></xenc:CipherData>",
+            "</xenc:EncryptedKey>",
+            "</saml2:EncryptedID>"
+        );
+        let root = XmlObject::parse_xml(InputStream::new(xml.as_bytes().to_vec())).unwrap();
+        let result = EncryptedID::try_from(root.borrow());
+        assert!(
+            matches!(result, Err(SAMLError::UnmarshallingError(message)) if message.contains("EncryptedData"))
+        );
     }
-}
 
-impl TryFrom<Ref<'_, XmlObject>> for EncryptedID {
-    type Error = SAMLError;
+    #[test]
+    fn we_can_decrypt_encrypted_id_to_base_id_subtype() {
+        let mut base_id = BaseID::new(Some("https://idp.example.org/metadata".to_string()), None);
+        let unknown_attributes = base_id.unknown_attributes_mut();
+        unknown_attributes.add_namespace(
+            "xsi".to_string(),
+            "http://www.w3.org/2001/XMLSchema-instance".to_string(),
+        );
+        unknown_attributes.add_namespace("ex".to_string(), "urn:example:id".to_string());
+        unknown_attributes.insert("xsi:type".to_string(), "ex:DeviceID".to_string());
 
-    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let credential = Credential::from_private_key_pem(PRIVATE_KEY).unwrap();
+        let (encrypted_data, encrypted_keys) = encrypt_element(
+            XmlObject::try_from(base_id).unwrap(),
+            &credential,
+            KeyPlacement::Peer,
+        )
+        .unwrap();
         let mut encrypted_id = EncryptedID::default();
-        for child in element.children() {
-            let child = child.borrow();
-            match child.q_name().local_name() {
-                EncryptedID::CHILD_ENCRYPTED_DATA => {
-                    encrypted_id.set_encrypted_data(EncryptedData::try_from(child)?);
-                }
-                EncryptedID::CHILD_ENCRYPTED_KEY => {
-                    encrypted_id.add_encrypted_key(EncryptedKey::try_from(child)?);
-                }
-                _ => {}
-            }
-        }
-        Ok(encrypted_id)
-    }
-}
+        encrypted_id.set_encrypted_data(encrypted_data);
+        encrypted_id.set_encrypted_keys(encrypted_keys);
 
-impl TryFrom<EncryptedID> for XmlObject {
-    type Error = SAMLError;
-
-    fn try_from(encrypted_id: EncryptedID) -> Result<Self, Self::Error> {
-        let mut xml_obj = XmlObject::new(
-            Some(EncryptedID::NS_PREFIX.to_string()),
-            EncryptedID::ELEMENT_NAME.to_string(),
-            Some(EncryptedID::NS_URI.to_string()),
+        let DecryptedID::BaseID(base_id) = encrypted_id.decrypt(&credential).unwrap() else {
+            panic!("expected a BaseID");
+        };
+        assert_eq!(
+            base_id.name_qualifier().unwrap(),
+            "https://idp.example.org/metadata"
         );
-        xml_obj.add_namespace(
-            EncryptedID::NS_PREFIX.to_string(),
-            EncryptedID::NS_URI.to_string(),
+        assert_eq!(base_id.xsi_type().unwrap(), "ex:DeviceID");
+        assert_eq!(
+            base_id.unknown_attributes().namespaces().get("ex").unwrap(),
+            "urn:example:id"
         );
-        xml_obj.add_child(Rc::new(RefCell::new(XmlObject::try_from(
-            encrypted_id.encrypted_data,
-        )?)));
-        for encrypted_key in encrypted_id.encrypted_keys {
-            xml_obj.add_child(Rc::new(RefCell::new(XmlObject::try_from(encrypted_key)?)));
-        }
-        Ok(xml_obj)
     }
 }
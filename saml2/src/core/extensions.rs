@@ -1,10 +1,14 @@
-use std::cell::Ref;
+use std::{
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
 
 use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
 
+/// `samlp:Extensions`, its elements are defined by other specifications and kept as they are
 #[derive(Debug, Default)]
 pub struct Extensions {
-    unknown_children: Vec<Box<dyn SAML2Obj>>,
+    unknown_children: Vec<Rc<RefCell<XmlObject>>>,
 }
 
 impl SAML2Obj for Extensions {}
@@ -15,9 +19,14 @@ impl Extensions {
     const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:protocol";
 
     #[inline]
-    pub fn unknown_children(&self) -> &Vec<Box<dyn SAML2Obj>> {
+    pub fn unknown_children(&self) -> &Vec<Rc<RefCell<XmlObject>>> {
         &self.unknown_children
     }
+
+    #[inline]
+    pub fn add_unknown_child(&mut self, unknown_child: Rc<RefCell<XmlObject>>) {
+        self.unknown_children.push(unknown_child);
+    }
 }
 
 impl TryFrom<Ref<'_, XmlObject>> for Extensions {
@@ -26,12 +35,7 @@ impl TryFrom<Ref<'_, XmlObject>> for Extensions {
     fn try_from(xml_obj: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
         let mut extensions = Extensions::default();
         for child in xml_obj.children() {
-            let child = child.borrow();
-            match child.q_name().local_name() {
-                _ => {
-                    todo!("Extensions::try_from<Ref<XmlObject>>")
-                }
-            }
+            extensions.add_unknown_child(Rc::clone(child));
         }
         Ok(extensions)
     }
@@ -42,17 +46,47 @@ impl TryFrom<Extensions> for XmlObject {
 
     fn try_from(extensions: Extensions) -> Result<Self, Self::Error> {
         let mut xml_obj = XmlObject::new(
-            Some(Extensions::NS_PREFIX.to_string()),
-            Extensions::ELEMENT_NAME.to_string(),
             Some(Extensions::NS_URI.to_string()),
+            Extensions::ELEMENT_NAME.to_string(),
+            Some(Extensions::NS_PREFIX.to_string()),
         );
         xml_obj.add_namespace(
             Extensions::NS_PREFIX.to_string(),
             Extensions::NS_URI.to_string(),
         );
         for child in extensions.unknown_children {
-            todo!("XmlObject::try_from<dyn SAML2Obj>")
+            xml_obj.add_child(child);
         }
         Ok(xml_obj)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{util::InputStream, xml::XmlObject};
+
+    use super::Extensions;
+
+    const EXTENSIONS: &str = r#"<samlp:Extensions xmlns:samlp="urn:oasis:names:tc:SAML:2.0:protocol"><ex:Hint xmlns:ex="urn:example:hint">mfa</ex:Hint></samlp:Extensions>"#;
+
+    fn parse(xml: String) -> Extensions {
+        let root = XmlObject::parse_xml(InputStream::new(xml.into_bytes())).unwrap();
+        let extensions = Extensions::try_from(root.borrow()).unwrap();
+        extensions
+    }
+
+    #[test]
+    fn we_can_parse_and_serialize_extensions() {
+        let extensions = parse(EXTENSIONS.to_string());
+        let element = XmlObject::try_from(extensions).unwrap();
+        let xml = XmlObject::write_xml(Rc::new(RefCell::new(element))).unwrap();
+
+        let extensions = parse(xml);
+        assert_eq!(extensions.unknown_children().len(), 1);
+        let hint = extensions.unknown_children()[0].borrow();
+        assert_eq!(hint.q_name().namespace_uri(), Some("urn:example:hint"));
+        assert_eq!(hint.text().unwrap(), "mfa");
+    }
+}
@@ -43,9 +43,9 @@ impl TryFrom<GetComplete> for XmlObject {
 
     fn try_from(get_complete: GetComplete) -> Result<Self, Self::Error> {
         let mut xml_object = XmlObject::new(
-            Some(GetComplete::NS_PREFIX.to_string()),
-            GetComplete::ELEMENT_NAME.to_string(),
             Some(GetComplete::NS_URI.to_string()),
+            GetComplete::ELEMENT_NAME.to_string(),
+            Some(GetComplete::NS_PREFIX.to_string()),
         );
         xml_object.add_namespace(
             GetComplete::NS_PREFIX.to_string(),
@@ -79,9 +79,9 @@ impl TryFrom<IDPEntry> for XmlObject {
 
     fn try_from(idp_entry: IDPEntry) -> Result<Self, Self::Error> {
         let mut xml_object = XmlObject::new(
-            Some(IDPEntry::NS_PREFIX.to_string()),
-            IDPEntry::ELEMENT_NAME.to_string(),
             Some(IDPEntry::NS_URI.to_string()),
+            IDPEntry::ELEMENT_NAME.to_string(),
+            Some(IDPEntry::NS_PREFIX.to_string()),
         );
         xml_object.add_attribute(
             IDPEntry::ATTRIB_PROVIDER_ID.to_string(),
@@ -70,9 +70,9 @@ impl TryFrom<IDPList> for XmlObject {
 
     fn try_from(idp_list: IDPList) -> Result<Self, Self::Error> {
         let mut xml_object = XmlObject::new(
-            Some(IDPList::NS_PREFIX.to_string()),
-            IDPList::ELEMENT_NAME.to_string(),
             Some(IDPList::NS_URI.to_string()),
+            IDPList::ELEMENT_NAME.to_string(),
+            Some(IDPList::NS_PREFIX.to_string()),
         );
         xml_object.add_namespace(IDPList::NS_PREFIX.to_string(), IDPList::NS_URI.to_string());
         for idp_entry in idp_list.idp_entry {
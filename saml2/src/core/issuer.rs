@@ -150,7 +150,6 @@ impl Display for Issuer {
             f,
             "Issuer: name_qualifier={:?}, sp_name_qualifier={:?}, format={:?}, sp_provided_id={:?}",
             self.name_qualifier, self.sp_name_qualifier, self.format, self.sp_provided_id
-        );
-        Ok(())
+        )
     }
 }
@@ -0,0 +1,330 @@
+use std::any::Any;
+use std::cell::{Ref, RefCell};
+use std::rc::Rc;
+
+use chrono::{DateTime, Utc};
+
+use crate::common::SAML2Obj;
+use crate::core::parse_from_string;
+use crate::signature::signature::Signature;
+use crate::{error::SAMLError, xml::XmlObject};
+
+use super::{
+    base_id::BaseID, encrypted_id::EncryptedID, extensions::Extensions, issuer::Issuer,
+    name_id::NameID, request_abstract_type::RequestAbstractType, saml_version::SAMLVersion,
+    session_index::SessionIndex,
+};
+
+#[derive(Debug, Default)]
+pub struct LogoutRequest {
+    id: String,
+    version: SAMLVersion,
+    issue_instant: DateTime<Utc>,
+    destination: Option<String>,
+    consent: Option<String>,
+    issuer: Option<Issuer>,
+    extensions: Option<Extensions>,
+    signature: Option<Signature>,
+    reason: Option<String>,
+    not_on_or_after: Option<DateTime<Utc>>,
+    base_id: Option<BaseID>,
+    name_id: Option<NameID>,
+    encrypted_id: Option<EncryptedID>,
+    session_indexes: Vec<SessionIndex>,
+}
+
+impl SAML2Obj for LogoutRequest {}
+
+impl LogoutRequest {
+    const ATTRIB_VERSION: &'static str = "Version";
+    const ATTRIB_ID: &'static str = "ID";
+    const ATTRIB_ISSUE_INSTANT: &'static str = "IssueInstant";
+    const ATTRIB_DESTINATION: &'static str = "Destination";
+    const ATTRIB_CONSENT: &'static str = "Consent";
+    const ATTRIB_REASON: &'static str = "Reason";
+    const ATTRIB_NOT_ON_OR_AFTER: &'static str = "NotOnOrAfter";
+
+    const CHILD_ISSUER: &'static str = "Issuer";
+    const CHILD_SIGNATURE: &'static str = "Signature";
+    const CHILD_EXTENSIONS: &'static str = "Extensions";
+    const CHILD_BASE_ID: &'static str = "BaseID";
+    const CHILD_NAME_ID: &'static str = "NameID";
+    const CHILD_ENCRYPTED_ID: &'static str = "EncryptedID";
+    const CHILD_SESSION_INDEX: &'static str = "SessionIndex";
+
+    pub const ELEMENT_NAME: &'static str = "LogoutRequest";
+    pub const NS_PREFIX: &'static str = "saml2p";
+    pub const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:protocol";
+
+    #[inline]
+    pub fn reason(&self) -> Option<&String> {
+        self.reason.as_ref()
+    }
+
+    #[inline]
+    pub fn set_reason(&mut self, reason: Option<String>) {
+        self.reason = reason;
+    }
+
+    #[inline]
+    pub fn not_on_or_after(&self) -> Option<&DateTime<Utc>> {
+        self.not_on_or_after.as_ref()
+    }
+
+    #[inline]
+    pub fn set_not_on_or_after(&mut self, not_on_or_after: Option<DateTime<Utc>>) {
+        self.not_on_or_after = not_on_or_after;
+    }
+
+    #[inline]
+    pub fn base_id(&self) -> Option<&BaseID> {
+        self.base_id.as_ref()
+    }
+
+    #[inline]
+    pub fn set_base_id(&mut self, base_id: Option<BaseID>) {
+        self.base_id = base_id;
+    }
+
+    #[inline]
+    pub fn name_id(&self) -> Option<&NameID> {
+        self.name_id.as_ref()
+    }
+
+    #[inline]
+    pub fn set_name_id(&mut self, name_id: Option<NameID>) {
+        self.name_id = name_id;
+    }
+
+    #[inline]
+    pub fn encrypted_id(&self) -> Option<&EncryptedID> {
+        self.encrypted_id.as_ref()
+    }
+
+    #[inline]
+    pub fn set_encrypted_id(&mut self, encrypted_id: Option<EncryptedID>) {
+        self.encrypted_id = encrypted_id;
+    }
+
+    #[inline]
+    pub fn session_indexes(&self) -> &Vec<SessionIndex> {
+        &self.session_indexes
+    }
+
+    #[inline]
+    pub fn add_session_index(&mut self, session_index: SessionIndex) {
+        self.session_indexes.push(session_index);
+    }
+}
+
+impl RequestAbstractType for LogoutRequest {
+    #[inline]
+    fn version(&self) -> &SAMLVersion {
+        &self.version
+    }
+
+    #[inline]
+    fn set_version(&mut self, saml_version: SAMLVersion) {
+        self.version = saml_version
+    }
+
+    #[inline]
+    fn id(&self) -> &String {
+        &self.id
+    }
+
+    #[inline]
+    fn set_id(&mut self, id: String) {
+        self.id = id;
+    }
+
+    #[inline]
+    fn issue_instant(&self) -> &DateTime<Utc> {
+        &self.issue_instant
+    }
+
+    #[inline]
+    fn set_issue_instant(&mut self, issue_instant: DateTime<Utc>) {
+        self.issue_instant = issue_instant
+    }
+
+    #[inline]
+    fn destination(&self) -> Option<&String> {
+        self.destination.as_ref()
+    }
+
+    #[inline]
+    fn set_destination(&mut self, destination: Option<String>) {
+        self.destination = destination
+    }
+
+    #[inline]
+    fn consent(&self) -> Option<&String> {
+        self.consent.as_ref()
+    }
+
+    #[inline]
+    fn set_consent(&mut self, consent: Option<String>) {
+        self.consent = consent
+    }
+
+    #[inline]
+    fn issuer(&self) -> Option<&Issuer> {
+        self.issuer.as_ref()
+    }
+
+    #[inline]
+    fn set_issuer(&mut self, issuer: Option<Issuer>) {
+        self.issuer = issuer
+    }
+
+    #[inline]
+    fn extensions(&self) -> Option<&Extensions> {
+        self.extensions.as_ref()
+    }
+
+    #[inline]
+    fn set_extensions(&mut self, extensions: Option<Extensions>) {
+        self.extensions = extensions
+    }
+
+    #[inline]
+    fn signature(&self) -> Option<&Signature> {
+        self.signature.as_ref()
+    }
+
+    #[inline]
+    fn set_signature(&mut self, signature: Option<Signature>) {
+        self.signature = signature
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for LogoutRequest {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut logout_request = LogoutRequest::default();
+        for attribute in element.attributes() {
+            let (key, value) = (attribute.0.as_str(), attribute.1.as_str());
+            match key {
+                LogoutRequest::ATTRIB_VERSION => {
+                    logout_request.set_version(SAMLVersion::from_string(value)?);
+                }
+                LogoutRequest::ATTRIB_ID => {
+                    logout_request.set_id(value.to_string());
+                }
+                LogoutRequest::ATTRIB_ISSUE_INSTANT => {
+                    logout_request.set_issue_instant(parse_from_string(value)?);
+                }
+                LogoutRequest::ATTRIB_DESTINATION => {
+                    logout_request.set_destination(Some(value.to_string()));
+                }
+                LogoutRequest::ATTRIB_CONSENT => {
+                    logout_request.set_consent(Some(value.to_string()));
+                }
+                LogoutRequest::ATTRIB_REASON => {
+                    logout_request.set_reason(Some(value.to_string()));
+                }
+                LogoutRequest::ATTRIB_NOT_ON_OR_AFTER => {
+                    logout_request.set_not_on_or_after(Some(parse_from_string(value)?));
+                }
+                _ => {}
+            }
+        }
+        for child in element.children() {
+            let child = child.borrow();
+            match child.q_name().local_name() {
+                LogoutRequest::CHILD_ISSUER => {
+                    logout_request.set_issuer(Some(Issuer::try_from(child)?));
+                }
+                LogoutRequest::CHILD_SIGNATURE => {
+                    logout_request.set_signature(Some(Signature::try_from(child)?));
+                }
+                LogoutRequest::CHILD_EXTENSIONS => {
+                    logout_request.set_extensions(Some(Extensions::try_from(child)?));
+                }
+                LogoutRequest::CHILD_BASE_ID => {
+                    logout_request.set_base_id(Some(BaseID::try_from(child)?));
+                }
+                LogoutRequest::CHILD_NAME_ID => {
+                    logout_request.set_name_id(Some(NameID::try_from(child)?));
+                }
+                LogoutRequest::CHILD_ENCRYPTED_ID => {
+                    logout_request.set_encrypted_id(Some(EncryptedID::try_from(child)?));
+                }
+                LogoutRequest::CHILD_SESSION_INDEX => {
+                    logout_request.add_session_index(SessionIndex::try_from(child)?);
+                }
+                _ => {}
+            }
+        }
+        Ok(logout_request)
+    }
+}
+
+impl TryFrom<LogoutRequest> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(logout_request: LogoutRequest) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(LogoutRequest::NS_URI.to_string()),
+            LogoutRequest::ELEMENT_NAME.to_string(),
+            Some(LogoutRequest::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            LogoutRequest::NS_PREFIX.to_string(),
+            LogoutRequest::NS_URI.to_string(),
+        );
+        xml_object.add_attribute(
+            LogoutRequest::ATTRIB_VERSION.to_string(),
+            logout_request.version.to_string(),
+        );
+        xml_object.add_attribute(LogoutRequest::ATTRIB_ID.to_string(), logout_request.id);
+        xml_object.add_attribute(
+            LogoutRequest::ATTRIB_ISSUE_INSTANT.to_string(),
+            logout_request.issue_instant.to_rfc3339(),
+        );
+        if let Some(destination) = logout_request.destination {
+            xml_object.add_attribute(LogoutRequest::ATTRIB_DESTINATION.to_string(), destination);
+        }
+        if let Some(consent) = logout_request.consent {
+            xml_object.add_attribute(LogoutRequest::ATTRIB_CONSENT.to_string(), consent);
+        }
+        if let Some(reason) = logout_request.reason {
+            xml_object.add_attribute(LogoutRequest::ATTRIB_REASON.to_string(), reason);
+        }
+        if let Some(not_on_or_after) = logout_request.not_on_or_after {
+            xml_object.add_attribute(
+                LogoutRequest::ATTRIB_NOT_ON_OR_AFTER.to_string(),
+                not_on_or_after.to_rfc3339(),
+            );
+        }
+
+        if let Some(issuer) = logout_request.issuer {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(issuer)?)));
+        }
+        if let Some(signature) = logout_request.signature {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(signature)?)));
+        }
+        if let Some(extensions) = logout_request.extensions {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(extensions)?)));
+        }
+        if let Some(base_id) = logout_request.base_id {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(base_id)?)));
+        }
+        if let Some(name_id) = logout_request.name_id {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(name_id)?)));
+        }
+        if let Some(encrypted_id) = logout_request.encrypted_id {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(encrypted_id)?)));
+        }
+        for session_index in logout_request.session_indexes {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(session_index)?)));
+        }
+        Ok(xml_object)
+    }
+}
@@ -0,0 +1,257 @@
+use std::any::Any;
+use std::cell::{Ref, RefCell};
+use std::rc::Rc;
+
+use chrono::{DateTime, Utc};
+
+use crate::common::SAML2Obj;
+use crate::core::parse_from_string;
+use crate::signature::signature::Signature;
+use crate::{error::SAMLError, xml::XmlObject};
+
+use super::{
+    extensions::Extensions, issuer::Issuer, saml_version::SAMLVersion, status::Status,
+    status_response_type::StatusResponseType,
+};
+
+/// `saml2p:LogoutResponse`, the outcome of a `LogoutRequest`
+#[derive(Debug, Default)]
+pub struct LogoutResponse {
+    id: String,
+    in_response_to: Option<String>,
+    version: SAMLVersion,
+    issue_instant: DateTime<Utc>,
+    destination: Option<String>,
+    consent: Option<String>,
+    issuer: Option<Issuer>,
+    signature: Option<Signature>,
+    extensions: Option<Extensions>,
+    status: Status,
+}
+
+impl SAML2Obj for LogoutResponse {}
+
+impl LogoutResponse {
+    const ATTRIB_ID: &'static str = "ID";
+    const ATTRIB_IN_RESPONSE_TO: &'static str = "InResponseTo";
+    const ATTRIB_VERSION: &'static str = "Version";
+    const ATTRIB_ISSUE_INSTANT: &'static str = "IssueInstant";
+    const ATTRIB_DESTINATION: &'static str = "Destination";
+    const ATTRIB_CONSENT: &'static str = "Consent";
+
+    const CHILD_ISSUER: &'static str = "Issuer";
+    const CHILD_SIGNATURE: &'static str = "Signature";
+    const CHILD_EXTENSIONS: &'static str = "Extensions";
+    const CHILD_STATUS: &'static str = "Status";
+
+    pub const ELEMENT_NAME: &'static str = "LogoutResponse";
+    pub const NS_PREFIX: &'static str = "saml2p";
+    pub const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:protocol";
+}
+
+impl StatusResponseType for LogoutResponse {
+    #[inline]
+    fn id(&self) -> &String {
+        &self.id
+    }
+
+    #[inline]
+    fn set_id(&mut self, id: String) {
+        self.id = id;
+    }
+
+    #[inline]
+    fn in_response_to(&self) -> Option<&String> {
+        self.in_response_to.as_ref()
+    }
+
+    #[inline]
+    fn set_in_response_to(&mut self, in_response_to: Option<String>) {
+        self.in_response_to = in_response_to
+    }
+
+    #[inline]
+    fn version(&self) -> &SAMLVersion {
+        &self.version
+    }
+
+    #[inline]
+    fn set_version(&mut self, version: SAMLVersion) {
+        self.version = version
+    }
+
+    #[inline]
+    fn issue_instant(&self) -> DateTime<Utc> {
+        self.issue_instant
+    }
+
+    #[inline]
+    fn set_issue_instant(&mut self, issue_instant: DateTime<Utc>) {
+        self.issue_instant = issue_instant
+    }
+
+    #[inline]
+    fn destination(&self) -> Option<&String> {
+        self.destination.as_ref()
+    }
+
+    #[inline]
+    fn set_destination(&mut self, destination: Option<String>) {
+        self.destination = destination
+    }
+
+    #[inline]
+    fn consent(&self) -> Option<&String> {
+        self.consent.as_ref()
+    }
+
+    #[inline]
+    fn set_consent(&mut self, consent: Option<String>) {
+        self.consent = consent
+    }
+
+    #[inline]
+    fn issuer(&self) -> Option<&Issuer> {
+        self.issuer.as_ref()
+    }
+
+    #[inline]
+    fn set_issuer(&mut self, issuer: Option<Issuer>) {
+        self.issuer = issuer
+    }
+
+    #[inline]
+    fn signature(&self) -> Option<&Signature> {
+        self.signature.as_ref()
+    }
+
+    #[inline]
+    fn set_signature(&mut self, signature: Option<Signature>) {
+        self.signature = signature
+    }
+
+    #[inline]
+    fn extensions(&self) -> Option<&Extensions> {
+        self.extensions.as_ref()
+    }
+
+    #[inline]
+    fn set_extensions(&mut self, extensions: Option<Extensions>) {
+        self.extensions = extensions
+    }
+
+    #[inline]
+    fn status(&self) -> &Status {
+        &self.status
+    }
+
+    #[inline]
+    fn set_status(&mut self, status: Status) {
+        self.status = status
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for LogoutResponse {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut logout_response = LogoutResponse::default();
+        for attribute in element.attributes() {
+            let (key, value) = (attribute.0.as_str(), attribute.1.as_str());
+            match key {
+                LogoutResponse::ATTRIB_ID => {
+                    logout_response.set_id(value.to_string());
+                }
+                LogoutResponse::ATTRIB_IN_RESPONSE_TO => {
+                    logout_response.set_in_response_to(Some(value.to_string()));
+                }
+                LogoutResponse::ATTRIB_VERSION => {
+                    logout_response.set_version(SAMLVersion::from_string(value)?);
+                }
+                LogoutResponse::ATTRIB_ISSUE_INSTANT => {
+                    logout_response.set_issue_instant(parse_from_string(value)?);
+                }
+                LogoutResponse::ATTRIB_DESTINATION => {
+                    logout_response.set_destination(Some(value.to_string()));
+                }
+                LogoutResponse::ATTRIB_CONSENT => {
+                    logout_response.set_consent(Some(value.to_string()));
+                }
+                _ => {}
+            }
+        }
+        for child in element.children() {
+            let child = child.borrow();
+            match child.q_name().local_name() {
+                LogoutResponse::CHILD_ISSUER => {
+                    logout_response.set_issuer(Some(Issuer::try_from(child)?));
+                }
+                LogoutResponse::CHILD_SIGNATURE => {
+                    logout_response.set_signature(Some(Signature::try_from(child)?));
+                }
+                LogoutResponse::CHILD_EXTENSIONS => {
+                    logout_response.set_extensions(Some(Extensions::try_from(child)?));
+                }
+                LogoutResponse::CHILD_STATUS => {
+                    logout_response.set_status(Status::try_from(child)?);
+                }
+                _ => {}
+            }
+        }
+        Ok(logout_response)
+    }
+}
+
+impl TryFrom<LogoutResponse> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(logout_response: LogoutResponse) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(LogoutResponse::NS_URI.to_string()),
+            LogoutResponse::ELEMENT_NAME.to_string(),
+            Some(LogoutResponse::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            LogoutResponse::NS_PREFIX.to_string(),
+            LogoutResponse::NS_URI.to_string(),
+        );
+        xml_object.add_attribute(LogoutResponse::ATTRIB_ID.to_string(), logout_response.id);
+        if let Some(in_response_to) = logout_response.in_response_to {
+            xml_object.add_attribute(
+                LogoutResponse::ATTRIB_IN_RESPONSE_TO.to_string(),
+                in_response_to,
+            );
+        }
+        xml_object.add_attribute(
+            LogoutResponse::ATTRIB_VERSION.to_string(),
+            logout_response.version.to_string(),
+        );
+        xml_object.add_attribute(
+            LogoutResponse::ATTRIB_ISSUE_INSTANT.to_string(),
+            logout_response.issue_instant.to_rfc3339(),
+        );
+        if let Some(destination) = logout_response.destination {
+            xml_object.add_attribute(LogoutResponse::ATTRIB_DESTINATION.to_string(), destination);
+        }
+        if let Some(consent) = logout_response.consent {
+            xml_object.add_attribute(LogoutResponse::ATTRIB_CONSENT.to_string(), consent);
+        }
+        if let Some(issuer) = logout_response.issuer {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(issuer)?)));
+        }
+        if let Some(signature) = logout_response.signature {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(signature)?)));
+        }
+        if let Some(extensions) = logout_response.extensions {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(extensions)?)));
+        }
+        xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(
+            logout_response.status,
+        )?)));
+        Ok(xml_object)
+    }
+}
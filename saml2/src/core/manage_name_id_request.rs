@@ -0,0 +1,324 @@
+use std::any::Any;
+use std::cell::{Ref, RefCell};
+use std::rc::Rc;
+
+use chrono::{DateTime, Utc};
+
+use crate::common::SAML2Obj;
+use crate::core::parse_from_string;
+use crate::signature::signature::Signature;
+use crate::{error::SAMLError, xml::XmlObject};
+
+use super::{
+    encrypted_id::EncryptedID, extensions::Extensions, issuer::Issuer, name_id::NameID,
+    new_encrypted_id::NewEncryptedID, new_id::NewID, request_abstract_type::RequestAbstractType,
+    saml_version::SAMLVersion,
+};
+
+/// `saml2p:ManageNameIDRequest`, changes the identifier of a principal or terminates it
+#[derive(Debug, Default)]
+pub struct ManageNameIDRequest {
+    id: String,
+    version: SAMLVersion,
+    issue_instant: DateTime<Utc>,
+    destination: Option<String>,
+    consent: Option<String>,
+    issuer: Option<Issuer>,
+    extensions: Option<Extensions>,
+    signature: Option<Signature>,
+    name_id: Option<NameID>,
+    encrypted_id: Option<EncryptedID>,
+    new_id: Option<NewID>,
+    new_encrypted_id: Option<NewEncryptedID>,
+    terminate: bool,
+}
+
+impl SAML2Obj for ManageNameIDRequest {}
+
+impl ManageNameIDRequest {
+    const ATTRIB_VERSION: &'static str = "Version";
+    const ATTRIB_ID: &'static str = "ID";
+    const ATTRIB_ISSUE_INSTANT: &'static str = "IssueInstant";
+    const ATTRIB_DESTINATION: &'static str = "Destination";
+    const ATTRIB_CONSENT: &'static str = "Consent";
+
+    const CHILD_ISSUER: &'static str = "Issuer";
+    const CHILD_SIGNATURE: &'static str = "Signature";
+    const CHILD_EXTENSIONS: &'static str = "Extensions";
+    const CHILD_NAME_ID: &'static str = "NameID";
+    const CHILD_ENCRYPTED_ID: &'static str = "EncryptedID";
+    const CHILD_NEW_ID: &'static str = "NewID";
+    const CHILD_NEW_ENCRYPTED_ID: &'static str = "NewEncryptedID";
+    const CHILD_TERMINATE: &'static str = "Terminate";
+
+    pub const ELEMENT_NAME: &'static str = "ManageNameIDRequest";
+    pub const NS_PREFIX: &'static str = "saml2p";
+    pub const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:protocol";
+
+    #[inline]
+    pub fn name_id(&self) -> Option<&NameID> {
+        self.name_id.as_ref()
+    }
+
+    #[inline]
+    pub fn set_name_id(&mut self, name_id: Option<NameID>) {
+        self.name_id = name_id;
+    }
+
+    #[inline]
+    pub fn encrypted_id(&self) -> Option<&EncryptedID> {
+        self.encrypted_id.as_ref()
+    }
+
+    #[inline]
+    pub fn set_encrypted_id(&mut self, encrypted_id: Option<EncryptedID>) {
+        self.encrypted_id = encrypted_id;
+    }
+
+    #[inline]
+    pub fn new_id(&self) -> Option<&NewID> {
+        self.new_id.as_ref()
+    }
+
+    #[inline]
+    pub fn set_new_id(&mut self, new_id: Option<NewID>) {
+        self.new_id = new_id;
+    }
+
+    #[inline]
+    pub fn new_encrypted_id(&self) -> Option<&NewEncryptedID> {
+        self.new_encrypted_id.as_ref()
+    }
+
+    #[inline]
+    pub fn set_new_encrypted_id(&mut self, new_encrypted_id: Option<NewEncryptedID>) {
+        self.new_encrypted_id = new_encrypted_id;
+    }
+
+    /// whether the identifier is no longer used, instead of being replaced
+    #[inline]
+    pub fn terminate(&self) -> bool {
+        self.terminate
+    }
+
+    #[inline]
+    pub fn set_terminate(&mut self, terminate: bool) {
+        self.terminate = terminate;
+    }
+}
+
+impl RequestAbstractType for ManageNameIDRequest {
+    #[inline]
+    fn version(&self) -> &SAMLVersion {
+        &self.version
+    }
+
+    #[inline]
+    fn set_version(&mut self, saml_version: SAMLVersion) {
+        self.version = saml_version
+    }
+
+    #[inline]
+    fn id(&self) -> &String {
+        &self.id
+    }
+
+    #[inline]
+    fn set_id(&mut self, id: String) {
+        self.id = id;
+    }
+
+    #[inline]
+    fn issue_instant(&self) -> &DateTime<Utc> {
+        &self.issue_instant
+    }
+
+    #[inline]
+    fn set_issue_instant(&mut self, issue_instant: DateTime<Utc>) {
+        self.issue_instant = issue_instant
+    }
+
+    #[inline]
+    fn destination(&self) -> Option<&String> {
+        self.destination.as_ref()
+    }
+
+    #[inline]
+    fn set_destination(&mut self, destination: Option<String>) {
+        self.destination = destination
+    }
+
+    #[inline]
+    fn consent(&self) -> Option<&String> {
+        self.consent.as_ref()
+    }
+
+    #[inline]
+    fn set_consent(&mut self, consent: Option<String>) {
+        self.consent = consent
+    }
+
+    #[inline]
+    fn issuer(&self) -> Option<&Issuer> {
+        self.issuer.as_ref()
+    }
+
+    #[inline]
+    fn set_issuer(&mut self, issuer: Option<Issuer>) {
+        self.issuer = issuer
+    }
+
+    #[inline]
+    fn extensions(&self) -> Option<&Extensions> {
+        self.extensions.as_ref()
+    }
+
+    #[inline]
+    fn set_extensions(&mut self, extensions: Option<Extensions>) {
+        self.extensions = extensions
+    }
+
+    #[inline]
+    fn signature(&self) -> Option<&Signature> {
+        self.signature.as_ref()
+    }
+
+    #[inline]
+    fn set_signature(&mut self, signature: Option<Signature>) {
+        self.signature = signature
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for ManageNameIDRequest {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut manage_name_id_request = ManageNameIDRequest::default();
+        for attribute in element.attributes() {
+            let (key, value) = (attribute.0.as_str(), attribute.1.as_str());
+            match key {
+                ManageNameIDRequest::ATTRIB_VERSION => {
+                    manage_name_id_request.set_version(SAMLVersion::from_string(value)?);
+                }
+                ManageNameIDRequest::ATTRIB_ID => {
+                    manage_name_id_request.set_id(value.to_string());
+                }
+                ManageNameIDRequest::ATTRIB_ISSUE_INSTANT => {
+                    manage_name_id_request.set_issue_instant(parse_from_string(value)?);
+                }
+                ManageNameIDRequest::ATTRIB_DESTINATION => {
+                    manage_name_id_request.set_destination(Some(value.to_string()));
+                }
+                ManageNameIDRequest::ATTRIB_CONSENT => {
+                    manage_name_id_request.set_consent(Some(value.to_string()));
+                }
+                _ => {}
+            }
+        }
+        for child in element.children() {
+            let child = child.borrow();
+            match child.q_name().local_name() {
+                ManageNameIDRequest::CHILD_ISSUER => {
+                    manage_name_id_request.set_issuer(Some(Issuer::try_from(child)?));
+                }
+                ManageNameIDRequest::CHILD_SIGNATURE => {
+                    manage_name_id_request.set_signature(Some(Signature::try_from(child)?));
+                }
+                ManageNameIDRequest::CHILD_EXTENSIONS => {
+                    manage_name_id_request.set_extensions(Some(Extensions::try_from(child)?));
+                }
+                ManageNameIDRequest::CHILD_NAME_ID => {
+                    manage_name_id_request.set_name_id(Some(NameID::try_from(child)?));
+                }
+                ManageNameIDRequest::CHILD_ENCRYPTED_ID => {
+                    manage_name_id_request.set_encrypted_id(Some(EncryptedID::try_from(child)?));
+                }
+                ManageNameIDRequest::CHILD_NEW_ID => {
+                    manage_name_id_request.set_new_id(Some(NewID::try_from(child)?));
+                }
+                ManageNameIDRequest::CHILD_NEW_ENCRYPTED_ID => {
+                    manage_name_id_request
+                        .set_new_encrypted_id(Some(NewEncryptedID::try_from(child)?));
+                }
+                ManageNameIDRequest::CHILD_TERMINATE => {
+                    manage_name_id_request.set_terminate(true);
+                }
+                _ => {}
+            }
+        }
+        Ok(manage_name_id_request)
+    }
+}
+
+impl TryFrom<ManageNameIDRequest> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(manage_name_id_request: ManageNameIDRequest) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(ManageNameIDRequest::NS_URI.to_string()),
+            ManageNameIDRequest::ELEMENT_NAME.to_string(),
+            Some(ManageNameIDRequest::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            ManageNameIDRequest::NS_PREFIX.to_string(),
+            ManageNameIDRequest::NS_URI.to_string(),
+        );
+        xml_object.add_attribute(
+            ManageNameIDRequest::ATTRIB_VERSION.to_string(),
+            manage_name_id_request.version.to_string(),
+        );
+        xml_object.add_attribute(
+            ManageNameIDRequest::ATTRIB_ID.to_string(),
+            manage_name_id_request.id,
+        );
+        xml_object.add_attribute(
+            ManageNameIDRequest::ATTRIB_ISSUE_INSTANT.to_string(),
+            manage_name_id_request.issue_instant.to_rfc3339(),
+        );
+        if let Some(destination) = manage_name_id_request.destination {
+            xml_object.add_attribute(
+                ManageNameIDRequest::ATTRIB_DESTINATION.to_string(),
+                destination,
+            );
+        }
+        if let Some(consent) = manage_name_id_request.consent {
+            xml_object.add_attribute(ManageNameIDRequest::ATTRIB_CONSENT.to_string(), consent);
+        }
+
+        if let Some(issuer) = manage_name_id_request.issuer {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(issuer)?)));
+        }
+        if let Some(signature) = manage_name_id_request.signature {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(signature)?)));
+        }
+        if let Some(extensions) = manage_name_id_request.extensions {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(extensions)?)));
+        }
+        if let Some(name_id) = manage_name_id_request.name_id {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(name_id)?)));
+        }
+        if let Some(encrypted_id) = manage_name_id_request.encrypted_id {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(encrypted_id)?)));
+        }
+        if let Some(new_id) = manage_name_id_request.new_id {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(new_id)?)));
+        }
+        if let Some(new_encrypted_id) = manage_name_id_request.new_encrypted_id {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(
+                new_encrypted_id,
+            )?)));
+        }
+        if manage_name_id_request.terminate {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::new(
+                Some(ManageNameIDRequest::NS_URI.to_string()),
+                ManageNameIDRequest::CHILD_TERMINATE.to_string(),
+                Some(ManageNameIDRequest::NS_PREFIX.to_string()),
+            ))));
+        }
+        Ok(xml_object)
+    }
+}
@@ -2,48 +2,62 @@ use std::str::FromStr;
 
 use crate::error::SAMLError;
 
-mod abstract_name_id_type;
-mod advice;
-mod assertion;
-mod audience;
-mod audience_restriction;
-mod authenticating_authority;
-mod authn_context;
-mod authn_context_class_ref;
-mod authn_context_comparison_type_enumeration;
-mod authn_context_decl;
-mod authn_context_decl_ref;
+pub mod abstract_name_id_type;
+pub mod advice;
+pub mod artifact;
+pub mod artifact_resolve;
+pub mod artifact_response;
+pub mod assertion;
+pub mod attribute;
+pub mod attribute_query;
+pub mod attribute_statement;
+pub mod attribute_value;
+pub mod audience;
+pub mod audience_restriction;
+pub mod authenticating_authority;
+pub mod authn_context;
+pub mod authn_context_class_ref;
+pub mod authn_context_comparison_type_enumeration;
+pub mod authn_context_decl;
+pub mod authn_context_decl_ref;
 pub mod authn_request;
-mod authn_statement;
-mod base_id;
-mod conditions;
-mod element_type;
-mod encrypted_assertion;
-mod encrypted_element_type;
-mod encrypted_id;
-mod extensions;
+pub mod authn_statement;
+pub mod base_id;
+pub mod conditions;
+pub mod element_type;
+pub mod encrypted_assertion;
+pub mod encrypted_attribute;
+pub mod encrypted_element_type;
+pub mod encrypted_id;
+pub mod extensions;
 pub mod get_complete;
-mod idp_entry;
-mod idp_list;
-mod issuer;
-mod name_id;
-mod name_id_policy;
+pub mod idp_entry;
+pub mod idp_list;
+pub mod issuer;
+pub mod logout_request;
+pub mod logout_response;
+pub mod manage_name_id_request;
+pub mod manage_name_id_response;
+pub mod name_id;
+pub mod name_id_policy;
+pub mod new_encrypted_id;
+pub mod new_id;
 pub mod request_abstract_type;
-mod requested_authn_context;
-mod requester_id;
+pub mod requested_authn_context;
+pub mod requester_id;
 pub mod response;
-mod saml_version;
-mod scoping;
-mod signature;
-mod statement;
-mod status;
-mod status_code;
-mod status_detail;
-mod status_message;
+pub mod saml_version;
+pub mod scoping;
+pub mod session_index;
+pub mod statement;
+pub mod status;
+pub mod status_code;
+pub mod status_detail;
+pub mod status_message;
 pub mod status_response_type;
-mod subject;
-mod subject_confirmation;
-mod subject_confirmation_data;
+pub mod subject;
+pub mod subject_confirmation;
+pub mod subject_confirmation_data;
 pub mod subject_locality;
 
 /// parse a xml string to a type that implements the fromStr trait
@@ -10,6 +10,7 @@ pub struct NameID {
     sp_name_qualifier: Option<String>,
     format: Option<String>,
     sp_provided_id: Option<String>,
+    value: Option<String>,
 }
 
 impl SAML2Obj for NameID {}
@@ -20,9 +21,19 @@ impl NameID {
     const ATTRIB_FORMAT: &'static str = "Format";
     const ATTRIB_SP_PROVIDED_ID: &'static str = "SPProvidedID";
 
-    const ELEMENT_NAME: &'static str = "NameID";
-    const NS_PREFIX: &'static str = "saml2";
-    const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:assertion";
+    pub const ELEMENT_NAME: &'static str = "NameID";
+    pub const NS_PREFIX: &'static str = "saml2";
+    pub const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:assertion";
+
+    #[inline]
+    pub fn value(&self) -> Option<&String> {
+        self.value.as_ref()
+    }
+
+    #[inline]
+    pub fn set_value(&mut self, value: Option<String>) {
+        self.value = value;
+    }
 }
 
 impl AbstractNameIDType for NameID {
@@ -81,6 +92,9 @@ impl TryFrom<Ref<'_, XmlObject>> for NameID {
                 _ => {}
             }
         }
+        if let Some(value) = element.text() {
+            name_id.set_value(Some(value.to_string()));
+        }
         Ok(name_id)
     }
 }
@@ -116,6 +130,9 @@ impl TryFrom<NameID> for XmlObject {
                 sp_provided_id.to_string(),
             );
         }
+        if let Some(value) = value.value {
+            xml_object.set_text(Some(value));
+        }
         Ok(xml_object)
     }
 }
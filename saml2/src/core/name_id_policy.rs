@@ -2,7 +2,7 @@ use std::cell::Ref;
 
 use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct NameIDPolicy {
     format: Option<String>,
     sp_name_qualifier: Option<String>,
@@ -81,9 +81,9 @@ impl TryFrom<NameIDPolicy> for XmlObject {
 
     fn try_from(name_id_policy: NameIDPolicy) -> Result<Self, Self::Error> {
         let mut xml_object = XmlObject::new(
-            Some(NameIDPolicy::NS_PREFIX.to_string()),
-            NameIDPolicy::ELEMENT_NAME.to_string(),
             Some(NameIDPolicy::NS_URI.to_string()),
+            NameIDPolicy::ELEMENT_NAME.to_string(),
+            Some(NameIDPolicy::NS_PREFIX.to_string()),
         );
         xml_object.add_namespace(
             NameIDPolicy::NS_PREFIX.to_string(),
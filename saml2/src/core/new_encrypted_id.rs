@@ -0,0 +1,47 @@
+use std::cell::Ref;
+
+use crate::{error::SAMLError, xml::XmlObject};
+
+use super::{
+    encrypted_element_type::{EncryptedContent, EncryptedElement},
+    new_id::NewID,
+};
+
+/// `saml2p:NewEncryptedID` of the name identifier management protocol, the encrypted form
+/// of a `NewID`
+pub type NewEncryptedID = EncryptedElement<NewID>;
+
+impl EncryptedContent for NewID {
+    const ENCRYPTED_ELEMENT_NAME: &'static str = "NewEncryptedID";
+    const ENCRYPTED_NS_PREFIX: &'static str = "saml2p";
+    const ENCRYPTED_NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:protocol";
+
+    fn from_decrypted(element: Ref<'_, XmlObject>) -> Result<Self, SAMLError> {
+        if element.q_name().local_name() != NewID::ELEMENT_NAME {
+            return Err(SAMLError::UnmarshallingError(
+                "decrypted element is not a NewID".to_string(),
+            ));
+        }
+        NewID::try_from(element)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{signature::credential::Credential, util::InputStream, xml::XmlObject};
+
+    use super::NewEncryptedID;
+
+    const PRIVATE_KEY: &str = include_str!("../../tests/resources/rsa_private_key.pem");
+    const NEW_ENCRYPTED_ID: &str = include_str!("../../tests/resources/new_encrypted_id.xml");
+
+    #[test]
+    fn we_can_decrypt_new_encrypted_id() {
+        let root =
+            XmlObject::parse_xml(InputStream::new(NEW_ENCRYPTED_ID.as_bytes().to_vec())).unwrap();
+        let new_encrypted_id = NewEncryptedID::try_from(root.borrow()).unwrap();
+        let credential = Credential::from_private_key_pem(PRIVATE_KEY).unwrap();
+        let new_id = new_encrypted_id.decrypt(&credential).unwrap();
+        assert_eq!(new_id.value(), "5e1c0d9b-72a4-4f3e-8b6d-0c4a9e2f7d13");
+    }
+}
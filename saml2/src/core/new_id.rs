@@ -0,0 +1,59 @@
+use std::cell::Ref;
+
+use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+
+/// `saml2p:NewID`, the identifier a principal is known by from now on
+#[derive(Debug, Default)]
+pub struct NewID {
+    value: String,
+}
+
+impl SAML2Obj for NewID {}
+
+impl NewID {
+    pub const ELEMENT_NAME: &'static str = "NewID";
+    pub const NS_PREFIX: &'static str = "saml2p";
+    pub const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:protocol";
+
+    pub fn new(value: String) -> Self {
+        NewID { value }
+    }
+
+    #[inline]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    #[inline]
+    pub fn set_value(&mut self, value: String) {
+        self.value = value;
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for NewID {
+    type Error = SAMLError;
+
+    fn try_from(object: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        match object.text() {
+            Some(value) => Ok(NewID {
+                value: value.to_string(),
+            }),
+            None => Err(SAMLError::UnmarshallingError("Invalid XML".to_string())),
+        }
+    }
+}
+
+impl TryFrom<NewID> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(new_id: NewID) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(NewID::NS_URI.to_string()),
+            NewID::ELEMENT_NAME.to_string(),
+            Some(NewID::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(NewID::NS_PREFIX.to_string(), NewID::NS_URI.to_string());
+        xml_object.set_text(Some(new_id.value.to_string()));
+        Ok(xml_object)
+    }
+}
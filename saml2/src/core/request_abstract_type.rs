@@ -2,6 +2,8 @@ use std::any::Any;
 
 use chrono::{DateTime, Utc};
 
+use crate::signature::signature::Signature;
+
 use super::{extensions::Extensions, issuer::Issuer, saml_version::SAMLVersion};
 
 pub trait RequestAbstractType: Any {
@@ -33,9 +35,9 @@ pub trait RequestAbstractType: Any {
 
     fn set_extensions(&mut self, extensions: Option<Extensions>);
 
-    fn signature(&self) -> Option<&String>;
+    fn signature(&self) -> Option<&Signature>;
 
-    fn set_signature(&mut self, signature: Option<String>);
+    fn set_signature(&mut self, signature: Option<Signature>);
 
     fn as_any(&self) -> &dyn Any;
 }
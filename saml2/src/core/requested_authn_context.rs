@@ -67,21 +67,24 @@ impl TryFrom<Ref<'_, XmlObject>> for RequestedAuthnContext {
     fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
         let mut requested_authn_context = RequestedAuthnContext::default();
         for attrib in element.attributes() {
-            match attrib.0.as_str() {
-                Self::ATTRIB_COMPARISON => {
-                    requested_authn_context.set_comparison(Some(
-                        AuthnContextComparisonTypeEnumeration::from_str(attrib.1.as_str())?,
-                    ));
-                }
-                _ => {}
+            if attrib.0 == Self::ATTRIB_COMPARISON {
+                requested_authn_context.set_comparison(Some(
+                    AuthnContextComparisonTypeEnumeration::from_str(attrib.1.as_str())?,
+                ));
             }
         }
         for child in element.children() {
             let child = child.borrow();
             match child.q_name().local_name() {
-                _ => {
-                    println!("child is {}", child.q_name())
+                Self::CHILD_AUTHN_CONTEXT_CLASS_REF => {
+                    requested_authn_context
+                        .add_authn_context_class_ref(AuthnContextClassRef::try_from(child)?);
                 }
+                Self::CHILD_AUTHN_CONTEXT_DECL_REF => {
+                    requested_authn_context
+                        .add_authn_context_decl_ref(AuthnContextDeclRef::try_from(child)?);
+                }
+                _ => {}
             }
         }
         Ok(requested_authn_context)
@@ -93,9 +96,9 @@ impl TryFrom<RequestedAuthnContext> for XmlObject {
 
     fn try_from(requested_authn_context: RequestedAuthnContext) -> Result<Self, Self::Error> {
         let mut xml_object = XmlObject::new(
-            Some(RequestedAuthnContext::NS_PREFIX.to_string()),
-            RequestedAuthnContext::ELEMENT_NAME.to_string(),
             Some(RequestedAuthnContext::NS_URI.to_string()),
+            RequestedAuthnContext::ELEMENT_NAME.to_string(),
+            Some(RequestedAuthnContext::NS_PREFIX.to_string()),
         );
         xml_object.add_namespace(
             RequestedAuthnContext::NS_PREFIX.to_string(),
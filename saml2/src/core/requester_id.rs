@@ -16,7 +16,7 @@ impl RequesterID {
 
     #[inline]
     pub fn value(&self) -> Option<&str> {
-        self.value.as_ref().map(|s| s.as_str())
+        self.value.as_deref()
     }
 
     #[inline]
@@ -43,9 +43,9 @@ impl TryFrom<RequesterID> for XmlObject {
 
     fn try_from(requester_id: RequesterID) -> Result<Self, Self::Error> {
         let mut xml_object = XmlObject::new(
-            Some(RequesterID::NS_PREFIX.to_string()),
-            RequesterID::ELEMENT_NAME.to_string(),
             Some(RequesterID::NS_URI.to_string()),
+            RequesterID::ELEMENT_NAME.to_string(),
+            Some(RequesterID::NS_PREFIX.to_string()),
         );
         xml_object.add_namespace(
             RequesterID::NS_PREFIX.to_string(),
@@ -1,4 +1,8 @@
-use std::cell::Ref;
+use std::{
+    any::Any,
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
 
 use chrono::{DateTime, Utc};
 
@@ -9,6 +13,7 @@ use crate::core::saml_version::SAMLVersion;
 use crate::core::status::Status;
 use crate::core::status_response_type::StatusResponseType;
 use crate::error::SAMLError;
+use crate::signature::signature::Signature;
 use crate::xml::XmlObject;
 
 use super::assertion::Assertion;
@@ -24,7 +29,7 @@ pub struct Response {
     destination: Option<String>,
     consent: Option<String>,
     issuer: Option<Issuer>,
-    signature: Option<String>,
+    signature: Option<Signature>,
     extensions: Option<Extensions>,
     status: Status,
     assertions: Vec<Assertion>,
@@ -42,11 +47,16 @@ impl Response {
     const ATTRIB_CONSENT: &'static str = "Consent";
 
     const CHILD_ISSUER: &'static str = "Issuer";
+    const CHILD_SIGNATURE: &'static str = "Signature";
     const CHILD_EXTENSIONS: &'static str = "Extensions";
     const CHILD_ASSERTION: &'static str = "Assertion";
     const CHILD_STATUS: &'static str = "Status";
     const CHILD_ENCRYPTED_ASSERTION: &'static str = "EncryptedAssertion";
 
+    pub const ELEMENT_NAME: &'static str = "Response";
+    pub const NS_PREFIX: &'static str = "saml2p";
+    pub const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:protocol";
+
     pub fn assertions(&self) -> &Vec<Assertion> {
         self.assertions.as_ref()
     }
@@ -121,11 +131,11 @@ impl StatusResponseType for Response {
         self.issuer = issuer
     }
 
-    fn signature(&self) -> Option<&String> {
+    fn signature(&self) -> Option<&Signature> {
         self.signature.as_ref()
     }
 
-    fn set_signature(&mut self, signature: Option<String>) {
+    fn set_signature(&mut self, signature: Option<Signature>) {
         self.signature = signature
     }
 
@@ -144,6 +154,10 @@ impl StatusResponseType for Response {
     fn set_status(&mut self, status: Status) {
         self.status = status
     }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
 
 impl Default for Response {
@@ -201,6 +215,9 @@ impl TryFrom<Ref<'_, XmlObject>> for Response {
                 Self::CHILD_ISSUER => {
                     response.set_issuer(Some(Issuer::try_from(child)?));
                 }
+                Self::CHILD_SIGNATURE => {
+                    response.set_signature(Some(Signature::try_from(child)?));
+                }
                 Self::CHILD_EXTENSIONS => {
                     response.set_extensions(Some(Extensions::try_from(child)?));
                 }
@@ -209,7 +226,7 @@ impl TryFrom<Ref<'_, XmlObject>> for Response {
                 }
                 Self::CHILD_ASSERTION => response.add_assertion(Assertion::try_from(child)?),
                 Self::CHILD_ENCRYPTED_ASSERTION => {
-                    // todo
+                    response.add_encrypted_assertion(EncryptedAssertion::try_from(child)?);
                 }
                 _ => {}
             }
@@ -217,3 +234,56 @@ impl TryFrom<Ref<'_, XmlObject>> for Response {
         Ok(response)
     }
 }
+
+impl TryFrom<Response> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(response: Response) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(Response::NS_URI.to_string()),
+            Response::ELEMENT_NAME.to_string(),
+            Some(Response::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            Response::NS_PREFIX.to_string(),
+            Response::NS_URI.to_string(),
+        );
+        xml_object.add_attribute(Response::ATTRIB_ID.to_string(), response.id);
+        if let Some(in_response_to) = response.in_response_to {
+            xml_object.add_attribute(Response::ATTRIB_IN_RESPONSE_TO.to_string(), in_response_to);
+        }
+        xml_object.add_attribute(
+            Response::ATTRIB_VERSION.to_string(),
+            response.version.to_string(),
+        );
+        xml_object.add_attribute(
+            Response::ATTRIB_ISSUE_INSTANT.to_string(),
+            response.issue_instant.to_rfc3339(),
+        );
+        if let Some(destination) = response.destination {
+            xml_object.add_attribute(Response::ATTRIB_DESTINATION.to_string(), destination);
+        }
+        if let Some(consent) = response.consent {
+            xml_object.add_attribute(Response::ATTRIB_CONSENT.to_string(), consent);
+        }
+        if let Some(issuer) = response.issuer {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(issuer)?)));
+        }
+        if let Some(signature) = response.signature {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(signature)?)));
+        }
+        if let Some(extensions) = response.extensions {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(extensions)?)));
+        }
+        xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(response.status)?)));
+        for assertion in response.assertions {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(assertion)?)));
+        }
+        for encrypted_assertion in response.encrypted_assertions {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(
+                encrypted_assertion,
+            )?)));
+        }
+        Ok(xml_object)
+    }
+}
@@ -1,3 +1,5 @@
+use std::fmt::Display;
+
 use crate::{common::SAML2Obj, error::SAMLError};
 
 #[derive(Clone, Default, Debug)]
@@ -33,8 +35,8 @@ impl SAMLVersion {
     }
 }
 
-impl ToString for SAMLVersion {
-    fn to_string(&self) -> String {
-        format!("{}.{}", self.major, self.minor)
+impl Display for SAMLVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
     }
 }
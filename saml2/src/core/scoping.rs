@@ -62,11 +62,8 @@ impl TryFrom<Ref<'_, XmlObject>> for Scoping {
     fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
         let mut scoping = Scoping::default();
         for attrib in element.attributes() {
-            match attrib.0.as_str() {
-                Scoping::ATTRIB_PROXY_COUNT => {
-                    scoping.set_proxy_count(attrib.1.parse().ok());
-                }
-                _ => {}
+            if attrib.0 == Scoping::ATTRIB_PROXY_COUNT {
+                scoping.set_proxy_count(attrib.1.parse().ok());
             }
         }
         for child in element.children() {
@@ -90,9 +87,9 @@ impl TryFrom<Scoping> for XmlObject {
 
     fn try_from(scoping: Scoping) -> Result<Self, Self::Error> {
         let mut xml_object = XmlObject::new(
-            Some(Scoping::NS_PREFIX.to_string()),
-            Scoping::ELEMENT_NAME.to_string(),
             Some(Scoping::NS_URI.to_string()),
+            Scoping::ELEMENT_NAME.to_string(),
+            Some(Scoping::NS_PREFIX.to_string()),
         );
         xml_object.add_namespace(Scoping::NS_PREFIX.to_string(), Scoping::NS_URI.to_string());
         if let Some(proxy_count) = scoping.proxy_count() {
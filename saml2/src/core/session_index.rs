@@ -0,0 +1,57 @@
+use std::cell::Ref;
+
+use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+
+#[derive(Debug, Default)]
+pub struct SessionIndex {
+    value: String,
+}
+
+impl SAML2Obj for SessionIndex {}
+
+impl SessionIndex {
+    pub const ELEMENT_NAME: &'static str = "SessionIndex";
+    pub const NS_PREFIX: &'static str = "saml2p";
+    pub const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:protocol";
+
+    #[inline]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    #[inline]
+    pub fn set_value(&mut self, value: String) {
+        self.value = value;
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for SessionIndex {
+    type Error = SAMLError;
+
+    fn try_from(object: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        match object.text() {
+            Some(value) => Ok(SessionIndex {
+                value: value.to_string(),
+            }),
+            None => Err(SAMLError::UnmarshallingError("Invalid XML".to_string())),
+        }
+    }
+}
+
+impl TryFrom<SessionIndex> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(session_index: SessionIndex) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(SessionIndex::NS_URI.to_string()),
+            SessionIndex::ELEMENT_NAME.to_string(),
+            Some(SessionIndex::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            SessionIndex::NS_PREFIX.to_string(),
+            SessionIndex::NS_URI.to_string(),
+        );
+        xml_object.set_text(Some(session_index.value.to_string()));
+        Ok(xml_object)
+    }
+}
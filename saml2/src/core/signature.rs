@@ -1,6 +0,0 @@
-use crate::common::SAML2Obj;
-
-#[derive(Debug, Default, Clone)]
-pub struct Signature {}
-
-impl SAML2Obj for Signature {}
@@ -1,3 +1,11 @@
-use std::fmt::Debug;
+use std::{any::Any, fmt::Debug};
 
-pub trait Statement: Debug {}
+use crate::{error::SAMLError, xml::XmlObject};
+
+pub trait Statement: Debug {
+    /// serialize the statement, statements are stored type erased in an assertion
+    fn into_xml_object(self: Box<Self>) -> Result<XmlObject, SAMLError>;
+
+    /// give access to the concrete statement, e.g. to downcast to an `AttributeStatement`
+    fn as_any(&self) -> &dyn Any;
+}
@@ -62,10 +62,10 @@ impl TryFrom<Ref<'_, XmlObject>> for Status {
                     status.set_status_code(StatusCode::try_from(child)?);
                 }
                 Status::CHILD_STATUS_DETAIL => {
-                    todo!("StatusDetail not implemented yet")
+                    status.set_status_detail(Some(StatusDetail::try_from(child)?));
                 }
                 Status::CHILD_STATUS_MESSAGE => {
-                    todo!("StatusMessage not implemented yet")
+                    status.set_status_message(Some(StatusMessage::try_from(child)?));
                 }
                 _ => {}
             }
@@ -88,11 +88,47 @@ impl TryFrom<Status> for XmlObject {
             value.status_code,
         )?)));
         if let Some(status_message) = value.status_message {
-            todo!("StatusMessage not implemented yet")
+            object.add_child(Rc::new(RefCell::new(XmlObject::try_from(status_message)?)));
         }
         if let Some(status_detail) = value.status_detail {
-            todo!("StatusDetail not implemented yet")
+            object.add_child(Rc::new(RefCell::new(XmlObject::try_from(status_detail)?)));
         }
         Ok(object)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{util::InputStream, xml::XmlObject};
+
+    use super::Status;
+
+    const STATUS: &str = r#"<saml2p:Status xmlns:saml2p="urn:oasis:names:tc:SAML:2.0:protocol"><saml2p:StatusCode Value="urn:oasis:names:tc:SAML:2.0:status:Responder"/><saml2p:StatusMessage>backend unavailable</saml2p:StatusMessage><saml2p:StatusDetail><ex:Retry xmlns:ex="urn:example:status" after="30"/></saml2p:StatusDetail></saml2p:Status>"#;
+
+    fn parse(xml: String) -> Status {
+        let root = XmlObject::parse_xml(InputStream::new(xml.into_bytes())).unwrap();
+        let status = Status::try_from(root.borrow()).unwrap();
+        status
+    }
+
+    #[test]
+    fn we_can_parse_and_serialize_status_detail() {
+        let status = parse(STATUS.to_string());
+        let element = XmlObject::try_from(status).unwrap();
+        let xml = XmlObject::write_xml(Rc::new(RefCell::new(element))).unwrap();
+
+        let status = parse(xml);
+        assert_eq!(
+            status.status_message().unwrap().value().unwrap(),
+            "backend unavailable"
+        );
+        let status_detail = status.status_detail().unwrap();
+        assert_eq!(status_detail.unknown_children().len(), 1);
+        let retry = status_detail.unknown_children()[0].borrow();
+        assert_eq!(retry.q_name().local_name(), "Retry");
+        assert_eq!(retry.q_name().namespace_uri(), Some("urn:example:status"));
+        assert_eq!(retry.attribute("after"), Some("30"));
+    }
+}
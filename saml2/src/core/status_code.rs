@@ -18,38 +18,40 @@ impl StatusCode {
     const NS_PREFIX: &'static str = "saml2p";
     const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:protocol";
 
-    const SUCCESS: &'static str = "urn:oasis:names:tc:SAML:2.0:status:Success";
-    const REQUESTER: &'static str = "urn:oasis:names:tc:SAML:2.0:status:Requester";
-    const RESPONDER: &'static str = "urn:oasis:names:tc:SAML:2.0:status:Responder";
-    const VERSION_MISMATCH: &'static str = "urn:oasis:names:tc:SAML:2.0:status:VersionMismatch";
-    const AUTHN_FAILED: &'static str = "urn:oasis:names:tc:SAML:2.0:status:AuthnFailed";
-    const INVALID_ATTR_NAME_OR_VALUE: &'static str =
+    pub const SUCCESS: &'static str = "urn:oasis:names:tc:SAML:2.0:status:Success";
+    pub const REQUESTER: &'static str = "urn:oasis:names:tc:SAML:2.0:status:Requester";
+    pub const RESPONDER: &'static str = "urn:oasis:names:tc:SAML:2.0:status:Responder";
+    pub const VERSION_MISMATCH: &'static str = "urn:oasis:names:tc:SAML:2.0:status:VersionMismatch";
+    pub const AUTHN_FAILED: &'static str = "urn:oasis:names:tc:SAML:2.0:status:AuthnFailed";
+    pub const INVALID_ATTR_NAME_OR_VALUE: &'static str =
         "urn:oasis:names:tc:SAML:2.0:status:InvalidAttrNameOrValue";
-    const INVALID_NAME_ID_POLICY: &'static str =
+    pub const INVALID_NAME_ID_POLICY: &'static str =
         "urn:oasis:names:tc:SAML:2.0:status:InvalidNameIDPolicy";
-    const NO_AUTHN_CONTEXT: &'static str = "urn:oasis:names:tc:SAML:2.0:status:NoAuthnContext";
-    const NO_AVAILABLE_IDP: &'static str = "urn:oasis:names:tc:SAML:2.0:status:NoAvailableIDP";
-    const NO_PASSIVE: &'static str = "urn:oasis:names:tc:SAML:2.0:status:NoPassive";
-    const NO_SUPPORTED_IDP: &'static str = "urn:oasis:names:tc:SAML:2.0:status:NoSupportedIDP";
-    const PARTIAL_LOGOUT: &'static str = "urn:oasis:names:tc:SAML:2.0:status:PartialLogout";
-    const PROXY_COUNT_EXCEEDED: &'static str =
+    pub const NO_AUTHN_CONTEXT: &'static str = "urn:oasis:names:tc:SAML:2.0:status:NoAuthnContext";
+    pub const NO_AVAILABLE_IDP: &'static str = "urn:oasis:names:tc:SAML:2.0:status:NoAvailableIDP";
+    pub const NO_PASSIVE: &'static str = "urn:oasis:names:tc:SAML:2.0:status:NoPassive";
+    pub const NO_SUPPORTED_IDP: &'static str = "urn:oasis:names:tc:SAML:2.0:status:NoSupportedIDP";
+    pub const PARTIAL_LOGOUT: &'static str = "urn:oasis:names:tc:SAML:2.0:status:PartialLogout";
+    pub const PROXY_COUNT_EXCEEDED: &'static str =
         "urn:oasis:names:tc:SAML:2.0:status:ProxyCountExceeded";
-    const REQUEST_DENIED: &'static str = "urn:oasis:names:tc:SAML:2.0:status:RequestDenied";
-    const REQUEST_UNSUPPORTED: &'static str =
+    pub const REQUEST_DENIED: &'static str = "urn:oasis:names:tc:SAML:2.0:status:RequestDenied";
+    pub const REQUEST_UNSUPPORTED: &'static str =
         "urn:oasis:names:tc:SAML:2.0:status:RequestUnsupported";
-    const REQUEST_VERSION_DEPRECATED: &'static str =
+    pub const REQUEST_VERSION_DEPRECATED: &'static str =
         "urn:oasis:names:tc:SAML:2.0:status:RequestVersionDeprecated";
-    const REQUEST_VERSION_TOO_HIGH: &'static str =
+    pub const REQUEST_VERSION_TOO_HIGH: &'static str =
         "urn:oasis:names:tc:SAML:2.0:status:RequestVersionTooHigh";
-    const REQUEST_VERSION_TOO_LOW: &'static str =
+    pub const REQUEST_VERSION_TOO_LOW: &'static str =
         "urn:oasis:names:tc:SAML:2.0:status:RequestVersionTooLow";
-    const RESOURCE_NOT_RECOGNIZED: &'static str =
+    pub const RESOURCE_NOT_RECOGNIZED: &'static str =
         "urn:oasis:names:tc:SAML:2.0:status:ResourceNotRecognized";
-    const TOO_MANY_RESPONSES: &'static str = "urn:oasis:names:tc:SAML:2.0:status:TooManyResponses";
-    const UNKNOWN_ATTR_PROFILE: &'static str =
+    pub const TOO_MANY_RESPONSES: &'static str =
+        "urn:oasis:names:tc:SAML:2.0:status:TooManyResponses";
+    pub const UNKNOWN_ATTR_PROFILE: &'static str =
         "urn:oasis:names:tc:SAML:2.0:status:UnknownAttrProfile";
-    const UNKNOWN_PRINCIPAL: &'static str = "urn:oasis:names:tc:SAML:2.0:status:UnknownPrincipal";
-    const UNSUPPORTED_BINDING: &'static str =
+    pub const UNKNOWN_PRINCIPAL: &'static str =
+        "urn:oasis:names:tc:SAML:2.0:status:UnknownPrincipal";
+    pub const UNSUPPORTED_BINDING: &'static str =
         "urn:oasis:names:tc:SAML:2.0:status:UnsupportedBinding";
 
     pub fn value(&self) -> &String {
@@ -60,8 +62,8 @@ impl StatusCode {
         self.value = value;
     }
 
-    pub fn status_code(&self) -> Option<&Box<StatusCode>> {
-        self.status_code.as_ref()
+    pub fn status_code(&self) -> Option<&StatusCode> {
+        self.status_code.as_deref()
     }
 
     pub fn set_status_code(&mut self, status_code: Option<Box<StatusCode>>) {
@@ -75,11 +77,8 @@ impl TryFrom<Ref<'_, XmlObject>> for StatusCode {
     fn try_from(object: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
         let mut status_code = StatusCode::default();
         for attribute in object.attributes() {
-            match attribute.0.as_str() {
-                StatusCode::ATTRIB_VALUE => {
-                    status_code.set_value(attribute.1.to_string());
-                }
-                _ => {}
+            if attribute.0 == StatusCode::ATTRIB_VALUE {
+                status_code.set_value(attribute.1.to_string());
             }
         }
         Ok(status_code)
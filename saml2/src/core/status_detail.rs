@@ -1,10 +1,59 @@
-use std::fmt::Debug;
+use std::{
+    cell::{Ref, RefCell},
+    fmt::Debug,
+    rc::Rc,
+};
 
-use crate::common::SAML2Obj;
+use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
 
+/// `samlp:StatusDetail`, its elements are defined by the responder and kept as they are
 #[derive(Default, Debug)]
 pub struct StatusDetail {
-    unknown_children: Vec<Box<dyn SAML2Obj>>,
+    unknown_children: Vec<Rc<RefCell<XmlObject>>>,
 }
 
 impl SAML2Obj for StatusDetail {}
+
+impl StatusDetail {
+    const ELEMENT_NAME: &'static str = "StatusDetail";
+    const NS_PREFIX: &'static str = "saml2p";
+    const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:protocol";
+
+    #[inline]
+    pub fn unknown_children(&self) -> &Vec<Rc<RefCell<XmlObject>>> {
+        &self.unknown_children
+    }
+
+    #[inline]
+    pub fn add_unknown_child(&mut self, unknown_child: Rc<RefCell<XmlObject>>) {
+        self.unknown_children.push(unknown_child);
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for StatusDetail {
+    type Error = SAMLError;
+
+    fn try_from(object: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut status_detail = StatusDetail::default();
+        for child in object.children() {
+            status_detail.add_unknown_child(Rc::clone(child));
+        }
+        Ok(status_detail)
+    }
+}
+
+impl TryFrom<StatusDetail> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(status_detail: StatusDetail) -> Result<Self, Self::Error> {
+        let mut object = XmlObject::new(
+            Some(StatusDetail::NS_URI.to_string()),
+            StatusDetail::ELEMENT_NAME.to_string(),
+            Some(StatusDetail::NS_PREFIX.to_string()),
+        );
+        for child in status_detail.unknown_children {
+            object.add_child(child);
+        }
+        Ok(object)
+    }
+}
@@ -1,8 +1,50 @@
-use crate::common::SAML2Obj;
+use std::cell::Ref;
 
-#[derive(Debug, Clone)]
+use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+
+#[derive(Debug, Clone, Default)]
 pub struct StatusMessage {
     value: Option<String>,
 }
 
 impl SAML2Obj for StatusMessage {}
+
+impl StatusMessage {
+    const ELEMENT_NAME: &'static str = "StatusMessage";
+    const NS_PREFIX: &'static str = "saml2p";
+    const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:protocol";
+
+    #[inline]
+    pub fn value(&self) -> Option<&String> {
+        self.value.as_ref()
+    }
+
+    #[inline]
+    pub fn set_value(&mut self, value: Option<String>) {
+        self.value = value;
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for StatusMessage {
+    type Error = SAMLError;
+
+    fn try_from(object: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut status_message = StatusMessage::default();
+        status_message.set_value(object.text().map(|s| s.to_string()));
+        Ok(status_message)
+    }
+}
+
+impl TryFrom<StatusMessage> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(status_message: StatusMessage) -> Result<Self, Self::Error> {
+        let mut object = XmlObject::new(
+            Some(StatusMessage::NS_URI.to_string()),
+            StatusMessage::ELEMENT_NAME.to_string(),
+            Some(StatusMessage::NS_PREFIX.to_string()),
+        );
+        object.set_text(status_message.value);
+        Ok(object)
+    }
+}
@@ -1,10 +1,13 @@
-use chrono::{DateTime, Utc};
+use std::any::Any;
+
 use crate::core::extensions::Extensions;
 use crate::core::issuer::Issuer;
 use crate::core::saml_version::SAMLVersion;
 use crate::core::status::Status;
+use crate::signature::signature::Signature;
+use chrono::{DateTime, Utc};
 
-pub trait StatusResponseType {
+pub trait StatusResponseType: Any {
     fn id(&self) -> &String;
 
     fn set_id(&mut self, id: String);
@@ -33,9 +36,9 @@ pub trait StatusResponseType {
 
     fn set_issuer(&mut self, issuer: Option<Issuer>);
 
-    fn signature(&self) -> Option<&String>;
+    fn signature(&self) -> Option<&Signature>;
 
-    fn set_signature(&mut self, signature: Option<String>);
+    fn set_signature(&mut self, signature: Option<Signature>);
 
     fn extensions(&self) -> Option<&Extensions>;
 
@@ -44,4 +47,6 @@ pub trait StatusResponseType {
     fn status(&self) -> &Status;
 
     fn set_status(&mut self, status: Status);
+
+    fn as_any(&self) -> &dyn Any;
 }
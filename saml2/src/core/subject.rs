@@ -103,9 +103,9 @@ impl TryFrom<Subject> for XmlObject {
 
     fn try_from(subject: Subject) -> Result<Self, Self::Error> {
         let mut xml_object = XmlObject::new(
-            Some(Subject::NS_PREFIX.to_string()),
-            Subject::ELEMENT_NAME.to_string(),
             Some(Subject::NS_URI.to_string()),
+            Subject::ELEMENT_NAME.to_string(),
+            Some(Subject::NS_PREFIX.to_string()),
         );
         xml_object.add_namespace(Subject::NS_PREFIX.to_string(), Subject::NS_URI.to_string());
         if let Some(base_id) = subject.base_id {
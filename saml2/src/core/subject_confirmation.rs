@@ -93,11 +93,8 @@ impl TryFrom<Ref<'_, XmlObject>> for SubjectConfirmation {
     fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
         let mut subject_confirmation = SubjectConfirmation::default();
         for attrib in element.attributes() {
-            match attrib.0.as_str() {
-                SubjectConfirmation::ATTRIB_METHOD => {
-                    subject_confirmation.set_method(attrib.1.to_string());
-                }
-                _ => {}
+            if attrib.0 == SubjectConfirmation::ATTRIB_METHOD {
+                subject_confirmation.set_method(attrib.1.to_string());
             }
         }
         for child in element.children() {
@@ -129,9 +126,9 @@ impl TryFrom<SubjectConfirmation> for XmlObject {
 
     fn try_from(subject_confirmation: SubjectConfirmation) -> Result<Self, Self::Error> {
         let mut xml_object = XmlObject::new(
-            Some(SubjectConfirmation::NS_PREFIX.to_string()),
-            SubjectConfirmation::ELEMENT_NAME.to_string(),
             Some(SubjectConfirmation::NS_URI.to_string()),
+            SubjectConfirmation::ELEMENT_NAME.to_string(),
+            Some(SubjectConfirmation::NS_PREFIX.to_string()),
         );
         xml_object.add_namespace(
             SubjectConfirmation::NS_PREFIX.to_string(),
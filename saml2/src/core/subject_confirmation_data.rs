@@ -1,4 +1,7 @@
-use std::cell::Ref;
+use std::{
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
 
 use chrono::{DateTime, Utc};
 
@@ -14,7 +17,7 @@ pub struct SubjectConfirmationData {
     in_response_to: Option<String>,
     address: Option<String>,
     unknown_attributes: AttributeMap,
-    unknown_children: Vec<Box<dyn SAML2Obj>>,
+    unknown_children: Vec<Rc<RefCell<XmlObject>>>,
 }
 
 impl SAML2Obj for SubjectConfirmationData {}
@@ -79,6 +82,21 @@ impl SubjectConfirmationData {
     pub fn set_address(&mut self, address: Option<String>) {
         self.address = address;
     }
+
+    #[inline]
+    pub fn unknown_attributes(&self) -> &AttributeMap {
+        &self.unknown_attributes
+    }
+
+    #[inline]
+    pub fn unknown_children(&self) -> &Vec<Rc<RefCell<XmlObject>>> {
+        &self.unknown_children
+    }
+
+    #[inline]
+    pub fn add_unknown_child(&mut self, unknown_child: Rc<RefCell<XmlObject>>) {
+        self.unknown_children.push(unknown_child);
+    }
 }
 
 impl TryFrom<Ref<'_, XmlObject>> for SubjectConfirmationData {
@@ -105,14 +123,14 @@ impl TryFrom<Ref<'_, XmlObject>> for SubjectConfirmationData {
                     subject_confirmation_data.set_address(Some(attribute.1.to_string()));
                 }
                 _ => {
-                    println!("subject confirmation data attribute: {}", attribute.0);
+                    subject_confirmation_data
+                        .unknown_attributes
+                        .add_attribute_of(&element, attribute.0.as_str(), value);
                 }
             }
         }
         for child in element.children() {
-            let child = child.borrow();
-            let name = child.q_name().local_name();
-            println!("subject confirmation data child: {}", name);
+            subject_confirmation_data.add_unknown_child(Rc::clone(child));
         }
         Ok(subject_confirmation_data)
     }
@@ -123,9 +141,9 @@ impl TryFrom<SubjectConfirmationData> for XmlObject {
 
     fn try_from(subject_confirmation_data: SubjectConfirmationData) -> Result<Self, Self::Error> {
         let mut xml_object = XmlObject::new(
-            Some(SubjectConfirmationData::NS_PREFIX.to_string()),
-            SubjectConfirmationData::ELEMENT_NAME.to_string(),
             Some(SubjectConfirmationData::NS_URI.to_string()),
+            SubjectConfirmationData::ELEMENT_NAME.to_string(),
+            Some(SubjectConfirmationData::NS_PREFIX.to_string()),
         );
         xml_object.add_namespace(
             SubjectConfirmationData::NS_PREFIX.to_string(),
@@ -161,7 +179,52 @@ impl TryFrom<SubjectConfirmationData> for XmlObject {
                 address.to_string(),
             );
         }
-        // TODO: add unknown attributes and children
+        subject_confirmation_data
+            .unknown_attributes
+            .write_to(&mut xml_object);
+        for child in subject_confirmation_data.unknown_children {
+            xml_object.add_child(child);
+        }
         Ok(xml_object)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{util::InputStream, xml::XmlObject};
+
+    use super::SubjectConfirmationData;
+
+    const SUBJECT_CONFIRMATION_DATA: &str = r#"<saml2:SubjectConfirmationData xmlns:saml2="urn:oasis:names:tc:SAML:2.0:assertion" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:type="saml2:KeyInfoConfirmationDataType" Recipient="https://sp.example.org/acs"><ds:KeyInfo xmlns:ds="http://www.w3.org/2000/09/xmldsig#"><ds:KeyName>sp</ds:KeyName></ds:KeyInfo></saml2:SubjectConfirmationData>"#;
+
+    fn parse(xml: String) -> SubjectConfirmationData {
+        let root = XmlObject::parse_xml(InputStream::new(xml.into_bytes())).unwrap();
+        let subject_confirmation_data = SubjectConfirmationData::try_from(root.borrow()).unwrap();
+        subject_confirmation_data
+    }
+
+    #[test]
+    fn we_can_parse_and_serialize_key_info_confirmation_data() {
+        let subject_confirmation_data = parse(SUBJECT_CONFIRMATION_DATA.to_string());
+        let element = XmlObject::try_from(subject_confirmation_data).unwrap();
+        let xml = XmlObject::write_xml(Rc::new(RefCell::new(element))).unwrap();
+
+        let subject_confirmation_data = parse(xml);
+        assert_eq!(
+            subject_confirmation_data.recipient().unwrap(),
+            "https://sp.example.org/acs"
+        );
+        assert_eq!(
+            subject_confirmation_data
+                .unknown_attributes()
+                .get("xsi:type"),
+            Some("saml2:KeyInfoConfirmationDataType")
+        );
+        assert_eq!(subject_confirmation_data.unknown_children().len(), 1);
+        let key_info = subject_confirmation_data.unknown_children()[0].borrow();
+        assert_eq!(key_info.q_name().local_name(), "KeyInfo");
+        assert_eq!(key_info.children()[0].borrow().text().unwrap(), "sp");
+    }
+}
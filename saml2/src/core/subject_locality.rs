@@ -64,9 +64,9 @@ impl TryFrom<SubjectLocality> for XmlObject {
 
     fn try_from(subject_locality: SubjectLocality) -> Result<Self, Self::Error> {
         let mut xml_object = XmlObject::new(
-            Some(SubjectLocality::NS_PREFIX.to_string()),
-            SubjectLocality::ELEMENT_NAME.to_string(),
             Some(SubjectLocality::NS_URI.to_string()),
+            SubjectLocality::ELEMENT_NAME.to_string(),
+            Some(SubjectLocality::NS_PREFIX.to_string()),
         );
         if let Some(address) = subject_locality.address() {
             xml_object.add_attribute(
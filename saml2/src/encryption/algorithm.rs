@@ -0,0 +1,308 @@
+use aes::{Aes128, Aes192, Aes256};
+use aes_gcm::{
+    aead::{consts::U12, generic_array::GenericArray, rand_core::RngCore, Aead, KeyInit, OsRng},
+    AesGcm,
+};
+use base64::{prelude::BASE64_STANDARD, Engine};
+use cbc::cipher::{block_padding::NoPadding, BlockDecryptMut, BlockEncryptMut, KeyIvInit};
+use rsa::{Oaep, RsaPrivateKey, RsaPublicKey};
+
+use crate::{
+    error::SAMLError,
+    signature::{algorithm::DigestAlgorithm, digest_method::DigestMethod},
+};
+
+use super::{encrypted_method::EncryptedMethod, mgf::MGF};
+
+const CBC_IV_LENGTH: usize = 16;
+const GCM_IV_LENGTH: usize = 12;
+const GCM_TAG_LENGTH: usize = 16;
+
+/// block encryption algorithms of the `EncryptedData` content
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockEncryptionAlgorithm {
+    Aes128Cbc,
+    Aes192Cbc,
+    Aes256Cbc,
+    Aes128Gcm,
+    Aes192Gcm,
+    Aes256Gcm,
+}
+
+impl BlockEncryptionAlgorithm {
+    pub const AES128_CBC: &'static str = "http://www.w3.org/2001/04/xmlenc#aes128-cbc";
+    pub const AES192_CBC: &'static str = "http://www.w3.org/2001/04/xmlenc#aes192-cbc";
+    pub const AES256_CBC: &'static str = "http://www.w3.org/2001/04/xmlenc#aes256-cbc";
+    pub const AES128_GCM: &'static str = "http://www.w3.org/2009/xmlenc11#aes128-gcm";
+    pub const AES192_GCM: &'static str = "http://www.w3.org/2009/xmlenc11#aes192-gcm";
+    pub const AES256_GCM: &'static str = "http://www.w3.org/2009/xmlenc11#aes256-gcm";
+
+    pub fn from_uri(uri: &str) -> Result<Self, SAMLError> {
+        match uri {
+            Self::AES128_CBC => Ok(BlockEncryptionAlgorithm::Aes128Cbc),
+            Self::AES192_CBC => Ok(BlockEncryptionAlgorithm::Aes192Cbc),
+            Self::AES256_CBC => Ok(BlockEncryptionAlgorithm::Aes256Cbc),
+            Self::AES128_GCM => Ok(BlockEncryptionAlgorithm::Aes128Gcm),
+            Self::AES192_GCM => Ok(BlockEncryptionAlgorithm::Aes192Gcm),
+            Self::AES256_GCM => Ok(BlockEncryptionAlgorithm::Aes256Gcm),
+            _ => Err(SAMLError::DecryptionError(format!(
+                "unsupported block encryption algorithm {}",
+                uri
+            ))),
+        }
+    }
+
+    pub fn uri(&self) -> &'static str {
+        match self {
+            BlockEncryptionAlgorithm::Aes128Cbc => Self::AES128_CBC,
+            BlockEncryptionAlgorithm::Aes192Cbc => Self::AES192_CBC,
+            BlockEncryptionAlgorithm::Aes256Cbc => Self::AES256_CBC,
+            BlockEncryptionAlgorithm::Aes128Gcm => Self::AES128_GCM,
+            BlockEncryptionAlgorithm::Aes192Gcm => Self::AES192_GCM,
+            BlockEncryptionAlgorithm::Aes256Gcm => Self::AES256_GCM,
+        }
+    }
+
+    /// length of the symmetric key in octets
+    pub fn key_length(&self) -> usize {
+        match self {
+            BlockEncryptionAlgorithm::Aes128Cbc | BlockEncryptionAlgorithm::Aes128Gcm => 16,
+            BlockEncryptionAlgorithm::Aes192Cbc | BlockEncryptionAlgorithm::Aes192Gcm => 24,
+            BlockEncryptionAlgorithm::Aes256Cbc | BlockEncryptionAlgorithm::Aes256Gcm => 32,
+        }
+    }
+
+    /// a fresh random content key
+    pub fn generate_key(&self) -> Vec<u8> {
+        let mut key = vec![0u8; self.key_length()];
+        OsRng.fill_bytes(&mut key);
+        key
+    }
+
+    /// encrypt `data` under a fresh random IV, the cipher value is laid out as `decrypt` expects it
+    pub fn encrypt(&self, key: &[u8], data: &[u8]) -> Result<Vec<u8>, SAMLError> {
+        if key.len() != self.key_length() {
+            return Err(SAMLError::EncryptionError(format!(
+                "{} requires a key of {} octets",
+                self.uri(),
+                self.key_length()
+            )));
+        }
+        match self {
+            BlockEncryptionAlgorithm::Aes128Cbc => cbc_encrypt::<cbc::Encryptor<Aes128>>(key, data),
+            BlockEncryptionAlgorithm::Aes192Cbc => cbc_encrypt::<cbc::Encryptor<Aes192>>(key, data),
+            BlockEncryptionAlgorithm::Aes256Cbc => cbc_encrypt::<cbc::Encryptor<Aes256>>(key, data),
+            BlockEncryptionAlgorithm::Aes128Gcm => gcm_encrypt::<AesGcm<Aes128, U12>>(key, data),
+            BlockEncryptionAlgorithm::Aes192Gcm => gcm_encrypt::<AesGcm<Aes192, U12>>(key, data),
+            BlockEncryptionAlgorithm::Aes256Gcm => gcm_encrypt::<AesGcm<Aes256, U12>>(key, data),
+        }
+    }
+
+    /// decrypt a cipher value, which starts with the IV and, for GCM, ends with the
+    /// authentication tag, every failure is the same opaque error
+    pub fn decrypt(&self, key: &[u8], data: &[u8]) -> Result<Vec<u8>, SAMLError> {
+        // the key usually comes out of a key transport, its length says something about it
+        if key.len() != self.key_length() {
+            return Err(decryption_failed());
+        }
+        match self {
+            BlockEncryptionAlgorithm::Aes128Cbc => cbc_decrypt::<cbc::Decryptor<Aes128>>(key, data),
+            BlockEncryptionAlgorithm::Aes192Cbc => cbc_decrypt::<cbc::Decryptor<Aes192>>(key, data),
+            BlockEncryptionAlgorithm::Aes256Cbc => cbc_decrypt::<cbc::Decryptor<Aes256>>(key, data),
+            BlockEncryptionAlgorithm::Aes128Gcm => gcm_decrypt::<AesGcm<Aes128, U12>>(key, data),
+            BlockEncryptionAlgorithm::Aes192Gcm => gcm_decrypt::<AesGcm<Aes192, U12>>(key, data),
+            BlockEncryptionAlgorithm::Aes256Gcm => gcm_decrypt::<AesGcm<Aes256, U12>>(key, data),
+        }
+    }
+}
+
+fn cbc_encrypt<E: KeyIvInit + BlockEncryptMut>(
+    key: &[u8],
+    data: &[u8],
+) -> Result<Vec<u8>, SAMLError> {
+    let mut iv = [0u8; CBC_IV_LENGTH];
+    OsRng.fill_bytes(&mut iv);
+    let encryptor = E::new_from_slices(key, &iv)
+        .map_err(|_| SAMLError::EncryptionError("invalid key or IV".to_string()))?;
+    let padding = CBC_IV_LENGTH - data.len() % CBC_IV_LENGTH;
+    let mut buffer = data.to_vec();
+    buffer.resize(data.len() + padding, padding as u8);
+    let cipher_text_length = encryptor
+        .encrypt_padded_mut::<NoPadding>(&mut buffer, data.len() + padding)
+        .map_err(|_| SAMLError::EncryptionError("invalid plain text length".to_string()))?
+        .len();
+    buffer.truncate(cipher_text_length);
+    Ok([iv.as_slice(), &buffer].concat())
+}
+
+fn cbc_decrypt<D: KeyIvInit + BlockDecryptMut>(
+    key: &[u8],
+    data: &[u8],
+) -> Result<Vec<u8>, SAMLError> {
+    if data.len() < 2 * CBC_IV_LENGTH {
+        return Err(decryption_failed());
+    }
+    let (iv, cipher_text) = data.split_at(CBC_IV_LENGTH);
+    let decryptor = D::new_from_slices(key, iv).map_err(|_| decryption_failed())?;
+    let mut buffer = cipher_text.to_vec();
+    let plain_text_length = decryptor
+        .decrypt_padded_mut::<NoPadding>(&mut buffer)
+        .map_err(|_| decryption_failed())?
+        .len();
+    buffer.truncate(plain_text_length);
+    // XML Encryption padding: the last octet is the padding length, the others are arbitrary
+    let padding = buffer.last().copied().unwrap_or_default() as usize;
+    if padding == 0 || padding > CBC_IV_LENGTH {
+        return Err(decryption_failed());
+    }
+    buffer.truncate(buffer.len() - padding);
+    Ok(buffer)
+}
+
+fn gcm_encrypt<C: KeyInit + Aead>(key: &[u8], data: &[u8]) -> Result<Vec<u8>, SAMLError> {
+    let mut iv = [0u8; GCM_IV_LENGTH];
+    OsRng.fill_bytes(&mut iv);
+    let cipher = C::new_from_slice(key)
+        .map_err(|_| SAMLError::EncryptionError("invalid key".to_string()))?;
+    let cipher_text = cipher
+        .encrypt(GenericArray::from_slice(&iv), data)
+        .map_err(|_| SAMLError::EncryptionError("encryption failed".to_string()))?;
+    Ok([iv.as_slice(), &cipher_text].concat())
+}
+
+fn gcm_decrypt<C: KeyInit + Aead>(key: &[u8], data: &[u8]) -> Result<Vec<u8>, SAMLError> {
+    if data.len() < GCM_IV_LENGTH + GCM_TAG_LENGTH {
+        return Err(decryption_failed());
+    }
+    let (iv, cipher_text) = data.split_at(GCM_IV_LENGTH);
+    let cipher = C::new_from_slice(key).map_err(|_| decryption_failed())?;
+    cipher
+        .decrypt(GenericArray::from_slice(iv), cipher_text)
+        .map_err(|_| decryption_failed())
+}
+
+/// The one error of a decryption that failed after the key is known. Telling a bad padding
+/// from a bad tag or from content that does not parse hands an attacker a decryption oracle.
+pub(crate) fn decryption_failed() -> SAMLError {
+    SAMLError::DecryptionError("decryption failed".to_string())
+}
+
+/// key transport algorithms of the `EncryptedKey`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyTransportAlgorithm {
+    RsaOaepMgf1p,
+    RsaOaep,
+}
+
+impl KeyTransportAlgorithm {
+    pub const RSA_OAEP_MGF1P: &'static str = "http://www.w3.org/2001/04/xmlenc#rsa-oaep-mgf1p";
+    pub const RSA_OAEP: &'static str = "http://www.w3.org/2009/xmlenc11#rsa-oaep";
+
+    pub fn from_uri(uri: &str) -> Result<Self, SAMLError> {
+        match uri {
+            Self::RSA_OAEP_MGF1P => Ok(KeyTransportAlgorithm::RsaOaepMgf1p),
+            Self::RSA_OAEP => Ok(KeyTransportAlgorithm::RsaOaep),
+            _ => Err(SAMLError::DecryptionError(format!(
+                "unsupported key transport algorithm {}",
+                uri
+            ))),
+        }
+    }
+
+    pub fn uri(&self) -> &'static str {
+        match self {
+            KeyTransportAlgorithm::RsaOaepMgf1p => Self::RSA_OAEP_MGF1P,
+            KeyTransportAlgorithm::RsaOaep => Self::RSA_OAEP,
+        }
+    }
+
+    /// the `EncryptionMethod` announcing the algorithm, RSA-OAEP of XML Encryption 1.1 is
+    /// used with SHA-256 and the default MGF1 with SHA-1
+    pub fn encrypted_method(&self) -> EncryptedMethod {
+        let mut encrypted_method = EncryptedMethod::new(self.uri().to_string());
+        let digest = match self {
+            KeyTransportAlgorithm::RsaOaepMgf1p => DigestAlgorithm::Sha1,
+            KeyTransportAlgorithm::RsaOaep => DigestAlgorithm::Sha256,
+        };
+        encrypted_method.set_digest_method(Some(DigestMethod::new(digest.uri().to_string())));
+        encrypted_method
+    }
+
+    /// wrap the symmetric `key` for the owner of `public_key` with the parameters of
+    /// `encrypted_method`
+    pub fn encrypt(
+        &self,
+        public_key: &RsaPublicKey,
+        encrypted_method: &EncryptedMethod,
+        key: &[u8],
+    ) -> Result<Vec<u8>, SAMLError> {
+        let padding = self
+            .oaep_padding(encrypted_method)
+            .map_err(SAMLError::EncryptionError)?;
+        public_key
+            .encrypt(&mut OsRng, padding, key)
+            .map_err(|e| SAMLError::EncryptionError(format!("key transport failed: {}", e)))
+    }
+
+    /// unwrap the symmetric key, the digest, mask generation function and label are taken
+    /// from the `EncryptionMethod` and default to SHA-1 and an empty label
+    pub fn decrypt(
+        &self,
+        private_key: &RsaPrivateKey,
+        encrypted_method: &EncryptedMethod,
+        data: &[u8],
+    ) -> Result<Vec<u8>, SAMLError> {
+        private_key
+            .decrypt(
+                self.oaep_padding(encrypted_method)
+                    .map_err(SAMLError::DecryptionError)?,
+                data,
+            )
+            .map_err(|_| decryption_failed())
+    }
+
+    fn oaep_padding(&self, encrypted_method: &EncryptedMethod) -> Result<Oaep, String> {
+        let digest = match encrypted_method.digest_method() {
+            Some(digest_method) => {
+                DigestAlgorithm::from_uri(digest_method.algorithm()).map_err(|_| {
+                    format!(
+                        "unsupported key transport digest {}",
+                        digest_method.algorithm()
+                    )
+                })?
+            }
+            None => DigestAlgorithm::Sha1,
+        };
+        let mgf_digest = match (self, encrypted_method.mgf()) {
+            (KeyTransportAlgorithm::RsaOaep, Some(mgf)) => match mgf.algorithm() {
+                MGF::MGF1_SHA1 => DigestAlgorithm::Sha1,
+                MGF::MGF1_SHA256 => DigestAlgorithm::Sha256,
+                MGF::MGF1_SHA512 => DigestAlgorithm::Sha512,
+                algorithm => {
+                    return Err(format!(
+                        "unsupported mask generation function {}",
+                        algorithm
+                    ))
+                }
+            },
+            _ => DigestAlgorithm::Sha1,
+        };
+        let label = match encrypted_method
+            .oeap_params()
+            .and_then(|oaep_params| oaep_params.value())
+        {
+            Some(value) => {
+                let label = BASE64_STANDARD
+                    .decode(value.trim())
+                    .map_err(|_| "invalid OAEPParams".to_string())?;
+                Some(String::from_utf8(label).map_err(|_| "unsupported OAEPParams".to_string())?)
+            }
+            None => None,
+        };
+        Ok(Oaep {
+            digest: digest.dyn_digest(),
+            mgf_digest: mgf_digest.dyn_digest(),
+            label,
+        })
+    }
+}
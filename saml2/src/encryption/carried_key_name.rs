@@ -44,9 +44,9 @@ impl TryFrom<CarriedKeyName> for XmlObject {
 
     fn try_from(carried_key_name: CarriedKeyName) -> Result<Self, Self::Error> {
         let mut xml_object = XmlObject::new(
-            Some(CarriedKeyName::NS_PREFIX.to_string()),
-            CarriedKeyName::ELEMENT_NAME.to_string(),
             Some(CarriedKeyName::NS_URI.to_string()),
+            CarriedKeyName::ELEMENT_NAME.to_string(),
+            Some(CarriedKeyName::NS_PREFIX.to_string()),
         );
         xml_object.add_namespace(
             CarriedKeyName::NS_PREFIX.to_string(),
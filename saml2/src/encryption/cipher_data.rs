@@ -70,9 +70,9 @@ impl TryFrom<CipherData> for XmlObject {
 
     fn try_from(cipher_data: CipherData) -> Result<Self, Self::Error> {
         let mut xml_obj = XmlObject::new(
-            Some(CipherData::NS_PREFIX.to_string()),
-            CipherData::ELEMENT_NAME.to_string(),
             Some(CipherData::NS_URI.to_string()),
+            CipherData::ELEMENT_NAME.to_string(),
+            Some(CipherData::NS_PREFIX.to_string()),
         );
         xml_obj.add_namespace(
             CipherData::NS_PREFIX.to_string(),
@@ -50,20 +50,14 @@ impl TryFrom<Ref<'_, XmlObject>> for CipherReference {
     fn try_from(cipher_reference: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
         let mut cipher_ref = CipherReference::default();
         for attrib in cipher_reference.attributes() {
-            match attrib.0.as_str() {
-                CipherReference::ATTRIB_URI => {
-                    cipher_ref.set_uri(Some(attrib.1.to_string()));
-                }
-                _ => {}
+            if attrib.0 == CipherReference::ATTRIB_URI {
+                cipher_ref.set_uri(Some(attrib.1.to_string()));
             }
         }
         for child in cipher_reference.children() {
             let child = child.borrow();
-            match child.q_name().local_name() {
-                CipherReference::CHILD_TRANSFORMS => {
-                    cipher_ref.set_transforms(Some(Transforms::try_from(child)?));
-                }
-                _ => {}
+            if child.q_name().local_name() == CipherReference::CHILD_TRANSFORMS {
+                cipher_ref.set_transforms(Some(Transforms::try_from(child)?));
             }
         }
         Ok(cipher_ref)
@@ -75,9 +69,9 @@ impl TryFrom<CipherReference> for XmlObject {
 
     fn try_from(cipher_ref: CipherReference) -> Result<Self, Self::Error> {
         let mut xml_object = XmlObject::new(
-            Some(CipherReference::NS_PREFIX.to_string()),
-            CipherReference::ELEMENT_NAME.to_string(),
             Some(CipherReference::NS_URI.to_string()),
+            CipherReference::ELEMENT_NAME.to_string(),
+            Some(CipherReference::NS_PREFIX.to_string()),
         );
         xml_object.add_namespace(
             CipherReference::NS_PREFIX.to_string(),
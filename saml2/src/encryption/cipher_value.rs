@@ -15,6 +15,10 @@ impl CipherValue {
     const NS_PREFIX: &'static str = "xenc";
     const NS_URI: &'static str = "http://www.w3.org/2001/04/xmlenc#";
 
+    pub fn new(value: String) -> Self {
+        CipherValue { value }
+    }
+
     #[inline]
     pub fn value(&self) -> &str {
         &self.value
@@ -44,9 +48,9 @@ impl TryFrom<CipherValue> for XmlObject {
 
     fn try_from(cipher_value: CipherValue) -> Result<Self, Self::Error> {
         let mut xml_object = XmlObject::new(
-            Some(CipherValue::NS_PREFIX.to_string()),
-            CipherValue::ELEMENT_NAME.to_string(),
             Some(CipherValue::NS_URI.to_string()),
+            CipherValue::ELEMENT_NAME.to_string(),
+            Some(CipherValue::NS_PREFIX.to_string()),
         );
         xml_object.add_namespace(
             CipherValue::NS_PREFIX.to_string(),
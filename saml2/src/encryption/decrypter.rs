@@ -0,0 +1,352 @@
+use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
+
+use base64::{prelude::BASE64_STANDARD, Engine};
+use rsa::RsaPrivateKey;
+use xml::{
+    escape::escape_str_attribute,
+    namespace::{NS_NO_PREFIX, NS_XMLNS_PREFIX, NS_XML_PREFIX},
+};
+
+use crate::{
+    core::encrypted_element_type::EncryptedElementType, error::SAMLError,
+    signature::credential::Credential, util::InputStream, xml::XmlObject,
+};
+
+use super::{
+    algorithm::{decryption_failed, BlockEncryptionAlgorithm, KeyTransportAlgorithm},
+    cipher_data::CipherData,
+    encrypted_data::EncryptedData,
+    encrypted_key::EncryptedKey,
+    encrypter::strip_xml_declaration,
+};
+
+const CONTEXT_ELEMENT: &str = "context";
+
+/// Decrypt the content of `encrypted_element` with the private key of `credential` and
+/// parse it back into XML.
+///
+/// The content key is unwrapped from the first `EncryptedKey` that decrypts with the
+/// credential, looking into the `KeyInfo` of the `EncryptedData` before the `EncryptedKey`
+/// siblings of the element. Once a key is tried, every failure is the same opaque
+/// `DecryptionError`.
+pub fn decrypt_element(
+    encrypted_element: &dyn EncryptedElementType,
+    credential: &Credential,
+) -> Result<Rc<RefCell<XmlObject>>, SAMLError> {
+    let private_key = credential.private_key().ok_or_else(|| {
+        SAMLError::CredentialError("decryption requires a private key".to_string())
+    })?;
+    let encrypted_data = encrypted_element.encrypted_data();
+    let encrypted_keys = encrypted_data
+        .key_info()
+        .map(|key_info| key_info.encrypted_keys().iter())
+        .into_iter()
+        .flatten()
+        .chain(encrypted_element.encrypted_keys().iter());
+
+    let mut encrypted_keys = encrypted_keys.peekable();
+    if encrypted_keys.peek().is_none() {
+        return Err(SAMLError::DecryptionError(
+            "no EncryptedKey to decrypt with".to_string(),
+        ));
+    }
+    for encrypted_key in encrypted_keys {
+        let element = decrypt_key(encrypted_key, private_key)
+            .and_then(|key| decrypt_data(encrypted_data, &key))
+            .and_then(|plain_text| {
+                parse_in_context(plain_text, encrypted_data.in_scope_namespaces())
+            });
+        if let Ok(element) = element {
+            return Ok(element);
+        }
+    }
+    // which key or step failed is not told apart, see `decryption_failed`
+    Err(decryption_failed())
+}
+
+/// unwrap the symmetric key carried by `encrypted_key`
+pub fn decrypt_key(
+    encrypted_key: &EncryptedKey,
+    private_key: &RsaPrivateKey,
+) -> Result<Vec<u8>, SAMLError> {
+    let encrypted_method = encrypted_key.encrypted_method().ok_or_else(|| {
+        SAMLError::DecryptionError("EncryptedKey has no EncryptionMethod".to_string())
+    })?;
+    KeyTransportAlgorithm::from_uri(encrypted_method.algorithm())?.decrypt(
+        private_key,
+        encrypted_method,
+        &cipher_value(encrypted_key.cipher_data())?,
+    )
+}
+
+/// decrypt the content of `encrypted_data` with the symmetric `key`
+pub fn decrypt_data(encrypted_data: &EncryptedData, key: &[u8]) -> Result<Vec<u8>, SAMLError> {
+    let encrypted_method = encrypted_data.encrypted_method().ok_or_else(|| {
+        SAMLError::DecryptionError("EncryptedData has no EncryptionMethod".to_string())
+    })?;
+    BlockEncryptionAlgorithm::from_uri(encrypted_method.algorithm())?
+        .decrypt(key, &cipher_value(encrypted_data.cipher_data())?)
+}
+
+/// Parse decrypted element content in the context it was encrypted in. The content may use
+/// prefixes declared only on ancestors of the encrypted element, so it is parsed inside a
+/// wrapper that declares the bindings in scope of the `EncryptedData`.
+fn parse_in_context(
+    plain_text: Vec<u8>,
+    namespaces: &BTreeMap<String, String>,
+) -> Result<Rc<RefCell<XmlObject>>, SAMLError> {
+    let plain_text = String::from_utf8(plain_text).map_err(|_| decryption_failed())?;
+    let mut document = format!("<{}", CONTEXT_ELEMENT);
+    for (prefix, uri) in namespaces {
+        match prefix.as_str() {
+            NS_XML_PREFIX | NS_XMLNS_PREFIX => {}
+            NS_NO_PREFIX if uri.is_empty() => {}
+            NS_NO_PREFIX => document.push_str(&format!(" xmlns=\"{}\"", escape_str_attribute(uri))),
+            _ => document.push_str(&format!(
+                " xmlns:{}=\"{}\"",
+                prefix,
+                escape_str_attribute(uri)
+            )),
+        }
+    }
+    document.push('>');
+    document.push_str(strip_xml_declaration(&plain_text));
+    document.push_str(&format!("</{}>", CONTEXT_ELEMENT));
+
+    let context = XmlObject::parse_xml(InputStream::new(document.into_bytes()))
+        .map_err(|_| decryption_failed())?;
+    let context = context.borrow();
+    match context.children().as_slice() {
+        [element] => Ok(element.clone()),
+        _ => Err(decryption_failed()),
+    }
+}
+
+fn cipher_value(cipher_data: &CipherData) -> Result<Vec<u8>, SAMLError> {
+    let cipher_value = cipher_data.cipher_value().ok_or_else(|| {
+        SAMLError::DecryptionError("only inline CipherValue is supported".to_string())
+    })?;
+    let value: String = cipher_value
+        .value()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    BASE64_STANDARD
+        .decode(value)
+        .map_err(|_| SAMLError::DecryptionError("invalid CipherValue".to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use base64::{prelude::BASE64_STANDARD, Engine};
+
+    use crate::{
+        core::{
+            authn_statement::AuthnStatement, encrypted_assertion::EncryptedAssertion,
+            encrypted_element_type::EncryptedElementType, response::Response,
+            status_response_type::StatusResponseType,
+        },
+        encryption::algorithm::{BlockEncryptionAlgorithm, KeyTransportAlgorithm},
+        error::SAMLError,
+        signature::credential::Credential,
+        util::InputStream,
+        xml::XmlObject,
+    };
+
+    const PRIVATE_KEY: &str = include_str!("../../tests/resources/rsa_private_key.pem");
+    const ENCRYPTED_ASSERTION_GCM: &str =
+        include_str!("../../tests/resources/encrypted_assertion_gcm.xml");
+    const ENCRYPTED_ASSERTION_CBC: &str =
+        include_str!("../../tests/resources/encrypted_assertion_cbc.xml");
+
+    #[test]
+    fn we_can_decrypt_aes_gcm_assertion_with_key_in_key_info() {
+        let root = XmlObject::parse_xml(InputStream::new(
+            ENCRYPTED_ASSERTION_GCM.as_bytes().to_vec(),
+        ))
+        .unwrap();
+        let response = Response::try_from(root.borrow()).unwrap();
+        assert!(response.assertions().is_empty());
+        assert_eq!(response.encrypted_assertions().len(), 1);
+        assert_eq!(
+            response.issuer().unwrap().value().unwrap(),
+            "https://idp.example.org/metadata"
+        );
+
+        let credential = Credential::from_private_key_pem(PRIVATE_KEY).unwrap();
+        let assertion = response.encrypted_assertions()[0]
+            .decrypt(&credential)
+            .unwrap();
+        assert_eq!(
+            assertion.id(),
+            "_d71a3a8e9fcc45c9e9d248ef7049393fc8f04e5f75"
+        );
+        assert_eq!(assertion.statements().len(), 1);
+    }
+
+    #[test]
+    fn we_can_decrypt_aes_cbc_assertion_with_sibling_key() {
+        let root = XmlObject::parse_xml(InputStream::new(
+            ENCRYPTED_ASSERTION_CBC.as_bytes().to_vec(),
+        ))
+        .unwrap();
+        let encrypted_assertion = EncryptedAssertion::try_from(root.borrow()).unwrap();
+        let credential = Credential::from_private_key_pem(PRIVATE_KEY).unwrap();
+        let assertion = encrypted_assertion.decrypt(&credential).unwrap();
+        assert_eq!(
+            assertion.issuer().value().unwrap(),
+            "https://idp.example.org/metadata"
+        );
+    }
+
+    #[test]
+    fn we_cannot_decrypt_tampered_assertion() {
+        // flip the first octets of the content IV, the key transport stays intact
+        let mut xml = ENCRYPTED_ASSERTION_GCM.to_string();
+        let position = xml.rfind("<xenc:CipherValue>").unwrap() + "<xenc:CipherValue>".len();
+        xml.replace_range(position..position + 4, "AAAA");
+        let root = XmlObject::parse_xml(InputStream::new(xml.into_bytes())).unwrap();
+        let response = Response::try_from(root.borrow()).unwrap();
+        let credential = Credential::from_private_key_pem(PRIVATE_KEY).unwrap();
+        assert!(matches!(
+            response.encrypted_assertions()[0].decrypt(&credential),
+            Err(SAMLError::DecryptionError(message)) if message == "decryption failed"
+        ));
+
+        // a broken CBC padding looks like any other failure
+        let mut xml = ENCRYPTED_ASSERTION_CBC.to_string();
+        let position = xml.rfind("==</xenc:CipherValue>").unwrap();
+        xml.replace_range(position - 4..position, "AAAA");
+        let root = XmlObject::parse_xml(InputStream::new(xml.into_bytes())).unwrap();
+        let encrypted_assertion = EncryptedAssertion::try_from(root.borrow()).unwrap();
+        assert!(matches!(
+            encrypted_assertion.decrypt(&credential),
+            Err(SAMLError::DecryptionError(message)) if message == "decryption failed"
+        ));
+
+        let verifier_only = Credential::from_certificate_pem(include_str!(
+            "../../tests/resources/rsa_certificate.pem"
+        ))
+        .unwrap();
+        assert!(matches!(
+            response.encrypted_assertions()[0].decrypt(&verifier_only),
+            Err(SAMLError::CredentialError(_))
+        ));
+    }
+
+    /// a `Response` whose `EncryptedAssertion` encrypts `plain_text` for the owner of
+    /// `credential`, `encryption_properties` are added to its `EncryptedData`
+    fn encrypted_response(
+        plain_text: &str,
+        encryption_properties: &str,
+        credential: &Credential,
+    ) -> String {
+        let data_algorithm = BlockEncryptionAlgorithm::Aes256Gcm;
+        let key = data_algorithm.generate_key();
+        let cipher_value = data_algorithm.encrypt(&key, plain_text.as_bytes()).unwrap();
+        let key_algorithm = KeyTransportAlgorithm::RsaOaepMgf1p;
+        let encrypted_key = key_algorithm
+            .encrypt(
+                credential.public_key(),
+                &key_algorithm.encrypted_method(),
+                &key,
+            )
+            .unwrap();
+        format!(
+            r#"<saml2p:Response xmlns:saml2p="urn:oasis:names:tc:SAML:2.0:protocol" xmlns:saml2="urn:oasis:names:tc:SAML:2.0:assertion">
+  <saml2:EncryptedAssertion>
+    <xenc:EncryptedData xmlns:xenc="http://www.w3.org/2001/04/xmlenc#" Type="http://www.w3.org/2001/04/xmlenc#Element">
+      <xenc:EncryptionMethod Algorithm="{}"/>
+      <xenc:CipherData><xenc:CipherValue>{}</xenc:CipherValue></xenc:CipherData>
+      {}
+    </xenc:EncryptedData>
+    <xenc:EncryptedKey xmlns:xenc="http://www.w3.org/2001/04/xmlenc#">
+      <xenc:EncryptionMethod Algorithm="{}"/>
+      <xenc:CipherData><xenc:CipherValue>{}</xenc:CipherValue></xenc:CipherData>
+    </xenc:EncryptedKey>
+  </saml2:EncryptedAssertion>
+</saml2p:Response>"#,
+            data_algorithm.uri(),
+            BASE64_STANDARD.encode(cipher_value),
+            encryption_properties,
+            key_algorithm.uri(),
+            BASE64_STANDARD.encode(encrypted_key),
+        )
+    }
+
+    fn encrypted_assertion(xml: String) -> EncryptedAssertion {
+        let root = XmlObject::parse_xml(InputStream::new(xml.into_bytes())).unwrap();
+        let element = root.borrow().children()[0].clone();
+        let encrypted_assertion = EncryptedAssertion::try_from(element.borrow()).unwrap();
+        encrypted_assertion
+    }
+
+    #[test]
+    fn we_can_decrypt_assertion_using_prefix_of_ancestor() {
+        // the assertion uses saml2: without declaring it, as it is declared on the Response
+        let plain_text = concat!(
+            r#"<saml2:Assertion ID="_inherited" Version="2.0" IssueInstant="2024-01-30T09:12:44Z">"#,
+            "<saml2:Issuer>https://idp.example.org/metadata</saml2:Issuer>",
+            "</saml2:Assertion>"
+        );
+        let credential = Credential::from_private_key_pem(PRIVATE_KEY).unwrap();
+        let encrypted_assertion =
+            encrypted_assertion(encrypted_response(plain_text, "", &credential));
+        let assertion = encrypted_assertion.decrypt(&credential).unwrap();
+        assert_eq!(assertion.id(), "_inherited");
+        assert_eq!(
+            assertion.issuer().value().unwrap(),
+            "https://idp.example.org/metadata"
+        );
+    }
+
+    #[test]
+    fn we_can_decrypt_assertion_with_encryption_properties() {
+        let plain_text = concat!(
+            r#"<saml2:Assertion ID="_properties" Version="2.0" IssueInstant="2024-01-30T09:12:44Z">"#,
+            "<saml2:Issuer>https://idp.example.org/metadata</saml2:Issuer>",
+            r#"<saml2:AuthnStatement AuthnInstant="2024-01-30T09:12:44Z"><saml2:AuthnContext>"#,
+            r#"<saml2:AuthnContextDecl xml:lang="en">password</saml2:AuthnContextDecl>"#,
+            "</saml2:AuthnContext></saml2:AuthnStatement>",
+            "</saml2:Assertion>"
+        );
+        let encryption_properties = concat!(
+            "<xenc:EncryptionProperties>",
+            r##"<xenc:EncryptionProperty Target="#_properties" xml:lang="en"><ex:Origin xmlns:ex="urn:example:origin">idp</ex:Origin></xenc:EncryptionProperty>"##,
+            "</xenc:EncryptionProperties>"
+        );
+        let credential = Credential::from_private_key_pem(PRIVATE_KEY).unwrap();
+        let encrypted_assertion = encrypted_assertion(encrypted_response(
+            plain_text,
+            encryption_properties,
+            &credential,
+        ));
+        let encryption_property = &encrypted_assertion
+            .encrypted_data()
+            .encryption_properties()
+            .unwrap()
+            .encryption_properties()[0];
+        assert_eq!(encryption_property.target(), "#_properties");
+        assert_eq!(
+            encryption_property.unknown_attributes().get("xml:lang"),
+            Some("en")
+        );
+        assert_eq!(encryption_property.unknown_children().len(), 1);
+
+        let assertion = encrypted_assertion.decrypt(&credential).unwrap();
+        assert_eq!(assertion.id(), "_properties");
+        let authn_statement = assertion.statements()[0]
+            .as_any()
+            .downcast_ref::<AuthnStatement>()
+            .unwrap();
+        let authn_context_decl = authn_statement
+            .authn_context()
+            .authn_context_decl()
+            .unwrap();
+        assert_eq!(authn_context_decl.text_content().unwrap(), "password");
+        assert_eq!(
+            authn_context_decl.unknown_attributes().get("xml:lang"),
+            Some("en")
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use std::{
     cell::{Ref, RefCell},
+    collections::BTreeMap,
     rc::Rc,
 };
 
@@ -20,6 +21,7 @@ pub struct EncryptedData {
     key_info: Option<KeyInfo>,
     cipher_data: CipherData,
     encryption_properties: Option<EncryptionProperties>,
+    in_scope_namespaces: BTreeMap<String, String>,
 }
 
 impl SAML2Obj for EncryptedData {}
@@ -35,6 +37,8 @@ impl EncryptedData {
     const CHILD_CIPHER_DATA: &'static str = "CipherData";
     const CHILD_ENCRYPTION_PROPERTIES: &'static str = "EncryptionProperties";
 
+    pub const TYPE_ELEMENT: &'static str = "http://www.w3.org/2001/04/xmlenc#Element";
+
     const ELEMENT_NAME: &'static str = "EncryptedData";
     const NS_PREFIX: &'static str = "xenc";
     const NS_URI: &'static str = "http://www.w3.org/2001/04/xmlenc#";
@@ -121,6 +125,18 @@ impl EncryptedData {
     ) {
         self.encryption_properties = encryption_properties;
     }
+
+    /// the namespace bindings in scope of the parsed element, the content is decrypted in
+    /// their context
+    #[inline]
+    pub fn in_scope_namespaces(&self) -> &BTreeMap<String, String> {
+        &self.in_scope_namespaces
+    }
+
+    #[inline]
+    pub fn set_in_scope_namespaces(&mut self, in_scope_namespaces: BTreeMap<String, String>) {
+        self.in_scope_namespaces = in_scope_namespaces;
+    }
 }
 
 impl TryFrom<Ref<'_, XmlObject>> for EncryptedData {
@@ -128,6 +144,7 @@ impl TryFrom<Ref<'_, XmlObject>> for EncryptedData {
 
     fn try_from(value: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
         let mut encrypted_data = EncryptedData::default();
+        encrypted_data.set_in_scope_namespaces(value.namespace().clone());
 
         for attrib in value.attributes() {
             match attrib.0.as_str() {
@@ -174,9 +191,9 @@ impl TryFrom<EncryptedData> for XmlObject {
 
     fn try_from(encrypted_data: EncryptedData) -> Result<Self, Self::Error> {
         let mut xml_object = XmlObject::new(
-            Some(EncryptedData::NS_PREFIX.to_string()),
-            EncryptedData::ELEMENT_NAME.to_string(),
             Some(EncryptedData::NS_URI.to_string()),
+            EncryptedData::ELEMENT_NAME.to_string(),
+            Some(EncryptedData::NS_PREFIX.to_string()),
         );
         xml_object.add_namespace(
             EncryptedData::NS_PREFIX.to_string(),
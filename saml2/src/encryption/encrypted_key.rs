@@ -217,9 +217,9 @@ impl TryFrom<EncryptedKey> for XmlObject {
 
     fn try_from(enc_key: EncryptedKey) -> Result<Self, Self::Error> {
         let mut xml_obj = XmlObject::new(
-            Some(EncryptedKey::NS_PREFIX.to_string()),
-            EncryptedKey::ELEMENT_NAME.to_string(),
             Some(EncryptedKey::NS_URI.to_string()),
+            EncryptedKey::ELEMENT_NAME.to_string(),
+            Some(EncryptedKey::NS_PREFIX.to_string()),
         );
         xml_obj.add_namespace(
             EncryptedKey::NS_PREFIX.to_string(),
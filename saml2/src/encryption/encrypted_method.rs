@@ -3,16 +3,20 @@ use std::{
     rc::Rc,
 };
 
-use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+use crate::{
+    common::SAML2Obj, error::SAMLError, signature::digest_method::DigestMethod, xml::XmlObject,
+};
 
-use super::{key_size::KeySize, oaep_params::OAEPParams};
+use super::{key_size::KeySize, mgf::MGF, oaep_params::OAEPParams};
 
 #[derive(Default, Debug)]
 pub struct EncryptedMethod {
     algorithm: String,
-    key_size: KeySize,
-    oeap_params: OAEPParams,
-    unknown_children: Vec<Box<dyn SAML2Obj>>,
+    key_size: Option<KeySize>,
+    oeap_params: Option<OAEPParams>,
+    digest_method: Option<DigestMethod>,
+    mgf: Option<MGF>,
+    unknown_children: Vec<Rc<RefCell<XmlObject>>>,
 }
 
 impl SAML2Obj for EncryptedMethod {}
@@ -22,11 +26,20 @@ impl EncryptedMethod {
 
     const CHILD_KEY_SIZE: &'static str = "KeySize";
     const CHILD_OEAP_PARAMS: &'static str = "OAEPParams";
+    const CHILD_DIGEST_METHOD: &'static str = "DigestMethod";
+    const CHILD_MGF: &'static str = "MGF";
 
-    const ELEMENT_NAME: &'static str = "EncryptedMethod";
+    const ELEMENT_NAME: &'static str = "EncryptionMethod";
     const NS_PREFIX: &'static str = "xenc";
     const NS_URI: &'static str = "http://www.w3.org/2001/04/xmlenc#";
 
+    pub fn new(algorithm: String) -> Self {
+        EncryptedMethod {
+            algorithm,
+            ..Default::default()
+        }
+    }
+
     #[inline]
     pub fn algorithm(&self) -> &str {
         &self.algorithm
@@ -38,32 +51,54 @@ impl EncryptedMethod {
     }
 
     #[inline]
-    pub fn key_size(&self) -> &KeySize {
-        &self.key_size
+    pub fn key_size(&self) -> Option<&KeySize> {
+        self.key_size.as_ref()
     }
 
     #[inline]
-    pub fn set_key_size(&mut self, key_size: KeySize) {
+    pub fn set_key_size(&mut self, key_size: Option<KeySize>) {
         self.key_size = key_size;
     }
 
     #[inline]
-    pub fn oeap_params(&self) -> &OAEPParams {
-        &self.oeap_params
+    pub fn oeap_params(&self) -> Option<&OAEPParams> {
+        self.oeap_params.as_ref()
     }
 
     #[inline]
-    pub fn set_oeap_params(&mut self, oeap_params: OAEPParams) {
+    pub fn set_oeap_params(&mut self, oeap_params: Option<OAEPParams>) {
         self.oeap_params = oeap_params;
     }
 
+    /// digest of the RSA-OAEP key transport algorithms
+    #[inline]
+    pub fn digest_method(&self) -> Option<&DigestMethod> {
+        self.digest_method.as_ref()
+    }
+
+    #[inline]
+    pub fn set_digest_method(&mut self, digest_method: Option<DigestMethod>) {
+        self.digest_method = digest_method;
+    }
+
+    /// mask generation function of the XML Encryption 1.1 RSA-OAEP key transport
+    #[inline]
+    pub fn mgf(&self) -> Option<&MGF> {
+        self.mgf.as_ref()
+    }
+
+    #[inline]
+    pub fn set_mgf(&mut self, mgf: Option<MGF>) {
+        self.mgf = mgf;
+    }
+
     #[inline]
-    pub fn unknown_children(&self) -> &Vec<Box<dyn SAML2Obj>> {
+    pub fn unknown_children(&self) -> &Vec<Rc<RefCell<XmlObject>>> {
         &self.unknown_children
     }
 
     #[inline]
-    pub fn add_unknown_child(&mut self, unknown_child: Box<dyn SAML2Obj>) {
+    pub fn add_unknown_child(&mut self, unknown_child: Rc<RefCell<XmlObject>>) {
         self.unknown_children.push(unknown_child);
     }
 }
@@ -74,25 +109,26 @@ impl TryFrom<Ref<'_, XmlObject>> for EncryptedMethod {
     fn try_from(value: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
         let mut encrypted_method = EncryptedMethod::default();
         for attr in value.attributes() {
-            match attr.0.as_str() {
-                EncryptedMethod::ATTRIBUTE_ALGORITHM => {
-                    encrypted_method.set_algorithm(attr.1.to_string());
-                }
-                _ => {}
+            if attr.0 == EncryptedMethod::ATTRIBUTE_ALGORITHM {
+                encrypted_method.set_algorithm(attr.1.to_string());
             }
         }
-        for child in value.children() {
-            let child = child.borrow();
+        for child_object in value.children() {
+            let child = child_object.borrow();
             match child.q_name().local_name() {
                 EncryptedMethod::CHILD_KEY_SIZE => {
-                    todo!("KeySize not implemented yet")
+                    encrypted_method.set_key_size(Some(KeySize::try_from(child)?));
                 }
                 EncryptedMethod::CHILD_OEAP_PARAMS => {
-                    todo!("OAEPParams not implemented yet")
+                    encrypted_method.set_oeap_params(Some(OAEPParams::try_from(child)?));
                 }
-                _ => {
-                    todo!("Unknown child not implemented yet")
+                EncryptedMethod::CHILD_DIGEST_METHOD => {
+                    encrypted_method.set_digest_method(Some(DigestMethod::try_from(child)?));
                 }
+                EncryptedMethod::CHILD_MGF => {
+                    encrypted_method.set_mgf(Some(MGF::try_from(child)?));
+                }
+                _ => encrypted_method.add_unknown_child(Rc::clone(child_object)),
             }
         }
         Ok(encrypted_method)
@@ -116,14 +152,20 @@ impl TryFrom<EncryptedMethod> for XmlObject {
             EncryptedMethod::ATTRIBUTE_ALGORITHM.to_string(),
             encrypted_method.algorithm().to_string(),
         );
-        xml_obj.add_child(Rc::new(RefCell::new(XmlObject::try_from(
-            encrypted_method.key_size,
-        )?)));
-        xml_obj.add_child(Rc::new(RefCell::new(XmlObject::try_from(
-            encrypted_method.oeap_params,
-        )?)));
+        if let Some(key_size) = encrypted_method.key_size {
+            xml_obj.add_child(Rc::new(RefCell::new(XmlObject::try_from(key_size)?)));
+        }
+        if let Some(oeap_params) = encrypted_method.oeap_params {
+            xml_obj.add_child(Rc::new(RefCell::new(XmlObject::try_from(oeap_params)?)));
+        }
+        if let Some(digest_method) = encrypted_method.digest_method {
+            xml_obj.add_child(Rc::new(RefCell::new(XmlObject::try_from(digest_method)?)));
+        }
+        if let Some(mgf) = encrypted_method.mgf {
+            xml_obj.add_child(Rc::new(RefCell::new(XmlObject::try_from(mgf)?)));
+        }
         for unknown_child in encrypted_method.unknown_children {
-            todo!("Implement conversion from EncryptedMethod to XmlObject")
+            xml_obj.add_child(unknown_child);
         }
         Ok(xml_obj)
     }
@@ -0,0 +1,196 @@
+use std::{cell::RefCell, rc::Rc};
+
+use base64::{prelude::BASE64_STANDARD, Engine};
+
+use crate::{
+    error::SAMLError,
+    signature::{
+        credential::Credential, key_info::KeyInfo, retrieval_method::RetrievalMethod,
+        x509_certificate::X509Certificate, x509_data::X509Data,
+    },
+    util::generate_id,
+    xml::XmlObject,
+};
+
+use super::{
+    algorithm::{BlockEncryptionAlgorithm, KeyTransportAlgorithm},
+    cipher_data::CipherData,
+    cipher_value::CipherValue,
+    encrypted_data::EncryptedData,
+    encrypted_key::EncryptedKey,
+    encrypted_method::EncryptedMethod,
+};
+
+const XML_DECLARATION_START: &str = "<?xml";
+const XML_DECLARATION_END: &str = "?>";
+
+/// where the `EncryptedKey` is put relative to the `EncryptedData` it belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeyPlacement {
+    /// inside the `KeyInfo` of the `EncryptedData`
+    #[default]
+    Inline,
+    /// next to the `EncryptedData`, whose `KeyInfo` points to it with a `RetrievalMethod`
+    Peer,
+}
+
+/// Encrypt `element` for the owner of `credential`.
+///
+/// The element is encrypted with a fresh AES-256-GCM content key, which is wrapped with
+/// RSA-OAEP for the public key of the credential. The certificate of the credential, if
+/// any, is published in the `KeyInfo` of the `EncryptedKey`. The returned keys are the
+/// `EncryptedKey` siblings of the `EncryptedData`, they are empty for
+/// [`KeyPlacement::Inline`].
+pub fn encrypt_element(
+    element: XmlObject,
+    credential: &Credential,
+    key_placement: KeyPlacement,
+) -> Result<(EncryptedData, Vec<EncryptedKey>), SAMLError> {
+    let data_algorithm = BlockEncryptionAlgorithm::Aes256Gcm;
+    let key_transport_algorithm = KeyTransportAlgorithm::RsaOaepMgf1p;
+
+    let xml = XmlObject::write_xml(Rc::new(RefCell::new(element)))
+        .map_err(|_| SAMLError::EncryptionError("element cannot be serialized".to_string()))?;
+    let key = data_algorithm.generate_key();
+    let cipher_text = data_algorithm.encrypt(&key, strip_xml_declaration(&xml).as_bytes())?;
+
+    let encrypted_method = key_transport_algorithm.encrypted_method();
+    let encrypted_key_value =
+        key_transport_algorithm.encrypt(credential.public_key(), &encrypted_method, &key)?;
+    let mut encrypted_key = EncryptedKey::default();
+    encrypted_key.set_id(Some(generate_id()));
+    encrypted_key.set_encrypted_method(Some(encrypted_method));
+    if let Some(certificate) = credential.certificate() {
+        let mut x509_data = X509Data::default();
+        x509_data.add_x509_certificate(X509Certificate::new(BASE64_STANDARD.encode(certificate)));
+        let mut key_info = KeyInfo::default();
+        key_info.add_x509_data(x509_data);
+        encrypted_key.set_key_info(Some(key_info));
+    }
+    encrypted_key.set_cipher_data(cipher_data(&encrypted_key_value));
+
+    let mut encrypted_data = EncryptedData::default();
+    encrypted_data.set_id(Some(generate_id()));
+    encrypted_data.set_encrypted_type(Some(EncryptedData::TYPE_ELEMENT.to_string()));
+    encrypted_data
+        .set_encrypted_method(Some(EncryptedMethod::new(data_algorithm.uri().to_string())));
+    encrypted_data.set_cipher_data(cipher_data(&cipher_text));
+    let mut key_info = KeyInfo::default();
+    let encrypted_keys = match key_placement {
+        KeyPlacement::Inline => {
+            key_info.add_encrypted_key(encrypted_key);
+            Vec::new()
+        }
+        KeyPlacement::Peer => {
+            let uri = format!("#{}", encrypted_key.id().map_or("", |id| id.as_str()));
+            key_info.add_retrieval_method(RetrievalMethod::new(
+                uri,
+                Some(RetrievalMethod::TYPE_ENCRYPTED_KEY.to_string()),
+            ));
+            vec![encrypted_key]
+        }
+    };
+    encrypted_data.set_key_info(Some(key_info));
+    Ok((encrypted_data, encrypted_keys))
+}
+
+fn cipher_data(value: &[u8]) -> CipherData {
+    let mut cipher_data = CipherData::default();
+    cipher_data.set_cipher_value(Some(CipherValue::new(BASE64_STANDARD.encode(value))));
+    cipher_data
+}
+
+/// the encrypted octets of an `Element` are the element alone, without a declaration
+pub(crate) fn strip_xml_declaration(xml: &str) -> &str {
+    match xml.strip_prefix(XML_DECLARATION_START) {
+        Some(declaration) => declaration.find(XML_DECLARATION_END).map_or(xml, |end| {
+            declaration[end + XML_DECLARATION_END.len()..].trim_start()
+        }),
+        None => xml,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        core::{
+            assertion::Assertion, encrypted_assertion::EncryptedAssertion,
+            encrypted_element_type::EncryptedElementType, issuer::Issuer, response::Response,
+            saml_version::SAMLVersion, status_response_type::StatusResponseType,
+        },
+        signature::credential::Credential,
+        util::InputStream,
+        xml::XmlObject,
+    };
+
+    use super::KeyPlacement;
+
+    const PRIVATE_KEY: &str = include_str!("../../tests/resources/rsa_private_key.pem");
+    const CERTIFICATE: &str = include_str!("../../tests/resources/rsa_certificate.pem");
+
+    fn assertion() -> Assertion {
+        let mut issuer = Issuer::new();
+        issuer.set_value(Some("https://idp.example.org".to_string()));
+        let mut assertion = Assertion::default();
+        assertion.set_id("_assertion".to_string());
+        assertion.set_version(SAMLVersion::new(2, 0));
+        assertion.set_issuer(issuer);
+        assertion
+    }
+
+    fn round_trip(encrypted_assertion: EncryptedAssertion) -> Response {
+        let mut response = Response::default();
+        response.set_id("_response".to_string());
+        response.set_version(SAMLVersion::new(2, 0));
+        response.add_encrypted_assertion(encrypted_assertion);
+        let element = XmlObject::try_from(response).unwrap();
+        let xml = XmlObject::write_xml(Rc::new(RefCell::new(element))).unwrap();
+        let root = XmlObject::parse_xml(InputStream::new(xml.into_bytes())).unwrap();
+        Response::try_from(root.borrow()).unwrap()
+    }
+
+    #[test]
+    fn we_can_encrypt_assertion_with_key_in_key_info() {
+        let recipient = Credential::from_certificate_pem(CERTIFICATE).unwrap();
+        let encrypted_assertion =
+            EncryptedAssertion::encrypt(assertion(), &recipient, KeyPlacement::Inline).unwrap();
+        let response = round_trip(encrypted_assertion);
+
+        let encrypted_assertion = &response.encrypted_assertions()[0];
+        assert!(encrypted_assertion.encrypted_keys().is_empty());
+        let key_info = encrypted_assertion.encrypted_data().key_info().unwrap();
+        assert_eq!(key_info.encrypted_keys().len(), 1);
+        assert!(key_info.encrypted_keys()[0].key_info().is_some());
+
+        let credential = Credential::from_private_key_pem(PRIVATE_KEY).unwrap();
+        let assertion = encrypted_assertion.decrypt(&credential).unwrap();
+        assert_eq!(assertion.id(), "_assertion");
+        assert_eq!(
+            assertion.issuer().value().unwrap(),
+            "https://idp.example.org"
+        );
+    }
+
+    #[test]
+    fn we_can_encrypt_assertion_with_retrieval_method() {
+        let recipient = Credential::from_certificate_pem(CERTIFICATE).unwrap();
+        let encrypted_assertion =
+            EncryptedAssertion::encrypt(assertion(), &recipient, KeyPlacement::Peer).unwrap();
+        let response = round_trip(encrypted_assertion);
+
+        let encrypted_assertion = &response.encrypted_assertions()[0];
+        assert_eq!(encrypted_assertion.encrypted_keys().len(), 1);
+        let key_info = encrypted_assertion.encrypted_data().key_info().unwrap();
+        assert!(key_info.encrypted_keys().is_empty());
+        assert_eq!(
+            key_info.retrieval_methods()[0].uri(),
+            format!("#{}", encrypted_assertion.encrypted_keys()[0].id().unwrap())
+        );
+
+        let credential = Credential::from_private_key_pem(PRIVATE_KEY).unwrap();
+        let assertion = encrypted_assertion.decrypt(&credential).unwrap();
+        assert_eq!(assertion.id(), "_assertion");
+    }
+}
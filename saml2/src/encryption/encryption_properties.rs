@@ -52,11 +52,8 @@ impl TryFrom<Ref<'_, XmlObject>> for EncryptionProperties {
         let mut enc_props = EncryptionProperties::default();
         for child in encryption_properties.children() {
             let child = child.borrow();
-            match child.q_name().local_name() {
-                EncryptionProperties::CHILD_ENCRYPTION_PROPERTY => {
-                    enc_props.add_encryption_property(EncryptionProperty::try_from(child)?);
-                }
-                _ => {}
+            if child.q_name().local_name() == EncryptionProperties::CHILD_ENCRYPTION_PROPERTY {
+                enc_props.add_encryption_property(EncryptionProperty::try_from(child)?);
             }
         }
         Ok(enc_props)
@@ -68,9 +65,9 @@ impl TryFrom<EncryptionProperties> for XmlObject {
 
     fn try_from(enc_props: EncryptionProperties) -> Result<Self, Self::Error> {
         let mut xml_obj = XmlObject::new(
-            Some(EncryptionProperties::NS_PREFIX.to_string()),
-            EncryptionProperties::ELEMENT_NAME.to_string(),
             Some(EncryptionProperties::NS_URI.to_string()),
+            EncryptionProperties::ELEMENT_NAME.to_string(),
+            Some(EncryptionProperties::NS_PREFIX.to_string()),
         );
         xml_obj.add_namespace(
             EncryptionProperties::NS_PREFIX.to_string(),
@@ -1,4 +1,7 @@
-use std::cell::Ref;
+use std::{
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
 
 use crate::{common::SAML2Obj, error::SAMLError, util::AttributeMap, xml::XmlObject};
 
@@ -6,7 +9,7 @@ use crate::{common::SAML2Obj, error::SAMLError, util::AttributeMap, xml::XmlObje
 pub struct EncryptionProperty {
     target: String,
     id: String,
-    unknown_children: Vec<Box<dyn SAML2Obj>>,
+    unknown_children: Vec<Rc<RefCell<XmlObject>>>,
     unknown_attributes: AttributeMap,
 }
 
@@ -41,10 +44,15 @@ impl EncryptionProperty {
     }
 
     #[inline]
-    pub fn unknown_children(&self) -> &Vec<Box<dyn SAML2Obj>> {
+    pub fn unknown_children(&self) -> &Vec<Rc<RefCell<XmlObject>>> {
         &self.unknown_children
     }
 
+    #[inline]
+    pub fn add_unknown_child(&mut self, unknown_child: Rc<RefCell<XmlObject>>) {
+        self.unknown_children.push(unknown_child);
+    }
+
     #[inline]
     pub fn unknown_attributes(&self) -> &AttributeMap {
         &self.unknown_attributes
@@ -65,12 +73,16 @@ impl TryFrom<Ref<'_, XmlObject>> for EncryptionProperty {
                     enc_prop.set_id(attrib.1.to_string());
                 }
                 _ => {
-                    todo!("Handle unknown attribute: {:?}", attrib);
+                    enc_prop.unknown_attributes.add_attribute_of(
+                        &encryption_property,
+                        &attrib.0,
+                        &attrib.1,
+                    );
                 }
             }
         }
         for child in encryption_property.children() {
-            todo!("Handle unknown child!");
+            enc_prop.add_unknown_child(Rc::clone(child));
         }
         Ok(enc_prop)
     }
@@ -81,9 +93,9 @@ impl TryFrom<EncryptionProperty> for XmlObject {
 
     fn try_from(encryption_prop: EncryptionProperty) -> Result<Self, Self::Error> {
         let mut xml_obj = XmlObject::new(
-            Some(EncryptionProperty::NS_PREFIX.to_string()),
-            EncryptionProperty::ELEMENT_NAME.to_string(),
             Some(EncryptionProperty::NS_URI.to_string()),
+            EncryptionProperty::ELEMENT_NAME.to_string(),
+            Some(EncryptionProperty::NS_PREFIX.to_string()),
         );
         xml_obj.add_namespace(
             EncryptionProperty::NS_PREFIX.to_string(),
@@ -97,9 +109,9 @@ impl TryFrom<EncryptionProperty> for XmlObject {
             EncryptionProperty::ATTRIBUTE_ID.to_string(),
             encryption_prop.id.to_string(),
         );
-        // TODO: Add unknown attributes
-        for child in encryption_prop.unknown_children() {
-            todo!("XmlObject::try_from<unknown child>");
+        encryption_prop.unknown_attributes.write_to(&mut xml_obj);
+        for child in encryption_prop.unknown_children {
+            xml_obj.add_child(child);
         }
         Ok(xml_obj)
     }
@@ -14,6 +14,10 @@ impl KeySize {
     const NS_PREFIX: &'static str = "xenc";
     const NS_URI: &'static str = "http://www.w3.org/2001/04/xmlenc#";
 
+    pub fn new(value: u32) -> Self {
+        KeySize { value }
+    }
+
     #[inline]
     pub fn value(&self) -> u32 {
         self.value
@@ -45,9 +49,9 @@ impl TryFrom<KeySize> for XmlObject {
 
     fn try_from(key_size: KeySize) -> Result<Self, Self::Error> {
         let mut xml_object = XmlObject::new(
-            Some(KeySize::ELEMENT_NAME.to_string()),
-            KeySize::NS_PREFIX.to_string(),
             Some(KeySize::NS_URI.to_string()),
+            KeySize::ELEMENT_NAME.to_string(),
+            Some(KeySize::NS_PREFIX.to_string()),
         );
         xml_object.set_text(Some(key_size.value.to_string()));
         Ok(xml_object)
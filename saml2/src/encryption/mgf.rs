@@ -0,0 +1,65 @@
+use std::cell::Ref;
+
+use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+
+/// `xenc11:MGF` mask generation function of the RSA-OAEP key transport
+#[derive(Default, Debug)]
+pub struct MGF {
+    algorithm: String,
+}
+
+impl SAML2Obj for MGF {}
+
+impl MGF {
+    const ATTRIB_ALGORITHM: &'static str = "Algorithm";
+
+    pub const MGF1_SHA1: &'static str = "http://www.w3.org/2009/xmlenc11#mgf1sha1";
+    pub const MGF1_SHA256: &'static str = "http://www.w3.org/2009/xmlenc11#mgf1sha256";
+    pub const MGF1_SHA512: &'static str = "http://www.w3.org/2009/xmlenc11#mgf1sha512";
+
+    const ELEMENT_NAME: &'static str = "MGF";
+    const NS_PREFIX: &'static str = "xenc11";
+    const NS_URI: &'static str = "http://www.w3.org/2009/xmlenc11#";
+
+    pub fn new(algorithm: String) -> Self {
+        MGF { algorithm }
+    }
+
+    #[inline]
+    pub fn algorithm(&self) -> &str {
+        &self.algorithm
+    }
+
+    #[inline]
+    pub fn set_algorithm(&mut self, algorithm: String) {
+        self.algorithm = algorithm;
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for MGF {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        match element.attribute(MGF::ATTRIB_ALGORITHM) {
+            Some(algorithm) => Ok(MGF::new(algorithm.to_string())),
+            None => Err(SAMLError::UnmarshallingError(
+                "MGF must have an Algorithm".to_string(),
+            )),
+        }
+    }
+}
+
+impl TryFrom<MGF> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(mgf: MGF) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(MGF::NS_URI.to_string()),
+            MGF::ELEMENT_NAME.to_string(),
+            Some(MGF::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(MGF::NS_PREFIX.to_string(), MGF::NS_URI.to_string());
+        xml_object.add_attribute(MGF::ATTRIB_ALGORITHM.to_string(), mgf.algorithm);
+        Ok(xml_object)
+    }
+}
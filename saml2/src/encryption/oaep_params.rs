@@ -40,9 +40,9 @@ impl TryFrom<OAEPParams> for XmlObject {
 
     fn try_from(oaep_params: OAEPParams) -> Result<XmlObject, Self::Error> {
         let mut xml_obj = XmlObject::new(
-            Some(OAEPParams::NS_PREFIX.to_string()),
-            OAEPParams::ELEMENT_NAME.to_string(),
             Some(OAEPParams::NS_URI.to_string()),
+            OAEPParams::ELEMENT_NAME.to_string(),
+            Some(OAEPParams::NS_PREFIX.to_string()),
         );
         if let Some(value) = oaep_params.value() {
             xml_obj.set_text(Some(value.to_string()));
@@ -1,10 +1,14 @@
-use std::cell::Ref;
+use std::{
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
 
 use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
 
+/// `xenc:ReferenceList`, its `DataReference` and `KeyReference` elements are kept as they are
 #[derive(Debug, Default)]
 pub struct ReferenceList {
-    indexed_children: Vec<Box<dyn SAML2Obj>>,
+    indexed_children: Vec<Rc<RefCell<XmlObject>>>,
 }
 
 impl SAML2Obj for ReferenceList {}
@@ -15,9 +19,14 @@ impl ReferenceList {
     const NS_URI: &'static str = "http://www.w3.org/2001/04/xmlenc#";
 
     #[inline]
-    pub fn indexed_children(&self) -> &Vec<Box<dyn SAML2Obj>> {
+    pub fn indexed_children(&self) -> &Vec<Rc<RefCell<XmlObject>>> {
         &self.indexed_children
     }
+
+    #[inline]
+    pub fn add_indexed_child(&mut self, child: Rc<RefCell<XmlObject>>) {
+        self.indexed_children.push(child);
+    }
 }
 
 impl TryFrom<Ref<'_, XmlObject>> for ReferenceList {
@@ -26,7 +35,7 @@ impl TryFrom<Ref<'_, XmlObject>> for ReferenceList {
     fn try_from(reference_list: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
         let mut ref_list = ReferenceList::default();
         for child in reference_list.children() {
-            todo!("Try from for child is not implemented yet.")
+            ref_list.add_indexed_child(Rc::clone(child));
         }
         Ok(ref_list)
     }
@@ -37,13 +46,61 @@ impl TryFrom<ReferenceList> for XmlObject {
 
     fn try_from(reference_list: ReferenceList) -> Result<XmlObject, Self::Error> {
         let mut xml_obj = XmlObject::new(
-            Some(ReferenceList::NS_PREFIX.to_string()),
-            ReferenceList::ELEMENT_NAME.to_string(),
             Some(ReferenceList::NS_URI.to_string()),
+            ReferenceList::ELEMENT_NAME.to_string(),
+            Some(ReferenceList::NS_PREFIX.to_string()),
+        );
+        xml_obj.add_namespace(
+            ReferenceList::NS_PREFIX.to_string(),
+            ReferenceList::NS_URI.to_string(),
         );
-        for child in reference_list.indexed_children() {
-            todo!("Try from for child is not implemented yet.")
+        for child in reference_list.indexed_children {
+            xml_obj.add_child(child);
         }
         Ok(xml_obj)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{util::InputStream, xml::XmlObject};
+
+    use super::ReferenceList;
+
+    const REFERENCE_LIST: &str = r##"<xenc:ReferenceList xmlns:xenc="http://www.w3.org/2001/04/xmlenc#"><xenc:DataReference URI="#_data"/><xenc:KeyReference URI="#_key"/></xenc:ReferenceList>"##;
+
+    fn parse(xml: String) -> ReferenceList {
+        let root = XmlObject::parse_xml(InputStream::new(xml.into_bytes())).unwrap();
+        let reference_list = ReferenceList::try_from(root.borrow()).unwrap();
+        reference_list
+    }
+
+    #[test]
+    fn we_can_parse_and_serialize_reference_list() {
+        let reference_list = parse(REFERENCE_LIST.to_string());
+        let element = XmlObject::try_from(reference_list).unwrap();
+        let xml = XmlObject::write_xml(Rc::new(RefCell::new(element))).unwrap();
+
+        let reference_list = parse(xml);
+        let references = reference_list
+            .indexed_children()
+            .iter()
+            .map(|child| {
+                let child = child.borrow();
+                (
+                    child.q_name().local_name().to_string(),
+                    child.attribute("URI").unwrap().to_string(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            references,
+            vec![
+                ("DataReference".to_string(), "#_data".to_string()),
+                ("KeyReference".to_string(), "#_key".to_string())
+            ]
+        );
+    }
+}
@@ -1,4 +1,7 @@
-use std::cell::Ref;
+use std::{
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
 
 use crate::{common::SAML2Obj, error::SAMLError, signature::transform::Transform, xml::XmlObject};
 
@@ -20,6 +23,11 @@ impl Transforms {
     pub fn transforms(&self) -> &Vec<Transform> {
         &self.transforms
     }
+
+    #[inline]
+    pub fn add_transform(&mut self, transform: Transform) {
+        self.transforms.push(transform);
+    }
 }
 
 impl TryFrom<Ref<'_, XmlObject>> for Transforms {
@@ -29,11 +37,8 @@ impl TryFrom<Ref<'_, XmlObject>> for Transforms {
         let mut transforms = Transforms::default();
         for child in xml_obj.children() {
             let child = child.borrow();
-            match child.q_name().local_name() {
-                Transforms::ELEMENT_TRANSFORM => {
-                    todo!("Transform::try_from<Ref<XmlObject>>")
-                }
-                _ => {}
+            if child.q_name().local_name() == Transforms::ELEMENT_TRANSFORM {
+                transforms.add_transform(Transform::try_from(child)?);
             }
         }
         Ok(transforms)
@@ -45,16 +50,16 @@ impl TryFrom<Transforms> for XmlObject {
 
     fn try_from(transforms: Transforms) -> Result<Self, Self::Error> {
         let mut xml_obj = XmlObject::new(
-            Some(Transforms::NS_PREFIX.to_string()),
-            Transforms::ELEMENT_NAME.to_string(),
             Some(Transforms::NS_URI.to_string()),
+            Transforms::ELEMENT_NAME.to_string(),
+            Some(Transforms::NS_PREFIX.to_string()),
         );
         xml_obj.add_namespace(
             Transforms::NS_PREFIX.to_string(),
             Transforms::NS_URI.to_string(),
         );
-        for transform in transforms.transforms() {
-            todo!("XmlObject::try_from<Transform>")
+        for transform in transforms.transforms {
+            xml_obj.add_child(Rc::new(RefCell::new(XmlObject::try_from(transform)?)));
         }
         Ok(xml_obj)
     }
@@ -1,5 +1,24 @@
 #[derive(Debug)]
 pub enum SAMLError {
     MessageDecodingError(String),
+    MessageEncodingError(String),
     UnmarshallingError(String),
+    SignatureError(String),
+    CredentialError(String),
+    DecryptionError(String),
+    EncryptionError(String),
+    /// the root element of a message is not a registered message type
+    UnknownMessageError {
+        namespace_uri: Option<String>,
+        local_name: String,
+    },
+    /// the peer answered with a `soap11:Fault`
+    SOAPFaultError {
+        fault_code: String,
+        fault_string: String,
+    },
+    /// a back-channel exchange failed below the SOAP layer
+    TransportError(String),
+    /// the metadata of a peer is missing, invalid or does not allow the operation
+    MetadataError(String),
 }
@@ -1,12 +1,12 @@
-mod binding;
-mod common;
-mod core;
-mod encryption;
-mod error;
-mod metadata;
-mod signature;
-mod util;
-mod xml;
+pub mod binding;
+pub mod common;
+pub mod core;
+pub mod encryption;
+pub mod error;
+pub mod metadata;
+pub mod signature;
+pub mod util;
+pub mod xml;
 
 pub fn add(left: usize, right: usize) -> usize {
     left + right
@@ -0,0 +1,293 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
+
+use crate::{common::SAML2Obj, core::attribute::Attribute, error::SAMLError, xml::XmlObject};
+
+use super::{
+    endpoint::Endpoint,
+    metadata_element,
+    role_descriptor::{RoleDescriptor, RoleDescriptorType},
+};
+
+/// `md:AttributeAuthorityDescriptor`, the role of an entity answering `AttributeQuery`s
+#[derive(Debug, Default)]
+pub struct AttributeAuthorityDescriptor {
+    role_descriptor_type: RoleDescriptorType,
+    attribute_services: Vec<Endpoint>,
+    assertion_id_request_services: Vec<Endpoint>,
+    name_id_formats: Vec<String>,
+    attribute_profiles: Vec<String>,
+    attributes: Vec<Attribute>,
+}
+
+impl SAML2Obj for AttributeAuthorityDescriptor {}
+
+impl AttributeAuthorityDescriptor {
+    const CHILD_ATTRIBUTE_SERVICE: &'static str = "AttributeService";
+    const CHILD_ASSERTION_ID_REQUEST_SERVICE: &'static str = "AssertionIDRequestService";
+    const CHILD_NAME_ID_FORMAT: &'static str = "NameIDFormat";
+    const CHILD_ATTRIBUTE_PROFILE: &'static str = "AttributeProfile";
+    const CHILD_ATTRIBUTE: &'static str = "Attribute";
+
+    pub const ELEMENT_NAME: &'static str = "AttributeAuthorityDescriptor";
+    pub const NS_PREFIX: &'static str = "md";
+    pub const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:metadata";
+
+    pub fn new() -> Self {
+        AttributeAuthorityDescriptor {
+            role_descriptor_type: RoleDescriptorType::new(),
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    pub fn attribute_services(&self) -> &Vec<Endpoint> {
+        &self.attribute_services
+    }
+
+    #[inline]
+    pub fn add_attribute_service(&mut self, attribute_service: Endpoint) {
+        self.attribute_services.push(attribute_service);
+    }
+
+    /// the `AttributeService` speaking `binding`
+    pub fn attribute_service(&self, binding: &str) -> Option<&Endpoint> {
+        self.attribute_services
+            .iter()
+            .find(|endpoint| endpoint.binding() == binding)
+    }
+
+    #[inline]
+    pub fn assertion_id_request_services(&self) -> &Vec<Endpoint> {
+        &self.assertion_id_request_services
+    }
+
+    #[inline]
+    pub fn add_assertion_id_request_service(&mut self, assertion_id_request_service: Endpoint) {
+        self.assertion_id_request_services
+            .push(assertion_id_request_service);
+    }
+
+    #[inline]
+    pub fn name_id_formats(&self) -> &Vec<String> {
+        &self.name_id_formats
+    }
+
+    #[inline]
+    pub fn add_name_id_format(&mut self, name_id_format: String) {
+        self.name_id_formats.push(name_id_format);
+    }
+
+    /// the attribute profiles the authority supports
+    #[inline]
+    pub fn attribute_profiles(&self) -> &Vec<String> {
+        &self.attribute_profiles
+    }
+
+    #[inline]
+    pub fn add_attribute_profile(&mut self, attribute_profile: String) {
+        self.attribute_profiles.push(attribute_profile);
+    }
+
+    /// the attributes the authority can assert
+    #[inline]
+    pub fn attributes(&self) -> &Vec<Attribute> {
+        &self.attributes
+    }
+
+    #[inline]
+    pub fn add_attribute(&mut self, attribute: Attribute) {
+        self.attributes.push(attribute);
+    }
+}
+
+impl RoleDescriptor for AttributeAuthorityDescriptor {
+    fn role_descriptor_type(&self) -> &RoleDescriptorType {
+        &self.role_descriptor_type
+    }
+
+    fn role_descriptor_type_mut(&mut self) -> &mut RoleDescriptorType {
+        &mut self.role_descriptor_type
+    }
+
+    fn into_xml_object(self: Box<Self>) -> Result<XmlObject, SAMLError> {
+        XmlObject::try_from(*self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for AttributeAuthorityDescriptor {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut attribute_authority_descriptor = AttributeAuthorityDescriptor {
+            role_descriptor_type: RoleDescriptorType::from_element(&element, &[])?,
+            ..Default::default()
+        };
+        for child in element.children() {
+            let child = child.borrow();
+            match child.q_name().local_name() {
+                AttributeAuthorityDescriptor::CHILD_ATTRIBUTE_SERVICE => {
+                    attribute_authority_descriptor
+                        .add_attribute_service(Endpoint::try_from(child)?);
+                }
+                AttributeAuthorityDescriptor::CHILD_ASSERTION_ID_REQUEST_SERVICE => {
+                    attribute_authority_descriptor
+                        .add_assertion_id_request_service(Endpoint::try_from(child)?);
+                }
+                AttributeAuthorityDescriptor::CHILD_NAME_ID_FORMAT => {
+                    if let Some(name_id_format) = child.text() {
+                        attribute_authority_descriptor
+                            .add_name_id_format(name_id_format.trim().to_string());
+                    }
+                }
+                AttributeAuthorityDescriptor::CHILD_ATTRIBUTE_PROFILE => {
+                    if let Some(attribute_profile) = child.text() {
+                        attribute_authority_descriptor
+                            .add_attribute_profile(attribute_profile.trim().to_string());
+                    }
+                }
+                AttributeAuthorityDescriptor::CHILD_ATTRIBUTE => {
+                    attribute_authority_descriptor.add_attribute(Attribute::try_from(child)?);
+                }
+                _ => {}
+            }
+        }
+        if attribute_authority_descriptor.attribute_services.is_empty() {
+            return Err(SAMLError::UnmarshallingError(
+                "AttributeAuthorityDescriptor must have an AttributeService".to_string(),
+            ));
+        }
+        Ok(attribute_authority_descriptor)
+    }
+}
+
+impl TryFrom<AttributeAuthorityDescriptor> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(
+        attribute_authority_descriptor: AttributeAuthorityDescriptor,
+    ) -> Result<Self, Self::Error> {
+        let mut xml_object = attribute_authority_descriptor
+            .role_descriptor_type
+            .into_xml_object(AttributeAuthorityDescriptor::ELEMENT_NAME)?;
+        for attribute_service in attribute_authority_descriptor.attribute_services {
+            xml_object
+                .add_child(Rc::new(RefCell::new(attribute_service.into_xml_object(
+                    AttributeAuthorityDescriptor::CHILD_ATTRIBUTE_SERVICE,
+                )?)));
+        }
+        for assertion_id_request_service in
+            attribute_authority_descriptor.assertion_id_request_services
+        {
+            xml_object.add_child(Rc::new(RefCell::new(
+                assertion_id_request_service.into_xml_object(
+                    AttributeAuthorityDescriptor::CHILD_ASSERTION_ID_REQUEST_SERVICE,
+                )?,
+            )));
+        }
+        for name_id_format in attribute_authority_descriptor.name_id_formats {
+            let mut element = metadata_element(AttributeAuthorityDescriptor::CHILD_NAME_ID_FORMAT);
+            element.set_text(Some(name_id_format));
+            xml_object.add_child(Rc::new(RefCell::new(element)));
+        }
+        for attribute_profile in attribute_authority_descriptor.attribute_profiles {
+            let mut element =
+                metadata_element(AttributeAuthorityDescriptor::CHILD_ATTRIBUTE_PROFILE);
+            element.set_text(Some(attribute_profile));
+            xml_object.add_child(Rc::new(RefCell::new(element)));
+        }
+        for attribute in attribute_authority_descriptor.attributes {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(attribute)?)));
+        }
+        Ok(xml_object)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{metadata::entity_descriptor::EntityDescriptor, util::InputStream, xml::XmlObject};
+
+    const AUTHORITY_METADATA: &str = include_str!("../../tests/resources/authority_metadata.xml");
+    const SOAP: &str = "urn:oasis:names:tc:SAML:2.0:bindings:SOAP";
+
+    fn parse(xml: &str) -> EntityDescriptor {
+        let root = XmlObject::parse_xml(InputStream::new(xml.as_bytes().to_vec())).unwrap();
+        let entity_descriptor = EntityDescriptor::try_from(root.borrow()).unwrap();
+        entity_descriptor
+    }
+
+    fn check(entity_descriptor: &EntityDescriptor) {
+        assert_eq!(entity_descriptor.role_descriptors().len(), 4);
+
+        let attribute_authority = entity_descriptor
+            .attribute_authority_descriptors()
+            .next()
+            .unwrap();
+        assert_eq!(
+            attribute_authority
+                .attribute_service(SOAP)
+                .unwrap()
+                .location(),
+            "https://aa.example.org/SAML2/SOAP/AttributeQuery"
+        );
+        assert_eq!(attribute_authority.assertion_id_request_services().len(), 1);
+        assert_eq!(
+            attribute_authority.name_id_formats()[0],
+            "urn:oasis:names:tc:SAML:2.0:nameid-format:persistent"
+        );
+        assert_eq!(
+            attribute_authority.attribute_profiles()[0],
+            "urn:oasis:names:tc:SAML:2.0:profiles:attribute:basic"
+        );
+        let attribute = &attribute_authority.attributes()[0];
+        assert_eq!(attribute.friendly_name().unwrap(), "eduPersonEntitlement");
+        assert_eq!(
+            attribute.attribute_values()[0].text(),
+            Some("urn:mace:example.org:library")
+        );
+
+        let authn_authority = entity_descriptor
+            .authn_authority_descriptors()
+            .next()
+            .unwrap();
+        assert_eq!(
+            authn_authority
+                .authn_query_service(SOAP)
+                .unwrap()
+                .location(),
+            "https://aa.example.org/SAML2/SOAP/AuthnQuery"
+        );
+        assert_eq!(authn_authority.name_id_formats().len(), 1);
+
+        let pdp = entity_descriptor.pdp_descriptors().next().unwrap();
+        assert_eq!(
+            pdp.authz_service(SOAP).unwrap().location(),
+            "https://aa.example.org/SAML2/SOAP/AuthzDecisionQuery"
+        );
+        assert_eq!(
+            pdp.assertion_id_request_services()[0].location(),
+            "https://aa.example.org/SAML2/PDP/AssertionID"
+        );
+    }
+
+    #[test]
+    fn we_can_parse_authority_descriptors() {
+        check(&parse(AUTHORITY_METADATA));
+    }
+
+    #[test]
+    fn we_can_round_trip_authority_descriptors() {
+        let xml_object = XmlObject::try_from(parse(AUTHORITY_METADATA)).unwrap();
+        let xml = XmlObject::write_xml(Rc::new(RefCell::new(xml_object))).unwrap();
+        check(&parse(&xml));
+    }
+}
@@ -0,0 +1,165 @@
+use std::{
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
+
+use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+
+use super::{
+    metadata_element, parse_from_string, requested_attribute::RequestedAttribute,
+    service_description::ServiceDescription, service_name::ServiceName,
+};
+
+/// `md:AttributeConsumingService`, a set of attributes an SP wants, selected by an
+/// AuthnRequest through its `AttributeConsumingServiceIndex`
+#[derive(Debug, Default)]
+pub struct AttributeConsumingService {
+    index: usize,
+    is_default: Option<bool>,
+    service_names: Vec<ServiceName>,
+    service_descriptions: Vec<ServiceDescription>,
+    requested_attributes: Vec<RequestedAttribute>,
+}
+
+impl SAML2Obj for AttributeConsumingService {}
+
+impl AttributeConsumingService {
+    const ATTRIB_INDEX: &'static str = "index";
+    const ATTRIB_IS_DEFAULT: &'static str = "isDefault";
+
+    const CHILD_SERVICE_NAME: &'static str = "ServiceName";
+    const CHILD_SERVICE_DESCRIPTION: &'static str = "ServiceDescription";
+    const CHILD_REQUESTED_ATTRIBUTE: &'static str = "RequestedAttribute";
+
+    pub const ELEMENT_NAME: &'static str = "AttributeConsumingService";
+    pub const NS_PREFIX: &'static str = "md";
+    pub const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:metadata";
+
+    pub fn new(index: usize) -> Self {
+        AttributeConsumingService {
+            index,
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    #[inline]
+    pub fn set_index(&mut self, index: usize) {
+        self.index = index;
+    }
+
+    #[inline]
+    pub fn is_default(&self) -> Option<bool> {
+        self.is_default
+    }
+
+    #[inline]
+    pub fn set_is_default(&mut self, is_default: Option<bool>) {
+        self.is_default = is_default;
+    }
+
+    #[inline]
+    pub fn service_names(&self) -> &Vec<ServiceName> {
+        &self.service_names
+    }
+
+    #[inline]
+    pub fn add_service_name(&mut self, service_name: ServiceName) {
+        self.service_names.push(service_name);
+    }
+
+    #[inline]
+    pub fn service_descriptions(&self) -> &Vec<ServiceDescription> {
+        &self.service_descriptions
+    }
+
+    #[inline]
+    pub fn add_service_description(&mut self, service_description: ServiceDescription) {
+        self.service_descriptions.push(service_description);
+    }
+
+    #[inline]
+    pub fn requested_attributes(&self) -> &Vec<RequestedAttribute> {
+        &self.requested_attributes
+    }
+
+    #[inline]
+    pub fn add_requested_attribute(&mut self, requested_attribute: RequestedAttribute) {
+        self.requested_attributes.push(requested_attribute);
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for AttributeConsumingService {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut attribute_consuming_service = AttributeConsumingService::default();
+        let index = element
+            .attribute(AttributeConsumingService::ATTRIB_INDEX)
+            .ok_or_else(|| {
+                SAMLError::UnmarshallingError(
+                    "AttributeConsumingService must have an index".to_string(),
+                )
+            })?;
+        attribute_consuming_service.set_index(parse_from_string(index)?);
+        if let Some(is_default) = element.attribute(AttributeConsumingService::ATTRIB_IS_DEFAULT) {
+            attribute_consuming_service.set_is_default(Some(parse_from_string(is_default)?));
+        }
+        for child in element.children() {
+            let child = child.borrow();
+            match child.q_name().local_name() {
+                AttributeConsumingService::CHILD_SERVICE_NAME => {
+                    attribute_consuming_service.add_service_name(ServiceName::try_from(child)?);
+                }
+                AttributeConsumingService::CHILD_SERVICE_DESCRIPTION => {
+                    attribute_consuming_service
+                        .add_service_description(ServiceDescription::try_from(child)?);
+                }
+                AttributeConsumingService::CHILD_REQUESTED_ATTRIBUTE => {
+                    attribute_consuming_service
+                        .add_requested_attribute(RequestedAttribute::try_from(child)?);
+                }
+                _ => {}
+            }
+        }
+        Ok(attribute_consuming_service)
+    }
+}
+
+impl TryFrom<AttributeConsumingService> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(
+        attribute_consuming_service: AttributeConsumingService,
+    ) -> Result<Self, Self::Error> {
+        let mut xml_object = metadata_element(AttributeConsumingService::ELEMENT_NAME);
+        xml_object.add_attribute(
+            AttributeConsumingService::ATTRIB_INDEX.to_string(),
+            attribute_consuming_service.index.to_string(),
+        );
+        if let Some(is_default) = attribute_consuming_service.is_default {
+            xml_object.add_attribute(
+                AttributeConsumingService::ATTRIB_IS_DEFAULT.to_string(),
+                is_default.to_string(),
+            );
+        }
+        for service_name in attribute_consuming_service.service_names {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(service_name)?)));
+        }
+        for service_description in attribute_consuming_service.service_descriptions {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(
+                service_description,
+            )?)));
+        }
+        for requested_attribute in attribute_consuming_service.requested_attributes {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(
+                requested_attribute,
+            )?)));
+        }
+        Ok(xml_object)
+    }
+}
@@ -0,0 +1,163 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
+
+use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+
+use super::{
+    endpoint::Endpoint,
+    metadata_element,
+    role_descriptor::{RoleDescriptor, RoleDescriptorType},
+};
+
+/// `md:AuthnAuthorityDescriptor`, the role of an entity answering `AuthnQuery`s about the sessions it knows
+#[derive(Debug, Default)]
+pub struct AuthnAuthorityDescriptor {
+    role_descriptor_type: RoleDescriptorType,
+    authn_query_services: Vec<Endpoint>,
+    assertion_id_request_services: Vec<Endpoint>,
+    name_id_formats: Vec<String>,
+}
+
+impl SAML2Obj for AuthnAuthorityDescriptor {}
+
+impl AuthnAuthorityDescriptor {
+    const CHILD_AUTHN_QUERY_SERVICE: &'static str = "AuthnQueryService";
+    const CHILD_ASSERTION_ID_REQUEST_SERVICE: &'static str = "AssertionIDRequestService";
+    const CHILD_NAME_ID_FORMAT: &'static str = "NameIDFormat";
+
+    pub const ELEMENT_NAME: &'static str = "AuthnAuthorityDescriptor";
+    pub const NS_PREFIX: &'static str = "md";
+    pub const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:metadata";
+
+    pub fn new() -> Self {
+        AuthnAuthorityDescriptor {
+            role_descriptor_type: RoleDescriptorType::new(),
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    pub fn authn_query_services(&self) -> &Vec<Endpoint> {
+        &self.authn_query_services
+    }
+
+    #[inline]
+    pub fn add_authn_query_service(&mut self, authn_query_service: Endpoint) {
+        self.authn_query_services.push(authn_query_service);
+    }
+
+    /// the `AuthnQueryService` speaking `binding`
+    pub fn authn_query_service(&self, binding: &str) -> Option<&Endpoint> {
+        self.authn_query_services
+            .iter()
+            .find(|endpoint| endpoint.binding() == binding)
+    }
+
+    #[inline]
+    pub fn assertion_id_request_services(&self) -> &Vec<Endpoint> {
+        &self.assertion_id_request_services
+    }
+
+    #[inline]
+    pub fn add_assertion_id_request_service(&mut self, assertion_id_request_service: Endpoint) {
+        self.assertion_id_request_services
+            .push(assertion_id_request_service);
+    }
+
+    #[inline]
+    pub fn name_id_formats(&self) -> &Vec<String> {
+        &self.name_id_formats
+    }
+
+    #[inline]
+    pub fn add_name_id_format(&mut self, name_id_format: String) {
+        self.name_id_formats.push(name_id_format);
+    }
+}
+
+impl RoleDescriptor for AuthnAuthorityDescriptor {
+    fn role_descriptor_type(&self) -> &RoleDescriptorType {
+        &self.role_descriptor_type
+    }
+
+    fn role_descriptor_type_mut(&mut self) -> &mut RoleDescriptorType {
+        &mut self.role_descriptor_type
+    }
+
+    fn into_xml_object(self: Box<Self>) -> Result<XmlObject, SAMLError> {
+        XmlObject::try_from(*self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for AuthnAuthorityDescriptor {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut authn_authority_descriptor = AuthnAuthorityDescriptor {
+            role_descriptor_type: RoleDescriptorType::from_element(&element, &[])?,
+            ..Default::default()
+        };
+        for child in element.children() {
+            let child = child.borrow();
+            match child.q_name().local_name() {
+                AuthnAuthorityDescriptor::CHILD_AUTHN_QUERY_SERVICE => {
+                    authn_authority_descriptor.add_authn_query_service(Endpoint::try_from(child)?);
+                }
+                AuthnAuthorityDescriptor::CHILD_ASSERTION_ID_REQUEST_SERVICE => {
+                    authn_authority_descriptor
+                        .add_assertion_id_request_service(Endpoint::try_from(child)?);
+                }
+                AuthnAuthorityDescriptor::CHILD_NAME_ID_FORMAT => {
+                    if let Some(name_id_format) = child.text() {
+                        authn_authority_descriptor
+                            .add_name_id_format(name_id_format.trim().to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+        if authn_authority_descriptor.authn_query_services.is_empty() {
+            return Err(SAMLError::UnmarshallingError(
+                "AuthnAuthorityDescriptor must have an AuthnQueryService".to_string(),
+            ));
+        }
+        Ok(authn_authority_descriptor)
+    }
+}
+
+impl TryFrom<AuthnAuthorityDescriptor> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(authn_authority_descriptor: AuthnAuthorityDescriptor) -> Result<Self, Self::Error> {
+        let mut xml_object = authn_authority_descriptor
+            .role_descriptor_type
+            .into_xml_object(AuthnAuthorityDescriptor::ELEMENT_NAME)?;
+        for authn_query_service in authn_authority_descriptor.authn_query_services {
+            xml_object
+                .add_child(Rc::new(RefCell::new(authn_query_service.into_xml_object(
+                    AuthnAuthorityDescriptor::CHILD_AUTHN_QUERY_SERVICE,
+                )?)));
+        }
+        for assertion_id_request_service in authn_authority_descriptor.assertion_id_request_services
+        {
+            xml_object.add_child(Rc::new(RefCell::new(
+                assertion_id_request_service.into_xml_object(
+                    AuthnAuthorityDescriptor::CHILD_ASSERTION_ID_REQUEST_SERVICE,
+                )?,
+            )));
+        }
+        for name_id_format in authn_authority_descriptor.name_id_formats {
+            let mut element = metadata_element(AuthnAuthorityDescriptor::CHILD_NAME_ID_FORMAT);
+            element.set_text(Some(name_id_format));
+            xml_object.add_child(Rc::new(RefCell::new(element)));
+        }
+        Ok(xml_object)
+    }
+}
@@ -0,0 +1,235 @@
+use std::{
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
+
+use crate::{common::SAML2Obj, error::SAMLError, util::AttributeMap, xml::XmlObject};
+
+use super::{contact_type::ContactType, extensions::Extensions, parse_from_string};
+
+/// `md:ContactPerson`, someone to reach about an entity or a role
+#[derive(Debug, Default)]
+pub struct ContactPerson {
+    contact_type: ContactType,
+    extensions: Option<Extensions>,
+    company: Option<String>,
+    given_name: Option<String>,
+    sur_name: Option<String>,
+    email_addresses: Vec<String>,
+    telephone_numbers: Vec<String>,
+    unknown_attributes: AttributeMap,
+}
+
+impl SAML2Obj for ContactPerson {}
+
+impl ContactPerson {
+    const ATTRIB_CONTACT_TYPE: &'static str = "contactType";
+
+    const CHILD_EXTENSIONS: &'static str = "Extensions";
+    const CHILD_COMPANY: &'static str = "Company";
+    const CHILD_GIVEN_NAME: &'static str = "GivenName";
+    const CHILD_SUR_NAME: &'static str = "SurName";
+    const CHILD_EMAIL_ADDRESS: &'static str = "EmailAddress";
+    const CHILD_TELEPHONE_NUMBER: &'static str = "TelephoneNumber";
+
+    pub const ELEMENT_NAME: &'static str = "ContactPerson";
+    pub const NS_PREFIX: &'static str = "md";
+    pub const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:metadata";
+
+    pub fn new(contact_type: ContactType) -> Self {
+        ContactPerson {
+            contact_type,
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    pub fn contact_type(&self) -> ContactType {
+        self.contact_type
+    }
+
+    #[inline]
+    pub fn set_contact_type(&mut self, contact_type: ContactType) {
+        self.contact_type = contact_type;
+    }
+
+    #[inline]
+    pub fn extensions(&self) -> Option<&Extensions> {
+        self.extensions.as_ref()
+    }
+
+    #[inline]
+    pub fn set_extensions(&mut self, extensions: Option<Extensions>) {
+        self.extensions = extensions;
+    }
+
+    #[inline]
+    pub fn company(&self) -> Option<&String> {
+        self.company.as_ref()
+    }
+
+    #[inline]
+    pub fn set_company(&mut self, company: Option<String>) {
+        self.company = company;
+    }
+
+    #[inline]
+    pub fn given_name(&self) -> Option<&String> {
+        self.given_name.as_ref()
+    }
+
+    #[inline]
+    pub fn set_given_name(&mut self, given_name: Option<String>) {
+        self.given_name = given_name;
+    }
+
+    #[inline]
+    pub fn sur_name(&self) -> Option<&String> {
+        self.sur_name.as_ref()
+    }
+
+    #[inline]
+    pub fn set_sur_name(&mut self, sur_name: Option<String>) {
+        self.sur_name = sur_name;
+    }
+
+    /// `mailto:` URIs
+    #[inline]
+    pub fn email_addresses(&self) -> &Vec<String> {
+        &self.email_addresses
+    }
+
+    #[inline]
+    pub fn add_email_address(&mut self, email_address: String) {
+        self.email_addresses.push(email_address);
+    }
+
+    #[inline]
+    pub fn telephone_numbers(&self) -> &Vec<String> {
+        &self.telephone_numbers
+    }
+
+    #[inline]
+    pub fn add_telephone_number(&mut self, telephone_number: String) {
+        self.telephone_numbers.push(telephone_number);
+    }
+
+    /// attributes from other namespaces, e.g. the REFEDS `remd:contactType`
+    #[inline]
+    pub fn unknown_attributes(&self) -> &AttributeMap {
+        &self.unknown_attributes
+    }
+
+    #[inline]
+    pub fn unknown_attributes_mut(&mut self) -> &mut AttributeMap {
+        &mut self.unknown_attributes
+    }
+}
+
+fn text_of(element: &XmlObject) -> String {
+    element
+        .text()
+        .map(|text| text.trim().to_string())
+        .unwrap_or_default()
+}
+
+fn text_element(local_name: &str, text: String) -> Rc<RefCell<XmlObject>> {
+    let mut xml_object = XmlObject::new(
+        Some(ContactPerson::NS_URI.to_string()),
+        local_name.to_string(),
+        Some(ContactPerson::NS_PREFIX.to_string()),
+    );
+    xml_object.set_text(Some(text));
+    Rc::new(RefCell::new(xml_object))
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for ContactPerson {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut contact_person = ContactPerson::default();
+        for (key, value) in element.attributes() {
+            match key.as_str() {
+                ContactPerson::ATTRIB_CONTACT_TYPE => {
+                    contact_person.set_contact_type(parse_from_string(value)?);
+                }
+                _ => {
+                    contact_person
+                        .unknown_attributes
+                        .add_attribute_of(&element, key, value);
+                }
+            }
+        }
+        for child in element.children() {
+            let child = child.borrow();
+            match child.q_name().local_name() {
+                ContactPerson::CHILD_EXTENSIONS => {
+                    contact_person.set_extensions(Some(Extensions::try_from(child)?));
+                }
+                ContactPerson::CHILD_COMPANY => {
+                    contact_person.set_company(Some(text_of(&child)));
+                }
+                ContactPerson::CHILD_GIVEN_NAME => {
+                    contact_person.set_given_name(Some(text_of(&child)));
+                }
+                ContactPerson::CHILD_SUR_NAME => {
+                    contact_person.set_sur_name(Some(text_of(&child)));
+                }
+                ContactPerson::CHILD_EMAIL_ADDRESS => {
+                    contact_person.add_email_address(text_of(&child));
+                }
+                ContactPerson::CHILD_TELEPHONE_NUMBER => {
+                    contact_person.add_telephone_number(text_of(&child));
+                }
+                _ => {}
+            }
+        }
+        Ok(contact_person)
+    }
+}
+
+impl TryFrom<ContactPerson> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(contact_person: ContactPerson) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(ContactPerson::NS_URI.to_string()),
+            ContactPerson::ELEMENT_NAME.to_string(),
+            Some(ContactPerson::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            ContactPerson::NS_PREFIX.to_string(),
+            ContactPerson::NS_URI.to_string(),
+        );
+        xml_object.add_attribute(
+            ContactPerson::ATTRIB_CONTACT_TYPE.to_string(),
+            contact_person.contact_type.to_string(),
+        );
+        contact_person.unknown_attributes.write_to(&mut xml_object);
+        if let Some(extensions) = contact_person.extensions {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(extensions)?)));
+        }
+        if let Some(company) = contact_person.company {
+            xml_object.add_child(text_element(ContactPerson::CHILD_COMPANY, company));
+        }
+        if let Some(given_name) = contact_person.given_name {
+            xml_object.add_child(text_element(ContactPerson::CHILD_GIVEN_NAME, given_name));
+        }
+        if let Some(sur_name) = contact_person.sur_name {
+            xml_object.add_child(text_element(ContactPerson::CHILD_SUR_NAME, sur_name));
+        }
+        for email_address in contact_person.email_addresses {
+            xml_object.add_child(text_element(
+                ContactPerson::CHILD_EMAIL_ADDRESS,
+                email_address,
+            ));
+        }
+        for telephone_number in contact_person.telephone_numbers {
+            xml_object.add_child(text_element(
+                ContactPerson::CHILD_TELEPHONE_NUMBER,
+                telephone_number,
+            ));
+        }
+        Ok(xml_object)
+    }
+}
@@ -0,0 +1,46 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{common::SAML2Obj, error::SAMLError};
+
+/// the `contactType` of a `ContactPerson`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ContactType {
+    #[default]
+    Technical,
+    Support,
+    Administrative,
+    Billing,
+    Other,
+}
+
+impl SAML2Obj for ContactType {}
+
+impl FromStr for ContactType {
+    type Err = SAMLError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "technical" => Ok(ContactType::Technical),
+            "support" => Ok(ContactType::Support),
+            "administrative" => Ok(ContactType::Administrative),
+            "billing" => Ok(ContactType::Billing),
+            "other" => Ok(ContactType::Other),
+            _ => Err(SAMLError::UnmarshallingError(format!(
+                "Invalid ContactType: {}",
+                s
+            ))),
+        }
+    }
+}
+
+impl Display for ContactType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContactType::Technical => write!(f, "technical"),
+            ContactType::Support => write!(f, "support"),
+            ContactType::Administrative => write!(f, "administrative"),
+            ContactType::Billing => write!(f, "billing"),
+            ContactType::Other => write!(f, "other"),
+        }
+    }
+}
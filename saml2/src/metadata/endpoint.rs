@@ -0,0 +1,116 @@
+use std::cell::Ref;
+
+use crate::{common::SAML2Obj, error::SAMLError, util::AttributeMap, xml::XmlObject};
+
+use super::metadata_element;
+
+/// `md:EndpointType`, where and over which binding a role receives protocol messages. The
+/// element name, e.g. `SingleLogoutService`, is given by the descriptor holding it.
+#[derive(Debug, Default)]
+pub struct Endpoint {
+    binding: String,
+    location: String,
+    response_location: Option<String>,
+    unknown_attributes: AttributeMap,
+}
+
+impl SAML2Obj for Endpoint {}
+
+impl Endpoint {
+    const ATTRIB_BINDING: &'static str = "Binding";
+    const ATTRIB_LOCATION: &'static str = "Location";
+    const ATTRIB_RESPONSE_LOCATION: &'static str = "ResponseLocation";
+
+    pub fn new(binding: String, location: String) -> Self {
+        Endpoint {
+            binding,
+            location,
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    pub fn binding(&self) -> &str {
+        &self.binding
+    }
+
+    #[inline]
+    pub fn set_binding(&mut self, binding: String) {
+        self.binding = binding;
+    }
+
+    #[inline]
+    pub fn location(&self) -> &str {
+        &self.location
+    }
+
+    #[inline]
+    pub fn set_location(&mut self, location: String) {
+        self.location = location;
+    }
+
+    /// where responses go when they are not sent to `location`
+    #[inline]
+    pub fn response_location(&self) -> Option<&String> {
+        self.response_location.as_ref()
+    }
+
+    #[inline]
+    pub fn set_response_location(&mut self, response_location: Option<String>) {
+        self.response_location = response_location;
+    }
+
+    #[inline]
+    pub fn unknown_attributes(&self) -> &AttributeMap {
+        &self.unknown_attributes
+    }
+
+    #[inline]
+    pub fn unknown_attributes_mut(&mut self) -> &mut AttributeMap {
+        &mut self.unknown_attributes
+    }
+
+    /// serialize the endpoint as the metadata element `local_name`
+    pub fn into_xml_object(self, local_name: &str) -> Result<XmlObject, SAMLError> {
+        let mut xml_object = metadata_element(local_name);
+        xml_object.add_attribute(Endpoint::ATTRIB_BINDING.to_string(), self.binding);
+        xml_object.add_attribute(Endpoint::ATTRIB_LOCATION.to_string(), self.location);
+        if let Some(response_location) = self.response_location {
+            xml_object.add_attribute(
+                Endpoint::ATTRIB_RESPONSE_LOCATION.to_string(),
+                response_location,
+            );
+        }
+        self.unknown_attributes.write_to(&mut xml_object);
+        Ok(xml_object)
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for Endpoint {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut endpoint = Endpoint::default();
+        for (key, value) in element.attributes() {
+            match key.as_str() {
+                Endpoint::ATTRIB_BINDING => endpoint.set_binding(value.to_string()),
+                Endpoint::ATTRIB_LOCATION => endpoint.set_location(value.to_string()),
+                Endpoint::ATTRIB_RESPONSE_LOCATION => {
+                    endpoint.set_response_location(Some(value.to_string()));
+                }
+                _ => {
+                    endpoint
+                        .unknown_attributes
+                        .add_attribute_of(&element, key, value);
+                }
+            }
+        }
+        if endpoint.binding.is_empty() || endpoint.location.is_empty() {
+            return Err(SAMLError::UnmarshallingError(format!(
+                "{} must have a Binding and a Location",
+                element.q_name().local_name()
+            )));
+        }
+        Ok(endpoint)
+    }
+}
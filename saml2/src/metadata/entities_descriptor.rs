@@ -0,0 +1,396 @@
+use std::{
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{
+    common::SAML2Obj, error::SAMLError, signature::signature::Signature, util::AttributeMap,
+    xml::XmlObject,
+};
+
+use super::{
+    entity_descriptor::EntityDescriptor, extensions::Extensions, format_duration, parse_duration,
+    parse_from_string,
+};
+
+/// a member of an `EntitiesDescriptor`, the group may nest further groups
+#[derive(Debug)]
+pub enum EntitiesDescriptorChild {
+    EntityDescriptor(EntityDescriptor),
+    EntitiesDescriptor(EntitiesDescriptor),
+}
+
+/// `md:EntitiesDescriptor`, a group of entities such as the members of a federation
+#[derive(Debug, Default)]
+pub struct EntitiesDescriptor {
+    id: Option<String>,
+    name: Option<String>,
+    valid_until: Option<DateTime<Utc>>,
+    cache_duration: Option<Duration>,
+    signature: Option<Signature>,
+    extensions: Option<Extensions>,
+    children: Vec<EntitiesDescriptorChild>,
+    unknown_attributes: AttributeMap,
+}
+
+impl SAML2Obj for EntitiesDescriptor {}
+
+impl EntitiesDescriptor {
+    const ATTRIB_ID: &'static str = "ID";
+    const ATTRIB_NAME: &'static str = "Name";
+    const ATTRIB_VALID_UNTIL: &'static str = "validUntil";
+    const ATTRIB_CACHE_DURATION: &'static str = "cacheDuration";
+
+    const CHILD_SIGNATURE: &'static str = "Signature";
+    const CHILD_EXTENSIONS: &'static str = "Extensions";
+
+    pub const ELEMENT_NAME: &'static str = "EntitiesDescriptor";
+    pub const NS_PREFIX: &'static str = "md";
+    pub const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:metadata";
+
+    #[inline]
+    pub fn id(&self) -> Option<&String> {
+        self.id.as_ref()
+    }
+
+    #[inline]
+    pub fn set_id(&mut self, id: Option<String>) {
+        self.id = id;
+    }
+
+    #[inline]
+    pub fn name(&self) -> Option<&String> {
+        self.name.as_ref()
+    }
+
+    #[inline]
+    pub fn set_name(&mut self, name: Option<String>) {
+        self.name = name;
+    }
+
+    #[inline]
+    pub fn valid_until(&self) -> Option<&DateTime<Utc>> {
+        self.valid_until.as_ref()
+    }
+
+    #[inline]
+    pub fn set_valid_until(&mut self, valid_until: Option<DateTime<Utc>>) {
+        self.valid_until = valid_until;
+    }
+
+    /// how long a consumer may cache the metadata before refreshing it
+    #[inline]
+    pub fn cache_duration(&self) -> Option<&Duration> {
+        self.cache_duration.as_ref()
+    }
+
+    #[inline]
+    pub fn set_cache_duration(&mut self, cache_duration: Option<Duration>) {
+        self.cache_duration = cache_duration;
+    }
+
+    #[inline]
+    pub fn signature(&self) -> Option<&Signature> {
+        self.signature.as_ref()
+    }
+
+    #[inline]
+    pub fn set_signature(&mut self, signature: Option<Signature>) {
+        self.signature = signature;
+    }
+
+    #[inline]
+    pub fn extensions(&self) -> Option<&Extensions> {
+        self.extensions.as_ref()
+    }
+
+    #[inline]
+    pub fn set_extensions(&mut self, extensions: Option<Extensions>) {
+        self.extensions = extensions;
+    }
+
+    /// the entities and nested groups, in document order
+    #[inline]
+    pub fn children(&self) -> &Vec<EntitiesDescriptorChild> {
+        &self.children
+    }
+
+    #[inline]
+    pub fn children_mut(&mut self) -> &mut Vec<EntitiesDescriptorChild> {
+        &mut self.children
+    }
+
+    /// take the entities and nested groups out of the group
+    #[inline]
+    pub fn into_children(self) -> Vec<EntitiesDescriptorChild> {
+        self.children
+    }
+
+    #[inline]
+    pub fn add_entity_descriptor(&mut self, entity_descriptor: EntityDescriptor) {
+        self.children
+            .push(EntitiesDescriptorChild::EntityDescriptor(entity_descriptor));
+    }
+
+    #[inline]
+    pub fn add_entities_descriptor(&mut self, entities_descriptor: EntitiesDescriptor) {
+        self.children
+            .push(EntitiesDescriptorChild::EntitiesDescriptor(
+                entities_descriptor,
+            ));
+    }
+
+    /// every `EntityDescriptor` of the group and of the groups nested in it
+    pub fn entity_descriptors(&self) -> Vec<&EntityDescriptor> {
+        let mut entity_descriptors = Vec::new();
+        for child in &self.children {
+            match child {
+                EntitiesDescriptorChild::EntityDescriptor(entity_descriptor) => {
+                    entity_descriptors.push(entity_descriptor);
+                }
+                EntitiesDescriptorChild::EntitiesDescriptor(entities_descriptor) => {
+                    entity_descriptors.extend(entities_descriptor.entity_descriptors());
+                }
+            }
+        }
+        entity_descriptors
+    }
+
+    #[inline]
+    pub fn unknown_attributes(&self) -> &AttributeMap {
+        &self.unknown_attributes
+    }
+
+    #[inline]
+    pub fn unknown_attributes_mut(&mut self) -> &mut AttributeMap {
+        &mut self.unknown_attributes
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for EntitiesDescriptor {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut entities_descriptor = EntitiesDescriptor::default();
+        for (key, value) in element.attributes() {
+            match key.as_str() {
+                EntitiesDescriptor::ATTRIB_ID => {
+                    entities_descriptor.set_id(Some(value.to_string()));
+                }
+                EntitiesDescriptor::ATTRIB_NAME => {
+                    entities_descriptor.set_name(Some(value.to_string()));
+                }
+                EntitiesDescriptor::ATTRIB_VALID_UNTIL => {
+                    entities_descriptor.set_valid_until(Some(parse_from_string(value)?));
+                }
+                EntitiesDescriptor::ATTRIB_CACHE_DURATION => {
+                    entities_descriptor.set_cache_duration(Some(parse_duration(value)?));
+                }
+                _ => {
+                    entities_descriptor
+                        .unknown_attributes
+                        .add_attribute_of(&element, key, value);
+                }
+            }
+        }
+        for child in element.children() {
+            let child = child.borrow();
+            match child.q_name().local_name() {
+                EntitiesDescriptor::CHILD_SIGNATURE => {
+                    entities_descriptor.set_signature(Some(Signature::try_from(child)?));
+                }
+                EntitiesDescriptor::CHILD_EXTENSIONS => {
+                    entities_descriptor.set_extensions(Some(Extensions::try_from(child)?));
+                }
+                EntityDescriptor::ELEMENT_NAME => {
+                    entities_descriptor.add_entity_descriptor(EntityDescriptor::try_from(child)?);
+                }
+                EntitiesDescriptor::ELEMENT_NAME => {
+                    entities_descriptor
+                        .add_entities_descriptor(EntitiesDescriptor::try_from(child)?);
+                }
+                _ => {}
+            }
+        }
+        Ok(entities_descriptor)
+    }
+}
+
+impl TryFrom<EntitiesDescriptor> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(entities_descriptor: EntitiesDescriptor) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(EntitiesDescriptor::NS_URI.to_string()),
+            EntitiesDescriptor::ELEMENT_NAME.to_string(),
+            Some(EntitiesDescriptor::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            EntitiesDescriptor::NS_PREFIX.to_string(),
+            EntitiesDescriptor::NS_URI.to_string(),
+        );
+        if let Some(id) = entities_descriptor.id {
+            xml_object.add_attribute(EntitiesDescriptor::ATTRIB_ID.to_string(), id);
+        }
+        if let Some(name) = entities_descriptor.name {
+            xml_object.add_attribute(EntitiesDescriptor::ATTRIB_NAME.to_string(), name);
+        }
+        if let Some(valid_until) = entities_descriptor.valid_until {
+            xml_object.add_attribute(
+                EntitiesDescriptor::ATTRIB_VALID_UNTIL.to_string(),
+                valid_until.to_rfc3339(),
+            );
+        }
+        if let Some(cache_duration) = entities_descriptor.cache_duration {
+            xml_object.add_attribute(
+                EntitiesDescriptor::ATTRIB_CACHE_DURATION.to_string(),
+                format_duration(cache_duration),
+            );
+        }
+        entities_descriptor
+            .unknown_attributes
+            .write_to(&mut xml_object);
+        if let Some(signature) = entities_descriptor.signature {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(signature)?)));
+        }
+        if let Some(extensions) = entities_descriptor.extensions {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(extensions)?)));
+        }
+        for child in entities_descriptor.children {
+            let child = match child {
+                EntitiesDescriptorChild::EntityDescriptor(entity_descriptor) => {
+                    XmlObject::try_from(entity_descriptor)?
+                }
+                EntitiesDescriptorChild::EntitiesDescriptor(entities_descriptor) => {
+                    XmlObject::try_from(entities_descriptor)?
+                }
+            };
+            xml_object.add_child(Rc::new(RefCell::new(child)));
+        }
+        Ok(xml_object)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use chrono::{DateTime, Duration, Utc};
+
+    use crate::{
+        metadata::{contact_type::ContactType, entity_descriptor::EntityDescriptor},
+        util::InputStream,
+        xml::XmlObject,
+    };
+
+    use super::{EntitiesDescriptor, EntitiesDescriptorChild};
+
+    const ENTITIES_DESCRIPTOR: &str = include_str!("../../tests/resources/entities_descriptor.xml");
+
+    fn parse(xml: &str) -> EntitiesDescriptor {
+        let root = XmlObject::parse_xml(InputStream::new(xml.as_bytes().to_vec())).unwrap();
+        let entities_descriptor = EntitiesDescriptor::try_from(root.borrow()).unwrap();
+        entities_descriptor
+    }
+
+    fn check(federation: &EntitiesDescriptor) {
+        assert_eq!(federation.name().unwrap(), "urn:example:federation");
+        assert_eq!(
+            federation.valid_until().unwrap(),
+            &"2031-01-01T00:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        assert_eq!(federation.cache_duration().unwrap(), &Duration::hours(6));
+        let publication_info = federation
+            .extensions()
+            .unwrap()
+            .find("urn:oasis:names:tc:SAML:metadata:rpi", "PublicationInfo")
+            .next()
+            .unwrap();
+        assert_eq!(
+            publication_info.borrow().attribute("publisher"),
+            Some("https://federation.example.org")
+        );
+
+        let entity_ids: Vec<&str> = federation
+            .entity_descriptors()
+            .iter()
+            .map(|entity_descriptor| entity_descriptor.entity_id())
+            .collect();
+        assert_eq!(
+            entity_ids,
+            vec![
+                "https://idp.example.org/metadata",
+                "https://sp.example.org/metadata"
+            ]
+        );
+
+        let idp = federation.entity_descriptors()[0];
+        assert_eq!(idp.id().unwrap(), "_idp");
+        let organization = idp.organization().unwrap();
+        assert_eq!(
+            organization.organization_names()[0].value(),
+            "Example University"
+        );
+        let display_name = &organization.organization_display_names()[1];
+        assert_eq!(
+            (display_name.lang(), display_name.value()),
+            ("fr", "Université Exemple")
+        );
+        assert_eq!(
+            organization.organization_urls()[0].value(),
+            "https://www.example.org/"
+        );
+        let contacts = idp.contact_persons();
+        assert_eq!(contacts[0].contact_type(), ContactType::Technical);
+        assert_eq!(contacts[0].sur_name().unwrap(), "Lovelace");
+        assert_eq!(
+            contacts[0].email_addresses(),
+            &vec!["mailto:idp-admin@example.org".to_string()]
+        );
+        assert_eq!(contacts[0].telephone_numbers()[0], "+1 555 0100");
+        assert_eq!(contacts[1].contact_type(), ContactType::Other);
+        assert_eq!(
+            contacts[1].unknown_attributes().get("remd:contactType"),
+            Some("http://refeds.org/metadata/contactType/security")
+        );
+
+        let sps = match &federation.children()[1] {
+            EntitiesDescriptorChild::EntitiesDescriptor(sps) => sps,
+            EntitiesDescriptorChild::EntityDescriptor(_) => panic!("expected a nested group"),
+        };
+        assert_eq!(sps.cache_duration().unwrap(), &Duration::days(1));
+        let sp = sps.entity_descriptors()[0];
+        assert_eq!(
+            sp.valid_until().unwrap(),
+            &"2030-06-30T12:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        assert_eq!(
+            sp.contact_persons()[0].company().unwrap(),
+            "Example Services"
+        );
+    }
+
+    #[test]
+    fn we_can_parse_entities_descriptor() {
+        check(&parse(ENTITIES_DESCRIPTOR));
+    }
+
+    #[test]
+    fn we_can_round_trip_entities_descriptor() {
+        let xml_object = XmlObject::try_from(parse(ENTITIES_DESCRIPTOR)).unwrap();
+        let xml = XmlObject::write_xml(Rc::new(RefCell::new(xml_object))).unwrap();
+        check(&parse(&xml));
+    }
+
+    #[test]
+    fn we_cannot_parse_entity_descriptor_without_entity_id() {
+        let root = XmlObject::parse_xml(InputStream::new(
+            br#"<md:EntityDescriptor xmlns:md="urn:oasis:names:tc:SAML:2.0:metadata" ID="_e"/>"#
+                .to_vec(),
+        ))
+        .unwrap();
+        assert!(EntityDescriptor::try_from(root.borrow()).is_err());
+    }
+}
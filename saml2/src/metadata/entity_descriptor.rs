@@ -0,0 +1,446 @@
+use std::{
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{
+    common::SAML2Obj, error::SAMLError, signature::signature::Signature, util::AttributeMap,
+    xml::XmlObject,
+};
+
+use super::{
+    attribute_authority_descriptor::AttributeAuthorityDescriptor,
+    authn_authority_descriptor::AuthnAuthorityDescriptor, contact_person::ContactPerson,
+    extensions::Extensions, format_duration, idp_sso_descriptor::IDPSSODescriptor,
+    organization::Organization, parse_duration, parse_from_string, pdp_descriptor::PDPDescriptor,
+    role_descriptor::RoleDescriptor, sp_sso_descriptor::SPSSODescriptor,
+    unknown_role_descriptor::UnknownRoleDescriptor,
+};
+
+/// `md:EntityDescriptor`, the metadata of a single SAML entity
+#[derive(Debug, Default)]
+pub struct EntityDescriptor {
+    entity_id: String,
+    id: Option<String>,
+    valid_until: Option<DateTime<Utc>>,
+    cache_duration: Option<Duration>,
+    signature: Option<Signature>,
+    extensions: Option<Extensions>,
+    role_descriptors: Vec<Box<dyn RoleDescriptor>>,
+    affiliation_descriptor: Option<Rc<RefCell<XmlObject>>>,
+    organization: Option<Organization>,
+    contact_persons: Vec<ContactPerson>,
+    unknown_children: Vec<Rc<RefCell<XmlObject>>>,
+    unknown_attributes: AttributeMap,
+}
+
+impl SAML2Obj for EntityDescriptor {}
+
+impl EntityDescriptor {
+    const ATTRIB_ENTITY_ID: &'static str = "entityID";
+    const ATTRIB_ID: &'static str = "ID";
+    const ATTRIB_VALID_UNTIL: &'static str = "validUntil";
+    const ATTRIB_CACHE_DURATION: &'static str = "cacheDuration";
+
+    const CHILD_SIGNATURE: &'static str = "Signature";
+    const CHILD_EXTENSIONS: &'static str = "Extensions";
+    const CHILD_IDP_SSO_DESCRIPTOR: &'static str = "IDPSSODescriptor";
+    const CHILD_SP_SSO_DESCRIPTOR: &'static str = "SPSSODescriptor";
+    const CHILD_ATTRIBUTE_AUTHORITY_DESCRIPTOR: &'static str = "AttributeAuthorityDescriptor";
+    const CHILD_AUTHN_AUTHORITY_DESCRIPTOR: &'static str = "AuthnAuthorityDescriptor";
+    const CHILD_PDP_DESCRIPTOR: &'static str = "PDPDescriptor";
+    const CHILD_ROLE_DESCRIPTOR: &'static str = "RoleDescriptor";
+    const CHILD_AFFILIATION_DESCRIPTOR: &'static str = "AffiliationDescriptor";
+    const CHILD_ORGANIZATION: &'static str = "Organization";
+    const CHILD_CONTACT_PERSON: &'static str = "ContactPerson";
+
+    pub const ELEMENT_NAME: &'static str = "EntityDescriptor";
+    pub const NS_PREFIX: &'static str = "md";
+    pub const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:metadata";
+
+    pub fn new(entity_id: String) -> Self {
+        EntityDescriptor {
+            entity_id,
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    pub fn entity_id(&self) -> &str {
+        &self.entity_id
+    }
+
+    #[inline]
+    pub fn set_entity_id(&mut self, entity_id: String) {
+        self.entity_id = entity_id;
+    }
+
+    #[inline]
+    pub fn id(&self) -> Option<&String> {
+        self.id.as_ref()
+    }
+
+    #[inline]
+    pub fn set_id(&mut self, id: Option<String>) {
+        self.id = id;
+    }
+
+    #[inline]
+    pub fn valid_until(&self) -> Option<&DateTime<Utc>> {
+        self.valid_until.as_ref()
+    }
+
+    #[inline]
+    pub fn set_valid_until(&mut self, valid_until: Option<DateTime<Utc>>) {
+        self.valid_until = valid_until;
+    }
+
+    /// how long a consumer may cache the metadata before refreshing it
+    #[inline]
+    pub fn cache_duration(&self) -> Option<&Duration> {
+        self.cache_duration.as_ref()
+    }
+
+    #[inline]
+    pub fn set_cache_duration(&mut self, cache_duration: Option<Duration>) {
+        self.cache_duration = cache_duration;
+    }
+
+    #[inline]
+    pub fn signature(&self) -> Option<&Signature> {
+        self.signature.as_ref()
+    }
+
+    #[inline]
+    pub fn set_signature(&mut self, signature: Option<Signature>) {
+        self.signature = signature;
+    }
+
+    #[inline]
+    pub fn extensions(&self) -> Option<&Extensions> {
+        self.extensions.as_ref()
+    }
+
+    #[inline]
+    pub fn set_extensions(&mut self, extensions: Option<Extensions>) {
+        self.extensions = extensions;
+    }
+
+    #[inline]
+    pub fn role_descriptors(&self) -> &Vec<Box<dyn RoleDescriptor>> {
+        &self.role_descriptors
+    }
+
+    #[inline]
+    pub fn role_descriptors_mut(&mut self) -> &mut Vec<Box<dyn RoleDescriptor>> {
+        &mut self.role_descriptors
+    }
+
+    #[inline]
+    pub fn add_role_descriptor(&mut self, role_descriptor: Box<dyn RoleDescriptor>) {
+        self.role_descriptors.push(role_descriptor);
+    }
+
+    /// the `IDPSSODescriptor`s among the roles of the entity
+    pub fn idp_sso_descriptors(&self) -> impl Iterator<Item = &IDPSSODescriptor> {
+        self.role_descriptors
+            .iter()
+            .filter_map(|role| role.as_any().downcast_ref::<IDPSSODescriptor>())
+    }
+
+    /// the `SPSSODescriptor`s among the roles of the entity
+    pub fn sp_sso_descriptors(&self) -> impl Iterator<Item = &SPSSODescriptor> {
+        self.role_descriptors
+            .iter()
+            .filter_map(|role| role.as_any().downcast_ref::<SPSSODescriptor>())
+    }
+
+    /// the `AttributeAuthorityDescriptor`s among the roles of the entity
+    pub fn attribute_authority_descriptors(
+        &self,
+    ) -> impl Iterator<Item = &AttributeAuthorityDescriptor> {
+        self.role_descriptors
+            .iter()
+            .filter_map(|role| role.as_any().downcast_ref::<AttributeAuthorityDescriptor>())
+    }
+
+    /// the `AuthnAuthorityDescriptor`s among the roles of the entity
+    pub fn authn_authority_descriptors(&self) -> impl Iterator<Item = &AuthnAuthorityDescriptor> {
+        self.role_descriptors
+            .iter()
+            .filter_map(|role| role.as_any().downcast_ref::<AuthnAuthorityDescriptor>())
+    }
+
+    /// the `PDPDescriptor`s among the roles of the entity
+    pub fn pdp_descriptors(&self) -> impl Iterator<Item = &PDPDescriptor> {
+        self.role_descriptors
+            .iter()
+            .filter_map(|role| role.as_any().downcast_ref::<PDPDescriptor>())
+    }
+
+    /// the roles defined by other specifications through `xsi:type`
+    pub fn unknown_role_descriptors(&self) -> impl Iterator<Item = &UnknownRoleDescriptor> {
+        self.role_descriptors
+            .iter()
+            .filter_map(|role| role.as_any().downcast_ref::<UnknownRoleDescriptor>())
+    }
+
+    /// `md:AffiliationDescriptor`, which an entity has instead of roles, kept as it is
+    #[inline]
+    pub fn affiliation_descriptor(&self) -> Option<&Rc<RefCell<XmlObject>>> {
+        self.affiliation_descriptor.as_ref()
+    }
+
+    #[inline]
+    pub fn set_affiliation_descriptor(
+        &mut self,
+        affiliation_descriptor: Option<Rc<RefCell<XmlObject>>>,
+    ) {
+        self.affiliation_descriptor = affiliation_descriptor;
+    }
+
+    #[inline]
+    pub fn organization(&self) -> Option<&Organization> {
+        self.organization.as_ref()
+    }
+
+    #[inline]
+    pub fn set_organization(&mut self, organization: Option<Organization>) {
+        self.organization = organization;
+    }
+
+    #[inline]
+    pub fn contact_persons(&self) -> &Vec<ContactPerson> {
+        &self.contact_persons
+    }
+
+    #[inline]
+    pub fn add_contact_person(&mut self, contact_person: ContactPerson) {
+        self.contact_persons.push(contact_person);
+    }
+
+    /// the children not modelled by this type, e.g. `md:AdditionalMetadataLocation`
+    #[inline]
+    pub fn unknown_children(&self) -> &Vec<Rc<RefCell<XmlObject>>> {
+        &self.unknown_children
+    }
+
+    #[inline]
+    pub fn add_unknown_child(&mut self, unknown_child: Rc<RefCell<XmlObject>>) {
+        self.unknown_children.push(unknown_child);
+    }
+
+    #[inline]
+    pub fn unknown_attributes(&self) -> &AttributeMap {
+        &self.unknown_attributes
+    }
+
+    #[inline]
+    pub fn unknown_attributes_mut(&mut self) -> &mut AttributeMap {
+        &mut self.unknown_attributes
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for EntityDescriptor {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut entity_descriptor = EntityDescriptor::default();
+        for (key, value) in element.attributes() {
+            match key.as_str() {
+                EntityDescriptor::ATTRIB_ENTITY_ID => {
+                    entity_descriptor.set_entity_id(value.to_string());
+                }
+                EntityDescriptor::ATTRIB_ID => entity_descriptor.set_id(Some(value.to_string())),
+                EntityDescriptor::ATTRIB_VALID_UNTIL => {
+                    entity_descriptor.set_valid_until(Some(parse_from_string(value)?));
+                }
+                EntityDescriptor::ATTRIB_CACHE_DURATION => {
+                    entity_descriptor.set_cache_duration(Some(parse_duration(value)?));
+                }
+                _ => {
+                    entity_descriptor
+                        .unknown_attributes
+                        .add_attribute_of(&element, key, value);
+                }
+            }
+        }
+        if entity_descriptor.entity_id.is_empty() {
+            return Err(SAMLError::UnmarshallingError(
+                "EntityDescriptor must have an entityID".to_string(),
+            ));
+        }
+        for child_object in element.children() {
+            let child = child_object.borrow();
+            let namespace_uri = child.q_name().namespace_uri();
+            if namespace_uri == Some(Signature::NS_URI)
+                && child.q_name().local_name() == EntityDescriptor::CHILD_SIGNATURE
+            {
+                entity_descriptor.set_signature(Some(Signature::try_from(child)?));
+                continue;
+            }
+            if namespace_uri != Some(EntityDescriptor::NS_URI) {
+                entity_descriptor.add_unknown_child(Rc::clone(child_object));
+                continue;
+            }
+            match child.q_name().local_name() {
+                EntityDescriptor::CHILD_EXTENSIONS => {
+                    entity_descriptor.set_extensions(Some(Extensions::try_from(child)?));
+                }
+                EntityDescriptor::CHILD_IDP_SSO_DESCRIPTOR => {
+                    entity_descriptor
+                        .add_role_descriptor(Box::new(IDPSSODescriptor::try_from(child)?));
+                }
+                EntityDescriptor::CHILD_SP_SSO_DESCRIPTOR => {
+                    entity_descriptor
+                        .add_role_descriptor(Box::new(SPSSODescriptor::try_from(child)?));
+                }
+                EntityDescriptor::CHILD_ATTRIBUTE_AUTHORITY_DESCRIPTOR => {
+                    entity_descriptor.add_role_descriptor(Box::new(
+                        AttributeAuthorityDescriptor::try_from(child)?,
+                    ));
+                }
+                EntityDescriptor::CHILD_AUTHN_AUTHORITY_DESCRIPTOR => {
+                    entity_descriptor
+                        .add_role_descriptor(Box::new(AuthnAuthorityDescriptor::try_from(child)?));
+                }
+                EntityDescriptor::CHILD_PDP_DESCRIPTOR => {
+                    entity_descriptor
+                        .add_role_descriptor(Box::new(PDPDescriptor::try_from(child)?));
+                }
+                EntityDescriptor::CHILD_ROLE_DESCRIPTOR => {
+                    entity_descriptor
+                        .add_role_descriptor(Box::new(UnknownRoleDescriptor::try_from(child)?));
+                }
+                EntityDescriptor::CHILD_ORGANIZATION => {
+                    entity_descriptor.set_organization(Some(Organization::try_from(child)?));
+                }
+                EntityDescriptor::CHILD_AFFILIATION_DESCRIPTOR => {
+                    entity_descriptor.set_affiliation_descriptor(Some(Rc::clone(child_object)));
+                }
+                EntityDescriptor::CHILD_CONTACT_PERSON => {
+                    entity_descriptor.add_contact_person(ContactPerson::try_from(child)?);
+                }
+                _ => entity_descriptor.add_unknown_child(Rc::clone(child_object)),
+            }
+        }
+        Ok(entity_descriptor)
+    }
+}
+
+impl TryFrom<EntityDescriptor> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(entity_descriptor: EntityDescriptor) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(EntityDescriptor::NS_URI.to_string()),
+            EntityDescriptor::ELEMENT_NAME.to_string(),
+            Some(EntityDescriptor::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            EntityDescriptor::NS_PREFIX.to_string(),
+            EntityDescriptor::NS_URI.to_string(),
+        );
+        xml_object.add_attribute(
+            EntityDescriptor::ATTRIB_ENTITY_ID.to_string(),
+            entity_descriptor.entity_id,
+        );
+        if let Some(id) = entity_descriptor.id {
+            xml_object.add_attribute(EntityDescriptor::ATTRIB_ID.to_string(), id);
+        }
+        if let Some(valid_until) = entity_descriptor.valid_until {
+            xml_object.add_attribute(
+                EntityDescriptor::ATTRIB_VALID_UNTIL.to_string(),
+                valid_until.to_rfc3339(),
+            );
+        }
+        if let Some(cache_duration) = entity_descriptor.cache_duration {
+            xml_object.add_attribute(
+                EntityDescriptor::ATTRIB_CACHE_DURATION.to_string(),
+                format_duration(cache_duration),
+            );
+        }
+        entity_descriptor
+            .unknown_attributes
+            .write_to(&mut xml_object);
+        if let Some(signature) = entity_descriptor.signature {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(signature)?)));
+        }
+        if let Some(extensions) = entity_descriptor.extensions {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(extensions)?)));
+        }
+        for role_descriptor in entity_descriptor.role_descriptors {
+            xml_object.add_child(Rc::new(RefCell::new(role_descriptor.into_xml_object()?)));
+        }
+        if let Some(affiliation_descriptor) = entity_descriptor.affiliation_descriptor {
+            xml_object.add_child(affiliation_descriptor);
+        }
+        if let Some(organization) = entity_descriptor.organization {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(organization)?)));
+        }
+        for contact_person in entity_descriptor.contact_persons {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(contact_person)?)));
+        }
+        for unknown_child in entity_descriptor.unknown_children {
+            xml_object.add_child(unknown_child);
+        }
+        Ok(xml_object)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{util::InputStream, xml::XmlObject};
+
+    use super::EntityDescriptor;
+
+    const AFFILIATION: &str = r#"<md:EntityDescriptor xmlns:md="urn:oasis:names:tc:SAML:2.0:metadata" entityID="https://affiliation.example.org"><md:AffiliationDescriptor affiliationOwnerID="https://owner.example.org"><md:AffiliateMember>https://sp.example.org/metadata</md:AffiliateMember></md:AffiliationDescriptor><md:ContactPerson contactType="technical"><md:EmailAddress>mailto:ops@example.org</md:EmailAddress></md:ContactPerson><md:AdditionalMetadataLocation namespace="urn:example:md">https://example.org/more.xml</md:AdditionalMetadataLocation><ex:IDPSSODescriptor xmlns:ex="urn:example:md"/></md:EntityDescriptor>"#;
+
+    fn parse(xml: String) -> EntityDescriptor {
+        let root = XmlObject::parse_xml(InputStream::new(xml.into_bytes())).unwrap();
+        let entity_descriptor = EntityDescriptor::try_from(root.borrow()).unwrap();
+        entity_descriptor
+    }
+
+    #[test]
+    fn we_can_parse_and_serialize_affiliation_and_unknown_children() {
+        let entity_descriptor = parse(AFFILIATION.to_string());
+        let element = XmlObject::try_from(entity_descriptor).unwrap();
+        let xml = XmlObject::write_xml(Rc::new(RefCell::new(element))).unwrap();
+
+        let entity_descriptor = parse(xml);
+        // an element of another namespace is not taken for a role of the entity
+        assert!(entity_descriptor.role_descriptors().is_empty());
+        let affiliation_descriptor = entity_descriptor.affiliation_descriptor().unwrap().borrow();
+        assert_eq!(
+            affiliation_descriptor.attribute("affiliationOwnerID"),
+            Some("https://owner.example.org")
+        );
+        assert_eq!(entity_descriptor.contact_persons().len(), 1);
+        let unknown_children = entity_descriptor
+            .unknown_children()
+            .iter()
+            .map(|child| {
+                let child = child.borrow();
+                (
+                    child.q_name().namespace_uri().unwrap().to_string(),
+                    child.q_name().local_name().to_string(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            unknown_children,
+            vec![
+                (
+                    EntityDescriptor::NS_URI.to_string(),
+                    "AdditionalMetadataLocation".to_string()
+                ),
+                ("urn:example:md".to_string(), "IDPSSODescriptor".to_string())
+            ]
+        );
+    }
+}
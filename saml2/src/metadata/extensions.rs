@@ -0,0 +1,75 @@
+use std::{
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
+
+use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+
+/// `md:Extensions`, its elements are defined by other specifications and kept as they are
+#[derive(Debug, Default)]
+pub struct Extensions {
+    children: Vec<Rc<RefCell<XmlObject>>>,
+}
+
+impl SAML2Obj for Extensions {}
+
+impl Extensions {
+    pub const ELEMENT_NAME: &'static str = "Extensions";
+    pub const NS_PREFIX: &'static str = "md";
+    pub const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:metadata";
+
+    #[inline]
+    pub fn children(&self) -> &Vec<Rc<RefCell<XmlObject>>> {
+        &self.children
+    }
+
+    #[inline]
+    pub fn add_child(&mut self, child: Rc<RefCell<XmlObject>>) {
+        self.children.push(child);
+    }
+
+    /// the extension elements named `local_name` in the namespace `namespace_uri`
+    pub fn find<'a>(
+        &'a self,
+        namespace_uri: &'a str,
+        local_name: &'a str,
+    ) -> impl Iterator<Item = &'a Rc<RefCell<XmlObject>>> {
+        self.children.iter().filter(move |child| {
+            let child = child.borrow();
+            child.q_name().namespace_uri() == Some(namespace_uri)
+                && child.q_name().local_name() == local_name
+        })
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for Extensions {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut extensions = Extensions::default();
+        for child in element.children() {
+            extensions.add_child(Rc::clone(child));
+        }
+        Ok(extensions)
+    }
+}
+
+impl TryFrom<Extensions> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(extensions: Extensions) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(Extensions::NS_URI.to_string()),
+            Extensions::ELEMENT_NAME.to_string(),
+            Some(Extensions::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            Extensions::NS_PREFIX.to_string(),
+            Extensions::NS_URI.to_string(),
+        );
+        for child in extensions.children {
+            xml_object.add_child(child);
+        }
+        Ok(xml_object)
+    }
+}
@@ -0,0 +1,321 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
+
+use crate::{common::SAML2Obj, core::attribute::Attribute, error::SAMLError, xml::XmlObject};
+
+use super::{
+    endpoint::Endpoint,
+    metadata_element, parse_from_string,
+    role_descriptor::{RoleDescriptor, RoleDescriptorType},
+    sso_descriptor_type::SSODescriptorType,
+};
+
+/// `md:IDPSSODescriptor`, the identity provider role of an entity
+#[derive(Debug, Default)]
+pub struct IDPSSODescriptor {
+    sso_descriptor_type: SSODescriptorType,
+    want_authn_requests_signed: Option<bool>,
+    single_sign_on_services: Vec<Endpoint>,
+    name_id_mapping_services: Vec<Endpoint>,
+    assertion_id_request_services: Vec<Endpoint>,
+    attribute_profiles: Vec<String>,
+    attributes: Vec<Attribute>,
+}
+
+impl SAML2Obj for IDPSSODescriptor {}
+
+impl IDPSSODescriptor {
+    const ATTRIB_WANT_AUTHN_REQUESTS_SIGNED: &'static str = "WantAuthnRequestsSigned";
+
+    const CHILD_SINGLE_SIGN_ON_SERVICE: &'static str = "SingleSignOnService";
+    const CHILD_NAME_ID_MAPPING_SERVICE: &'static str = "NameIDMappingService";
+    const CHILD_ASSERTION_ID_REQUEST_SERVICE: &'static str = "AssertionIDRequestService";
+    const CHILD_ATTRIBUTE_PROFILE: &'static str = "AttributeProfile";
+    const CHILD_ATTRIBUTE: &'static str = "Attribute";
+
+    pub const ELEMENT_NAME: &'static str = "IDPSSODescriptor";
+    pub const NS_PREFIX: &'static str = "md";
+    pub const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:metadata";
+
+    pub fn new() -> Self {
+        IDPSSODescriptor {
+            sso_descriptor_type: SSODescriptorType::new(),
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    pub fn sso_descriptor_type(&self) -> &SSODescriptorType {
+        &self.sso_descriptor_type
+    }
+
+    #[inline]
+    pub fn sso_descriptor_type_mut(&mut self) -> &mut SSODescriptorType {
+        &mut self.sso_descriptor_type
+    }
+
+    #[inline]
+    pub fn want_authn_requests_signed(&self) -> Option<bool> {
+        self.want_authn_requests_signed
+    }
+
+    #[inline]
+    pub fn set_want_authn_requests_signed(&mut self, want_authn_requests_signed: Option<bool>) {
+        self.want_authn_requests_signed = want_authn_requests_signed;
+    }
+
+    #[inline]
+    pub fn single_sign_on_services(&self) -> &Vec<Endpoint> {
+        &self.single_sign_on_services
+    }
+
+    #[inline]
+    pub fn add_single_sign_on_service(&mut self, single_sign_on_service: Endpoint) {
+        self.single_sign_on_services.push(single_sign_on_service);
+    }
+
+    /// the `SingleSignOnService` speaking `binding`
+    pub fn single_sign_on_service(&self, binding: &str) -> Option<&Endpoint> {
+        self.single_sign_on_services
+            .iter()
+            .find(|endpoint| endpoint.binding() == binding)
+    }
+
+    #[inline]
+    pub fn name_id_mapping_services(&self) -> &Vec<Endpoint> {
+        &self.name_id_mapping_services
+    }
+
+    #[inline]
+    pub fn add_name_id_mapping_service(&mut self, name_id_mapping_service: Endpoint) {
+        self.name_id_mapping_services.push(name_id_mapping_service);
+    }
+
+    #[inline]
+    pub fn assertion_id_request_services(&self) -> &Vec<Endpoint> {
+        &self.assertion_id_request_services
+    }
+
+    #[inline]
+    pub fn add_assertion_id_request_service(&mut self, assertion_id_request_service: Endpoint) {
+        self.assertion_id_request_services
+            .push(assertion_id_request_service);
+    }
+
+    #[inline]
+    pub fn attribute_profiles(&self) -> &Vec<String> {
+        &self.attribute_profiles
+    }
+
+    #[inline]
+    pub fn add_attribute_profile(&mut self, attribute_profile: String) {
+        self.attribute_profiles.push(attribute_profile);
+    }
+
+    /// the attributes the IdP can assert
+    #[inline]
+    pub fn attributes(&self) -> &Vec<Attribute> {
+        &self.attributes
+    }
+
+    #[inline]
+    pub fn add_attribute(&mut self, attribute: Attribute) {
+        self.attributes.push(attribute);
+    }
+}
+
+impl RoleDescriptor for IDPSSODescriptor {
+    fn role_descriptor_type(&self) -> &RoleDescriptorType {
+        self.sso_descriptor_type.role_descriptor_type()
+    }
+
+    fn role_descriptor_type_mut(&mut self) -> &mut RoleDescriptorType {
+        self.sso_descriptor_type.role_descriptor_type_mut()
+    }
+
+    fn into_xml_object(self: Box<Self>) -> Result<XmlObject, SAMLError> {
+        XmlObject::try_from(*self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for IDPSSODescriptor {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut idp_sso_descriptor = IDPSSODescriptor {
+            sso_descriptor_type: SSODescriptorType::from_element(
+                &element,
+                &[IDPSSODescriptor::ATTRIB_WANT_AUTHN_REQUESTS_SIGNED],
+            )?,
+            ..Default::default()
+        };
+        if let Some(value) = element.attribute(IDPSSODescriptor::ATTRIB_WANT_AUTHN_REQUESTS_SIGNED)
+        {
+            idp_sso_descriptor.set_want_authn_requests_signed(Some(parse_from_string(value)?));
+        }
+        for child in element.children() {
+            let child = child.borrow();
+            match child.q_name().local_name() {
+                IDPSSODescriptor::CHILD_SINGLE_SIGN_ON_SERVICE => {
+                    idp_sso_descriptor.add_single_sign_on_service(Endpoint::try_from(child)?);
+                }
+                IDPSSODescriptor::CHILD_NAME_ID_MAPPING_SERVICE => {
+                    idp_sso_descriptor.add_name_id_mapping_service(Endpoint::try_from(child)?);
+                }
+                IDPSSODescriptor::CHILD_ASSERTION_ID_REQUEST_SERVICE => {
+                    idp_sso_descriptor.add_assertion_id_request_service(Endpoint::try_from(child)?);
+                }
+                IDPSSODescriptor::CHILD_ATTRIBUTE_PROFILE => {
+                    if let Some(attribute_profile) = child.text() {
+                        idp_sso_descriptor
+                            .add_attribute_profile(attribute_profile.trim().to_string());
+                    }
+                }
+                IDPSSODescriptor::CHILD_ATTRIBUTE => {
+                    idp_sso_descriptor.add_attribute(Attribute::try_from(child)?);
+                }
+                _ => {}
+            }
+        }
+        if idp_sso_descriptor.single_sign_on_services.is_empty() {
+            return Err(SAMLError::UnmarshallingError(
+                "IDPSSODescriptor must have a SingleSignOnService".to_string(),
+            ));
+        }
+        Ok(idp_sso_descriptor)
+    }
+}
+
+impl TryFrom<IDPSSODescriptor> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(idp_sso_descriptor: IDPSSODescriptor) -> Result<Self, Self::Error> {
+        let mut xml_object = idp_sso_descriptor
+            .sso_descriptor_type
+            .into_xml_object(IDPSSODescriptor::ELEMENT_NAME)?;
+        if let Some(want_authn_requests_signed) = idp_sso_descriptor.want_authn_requests_signed {
+            xml_object.add_attribute(
+                IDPSSODescriptor::ATTRIB_WANT_AUTHN_REQUESTS_SIGNED.to_string(),
+                want_authn_requests_signed.to_string(),
+            );
+        }
+        for single_sign_on_service in idp_sso_descriptor.single_sign_on_services {
+            xml_object.add_child(Rc::new(RefCell::new(
+                single_sign_on_service
+                    .into_xml_object(IDPSSODescriptor::CHILD_SINGLE_SIGN_ON_SERVICE)?,
+            )));
+        }
+        for name_id_mapping_service in idp_sso_descriptor.name_id_mapping_services {
+            xml_object.add_child(Rc::new(RefCell::new(
+                name_id_mapping_service
+                    .into_xml_object(IDPSSODescriptor::CHILD_NAME_ID_MAPPING_SERVICE)?,
+            )));
+        }
+        for assertion_id_request_service in idp_sso_descriptor.assertion_id_request_services {
+            xml_object.add_child(Rc::new(RefCell::new(
+                assertion_id_request_service
+                    .into_xml_object(IDPSSODescriptor::CHILD_ASSERTION_ID_REQUEST_SERVICE)?,
+            )));
+        }
+        for attribute_profile in idp_sso_descriptor.attribute_profiles {
+            let mut element = metadata_element(IDPSSODescriptor::CHILD_ATTRIBUTE_PROFILE);
+            element.set_text(Some(attribute_profile));
+            xml_object.add_child(Rc::new(RefCell::new(element)));
+        }
+        for attribute in idp_sso_descriptor.attributes {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(attribute)?)));
+        }
+        Ok(xml_object)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        metadata::{entity_descriptor::EntityDescriptor, key_type::KeyType},
+        signature::credential::Credential,
+        util::InputStream,
+        xml::XmlObject,
+    };
+
+    const IDP_METADATA: &str = include_str!("../../tests/resources/idp_metadata.xml");
+    const CERTIFICATE: &str = include_str!("../../tests/resources/rsa_certificate.pem");
+
+    fn parse(xml: &str) -> EntityDescriptor {
+        let root = XmlObject::parse_xml(InputStream::new(xml.as_bytes().to_vec())).unwrap();
+        let entity_descriptor = EntityDescriptor::try_from(root.borrow()).unwrap();
+        entity_descriptor
+    }
+
+    fn check(entity_descriptor: &EntityDescriptor) {
+        assert_eq!(entity_descriptor.role_descriptors().len(), 1);
+        assert_eq!(entity_descriptor.sp_sso_descriptors().count(), 0);
+        let idp = entity_descriptor.idp_sso_descriptors().next().unwrap();
+        assert_eq!(idp.want_authn_requests_signed(), Some(true));
+        assert_eq!(
+            idp.single_sign_on_service("urn:oasis:names:tc:SAML:2.0:bindings:HTTP-POST")
+                .unwrap()
+                .location(),
+            "https://idp.example.org/SAML2/SSO/POST"
+        );
+        assert!(idp
+            .single_sign_on_service("urn:oasis:names:tc:SAML:2.0:bindings:PAOS")
+            .is_none());
+        assert_eq!(idp.attributes()[0].friendly_name().unwrap(), "mail");
+
+        let sso = idp.sso_descriptor_type();
+        assert_eq!(sso.name_id_formats().len(), 2);
+        let slo = sso
+            .single_logout_service("urn:oasis:names:tc:SAML:2.0:bindings:HTTP-Redirect")
+            .unwrap();
+        assert_eq!(
+            slo.response_location().unwrap(),
+            "https://idp.example.org/SAML2/SLO/Response"
+        );
+        assert_eq!(sso.artifact_resolution_services()[0].index(), 0);
+
+        let role = sso.role_descriptor_type();
+        assert!(role.supports_protocol("urn:oasis:names:tc:SAML:2.0:protocol"));
+        assert_eq!(role.key_descriptors_for(KeyType::Encryption).count(), 0);
+        let signing = role.key_descriptors_for(KeyType::Signing).next().unwrap();
+        let credentials = signing.credentials().unwrap();
+        assert_eq!(
+            credentials[0].public_key(),
+            Credential::from_certificate_pem(CERTIFICATE)
+                .unwrap()
+                .public_key()
+        );
+    }
+
+    #[test]
+    fn we_can_parse_idp_sso_descriptor() {
+        check(&parse(IDP_METADATA));
+    }
+
+    #[test]
+    fn we_can_round_trip_idp_sso_descriptor() {
+        let xml_object = XmlObject::try_from(parse(IDP_METADATA)).unwrap();
+        let xml = XmlObject::write_xml(Rc::new(RefCell::new(xml_object))).unwrap();
+        check(&parse(&xml));
+    }
+
+    #[test]
+    fn we_cannot_parse_idp_sso_descriptor_without_single_sign_on_service() {
+        let xml = IDP_METADATA
+            .lines()
+            .filter(|line| !line.contains("SingleSignOnService"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let root = XmlObject::parse_xml(InputStream::new(xml.into_bytes())).unwrap();
+        assert!(EntityDescriptor::try_from(root.borrow()).is_err());
+    }
+}
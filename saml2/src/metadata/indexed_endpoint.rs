@@ -0,0 +1,181 @@
+use std::cell::Ref;
+
+use crate::{common::SAML2Obj, error::SAMLError, util::AttributeMap, xml::XmlObject};
+
+use super::{metadata_element, parse_from_string};
+
+/// `md:IndexedEndpointType`, an endpoint a request may refer to by its `index`, e.g. an
+/// `AssertionConsumerService`
+#[derive(Debug, Default)]
+pub struct IndexedEndpoint {
+    binding: String,
+    location: String,
+    response_location: Option<String>,
+    index: usize,
+    is_default: Option<bool>,
+    unknown_attributes: AttributeMap,
+}
+
+impl SAML2Obj for IndexedEndpoint {}
+
+impl IndexedEndpoint {
+    const ATTRIB_BINDING: &'static str = "Binding";
+    const ATTRIB_LOCATION: &'static str = "Location";
+    const ATTRIB_RESPONSE_LOCATION: &'static str = "ResponseLocation";
+    const ATTRIB_INDEX: &'static str = "index";
+    const ATTRIB_IS_DEFAULT: &'static str = "isDefault";
+
+    pub fn new(binding: String, location: String, index: usize) -> Self {
+        IndexedEndpoint {
+            binding,
+            location,
+            index,
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    pub fn binding(&self) -> &str {
+        &self.binding
+    }
+
+    #[inline]
+    pub fn set_binding(&mut self, binding: String) {
+        self.binding = binding;
+    }
+
+    #[inline]
+    pub fn location(&self) -> &str {
+        &self.location
+    }
+
+    #[inline]
+    pub fn set_location(&mut self, location: String) {
+        self.location = location;
+    }
+
+    /// where responses go when they are not sent to `location`
+    #[inline]
+    pub fn response_location(&self) -> Option<&String> {
+        self.response_location.as_ref()
+    }
+
+    #[inline]
+    pub fn set_response_location(&mut self, response_location: Option<String>) {
+        self.response_location = response_location;
+    }
+
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    #[inline]
+    pub fn set_index(&mut self, index: usize) {
+        self.index = index;
+    }
+
+    #[inline]
+    pub fn is_default(&self) -> Option<bool> {
+        self.is_default
+    }
+
+    #[inline]
+    pub fn set_is_default(&mut self, is_default: Option<bool>) {
+        self.is_default = is_default;
+    }
+
+    #[inline]
+    pub fn unknown_attributes(&self) -> &AttributeMap {
+        &self.unknown_attributes
+    }
+
+    #[inline]
+    pub fn unknown_attributes_mut(&mut self) -> &mut AttributeMap {
+        &mut self.unknown_attributes
+    }
+
+    /// serialize the endpoint as the metadata element `local_name`
+    pub fn into_xml_object(self, local_name: &str) -> Result<XmlObject, SAMLError> {
+        let mut xml_object = metadata_element(local_name);
+        xml_object.add_attribute(IndexedEndpoint::ATTRIB_BINDING.to_string(), self.binding);
+        xml_object.add_attribute(IndexedEndpoint::ATTRIB_LOCATION.to_string(), self.location);
+        if let Some(response_location) = self.response_location {
+            xml_object.add_attribute(
+                IndexedEndpoint::ATTRIB_RESPONSE_LOCATION.to_string(),
+                response_location,
+            );
+        }
+        xml_object.add_attribute(
+            IndexedEndpoint::ATTRIB_INDEX.to_string(),
+            self.index.to_string(),
+        );
+        if let Some(is_default) = self.is_default {
+            xml_object.add_attribute(
+                IndexedEndpoint::ATTRIB_IS_DEFAULT.to_string(),
+                is_default.to_string(),
+            );
+        }
+        self.unknown_attributes.write_to(&mut xml_object);
+        Ok(xml_object)
+    }
+}
+
+/// The default among `endpoints`: the first one marked `isDefault="true"`, else the first
+/// one not marked `isDefault="false"`, else the first one.
+pub fn default_endpoint<'a, I>(endpoints: I) -> Option<&'a IndexedEndpoint>
+where
+    I: IntoIterator<Item = &'a IndexedEndpoint>,
+{
+    let endpoints: Vec<&IndexedEndpoint> = endpoints.into_iter().collect();
+    endpoints
+        .iter()
+        .find(|endpoint| endpoint.is_default == Some(true))
+        .or_else(|| {
+            endpoints
+                .iter()
+                .find(|endpoint| endpoint.is_default.is_none())
+        })
+        .or_else(|| endpoints.first())
+        .copied()
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for IndexedEndpoint {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut indexed_endpoint = IndexedEndpoint::default();
+        let mut has_index = false;
+        for (key, value) in element.attributes() {
+            match key.as_str() {
+                IndexedEndpoint::ATTRIB_BINDING => indexed_endpoint.set_binding(value.to_string()),
+                IndexedEndpoint::ATTRIB_LOCATION => {
+                    indexed_endpoint.set_location(value.to_string());
+                }
+                IndexedEndpoint::ATTRIB_RESPONSE_LOCATION => {
+                    indexed_endpoint.set_response_location(Some(value.to_string()));
+                }
+                IndexedEndpoint::ATTRIB_INDEX => {
+                    indexed_endpoint.set_index(parse_from_string(value)?);
+                    has_index = true;
+                }
+                IndexedEndpoint::ATTRIB_IS_DEFAULT => {
+                    indexed_endpoint.set_is_default(Some(parse_from_string(value)?));
+                }
+                _ => {
+                    indexed_endpoint
+                        .unknown_attributes
+                        .add_attribute_of(&element, key, value);
+                }
+            }
+        }
+        if indexed_endpoint.binding.is_empty() || indexed_endpoint.location.is_empty() || !has_index
+        {
+            return Err(SAMLError::UnmarshallingError(format!(
+                "{} must have a Binding, a Location and an index",
+                element.q_name().local_name()
+            )));
+        }
+        Ok(indexed_endpoint)
+    }
+}
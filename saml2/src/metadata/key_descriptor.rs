@@ -0,0 +1,149 @@
+use std::{
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
+
+use base64::{prelude::BASE64_STANDARD, Engine};
+
+use crate::{
+    common::SAML2Obj,
+    encryption::encrypted_method::EncryptedMethod,
+    error::SAMLError,
+    signature::{credential::Credential, key_info::KeyInfo},
+    xml::XmlObject,
+};
+
+use super::{key_type::KeyType, metadata_element, parse_from_string, rename};
+
+/// `md:KeyDescriptor`, a key of a role and what it is used for
+#[derive(Debug, Default)]
+pub struct KeyDescriptor {
+    key_type: Option<KeyType>,
+    key_info: KeyInfo,
+    encryption_methods: Vec<EncryptedMethod>,
+}
+
+impl SAML2Obj for KeyDescriptor {}
+
+impl KeyDescriptor {
+    const ATTRIB_USE: &'static str = "use";
+
+    const CHILD_KEY_INFO: &'static str = "KeyInfo";
+    const CHILD_ENCRYPTION_METHOD: &'static str = "EncryptionMethod";
+
+    pub const ELEMENT_NAME: &'static str = "KeyDescriptor";
+    pub const NS_PREFIX: &'static str = "md";
+    pub const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:metadata";
+
+    pub fn new(key_type: Option<KeyType>, key_info: KeyInfo) -> Self {
+        KeyDescriptor {
+            key_type,
+            key_info,
+            encryption_methods: Vec::new(),
+        }
+    }
+
+    /// the `use` of the key, `None` for a key used both to sign and to encrypt
+    #[inline]
+    pub fn key_type(&self) -> Option<KeyType> {
+        self.key_type
+    }
+
+    #[inline]
+    pub fn set_key_type(&mut self, key_type: Option<KeyType>) {
+        self.key_type = key_type;
+    }
+
+    /// whether the key may be used for `key_type`
+    #[inline]
+    pub fn is_usable_for(&self, key_type: KeyType) -> bool {
+        self.key_type.is_none_or(|usage| usage == key_type)
+    }
+
+    #[inline]
+    pub fn key_info(&self) -> &KeyInfo {
+        &self.key_info
+    }
+
+    #[inline]
+    pub fn set_key_info(&mut self, key_info: KeyInfo) {
+        self.key_info = key_info;
+    }
+
+    /// the algorithms the role supports when a message is encrypted for it
+    #[inline]
+    pub fn encryption_methods(&self) -> &Vec<EncryptedMethod> {
+        &self.encryption_methods
+    }
+
+    #[inline]
+    pub fn add_encryption_method(&mut self, encryption_method: EncryptedMethod) {
+        self.encryption_methods.push(encryption_method);
+    }
+
+    /// the credentials of the X.509 certificates in the `KeyInfo`
+    pub fn credentials(&self) -> Result<Vec<Credential>, SAMLError> {
+        let mut credentials = Vec::new();
+        for x509_data in self.key_info.x509_datas() {
+            for certificate in x509_data.x509_certificates() {
+                let value: String = certificate
+                    .value()
+                    .chars()
+                    .filter(|c| !c.is_ascii_whitespace())
+                    .collect();
+                let der = BASE64_STANDARD.decode(value).map_err(|_| {
+                    SAMLError::CredentialError("invalid X509Certificate".to_string())
+                })?;
+                credentials.push(Credential::from_certificate_der(&der)?);
+            }
+        }
+        Ok(credentials)
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for KeyDescriptor {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut key_descriptor = KeyDescriptor::default();
+        if let Some(key_type) = element.attribute(KeyDescriptor::ATTRIB_USE) {
+            key_descriptor.set_key_type(Some(parse_from_string(key_type)?));
+        }
+        for child in element.children() {
+            let child = child.borrow();
+            match child.q_name().local_name() {
+                KeyDescriptor::CHILD_KEY_INFO => {
+                    key_descriptor.set_key_info(KeyInfo::try_from(child)?);
+                }
+                KeyDescriptor::CHILD_ENCRYPTION_METHOD => {
+                    key_descriptor.add_encryption_method(EncryptedMethod::try_from(child)?);
+                }
+                _ => {}
+            }
+        }
+        Ok(key_descriptor)
+    }
+}
+
+impl TryFrom<KeyDescriptor> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(key_descriptor: KeyDescriptor) -> Result<Self, Self::Error> {
+        let mut xml_object = metadata_element(KeyDescriptor::ELEMENT_NAME);
+        if let Some(key_type) = key_descriptor.key_type {
+            xml_object.add_attribute(KeyDescriptor::ATTRIB_USE.to_string(), key_type.to_string());
+        }
+        xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(
+            key_descriptor.key_info,
+        )?)));
+        for encryption_method in key_descriptor.encryption_methods {
+            // md:EncryptionMethod is of the xenc type but in the metadata namespace
+            let encryption_method = rename(
+                XmlObject::try_from(encryption_method)?,
+                KeyDescriptor::CHILD_ENCRYPTION_METHOD,
+            );
+            xml_object.add_child(Rc::new(RefCell::new(encryption_method)));
+        }
+        Ok(xml_object)
+    }
+}
@@ -0,0 +1,36 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{common::SAML2Obj, error::SAMLError};
+
+/// the `use` of a `KeyDescriptor`, a key without one serves both
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyType {
+    Signing,
+    Encryption,
+}
+
+impl SAML2Obj for KeyType {}
+
+impl FromStr for KeyType {
+    type Err = SAMLError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "signing" => Ok(KeyType::Signing),
+            "encryption" => Ok(KeyType::Encryption),
+            _ => Err(SAMLError::UnmarshallingError(format!(
+                "Invalid KeyType: {}",
+                s
+            ))),
+        }
+    }
+}
+
+impl Display for KeyType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeyType::Signing => write!(f, "signing"),
+            KeyType::Encryption => write!(f, "encryption"),
+        }
+    }
+}
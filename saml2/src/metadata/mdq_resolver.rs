@@ -0,0 +1,373 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{binding::encoding::url_encode, error::SAMLError};
+
+use super::{
+    entity_descriptor::EntityDescriptor,
+    metadata_resolver::{parse_metadata, MetadataIndex, MetadataResolver},
+    metadata_verifier::MetadataVerifier,
+};
+
+/// Carries an HTTP GET of `url` to a metadata responder.
+pub trait MetadataTransport {
+    /// the body of a successful response, `None` if the responder answers 404 Not Found
+    fn get(&self, url: &str) -> Result<Option<String>, SAMLError>;
+}
+
+/// A [`MetadataResolver`] asking a Metadata Query Protocol responder for one entity at a
+/// time, at `{base_url}/entities/{identifier}`.
+///
+/// Entities are looked up by their URL-encoded entityID, or as `{sha1}` and the hex
+/// digest of the entityID when resolving a `SourceID`. Only the entity queried is taken
+/// from an answer, an answer lacking it counts as not knowing it. Answers are cached for
+/// their `cacheDuration` but at most `max_cache_duration`, entities the responder does
+/// not know for `negative_cache_duration`.
+pub struct MDQResolver<'a> {
+    base_url: String,
+    transport: &'a dyn MetadataTransport,
+    verifier: Option<MetadataVerifier>,
+    max_cache_duration: Duration,
+    negative_cache_duration: Duration,
+    index: RefCell<MetadataIndex>,
+    unknown: RefCell<HashMap<String, DateTime<Utc>>>,
+}
+
+impl<'a> MDQResolver<'a> {
+    pub fn new(base_url: &str, transport: &'a dyn MetadataTransport) -> Self {
+        MDQResolver {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            transport,
+            verifier: None,
+            max_cache_duration: Duration::hours(1),
+            negative_cache_duration: Duration::minutes(10),
+            index: RefCell::new(MetadataIndex::default()),
+            unknown: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// verify each answer of the responder with `verifier`
+    pub fn with_verifier(mut self, verifier: MetadataVerifier) -> Self {
+        self.verifier = Some(verifier);
+        self
+    }
+
+    pub fn with_max_cache_duration(mut self, max_cache_duration: Duration) -> Self {
+        self.max_cache_duration = max_cache_duration;
+        self
+    }
+
+    pub fn with_negative_cache_duration(mut self, negative_cache_duration: Duration) -> Self {
+        self.negative_cache_duration = negative_cache_duration;
+        self
+    }
+
+    /// the URL querying the entity `identifier`, an entityID or a `{sha1}` identifier
+    pub fn query_url(&self, identifier: &str) -> String {
+        format!("{}/entities/{}", self.base_url, url_encode(identifier))
+    }
+
+    /// Query the responder for `identifier` unless it is known to be unknown. The entities
+    /// of the answer are indexed apart, `None` if the responder does not know `identifier`.
+    fn query(
+        &self,
+        identifier: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<MetadataIndex>, SAMLError> {
+        {
+            let mut unknown = self.unknown.borrow_mut();
+            unknown.retain(|_, expires_at| *expires_at > now);
+            if unknown.contains_key(identifier) {
+                return Ok(None);
+            }
+        }
+
+        let metadata = match self.transport.get(&self.query_url(identifier))? {
+            Some(metadata) => metadata,
+            None => {
+                self.mark_unknown(identifier, now);
+                return Ok(None);
+            }
+        };
+        let root = parse_metadata(metadata.into_bytes())?;
+        let mut answer = MetadataIndex::default();
+        answer.load(
+            root.borrow(),
+            self.verifier.as_ref(),
+            Some(now + self.max_cache_duration),
+            now,
+        )?;
+        Ok(Some(answer))
+    }
+
+    /// remember that the responder's answer to `identifier` lacked the entity
+    fn mark_unknown(&self, identifier: &str, now: DateTime<Utc>) {
+        self.unknown
+            .borrow_mut()
+            .insert(identifier.to_string(), now + self.negative_cache_duration);
+    }
+}
+
+impl MetadataResolver for MDQResolver<'_> {
+    fn resolve(&self, entity_id: &str) -> Result<Option<Rc<EntityDescriptor>>, SAMLError> {
+        let now = Utc::now();
+        if let Some(entity_descriptor) = self.index.borrow().get(entity_id, now) {
+            return Ok(Some(entity_descriptor));
+        }
+        let Some(answer) = self.query(entity_id, now)? else {
+            return Ok(None);
+        };
+        let entity_descriptor = self.index.borrow_mut().insert_from(answer, entity_id, now);
+        if entity_descriptor.is_none() {
+            self.mark_unknown(entity_id, now);
+        }
+        Ok(entity_descriptor)
+    }
+
+    fn resolve_source_id(
+        &self,
+        source_id: &[u8; 20],
+    ) -> Result<Option<Rc<EntityDescriptor>>, SAMLError> {
+        let now = Utc::now();
+        let lookup = || {
+            let index = self.index.borrow();
+            index
+                .entity_id_of(source_id)
+                .and_then(|entity_id| index.get(entity_id, now))
+        };
+        if let Some(entity_descriptor) = lookup() {
+            return Ok(Some(entity_descriptor));
+        }
+        let identifier = sha1_identifier(source_id);
+        let Some(answer) = self.query(&identifier, now)? else {
+            return Ok(None);
+        };
+        let entity_id = answer.entity_id_of(source_id).cloned();
+        let entity_descriptor = entity_id
+            .and_then(|entity_id| self.index.borrow_mut().insert_from(answer, &entity_id, now));
+        if entity_descriptor.is_none() {
+            self.mark_unknown(&identifier, now);
+        }
+        Ok(entity_descriptor)
+    }
+}
+
+/// the identifier of the entity whose entityID hashes to `source_id`
+fn sha1_identifier(source_id: &[u8; 20]) -> String {
+    let hex: String = source_id
+        .iter()
+        .map(|octet| format!("{:02x}", octet))
+        .collect();
+    format!("{{sha1}}{}", hex)
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        collections::HashMap,
+        io::{Read, Write},
+        net::{TcpListener, TcpStream},
+        sync::{Arc, Mutex},
+        thread,
+    };
+
+    use chrono::Duration;
+
+    use crate::{
+        binding::artifact::ArtifactType0004,
+        error::SAMLError,
+        metadata::{
+            metadata_resolver::MetadataResolver,
+            test_fixtures::{signing_credential, sp, verifier},
+        },
+        signature::algorithm::SignatureAlgorithm,
+    };
+
+    use super::{sha1_identifier, MDQResolver, MetadataTransport};
+
+    const SP: &str = "https://sp.example.org";
+    const UNSIGNED: &str = "https://unsigned.example.org";
+    const UNKNOWN: &str = "https://unknown.example.org";
+    const OTHER: &str = "https://other.example.org";
+
+    /// a responder on a loopback port, answering the paths it knows and 404 otherwise
+    struct StubResponder {
+        base_url: String,
+        requests: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl StubResponder {
+        fn start(documents: HashMap<String, String>) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let base_url = format!("http://{}/mdq/", listener.local_addr().unwrap());
+            let requests = Arc::new(Mutex::new(Vec::new()));
+            let log = requests.clone();
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    let mut stream = stream.unwrap();
+                    let request = read_head(&mut stream);
+                    let path = request.split(' ').nth(1).unwrap_or_default().to_string();
+                    let response = match documents.get(&path) {
+                        Some(document) => format!(
+                            "HTTP/1.1 200 OK\r\nContent-Type: application/samlmetadata+xml\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                            document.len(),
+                            document
+                        ),
+                        None => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string(),
+                    };
+                    log.lock().unwrap().push(path);
+                    stream.write_all(response.as_bytes()).unwrap();
+                }
+            });
+            StubResponder { base_url, requests }
+        }
+
+        fn requests(&self) -> Vec<String> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    fn read_head(stream: &mut TcpStream) -> String {
+        let mut head = Vec::new();
+        let mut octet = [0u8; 1];
+        while !head.ends_with(b"\r\n\r\n") && stream.read(&mut octet).unwrap() == 1 {
+            head.push(octet[0]);
+        }
+        String::from_utf8(head).unwrap()
+    }
+
+    /// a bare HTTP/1.1 client speaking to the loopback responder
+    struct LoopbackTransport;
+
+    impl MetadataTransport for LoopbackTransport {
+        fn get(&self, url: &str) -> Result<Option<String>, SAMLError> {
+            let transport_error = |e: std::io::Error| SAMLError::TransportError(e.to_string());
+            let rest = url.strip_prefix("http://").unwrap();
+            let (host, path) = rest.split_at(rest.find('/').unwrap());
+            let mut stream = TcpStream::connect(host).map_err(transport_error)?;
+            let request = format!(
+                "GET {} HTTP/1.1\r\nHost: {}\r\nAccept: application/samlmetadata+xml\r\nConnection: close\r\n\r\n",
+                path, host
+            );
+            stream
+                .write_all(request.as_bytes())
+                .map_err(transport_error)?;
+            let mut response = String::new();
+            stream
+                .read_to_string(&mut response)
+                .map_err(transport_error)?;
+            let (head, body) = response.split_once("\r\n\r\n").unwrap();
+            match head.split(' ').nth(1) {
+                Some("200") => Ok(Some(body.to_string())),
+                Some("404") => Ok(None),
+                status => Err(SAMLError::TransportError(format!(
+                    "unexpected status {:?}",
+                    status
+                ))),
+            }
+        }
+    }
+
+    fn responder() -> StubResponder {
+        let metadata = sp(SP)
+            .build_signed_xml(&signing_credential(), SignatureAlgorithm::RSA_SHA256)
+            .unwrap();
+        let sha1 = sha1_identifier(&ArtifactType0004::source_id_of(SP));
+        let mut documents = HashMap::new();
+        documents.insert(
+            "/mdq/entities/https%3A%2F%2Fsp.example.org".to_string(),
+            metadata.clone(),
+        );
+        documents.insert(format!("/mdq/entities/%7Bsha1%7D{}", &sha1[6..]), metadata);
+        let unsigned = sp(UNSIGNED).build_xml().unwrap();
+        documents.insert(
+            "/mdq/entities/https%3A%2F%2Funsigned.example.org".to_string(),
+            unsigned,
+        );
+        StubResponder::start(documents)
+    }
+
+    #[test]
+    fn we_can_resolve_entity_by_mdq() {
+        let responder = responder();
+        let resolver =
+            MDQResolver::new(&responder.base_url, &LoopbackTransport).with_verifier(verifier());
+
+        let sp = resolver.resolve(SP).unwrap().unwrap();
+        assert_eq!(sp.sp_sso_descriptors().count(), 1);
+        assert!(resolver.resolve(SP).unwrap().is_some());
+        let source_id = ArtifactType0004::source_id_of(SP);
+        assert!(resolver.resolve_source_id(&source_id).unwrap().is_some());
+        assert_eq!(
+            responder.requests(),
+            vec!["/mdq/entities/https%3A%2F%2Fsp.example.org"]
+        );
+
+        let resolver =
+            MDQResolver::new(&responder.base_url, &LoopbackTransport).with_verifier(verifier());
+        let sp = resolver.resolve_source_id(&source_id).unwrap().unwrap();
+        assert_eq!(sp.entity_id(), SP);
+        assert!(responder.requests()[1].starts_with("/mdq/entities/%7Bsha1%7D"));
+    }
+
+    #[test]
+    fn we_can_cache_unknown_entities() {
+        let responder = responder();
+        let resolver = MDQResolver::new(&responder.base_url, &LoopbackTransport);
+        assert!(resolver.resolve(UNKNOWN).unwrap().is_none());
+        assert!(resolver.resolve(UNKNOWN).unwrap().is_none());
+        let source_id = ArtifactType0004::source_id_of(UNKNOWN);
+        assert!(resolver.resolve_source_id(&source_id).unwrap().is_none());
+        assert!(resolver.resolve_source_id(&source_id).unwrap().is_none());
+        assert_eq!(responder.requests().len(), 2);
+
+        let resolver = MDQResolver::new(&responder.base_url, &LoopbackTransport)
+            .with_negative_cache_duration(Duration::zero());
+        assert!(resolver.resolve(UNKNOWN).unwrap().is_none());
+        assert!(resolver.resolve(UNKNOWN).unwrap().is_none());
+        assert_eq!(responder.requests().len(), 4);
+    }
+
+    #[test]
+    fn we_cannot_resolve_entity_from_answer_to_another_query() {
+        let mut documents = HashMap::new();
+        documents.insert(
+            "/mdq/entities/https%3A%2F%2Fsp.example.org".to_string(),
+            format!(
+                r#"<md:EntitiesDescriptor xmlns:md="urn:oasis:names:tc:SAML:2.0:metadata"><md:EntityDescriptor entityID="{}"/><md:EntityDescriptor entityID="{}"/></md:EntitiesDescriptor>"#,
+                SP, OTHER
+            ),
+        );
+        let sha1 = sha1_identifier(&ArtifactType0004::source_id_of(UNKNOWN));
+        documents.insert(
+            format!("/mdq/entities/%7Bsha1%7D{}", &sha1[6..]),
+            format!(
+                r#"<md:EntityDescriptor xmlns:md="urn:oasis:names:tc:SAML:2.0:metadata" entityID="{}"/>"#,
+                OTHER
+            ),
+        );
+        let responder = StubResponder::start(documents);
+        let resolver = MDQResolver::new(&responder.base_url, &LoopbackTransport);
+
+        assert!(resolver.resolve(SP).unwrap().is_some());
+        let source_id = ArtifactType0004::source_id_of(UNKNOWN);
+        assert!(resolver.resolve_source_id(&source_id).unwrap().is_none());
+        // the entity the answers carried besides the one queried is asked for
+        let source_id = ArtifactType0004::source_id_of(OTHER);
+        assert!(resolver.resolve_source_id(&source_id).unwrap().is_none());
+        assert!(resolver.resolve(OTHER).unwrap().is_none());
+        assert_eq!(responder.requests().len(), 4);
+    }
+
+    #[test]
+    fn we_cannot_resolve_unsigned_entity() {
+        let responder = responder();
+        let resolver =
+            MDQResolver::new(&responder.base_url, &LoopbackTransport).with_verifier(verifier());
+        assert!(matches!(
+            resolver.resolve(UNSIGNED),
+            Err(SAMLError::SignatureError(_))
+        ));
+    }
+}
@@ -0,0 +1,467 @@
+use std::{cell::RefCell, rc::Rc};
+
+use base64::{prelude::BASE64_STANDARD, Engine};
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{
+    error::SAMLError,
+    signature::{
+        credential::Credential, key_info::KeyInfo, signer::sign_enveloped,
+        x509_certificate::X509Certificate, x509_data::X509Data,
+    },
+    util::generate_id,
+    xml::XmlObject,
+};
+
+use super::{
+    attribute_consuming_service::AttributeConsumingService, contact_person::ContactPerson,
+    endpoint::Endpoint, entity_descriptor::EntityDescriptor, idp_sso_descriptor::IDPSSODescriptor,
+    indexed_endpoint::IndexedEndpoint, key_descriptor::KeyDescriptor, key_type::KeyType,
+    organization::Organization, requested_attribute::RequestedAttribute, service_name::ServiceName,
+    sp_sso_descriptor::SPSSODescriptor, sso_descriptor_type::SSODescriptorType,
+};
+
+/// Generates the `md:EntityDescriptor` of our own entity from its configuration.
+///
+/// Setting an SP or an IdP specific property adds the corresponding role. The
+/// certificates, `NameIDFormat`s and single logout endpoints are shared by the roles.
+///
+/// Endpoints take the URI of their binding, such as
+/// [`HTTP_POST_BINDING`](crate::binding::encoding::post::HTTP_POST_BINDING) or
+/// [`HTTP_REDIRECT_BINDING`](crate::binding::encoding::HTTP_REDIRECT_BINDING).
+#[derive(Debug)]
+pub struct MetadataBuilder {
+    entity_id: String,
+    id: Option<String>,
+    valid_until: Option<DateTime<Utc>>,
+    cache_duration: Option<Duration>,
+    signing_credentials: Vec<Credential>,
+    encryption_credentials: Vec<Credential>,
+    name_id_formats: Vec<String>,
+    single_logout_services: Vec<(String, String)>,
+    organization: Option<Organization>,
+    contact_persons: Vec<ContactPerson>,
+    sp_sso_descriptor: Option<SPSSODescriptor>,
+    service_names: Vec<ServiceName>,
+    requested_attributes: Vec<RequestedAttribute>,
+    idp_sso_descriptor: Option<IDPSSODescriptor>,
+}
+
+impl MetadataBuilder {
+    pub fn new(entity_id: String) -> Self {
+        MetadataBuilder {
+            entity_id,
+            id: None,
+            valid_until: None,
+            cache_duration: None,
+            signing_credentials: vec![],
+            encryption_credentials: vec![],
+            name_id_formats: vec![],
+            single_logout_services: vec![],
+            organization: None,
+            contact_persons: vec![],
+            sp_sso_descriptor: None,
+            service_names: vec![],
+            requested_attributes: vec![],
+            idp_sso_descriptor: None,
+        }
+    }
+
+    /// the `ID` of the entity descriptor, a random one is used when signing without it
+    pub fn with_id(mut self, id: String) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    pub fn with_valid_until(mut self, valid_until: DateTime<Utc>) -> Self {
+        self.valid_until = Some(valid_until);
+        self
+    }
+
+    pub fn with_cache_duration(mut self, cache_duration: Duration) -> Self {
+        self.cache_duration = Some(cache_duration);
+        self
+    }
+
+    /// publish the certificate of `credential` as a signing key
+    pub fn with_signing_credential(mut self, credential: Credential) -> Self {
+        self.signing_credentials.push(credential);
+        self
+    }
+
+    /// publish the certificate of `credential` as an encryption key
+    pub fn with_encryption_credential(mut self, credential: Credential) -> Self {
+        self.encryption_credentials.push(credential);
+        self
+    }
+
+    pub fn with_name_id_format(mut self, name_id_format: String) -> Self {
+        self.name_id_formats.push(name_id_format);
+        self
+    }
+
+    pub fn with_single_logout_service(mut self, binding: String, location: String) -> Self {
+        self.single_logout_services.push((binding, location));
+        self
+    }
+
+    pub fn with_organization(mut self, organization: Organization) -> Self {
+        self.organization = Some(organization);
+        self
+    }
+
+    pub fn with_contact_person(mut self, contact_person: ContactPerson) -> Self {
+        self.contact_persons.push(contact_person);
+        self
+    }
+
+    /// add an `AssertionConsumerService`, endpoints are indexed in the order they are
+    /// added and the first one is the default
+    pub fn with_assertion_consumer_service(mut self, binding: String, location: String) -> Self {
+        let sp_sso_descriptor = self.sp_sso_descriptor();
+        let index = sp_sso_descriptor.assertion_consumer_services().len();
+        let mut endpoint = IndexedEndpoint::new(binding, location, index);
+        if index == 0 {
+            endpoint.set_is_default(Some(true));
+        }
+        sp_sso_descriptor.add_assertion_consumer_service(endpoint);
+        self
+    }
+
+    pub fn with_authn_requests_signed(mut self, authn_requests_signed: bool) -> Self {
+        self.sp_sso_descriptor()
+            .set_authn_requests_signed(Some(authn_requests_signed));
+        self
+    }
+
+    pub fn with_want_assertions_signed(mut self, want_assertions_signed: bool) -> Self {
+        self.sp_sso_descriptor()
+            .set_want_assertions_signed(Some(want_assertions_signed));
+        self
+    }
+
+    /// a name of the `AttributeConsumingService` carrying the requested attributes
+    pub fn with_service_name(mut self, service_name: ServiceName) -> Self {
+        self.sp_sso_descriptor();
+        self.service_names.push(service_name);
+        self
+    }
+
+    pub fn with_requested_attribute(mut self, requested_attribute: RequestedAttribute) -> Self {
+        self.sp_sso_descriptor();
+        self.requested_attributes.push(requested_attribute);
+        self
+    }
+
+    pub fn with_single_sign_on_service(mut self, binding: String, location: String) -> Self {
+        self.idp_sso_descriptor()
+            .add_single_sign_on_service(Endpoint::new(binding, location));
+        self
+    }
+
+    pub fn with_want_authn_requests_signed(mut self, want_authn_requests_signed: bool) -> Self {
+        self.idp_sso_descriptor()
+            .set_want_authn_requests_signed(Some(want_authn_requests_signed));
+        self
+    }
+
+    fn sp_sso_descriptor(&mut self) -> &mut SPSSODescriptor {
+        self.sp_sso_descriptor
+            .get_or_insert_with(SPSSODescriptor::new)
+    }
+
+    fn idp_sso_descriptor(&mut self) -> &mut IDPSSODescriptor {
+        self.idp_sso_descriptor
+            .get_or_insert_with(IDPSSODescriptor::new)
+    }
+
+    /// Build the entity descriptor. It fails if no role is configured, if a role misses
+    /// its required endpoints or if a credential has no certificate to publish.
+    pub fn build(self) -> Result<EntityDescriptor, SAMLError> {
+        let key_descriptors = self.key_descriptors()?;
+        let mut entity_descriptor = EntityDescriptor::new(self.entity_id);
+        entity_descriptor.set_id(self.id);
+        entity_descriptor.set_valid_until(self.valid_until);
+        entity_descriptor.set_cache_duration(self.cache_duration);
+
+        if self.idp_sso_descriptor.is_none() && self.sp_sso_descriptor.is_none() {
+            return Err(SAMLError::MetadataError(
+                "metadata needs an SP or an IdP role".to_string(),
+            ));
+        }
+        if let Some(mut idp_sso_descriptor) = self.idp_sso_descriptor {
+            if idp_sso_descriptor.single_sign_on_services().is_empty() {
+                return Err(SAMLError::MetadataError(
+                    "IdP role needs a SingleSignOnService".to_string(),
+                ));
+            }
+            Self::fill_sso_descriptor_type(
+                idp_sso_descriptor.sso_descriptor_type_mut(),
+                &key_descriptors,
+                &self.name_id_formats,
+                &self.single_logout_services,
+            );
+            entity_descriptor.add_role_descriptor(Box::new(idp_sso_descriptor));
+        }
+        if let Some(mut sp_sso_descriptor) = self.sp_sso_descriptor {
+            if sp_sso_descriptor.assertion_consumer_services().is_empty() {
+                return Err(SAMLError::MetadataError(
+                    "SP role needs an AssertionConsumerService".to_string(),
+                ));
+            }
+            if !self.requested_attributes.is_empty() {
+                if self.service_names.is_empty() {
+                    return Err(SAMLError::MetadataError(
+                        "requested attributes need a ServiceName".to_string(),
+                    ));
+                }
+                let mut attribute_consuming_service = AttributeConsumingService::new(0);
+                attribute_consuming_service.set_is_default(Some(true));
+                for service_name in self.service_names {
+                    attribute_consuming_service.add_service_name(service_name);
+                }
+                for requested_attribute in self.requested_attributes {
+                    attribute_consuming_service.add_requested_attribute(requested_attribute);
+                }
+                sp_sso_descriptor.add_attribute_consuming_service(attribute_consuming_service);
+            }
+            Self::fill_sso_descriptor_type(
+                sp_sso_descriptor.sso_descriptor_type_mut(),
+                &key_descriptors,
+                &self.name_id_formats,
+                &self.single_logout_services,
+            );
+            entity_descriptor.add_role_descriptor(Box::new(sp_sso_descriptor));
+        }
+
+        entity_descriptor.set_organization(self.organization);
+        for contact_person in self.contact_persons {
+            entity_descriptor.add_contact_person(contact_person);
+        }
+        Ok(entity_descriptor)
+    }
+
+    /// build the entity descriptor and serialize it
+    pub fn build_xml(self) -> Result<String, SAMLError> {
+        let xml_object = XmlObject::try_from(self.build()?)?;
+        Self::write_xml(xml_object)
+    }
+
+    /// build the entity descriptor, sign it with an enveloped signature made with the
+    /// private key of `credential` and serialize it
+    pub fn build_signed_xml(
+        mut self,
+        credential: &Credential,
+        algorithm: &str,
+    ) -> Result<String, SAMLError> {
+        if self.id.is_none() {
+            self.id = Some(generate_id());
+        }
+        let mut xml_object = XmlObject::try_from(self.build()?)?;
+        sign_enveloped(&mut xml_object, credential, algorithm)?;
+        Self::write_xml(xml_object)
+    }
+
+    fn write_xml(xml_object: XmlObject) -> Result<String, SAMLError> {
+        XmlObject::write_xml(Rc::new(RefCell::new(xml_object)))
+            .map_err(|_| SAMLError::MetadataError("invalid xml format!".to_string()))
+    }
+
+    /// the certificates as `(use, base64 DER)`, roles get their own `KeyDescriptor`s
+    fn key_descriptors(&self) -> Result<Vec<(KeyType, String)>, SAMLError> {
+        let signing = self
+            .signing_credentials
+            .iter()
+            .map(|credential| (KeyType::Signing, credential));
+        let encryption = self
+            .encryption_credentials
+            .iter()
+            .map(|credential| (KeyType::Encryption, credential));
+        signing
+            .chain(encryption)
+            .map(|(key_type, credential)| {
+                let certificate = credential.certificate().ok_or_else(|| {
+                    SAMLError::CredentialError(format!(
+                        "{} credential has no certificate to publish",
+                        key_type
+                    ))
+                })?;
+                Ok((key_type, BASE64_STANDARD.encode(certificate)))
+            })
+            .collect()
+    }
+
+    fn fill_sso_descriptor_type(
+        sso_descriptor_type: &mut SSODescriptorType,
+        key_descriptors: &[(KeyType, String)],
+        name_id_formats: &[String],
+        single_logout_services: &[(String, String)],
+    ) {
+        for (binding, location) in single_logout_services {
+            sso_descriptor_type
+                .add_single_logout_service(Endpoint::new(binding.clone(), location.clone()));
+        }
+        for name_id_format in name_id_formats {
+            sso_descriptor_type.add_name_id_format(name_id_format.clone());
+        }
+        let role_descriptor_type = sso_descriptor_type.role_descriptor_type_mut();
+        for (key_type, certificate) in key_descriptors {
+            let mut x509_data = X509Data::default();
+            x509_data.add_x509_certificate(X509Certificate::new(certificate.clone()));
+            let mut key_info = KeyInfo::default();
+            key_info.add_x509_data(x509_data);
+            role_descriptor_type.add_key_descriptor(KeyDescriptor::new(Some(*key_type), key_info));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::Duration;
+
+    use crate::{
+        binding::encoding::{post::HTTP_POST_BINDING, HTTP_REDIRECT_BINDING},
+        core::attribute::Attribute,
+        error::SAMLError,
+        metadata::{
+            entity_descriptor::EntityDescriptor,
+            key_type::KeyType,
+            requested_attribute::RequestedAttribute,
+            service_name::ServiceName,
+            test_fixtures::{signing_credential, CERTIFICATE, PRIVATE_KEY},
+        },
+        signature::{
+            algorithm::SignatureAlgorithm, credential::Credential,
+            validator::validate_enveloped_signature,
+        },
+        util::InputStream,
+        xml::XmlObject,
+    };
+
+    use super::MetadataBuilder;
+
+    const PERSISTENT: &str = "urn:oasis:names:tc:SAML:2.0:nameid-format:persistent";
+
+    fn sp_builder() -> MetadataBuilder {
+        let mut requested_attribute =
+            Attribute::new("urn:oid:0.9.2342.19200300.100.1.3".to_string());
+        requested_attribute.set_friendly_name(Some("mail".to_string()));
+        MetadataBuilder::new("https://sp.example.org".to_string())
+            .with_cache_duration(Duration::hours(6))
+            .with_signing_credential(signing_credential())
+            .with_encryption_credential(Credential::from_certificate_pem(CERTIFICATE).unwrap())
+            .with_name_id_format(PERSISTENT.to_string())
+            .with_single_logout_service(
+                HTTP_REDIRECT_BINDING.to_string(),
+                "https://sp.example.org/slo".to_string(),
+            )
+            .with_assertion_consumer_service(
+                HTTP_POST_BINDING.to_string(),
+                "https://sp.example.org/acs/post".to_string(),
+            )
+            .with_assertion_consumer_service(
+                HTTP_REDIRECT_BINDING.to_string(),
+                "https://sp.example.org/acs/redirect".to_string(),
+            )
+            .with_authn_requests_signed(true)
+            .with_service_name(ServiceName::new("en".to_string(), "Example SP".to_string()))
+            .with_requested_attribute(RequestedAttribute::new(requested_attribute, Some(true)))
+    }
+
+    fn parse(xml: &str) -> EntityDescriptor {
+        let root = XmlObject::parse_xml(InputStream::new(xml.as_bytes().to_vec())).unwrap();
+        let entity_descriptor = EntityDescriptor::try_from(root.borrow()).unwrap();
+        entity_descriptor
+    }
+
+    #[test]
+    fn we_can_generate_sp_metadata() {
+        let entity_descriptor = parse(&sp_builder().build_xml().unwrap());
+        assert_eq!(entity_descriptor.entity_id(), "https://sp.example.org");
+        assert_eq!(
+            entity_descriptor.cache_duration(),
+            Some(&Duration::hours(6))
+        );
+        assert!(entity_descriptor.signature().is_none());
+        assert_eq!(entity_descriptor.idp_sso_descriptors().count(), 0);
+
+        let sp_sso_descriptor = entity_descriptor.sp_sso_descriptors().next().unwrap();
+        assert_eq!(sp_sso_descriptor.authn_requests_signed(), Some(true));
+        let acs = sp_sso_descriptor
+            .default_assertion_consumer_service()
+            .unwrap();
+        assert_eq!(acs.binding(), HTTP_POST_BINDING);
+        assert_eq!(
+            sp_sso_descriptor.assertion_consumer_services()[1].index(),
+            1
+        );
+        let attribute_consuming_service =
+            sp_sso_descriptor.attribute_consuming_service(None).unwrap();
+        assert_eq!(
+            attribute_consuming_service.service_names()[0].value(),
+            "Example SP"
+        );
+        let requested_attribute = &attribute_consuming_service.requested_attributes()[0];
+        assert_eq!(requested_attribute.is_required(), Some(true));
+        assert_eq!(
+            requested_attribute.attribute().friendly_name().unwrap(),
+            "mail"
+        );
+
+        let sso_descriptor_type = sp_sso_descriptor.sso_descriptor_type();
+        assert_eq!(sso_descriptor_type.name_id_formats()[0], PERSISTENT);
+        assert!(sso_descriptor_type
+            .single_logout_service(HTTP_REDIRECT_BINDING)
+            .is_some());
+        let role_descriptor_type = sso_descriptor_type.role_descriptor_type();
+        let expected = Credential::from_certificate_pem(CERTIFICATE).unwrap();
+        for key_type in [KeyType::Signing, KeyType::Encryption] {
+            let key_descriptors: Vec<_> =
+                role_descriptor_type.key_descriptors_for(key_type).collect();
+            assert_eq!(key_descriptors.len(), 1);
+            let credentials = key_descriptors[0].credentials().unwrap();
+            assert_eq!(credentials[0].public_key(), expected.public_key());
+        }
+    }
+
+    #[test]
+    fn we_can_generate_signed_idp_metadata() {
+        let xml = MetadataBuilder::new("https://idp.example.org".to_string())
+            .with_signing_credential(signing_credential())
+            .with_single_sign_on_service(
+                HTTP_REDIRECT_BINDING.to_string(),
+                "https://idp.example.org/sso".to_string(),
+            )
+            .with_want_authn_requests_signed(true)
+            .build_signed_xml(&signing_credential(), SignatureAlgorithm::RSA_SHA256)
+            .unwrap();
+
+        let root = XmlObject::parse_xml(InputStream::new(xml.as_bytes().to_vec())).unwrap();
+        let verifier = Credential::from_certificate_pem(CERTIFICATE).unwrap();
+        validate_enveloped_signature(&root.borrow(), &verifier).unwrap();
+        let entity_descriptor = EntityDescriptor::try_from(root.borrow()).unwrap();
+        assert!(entity_descriptor.id().is_some());
+        assert!(entity_descriptor.signature().is_some());
+        let idp_sso_descriptor = entity_descriptor.idp_sso_descriptors().next().unwrap();
+        assert_eq!(idp_sso_descriptor.want_authn_requests_signed(), Some(true));
+        assert!(idp_sso_descriptor
+            .single_sign_on_service(HTTP_REDIRECT_BINDING)
+            .is_some());
+    }
+
+    #[test]
+    fn we_cannot_generate_incomplete_metadata() {
+        let no_role = MetadataBuilder::new("https://sp.example.org".to_string()).build();
+        assert!(matches!(no_role, Err(SAMLError::MetadataError(_))));
+
+        let no_acs = MetadataBuilder::new("https://sp.example.org".to_string())
+            .with_want_assertions_signed(true)
+            .build();
+        assert!(matches!(no_acs, Err(SAMLError::MetadataError(_))));
+
+        let no_certificate = sp_builder()
+            .with_signing_credential(Credential::from_private_key_pem(PRIVATE_KEY).unwrap())
+            .build();
+        assert!(matches!(no_certificate, Err(SAMLError::CredentialError(_))));
+    }
+}
@@ -0,0 +1,617 @@
+use std::{cell::RefCell, collections::VecDeque, io::Read, mem, rc::Rc};
+
+use chrono::{DateTime, Duration, Utc};
+use sha2::digest::DynDigest;
+use xml::{
+    reader::{Events, ParserConfig, XmlEvent},
+    EventReader,
+};
+
+use crate::{
+    error::SAMLError,
+    signature::{
+        signature::Signature,
+        validator::{verify_signed_info, EnvelopedReference, ID_ATTRIBUTES},
+    },
+    xml::{
+        c14n::{Canonicalizer, StreamCanonicalizer},
+        XmlObject, XmlTreeBuilder,
+    },
+};
+
+use super::{
+    entities_descriptor::EntitiesDescriptor, entity_descriptor::EntityDescriptor,
+    metadata_verifier::MetadataVerifier, parse_duration, parse_from_string,
+};
+
+const ATTRIB_NAME: &str = "Name";
+const ATTRIB_VALID_UNTIL: &str = "validUntil";
+const ATTRIB_CACHE_DURATION: &str = "cacheDuration";
+
+/// where the reader is with the signature of the aggregate
+enum SignatureState {
+    /// the first child of the aggregate is not read yet, the start tag of the aggregate
+    /// and the character data before that child wait for it
+    Pending { root: XmlObject, text: String },
+    /// the `ds:Signature` element is being read
+    Reading {
+        root: XmlObject,
+        text: String,
+        builder: XmlTreeBuilder,
+    },
+    /// the aggregate is not signed or its signature is not verified
+    Unchecked,
+    /// the signed content is canonicalized and digested as it is read
+    Digesting {
+        canonicalizer: StreamCanonicalizer<'static>,
+        hasher: Box<dyn DynDigest + Send + Sync>,
+        reference: EnvelopedReference,
+    },
+}
+
+/// which entities the reader yields before the end tag of the aggregate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Yielding {
+    /// entities are held back when there is a verifier
+    Default,
+    VerifiedOnly,
+    Unverified,
+}
+
+/// Reads an `md:EntitiesDescriptor` aggregate one `EntityDescriptor` at a time, without
+/// building the tree of the whole aggregate.
+///
+/// With a verifier, the enveloped signature of the aggregate is checked while reading:
+/// `SignedInfo` is verified as soon as the signature is read and the digest of the
+/// aggregate is computed incrementally and compared at its end tag. The `validUntil`
+/// of the aggregate, of nested groups and of entities is enforced as `MetadataVerifier`
+/// does. The entities are then held back until the digest is checked, unless the reader
+/// is made to [`yield_unverified`](MetadataReader::yield_unverified) entities, which
+/// keeps a single entity in memory however large the aggregate is.
+pub struct MetadataReader<'a, R: Read> {
+    events: Events<R>,
+    verifier: Option<&'a MetadataVerifier>,
+    now: DateTime<Utc>,
+    depth: usize,
+    signature: SignatureState,
+    /// the depth and `validUntil` of the groups open at the current position
+    groups: Vec<(usize, Option<DateTime<Utc>>)>,
+    entity: Option<XmlTreeBuilder>,
+    name: Option<String>,
+    valid_until: Option<DateTime<Utc>>,
+    cache_duration: Option<Duration>,
+    yielding: Yielding,
+    /// the entities read so far while they are held back
+    held: VecDeque<EntityDescriptor>,
+    finished: bool,
+}
+
+impl<'a, R: Read> MetadataReader<'a, R> {
+    pub fn new(input: R) -> Self {
+        let reader =
+            EventReader::new_with_config(input, ParserConfig::new().ignore_comments(false));
+        MetadataReader {
+            events: reader.into_iter(),
+            verifier: None,
+            now: Utc::now(),
+            depth: 0,
+            signature: SignatureState::Unchecked,
+            groups: Vec::new(),
+            entity: None,
+            name: None,
+            valid_until: None,
+            cache_duration: None,
+            yielding: Yielding::Default,
+            held: VecDeque::new(),
+            finished: false,
+        }
+    }
+
+    /// Verify the signature and the validity of the aggregate with `verifier`.
+    ///
+    /// The entities are held back until the end tag of the aggregate, where its digest is
+    /// checked, so only entities of a verified aggregate are yielded. They are all kept in
+    /// memory until then, a failure yields the error alone.
+    pub fn with_verifier(mut self, verifier: &'a MetadataVerifier) -> Self {
+        self.verifier = Some(verifier);
+        self
+    }
+
+    /// hold the entities back until the aggregate is verified, which is what a reader with
+    /// a verifier does anyway. Reading fails without a verifier.
+    pub fn verified_only(mut self) -> Self {
+        self.yielding = Yielding::VerifiedOnly;
+        self
+    }
+
+    /// Yield each entity as soon as it is read, before the digest of the aggregate is
+    /// known, so a single entity is held in memory. The entities are only trustworthy
+    /// once the reader ends without an error, a tampered aggregate yields the entities
+    /// read before the error.
+    pub fn yield_unverified(mut self) -> Self {
+        self.yielding = Yielding::Unverified;
+        self
+    }
+
+    fn holds_back(&self) -> bool {
+        match self.yielding {
+            Yielding::Default => self.verifier.is_some(),
+            Yielding::VerifiedOnly => true,
+            Yielding::Unverified => false,
+        }
+    }
+
+    /// the `Name` of the aggregate, known once the first entity is read
+    #[inline]
+    pub fn name(&self) -> Option<&String> {
+        self.name.as_ref()
+    }
+
+    #[inline]
+    pub fn valid_until(&self) -> Option<&DateTime<Utc>> {
+        self.valid_until.as_ref()
+    }
+
+    #[inline]
+    pub fn cache_duration(&self) -> Option<&Duration> {
+        self.cache_duration.as_ref()
+    }
+
+    fn handle(&mut self, event: XmlEvent) -> Result<Option<EntityDescriptor>, SAMLError> {
+        match event {
+            XmlEvent::StartElement {
+                name,
+                attributes,
+                namespace,
+            } => {
+                let element = XmlObject::from_start_element(&name, &attributes, &namespace);
+                self.start_element(element)?;
+                Ok(None)
+            }
+            XmlEvent::EndElement { .. } => self.end_element(),
+            XmlEvent::Characters(s) | XmlEvent::Whitespace(s) | XmlEvent::CData(s) => {
+                self.characters(&s)?;
+                Ok(None)
+            }
+            XmlEvent::Comment(s) => {
+                if let Some(builder) = &mut self.entity {
+                    builder.comment(s);
+                }
+                Ok(None)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn start_element(&mut self, element: XmlObject) -> Result<(), SAMLError> {
+        self.depth += 1;
+        if self.depth == 1 {
+            return self.start_aggregate(element);
+        }
+        if self.depth == 2 && Signature::is_signature_element(&element) {
+            match mem::replace(&mut self.signature, SignatureState::Unchecked) {
+                SignatureState::Pending { root, text } => {
+                    self.signature = SignatureState::Reading {
+                        root,
+                        text,
+                        builder: XmlTreeBuilder::default(),
+                    };
+                }
+                _ if self.verifier.is_some() => {
+                    return Err(SAMLError::SignatureError(
+                        "signature must be the first child of the aggregate".to_string(),
+                    ))
+                }
+                state => self.signature = state,
+            }
+        } else if self.depth == 2 {
+            if let SignatureState::Pending { .. } = self.signature {
+                self.unsigned()?;
+            }
+        }
+
+        match &mut self.signature {
+            SignatureState::Reading { builder, .. } => {
+                builder.start(element);
+                return Ok(());
+            }
+            SignatureState::Digesting {
+                canonicalizer,
+                hasher,
+                ..
+            } => {
+                let mut output = String::new();
+                canonicalizer.start_element(&element, &mut output);
+                hasher.update(output.as_bytes());
+            }
+            _ => {}
+        }
+
+        // only the children of the innermost group are entities or groups, not those
+        // nested in `md:Extensions` or in any other element of a group
+        let child_of_group = self
+            .groups
+            .last()
+            .is_some_and(|(group_depth, _)| *group_depth == self.depth - 1);
+        if let Some(builder) = &mut self.entity {
+            builder.start(element);
+        } else if child_of_group && is_metadata_element(&element, EntityDescriptor::ELEMENT_NAME) {
+            let mut builder = XmlTreeBuilder::default();
+            builder.start(element);
+            self.entity = Some(builder);
+        } else if child_of_group && is_metadata_element(&element, EntitiesDescriptor::ELEMENT_NAME)
+        {
+            let valid_until = match element.attribute(ATTRIB_VALID_UNTIL) {
+                Some(value) => Some(parse_from_string(value)?),
+                None => None,
+            };
+            self.groups.push((self.depth, valid_until));
+        }
+        Ok(())
+    }
+
+    fn start_aggregate(&mut self, root: XmlObject) -> Result<(), SAMLError> {
+        if self.yielding == Yielding::VerifiedOnly && self.verifier.is_none() {
+            return Err(SAMLError::MetadataError(
+                "only verified entities are yielded but there is no verifier".to_string(),
+            ));
+        }
+        if !is_metadata_element(&root, EntitiesDescriptor::ELEMENT_NAME) {
+            return Err(SAMLError::MetadataError(format!(
+                "{} is not a metadata aggregate",
+                root.q_name().local_name()
+            )));
+        }
+        self.name = root.attribute(ATTRIB_NAME).map(|name| name.to_string());
+        if let Some(value) = root.attribute(ATTRIB_VALID_UNTIL) {
+            self.valid_until = Some(parse_from_string(value)?);
+        }
+        if let Some(value) = root.attribute(ATTRIB_CACHE_DURATION) {
+            self.cache_duration = Some(parse_duration(value)?);
+        }
+        if let Some(verifier) = self.verifier {
+            verifier.check_valid_until(self.valid_until.as_ref(), self.now)?;
+        }
+        self.groups.push((self.depth, self.valid_until));
+        self.signature = SignatureState::Pending {
+            root,
+            text: String::new(),
+        };
+        Ok(())
+    }
+
+    fn end_element(&mut self) -> Result<Option<EntityDescriptor>, SAMLError> {
+        let depth = self.depth;
+        self.depth -= 1;
+        match &mut self.signature {
+            SignatureState::Reading { builder, .. } => {
+                if let Some(signature_object) = builder.end().map_err(xml_error)? {
+                    self.read_signature(signature_object)?;
+                }
+                return Ok(None);
+            }
+            SignatureState::Digesting {
+                canonicalizer,
+                hasher,
+                ..
+            } => {
+                let mut output = String::new();
+                canonicalizer.end_element(&mut output);
+                hasher.update(output.as_bytes());
+            }
+            SignatureState::Pending { .. } => self.unsigned()?,
+            SignatureState::Unchecked => {}
+        }
+
+        if depth == 1 {
+            if let SignatureState::Digesting {
+                hasher, reference, ..
+            } = mem::replace(&mut self.signature, SignatureState::Unchecked)
+            {
+                reference.check_digest(&hasher.finalize())?;
+            }
+            self.finished = true;
+            return Ok(None);
+        }
+        if let Some(builder) = &mut self.entity {
+            return match builder.end().map_err(xml_error)? {
+                Some(entity_object) => {
+                    self.entity = None;
+                    self.read_entity(entity_object)
+                }
+                None => Ok(None),
+            };
+        }
+        if self
+            .groups
+            .last()
+            .is_some_and(|(group_depth, _)| *group_depth == depth)
+        {
+            self.groups.pop();
+        }
+        Ok(None)
+    }
+
+    fn characters(&mut self, data: &str) -> Result<(), SAMLError> {
+        match &mut self.signature {
+            SignatureState::Pending { text, .. } => text.push_str(data),
+            SignatureState::Reading { builder, .. } => {
+                return builder.characters(data).map_err(xml_error);
+            }
+            SignatureState::Digesting {
+                canonicalizer,
+                hasher,
+                ..
+            } => {
+                let mut output = String::new();
+                canonicalizer.characters(data, &mut output);
+                hasher.update(output.as_bytes());
+            }
+            SignatureState::Unchecked => {}
+        }
+        if let Some(builder) = &mut self.entity {
+            builder.characters(data).map_err(xml_error)?;
+        }
+        Ok(())
+    }
+
+    /// the first child of the aggregate is not a signature
+    fn unsigned(&mut self) -> Result<(), SAMLError> {
+        self.signature = SignatureState::Unchecked;
+        match self.verifier {
+            Some(verifier) if !verifier.policy().allow_unsigned() => Err(
+                SAMLError::SignatureError("metadata is not signed".to_string()),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// verify `SignedInfo` and start digesting the aggregate with the signature left out
+    fn read_signature(
+        &mut self,
+        signature_object: Rc<RefCell<XmlObject>>,
+    ) -> Result<(), SAMLError> {
+        let (root, text) = match mem::replace(&mut self.signature, SignatureState::Unchecked) {
+            SignatureState::Reading { root, text, .. } => (root, text),
+            _ => return Ok(()),
+        };
+        let verifier = match self.verifier {
+            Some(verifier) => verifier,
+            None => return Ok(()),
+        };
+        let signature = Signature::try_from(signature_object.borrow())?;
+        let element_id = ID_ATTRIBUTES
+            .iter()
+            .find_map(|key| root.attribute(key))
+            .ok_or_else(|| SAMLError::SignatureError("signed element has no ID".to_string()))?;
+        let reference = EnvelopedReference::of(&signature, element_id)?;
+        let signature_object = signature_object.borrow();
+        verify_signed_info(
+            &signature_object,
+            &signature_object,
+            &signature,
+            verifier.trust_anchor(),
+        )?;
+
+        let mut canonicalizer = StreamCanonicalizer::new(
+            Canonicalizer::new(reference.c14n_algorithm)
+                .inclusive_prefixes(reference.inclusive_prefixes.clone()),
+        );
+        let mut output = String::new();
+        canonicalizer.start_element(&root, &mut output);
+        canonicalizer.characters(&text, &mut output);
+        let mut hasher = reference.digest_algorithm.dyn_digest();
+        hasher.update(output.as_bytes());
+        self.signature = SignatureState::Digesting {
+            canonicalizer,
+            hasher,
+            reference,
+        };
+        Ok(())
+    }
+
+    /// parse an entity, it is dropped if it or one of its groups expired
+    fn read_entity(
+        &self,
+        entity_object: Rc<RefCell<XmlObject>>,
+    ) -> Result<Option<EntityDescriptor>, SAMLError> {
+        let mut entity_descriptor = EntityDescriptor::try_from(entity_object.borrow())?;
+        if let Some(verifier) = self.verifier {
+            if !verifier.policy().allow_expired() {
+                let expired = |valid_until: Option<&DateTime<Utc>>| {
+                    valid_until.is_some_and(|valid_until| *valid_until <= self.now)
+                };
+                if expired(entity_descriptor.valid_until())
+                    || self
+                        .groups
+                        .iter()
+                        .any(|(_, valid_until)| expired(valid_until.as_ref()))
+                {
+                    return Ok(None);
+                }
+                MetadataVerifier::drop_expired_roles(&mut entity_descriptor, self.now);
+            }
+        }
+        Ok(Some(entity_descriptor))
+    }
+
+    fn fail(&mut self, error: SAMLError) -> Option<Result<EntityDescriptor, SAMLError>> {
+        self.finished = true;
+        self.held.clear();
+        Some(Err(error))
+    }
+}
+
+impl<R: Read> Iterator for MetadataReader<'_, R> {
+    type Item = Result<EntityDescriptor, SAMLError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.finished {
+            let event = match self.events.next() {
+                Some(Ok(event)) => event,
+                Some(Err(e)) => return self.fail(SAMLError::MetadataError(e.to_string())),
+                None => {
+                    return self.fail(SAMLError::MetadataError(
+                        "metadata ended before the aggregate".to_string(),
+                    ))
+                }
+            };
+            match self.handle(event) {
+                Ok(Some(entity_descriptor)) => {
+                    if !self.holds_back() {
+                        return Some(Ok(entity_descriptor));
+                    }
+                    self.held.push_back(entity_descriptor);
+                }
+                Ok(None) => {}
+                Err(e) => return self.fail(e),
+            }
+        }
+        self.held.pop_front().map(Ok)
+    }
+}
+
+fn is_metadata_element(element: &XmlObject, local_name: &str) -> bool {
+    element.q_name().local_name() == local_name
+        && element.q_name().namespace_uri() == Some(super::NS_URI)
+}
+
+fn xml_error(error: crate::xml::XmlError) -> SAMLError {
+    SAMLError::MetadataError(error.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::Duration;
+
+    use crate::{
+        error::SAMLError,
+        metadata::{
+            entity_descriptor::EntityDescriptor,
+            metadata_verifier::VerificationPolicy,
+            test_fixtures::{aggregate, verifier},
+        },
+    };
+
+    use super::MetadataReader;
+
+    fn entity_ids(entity_descriptors: &[Result<EntityDescriptor, SAMLError>]) -> Vec<&str> {
+        entity_descriptors
+            .iter()
+            .filter_map(|entity_descriptor| entity_descriptor.as_ref().ok())
+            .map(|entity_descriptor| entity_descriptor.entity_id())
+            .collect()
+    }
+
+    #[test]
+    fn we_can_stream_signed_aggregate() {
+        let xml = aggregate(Duration::days(1), true);
+        let verifier = verifier();
+        let mut reader = MetadataReader::new(xml.as_bytes()).with_verifier(&verifier);
+        let entity_descriptors = reader.by_ref().collect::<Vec<_>>();
+        assert!(entity_descriptors.iter().all(|result| result.is_ok()));
+        assert_eq!(
+            entity_ids(&entity_descriptors),
+            vec!["https://sp.example.org", "https://idp.example.org"]
+        );
+        assert_eq!(
+            reader.name().map(|name| name.as_str()),
+            Some("https://federation.example.org")
+        );
+        assert!(reader.valid_until().is_some());
+
+        // without a verifier nothing is dropped
+        let entity_descriptors = MetadataReader::new(xml.as_bytes()).collect::<Vec<_>>();
+        assert_eq!(entity_ids(&entity_descriptors).len(), 4);
+    }
+
+    #[test]
+    fn we_cannot_stream_tampered_aggregate() {
+        let xml = aggregate(Duration::days(1), true)
+            .replace("https://idp.example.org", "https://evil.example.org");
+        let verifier = verifier();
+        let entity_descriptors = MetadataReader::new(xml.as_bytes())
+            .with_verifier(&verifier)
+            .collect::<Vec<_>>();
+        assert_eq!(entity_descriptors.len(), 1);
+        assert!(matches!(
+            entity_descriptors[0],
+            Err(SAMLError::SignatureError(_))
+        ));
+
+        // unless asked for, the entities read before the digest is known are yielded
+        let entity_descriptors = MetadataReader::new(xml.as_bytes())
+            .with_verifier(&verifier)
+            .yield_unverified()
+            .collect::<Vec<_>>();
+        assert_eq!(entity_ids(&entity_descriptors).len(), 2);
+        assert!(matches!(
+            entity_descriptors.last(),
+            Some(Err(SAMLError::SignatureError(_)))
+        ));
+    }
+
+    #[test]
+    fn we_cannot_stream_only_verified_entities_without_verifier() {
+        let xml = aggregate(Duration::days(1), true);
+        let entity_descriptors = MetadataReader::new(xml.as_bytes())
+            .verified_only()
+            .collect::<Vec<_>>();
+        assert_eq!(entity_descriptors.len(), 1);
+        assert!(matches!(
+            entity_descriptors[0],
+            Err(SAMLError::MetadataError(_))
+        ));
+
+        let verifier = verifier();
+        let entity_descriptors = MetadataReader::new(xml.as_bytes())
+            .verified_only()
+            .with_verifier(&verifier)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            entity_ids(&entity_descriptors),
+            vec!["https://sp.example.org", "https://idp.example.org"]
+        );
+    }
+
+    #[test]
+    fn we_cannot_stream_entity_nested_in_extensions() {
+        let xml = aggregate(Duration::days(1), false).replacen(
+            "<md:EntityDescriptor",
+            r#"<md:Extensions><md:EntityDescriptor entityID="https://hidden.example.org"/><md:EntitiesDescriptor><md:EntityDescriptor entityID="https://hidden-group.example.org"/></md:EntitiesDescriptor></md:Extensions><md:EntityDescriptor"#,
+            1,
+        );
+        let entity_descriptors = MetadataReader::new(xml.as_bytes()).collect::<Vec<_>>();
+        assert!(entity_descriptors.iter().all(|result| result.is_ok()));
+        assert_eq!(
+            entity_ids(&entity_descriptors),
+            vec![
+                "https://sp.example.org",
+                "https://expired.example.org",
+                "https://idp.example.org",
+                "https://old.example.org"
+            ]
+        );
+    }
+
+    #[test]
+    fn we_cannot_stream_unsigned_aggregate_unless_allowed() {
+        let xml = aggregate(Duration::days(1), false);
+        let verifier = verifier();
+        let mut reader = MetadataReader::new(xml.as_bytes()).with_verifier(&verifier);
+        assert!(matches!(
+            reader.next(),
+            Some(Err(SAMLError::SignatureError(_)))
+        ));
+        assert!(reader.next().is_none());
+
+        let mut policy = VerificationPolicy::default();
+        policy.set_allow_unsigned(true);
+        let verifier = verifier.with_policy(policy);
+        let entity_descriptors = MetadataReader::new(xml.as_bytes())
+            .with_verifier(&verifier)
+            .collect::<Vec<_>>();
+        assert!(entity_descriptors.iter().all(|result| result.is_ok()));
+        assert_eq!(entity_ids(&entity_descriptors).len(), 2);
+    }
+}
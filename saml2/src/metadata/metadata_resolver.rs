@@ -0,0 +1,610 @@
+use std::{
+    cell::{Cell, Ref, RefCell},
+    collections::HashMap,
+    fs,
+    path::PathBuf,
+    rc::Rc,
+    time::SystemTime,
+};
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{
+    binding::artifact::ArtifactType0004, error::SAMLError, util::InputStream, xml::XmlObject,
+};
+
+use super::{
+    entities_descriptor::{EntitiesDescriptor, EntitiesDescriptorChild},
+    entity_descriptor::EntityDescriptor,
+    metadata_verifier::MetadataVerifier,
+};
+
+/// Looks up the metadata of the entities we federate with.
+pub trait MetadataResolver {
+    /// the entity named `entity_id`, `None` if it is unknown
+    fn resolve(&self, entity_id: &str) -> Result<Option<Rc<EntityDescriptor>>, SAMLError>;
+
+    /// the entity whose entityID hashes to `source_id` under SHA-1, the `SourceID` of a
+    /// type 0x0004 artifact
+    fn resolve_source_id(
+        &self,
+        source_id: &[u8; 20],
+    ) -> Result<Option<Rc<EntityDescriptor>>, SAMLError>;
+}
+
+#[derive(Debug)]
+struct IndexedEntity {
+    entity_descriptor: Rc<EntityDescriptor>,
+    expires_at: Option<DateTime<Utc>>,
+}
+
+/// Entities indexed by entityID and by `SourceID`, each cached until the shortest
+/// `cacheDuration` of the entity and of the groups enclosing it runs out, and no later
+/// than the earliest `validUntil` among them.
+#[derive(Debug, Default)]
+pub(crate) struct MetadataIndex {
+    entities: HashMap<String, IndexedEntity>,
+    source_ids: HashMap<[u8; 20], String>,
+}
+
+impl MetadataIndex {
+    /// index `entity_descriptor`, replacing an entity of the same entityID
+    pub(crate) fn insert(
+        &mut self,
+        entity_descriptor: EntityDescriptor,
+        expires_at: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) {
+        let expires_at = Self::earliest(
+            expires_at,
+            entity_descriptor.valid_until(),
+            entity_descriptor.cache_duration(),
+            now,
+        );
+        let entity_id = entity_descriptor.entity_id().to_string();
+        self.source_ids.insert(
+            ArtifactType0004::source_id_of(&entity_id),
+            entity_id.clone(),
+        );
+        self.entities.insert(
+            entity_id,
+            IndexedEntity {
+                entity_descriptor: Rc::new(entity_descriptor),
+                expires_at,
+            },
+        );
+    }
+
+    /// move the entity `entity_id` of `other` into the index, leaving the other entities of
+    /// `other` out, and return it unless its cache duration ran out
+    pub(crate) fn insert_from(
+        &mut self,
+        mut other: MetadataIndex,
+        entity_id: &str,
+        now: DateTime<Utc>,
+    ) -> Option<Rc<EntityDescriptor>> {
+        let entity = other.entities.remove(entity_id)?;
+        self.source_ids.insert(
+            ArtifactType0004::source_id_of(entity_id),
+            entity_id.to_string(),
+        );
+        self.entities.insert(entity_id.to_string(), entity);
+        self.get(entity_id, now)
+    }
+
+    /// Index the entities of the metadata document `root`, an `EntityDescriptor` or an
+    /// `EntitiesDescriptor`, verified by `verifier` if one is given. They are cached no
+    /// later than `expires_at`.
+    pub(crate) fn load(
+        &mut self,
+        root: Ref<'_, XmlObject>,
+        verifier: Option<&MetadataVerifier>,
+        expires_at: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> Result<(), SAMLError> {
+        let local_name = root.q_name().local_name().to_string();
+        if local_name == EntityDescriptor::ELEMENT_NAME {
+            let entity_descriptor = match verifier {
+                Some(verifier) => verifier.verify_entity_descriptor(root)?,
+                None => EntityDescriptor::try_from(root)?,
+            };
+            self.insert(entity_descriptor, expires_at, now);
+        } else if local_name == EntitiesDescriptor::ELEMENT_NAME {
+            let entities_descriptor = match verifier {
+                Some(verifier) => verifier.verify_entities_descriptor(root)?,
+                None => EntitiesDescriptor::try_from(root)?,
+            };
+            self.insert_group(entities_descriptor, expires_at, now);
+        } else {
+            return Err(SAMLError::MetadataError(format!(
+                "{} is not a metadata document",
+                local_name
+            )));
+        }
+        Ok(())
+    }
+
+    fn insert_group(
+        &mut self,
+        entities_descriptor: EntitiesDescriptor,
+        expires_at: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) {
+        let expires_at = Self::earliest(
+            expires_at,
+            entities_descriptor.valid_until(),
+            entities_descriptor.cache_duration(),
+            now,
+        );
+        for child in entities_descriptor.into_children() {
+            match child {
+                EntitiesDescriptorChild::EntityDescriptor(entity_descriptor) => {
+                    self.insert(entity_descriptor, expires_at, now);
+                }
+                EntitiesDescriptorChild::EntitiesDescriptor(entities_descriptor) => {
+                    self.insert_group(entities_descriptor, expires_at, now);
+                }
+            }
+        }
+    }
+
+    /// the entity `entity_id` unless its cache duration ran out
+    pub(crate) fn get(&self, entity_id: &str, now: DateTime<Utc>) -> Option<Rc<EntityDescriptor>> {
+        self.entities
+            .get(entity_id)
+            .filter(|entity| entity.expires_at.is_none_or(|expires_at| expires_at > now))
+            .map(|entity| entity.entity_descriptor.clone())
+    }
+
+    pub(crate) fn entity_id_of(&self, source_id: &[u8; 20]) -> Option<&String> {
+        self.source_ids.get(source_id)
+    }
+
+    /// when the first entity of the index has to be refreshed
+    pub(crate) fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self.entities
+            .values()
+            .filter_map(|entity| entity.expires_at)
+            .min()
+    }
+
+    /// a `cacheDuration` reaching beyond the dates we can represent does not expire
+    fn earliest(
+        expires_at: Option<DateTime<Utc>>,
+        valid_until: Option<&DateTime<Utc>>,
+        cache_duration: Option<&Duration>,
+        now: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        let cached_until =
+            cache_duration.and_then(|cache_duration| now.checked_add_signed(*cache_duration));
+        [expires_at, valid_until.copied(), cached_until]
+            .into_iter()
+            .flatten()
+            .min()
+    }
+}
+
+/// parse a metadata document received as `xml`
+pub(crate) fn parse_metadata(xml: Vec<u8>) -> Result<Rc<RefCell<XmlObject>>, SAMLError> {
+    XmlObject::parse_xml(InputStream::new(xml))
+        .map_err(|_| SAMLError::MetadataError("invalid xml format!".to_string()))
+}
+
+/// A [`MetadataResolver`] over metadata handed to it by the application.
+///
+/// Entities are forgotten once their `cacheDuration` or `validUntil` runs out, the
+/// application is expected to load fresh metadata before that.
+#[derive(Debug, Default)]
+pub struct InMemoryMetadataResolver {
+    index: RefCell<MetadataIndex>,
+    verifier: Option<MetadataVerifier>,
+}
+
+impl InMemoryMetadataResolver {
+    pub fn new() -> Self {
+        InMemoryMetadataResolver::default()
+    }
+
+    /// verify the metadata documents loaded from now on with `verifier`
+    pub fn with_verifier(mut self, verifier: MetadataVerifier) -> Self {
+        self.verifier = Some(verifier);
+        self
+    }
+
+    /// add the entities of the metadata document `xml`
+    pub fn load(&self, xml: &str) -> Result<(), SAMLError> {
+        let root = parse_metadata(xml.as_bytes().to_vec())?;
+        let result =
+            self.index
+                .borrow_mut()
+                .load(root.borrow(), self.verifier.as_ref(), None, Utc::now());
+        result
+    }
+
+    pub fn add_entity_descriptor(&self, entity_descriptor: EntityDescriptor) {
+        self.index
+            .borrow_mut()
+            .insert(entity_descriptor, None, Utc::now());
+    }
+}
+
+impl MetadataResolver for InMemoryMetadataResolver {
+    fn resolve(&self, entity_id: &str) -> Result<Option<Rc<EntityDescriptor>>, SAMLError> {
+        Ok(self.index.borrow().get(entity_id, Utc::now()))
+    }
+
+    fn resolve_source_id(
+        &self,
+        source_id: &[u8; 20],
+    ) -> Result<Option<Rc<EntityDescriptor>>, SAMLError> {
+        let index = self.index.borrow();
+        Ok(index
+            .entity_id_of(source_id)
+            .and_then(|entity_id| index.get(entity_id, Utc::now())))
+    }
+}
+
+#[derive(Debug)]
+struct FileState {
+    index: MetadataIndex,
+    modified: SystemTime,
+    len: u64,
+}
+
+/// A [`MetadataResolver`] over a metadata document on disk.
+///
+/// The file is read on the first lookup and read again once it changes or the
+/// `cacheDuration` of an entity runs out. When it fails to load again the entities
+/// read last are served until their own `cacheDuration` runs out, the failure is kept
+/// in [`FileMetadataResolver::reload_error`] and the file is not read again before it
+/// changes. Lookups fail while there is nothing left to serve.
+#[derive(Debug)]
+pub struct FileMetadataResolver {
+    path: PathBuf,
+    verifier: Option<MetadataVerifier>,
+    state: RefCell<Option<FileState>>,
+    reload_error: RefCell<Option<SAMLError>>,
+    /// the modification time and length of the file when it last failed to load
+    failed_file: Cell<Option<(SystemTime, u64)>>,
+}
+
+impl FileMetadataResolver {
+    pub fn new(path: PathBuf) -> Self {
+        FileMetadataResolver {
+            path,
+            verifier: None,
+            state: RefCell::new(None),
+            reload_error: RefCell::new(None),
+            failed_file: Cell::new(None),
+        }
+    }
+
+    /// verify the metadata document with `verifier` each time it is read
+    pub fn with_verifier(mut self, verifier: MetadataVerifier) -> Self {
+        self.verifier = Some(verifier);
+        self
+    }
+
+    #[inline]
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    /// why the file failed to load the last time it was read, `None` once it loads again
+    pub fn reload_error(&self) -> Option<Ref<'_, SAMLError>> {
+        Ref::filter_map(self.reload_error.borrow(), Option::as_ref).ok()
+    }
+
+    /// read the file again if it changed or its entities are due for a refresh, keeping
+    /// the entities read last while they are fresh if it fails to load
+    fn refresh(&self, now: DateTime<Utc>) -> Result<(), SAMLError> {
+        match self.reload(now) {
+            Ok(()) => {
+                *self.reload_error.borrow_mut() = None;
+                Ok(())
+            }
+            Err(error) => {
+                let fresh = self.state.borrow().as_ref().is_some_and(|state| {
+                    state
+                        .index
+                        .expires_at()
+                        .is_none_or(|expires_at| expires_at > now)
+                });
+                if !fresh {
+                    return Err(error);
+                }
+                *self.reload_error.borrow_mut() = Some(error);
+                Ok(())
+            }
+        }
+    }
+
+    fn reload(&self, now: DateTime<Utc>) -> Result<(), SAMLError> {
+        let io_error = |e: std::io::Error| {
+            SAMLError::MetadataError(format!("cannot read {}: {}", self.path.display(), e))
+        };
+        let metadata = fs::metadata(&self.path).map_err(io_error)?;
+        let modified = metadata.modified().map_err(io_error)?;
+        let len = metadata.len();
+        let up_to_date = self.state.borrow().as_ref().is_some_and(|state| {
+            state.modified == modified
+                && state.len == len
+                && state
+                    .index
+                    .expires_at()
+                    .is_none_or(|expires_at| expires_at > now)
+        });
+        if up_to_date {
+            return Ok(());
+        }
+        if self.failed_file.get() == Some((modified, len)) {
+            return Err(self.reload_error.take().unwrap_or_else(|| {
+                SAMLError::MetadataError(format!(
+                    "{} has not changed since it failed to load",
+                    self.path.display()
+                ))
+            }));
+        }
+
+        let index = match self.load(now) {
+            Ok(index) => index,
+            Err(error) => {
+                self.failed_file.set(Some((modified, len)));
+                return Err(error);
+            }
+        };
+        self.failed_file.set(None);
+        *self.state.borrow_mut() = Some(FileState {
+            index,
+            modified,
+            len,
+        });
+        Ok(())
+    }
+
+    fn load(&self, now: DateTime<Utc>) -> Result<MetadataIndex, SAMLError> {
+        let xml = fs::read(&self.path).map_err(|e| {
+            SAMLError::MetadataError(format!("cannot read {}: {}", self.path.display(), e))
+        })?;
+        let root = parse_metadata(xml)?;
+        let mut index = MetadataIndex::default();
+        index.load(root.borrow(), self.verifier.as_ref(), None, now)?;
+        Ok(index)
+    }
+}
+
+impl MetadataResolver for FileMetadataResolver {
+    fn resolve(&self, entity_id: &str) -> Result<Option<Rc<EntityDescriptor>>, SAMLError> {
+        let now = Utc::now();
+        self.refresh(now)?;
+        let state = self.state.borrow();
+        Ok(state
+            .as_ref()
+            .and_then(|state| state.index.get(entity_id, now)))
+    }
+
+    fn resolve_source_id(
+        &self,
+        source_id: &[u8; 20],
+    ) -> Result<Option<Rc<EntityDescriptor>>, SAMLError> {
+        let now = Utc::now();
+        self.refresh(now)?;
+        let state = self.state.borrow();
+        Ok(state.as_ref().and_then(|state| {
+            state
+                .index
+                .entity_id_of(source_id)
+                .and_then(|entity_id| state.index.get(entity_id, now))
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        env,
+        fs::{self, File},
+        time::Duration as StdDuration,
+    };
+
+    use chrono::{Duration, Utc};
+
+    use crate::{
+        binding::artifact::ArtifactType0004,
+        error::SAMLError,
+        metadata::{entity_descriptor::EntityDescriptor, test_fixtures::sp},
+        util::generate_id,
+    };
+
+    use super::{FileMetadataResolver, InMemoryMetadataResolver, MetadataResolver};
+
+    const ENTITIES_DESCRIPTOR: &str = include_str!("../../tests/resources/entities_descriptor.xml");
+
+    fn sp_metadata(entity_id: &str) -> String {
+        sp(entity_id).build_xml().unwrap()
+    }
+
+    #[test]
+    fn we_can_resolve_by_entity_id_and_source_id() {
+        let resolver = InMemoryMetadataResolver::new();
+        resolver.load(ENTITIES_DESCRIPTOR).unwrap();
+
+        let idp = resolver
+            .resolve("https://idp.example.org/metadata")
+            .unwrap();
+        assert!(idp.unwrap().organization().is_some());
+        let source_id = ArtifactType0004::source_id_of("https://sp.example.org/metadata");
+        let sp = resolver.resolve_source_id(&source_id).unwrap().unwrap();
+        assert_eq!(sp.entity_id(), "https://sp.example.org/metadata");
+        assert!(resolver
+            .resolve("https://unknown.example.org")
+            .unwrap()
+            .is_none());
+
+        // the nested group caches for a day, the enclosing one for six hours only
+        let expires_at = resolver.index.borrow().expires_at().unwrap();
+        assert!(expires_at <= Utc::now() + Duration::hours(6));
+        assert!(expires_at > Utc::now() + Duration::hours(5));
+    }
+
+    #[test]
+    fn we_cannot_resolve_entity_past_cache_duration() {
+        let resolver = InMemoryMetadataResolver::new();
+        let mut entity_descriptor = EntityDescriptor::new("https://sp.example.org".to_string());
+        entity_descriptor.set_cache_duration(Some(Duration::zero()));
+        resolver.add_entity_descriptor(entity_descriptor);
+        assert!(resolver
+            .resolve("https://sp.example.org")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn we_cannot_resolve_entity_past_valid_until() {
+        let resolver = InMemoryMetadataResolver::new();
+        let mut entity_descriptor = EntityDescriptor::new("https://sp.example.org".to_string());
+        entity_descriptor.set_valid_until(Some(Utc::now() - Duration::minutes(1)));
+        entity_descriptor.set_cache_duration(Some(Duration::hours(6)));
+        resolver.add_entity_descriptor(entity_descriptor);
+        assert!(resolver
+            .resolve("https://sp.example.org")
+            .unwrap()
+            .is_none());
+
+        // the validUntil of the enclosing group applies to its entities
+        let valid_until = (Utc::now() - Duration::minutes(1)).to_rfc3339();
+        resolver
+            .load(&format!(
+                r#"<md:EntitiesDescriptor xmlns:md="urn:oasis:names:tc:SAML:2.0:metadata" validUntil="{}"><md:EntityDescriptor entityID="https://idp.example.org" cacheDuration="PT6H"/></md:EntitiesDescriptor>"#,
+                valid_until
+            ))
+            .unwrap();
+        assert!(resolver
+            .resolve("https://idp.example.org")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn we_can_resolve_entity_with_cache_duration_beyond_representable_dates() {
+        let resolver = InMemoryMetadataResolver::new();
+        resolver
+            .load(r#"<md:EntityDescriptor xmlns:md="urn:oasis:names:tc:SAML:2.0:metadata" entityID="https://sp.example.org" cacheDuration="P100000000Y"/>"#)
+            .unwrap();
+        assert!(resolver
+            .resolve("https://sp.example.org")
+            .unwrap()
+            .is_some());
+        assert!(resolver.index.borrow().expires_at().is_none());
+    }
+
+    #[test]
+    fn we_can_reload_metadata_file_when_it_changes() {
+        let path = env::temp_dir().join(format!("saml2-metadata{}.xml", generate_id()));
+        fs::write(&path, sp_metadata("https://sp.example.org")).unwrap();
+        let resolver = FileMetadataResolver::new(path.clone());
+        assert!(resolver
+            .resolve("https://sp.example.org")
+            .unwrap()
+            .is_some());
+
+        fs::write(&path, sp_metadata("https://other-sp.example.org")).unwrap();
+        let source_id = ArtifactType0004::source_id_of("https://other-sp.example.org");
+        assert!(resolver.resolve_source_id(&source_id).unwrap().is_some());
+        assert!(resolver
+            .resolve("https://sp.example.org")
+            .unwrap()
+            .is_none());
+
+        fs::remove_file(&path).unwrap();
+        assert!(resolver
+            .resolve("https://other-sp.example.org")
+            .unwrap()
+            .is_some());
+        assert!(matches!(
+            resolver.reload_error().as_deref(),
+            Some(SAMLError::MetadataError(_))
+        ));
+
+        fs::write(&path, sp_metadata("https://sp.example.org")).unwrap();
+        assert!(resolver
+            .resolve("https://sp.example.org")
+            .unwrap()
+            .is_some());
+        assert!(resolver.reload_error().is_none());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn we_cannot_reload_failed_metadata_file_before_it_changes() {
+        let path = env::temp_dir().join(format!("saml2-metadata{}.xml", generate_id()));
+        fs::write(&path, sp_metadata("https://sp.example.org")).unwrap();
+        let resolver = FileMetadataResolver::new(path.clone());
+        assert!(resolver
+            .resolve("https://sp.example.org")
+            .unwrap()
+            .is_some());
+
+        // the broken file is as long as the fixed one written below
+        let fixed = sp_metadata("https://other-sp.example.org");
+        fs::write(
+            &path,
+            format!("{:<1$}", "<md:EntityDescriptor", fixed.len()),
+        )
+        .unwrap();
+        assert!(resolver
+            .resolve("https://sp.example.org")
+            .unwrap()
+            .is_some());
+        assert!(resolver.reload_error().is_some());
+        let modified = fs::metadata(&path).unwrap().modified().unwrap();
+
+        // a file that looks unchanged is not read again
+        fs::write(&path, &fixed).unwrap();
+        let file = File::options().write(true).open(&path).unwrap();
+        file.set_modified(modified).unwrap();
+        assert!(resolver
+            .resolve("https://other-sp.example.org")
+            .unwrap()
+            .is_none());
+        assert!(resolver.reload_error().is_some());
+
+        file.set_modified(modified + StdDuration::from_secs(1))
+            .unwrap();
+        assert!(resolver
+            .resolve("https://other-sp.example.org")
+            .unwrap()
+            .is_some());
+        assert!(resolver.reload_error().is_none());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn we_can_serve_last_metadata_until_cache_duration_when_reload_fails() {
+        let path = env::temp_dir().join(format!("saml2-metadata{}.xml", generate_id()));
+        let metadata = sp("https://sp.example.org")
+            .with_cache_duration(Duration::hours(1))
+            .build_xml()
+            .unwrap();
+        fs::write(&path, metadata).unwrap();
+        let resolver = FileMetadataResolver::new(path.clone());
+        assert!(resolver
+            .resolve("https://sp.example.org")
+            .unwrap()
+            .is_some());
+
+        fs::write(&path, "<md:EntityDescriptor").unwrap();
+        assert!(resolver
+            .resolve("https://sp.example.org")
+            .unwrap()
+            .is_some());
+        assert!(resolver.reload_error().is_some());
+
+        let later = Utc::now() + Duration::hours(2);
+        assert!(matches!(
+            resolver.refresh(later),
+            Err(SAMLError::MetadataError(_))
+        ));
+        fs::remove_file(&path).unwrap();
+    }
+}
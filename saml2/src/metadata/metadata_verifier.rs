@@ -0,0 +1,248 @@
+use std::cell::Ref;
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    error::SAMLError,
+    signature::{
+        credential::Credential, signature::Signature, validator::validate_enveloped_signature,
+    },
+    xml::XmlObject,
+};
+
+use super::{
+    entities_descriptor::{EntitiesDescriptor, EntitiesDescriptorChild},
+    entity_descriptor::EntityDescriptor,
+};
+
+/// what a `MetadataVerifier` tolerates, by default metadata must be signed and current
+#[derive(Debug, Default, Clone, Copy)]
+pub struct VerificationPolicy {
+    allow_unsigned: bool,
+    allow_expired: bool,
+}
+
+impl VerificationPolicy {
+    /// accept metadata without a signature, a signature that is present is still verified
+    #[inline]
+    pub fn allow_unsigned(&self) -> bool {
+        self.allow_unsigned
+    }
+
+    #[inline]
+    pub fn set_allow_unsigned(&mut self, allow_unsigned: bool) {
+        self.allow_unsigned = allow_unsigned;
+    }
+
+    /// accept metadata and keep descriptors whose `validUntil` has passed
+    #[inline]
+    pub fn allow_expired(&self) -> bool {
+        self.allow_expired
+    }
+
+    #[inline]
+    pub fn set_allow_expired(&mut self, allow_expired: bool) {
+        self.allow_expired = allow_expired;
+    }
+}
+
+/// Verifies signed metadata, such as the aggregate of a federation, against a pinned
+/// certificate.
+///
+/// The signature has to be an enveloped signature of the root element. A root whose
+/// `validUntil` has passed is rejected, expired entities, groups and roles below it
+/// are dropped.
+#[derive(Debug)]
+pub struct MetadataVerifier {
+    trust_anchor: Credential,
+    policy: VerificationPolicy,
+}
+
+impl MetadataVerifier {
+    pub fn new(trust_anchor: Credential) -> Self {
+        MetadataVerifier {
+            trust_anchor,
+            policy: VerificationPolicy::default(),
+        }
+    }
+
+    pub fn with_policy(mut self, policy: VerificationPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    #[inline]
+    pub fn trust_anchor(&self) -> &Credential {
+        &self.trust_anchor
+    }
+
+    #[inline]
+    pub fn policy(&self) -> &VerificationPolicy {
+        &self.policy
+    }
+
+    /// verify and parse an `md:EntitiesDescriptor`
+    pub fn verify_entities_descriptor(
+        &self,
+        element: Ref<'_, XmlObject>,
+    ) -> Result<EntitiesDescriptor, SAMLError> {
+        self.verify_signature(&element)?;
+        let mut entities_descriptor = EntitiesDescriptor::try_from(element)?;
+        let now = Utc::now();
+        self.check_valid_until(entities_descriptor.valid_until(), now)?;
+        if !self.policy.allow_expired {
+            Self::drop_expired_children(&mut entities_descriptor, now);
+        }
+        Ok(entities_descriptor)
+    }
+
+    /// verify and parse an `md:EntityDescriptor` published on its own
+    pub fn verify_entity_descriptor(
+        &self,
+        element: Ref<'_, XmlObject>,
+    ) -> Result<EntityDescriptor, SAMLError> {
+        self.verify_signature(&element)?;
+        let mut entity_descriptor = EntityDescriptor::try_from(element)?;
+        let now = Utc::now();
+        self.check_valid_until(entity_descriptor.valid_until(), now)?;
+        if !self.policy.allow_expired {
+            Self::drop_expired_roles(&mut entity_descriptor, now);
+        }
+        Ok(entity_descriptor)
+    }
+
+    fn verify_signature(&self, element: &XmlObject) -> Result<(), SAMLError> {
+        let signed = element
+            .children()
+            .iter()
+            .any(|child| Signature::is_signature_element(&child.borrow()));
+        if !signed {
+            return match self.policy.allow_unsigned {
+                true => Ok(()),
+                false => Err(SAMLError::SignatureError(
+                    "metadata is not signed".to_string(),
+                )),
+            };
+        }
+        validate_enveloped_signature(element, &self.trust_anchor)?;
+        Ok(())
+    }
+
+    pub(crate) fn check_valid_until(
+        &self,
+        valid_until: Option<&DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> Result<(), SAMLError> {
+        match valid_until {
+            Some(valid_until) if !self.policy.allow_expired && *valid_until <= now => Err(
+                SAMLError::MetadataError(format!("metadata expired at {}", valid_until)),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    fn drop_expired_children(entities_descriptor: &mut EntitiesDescriptor, now: DateTime<Utc>) {
+        let current = |valid_until: Option<&DateTime<Utc>>| {
+            valid_until.is_none_or(|valid_until| *valid_until > now)
+        };
+        entities_descriptor
+            .children_mut()
+            .retain_mut(|child| match child {
+                EntitiesDescriptorChild::EntityDescriptor(entity_descriptor) => {
+                    Self::drop_expired_roles(entity_descriptor, now);
+                    current(entity_descriptor.valid_until())
+                }
+                EntitiesDescriptorChild::EntitiesDescriptor(entities_descriptor) => {
+                    Self::drop_expired_children(entities_descriptor, now);
+                    current(entities_descriptor.valid_until())
+                }
+            });
+    }
+
+    pub(crate) fn drop_expired_roles(entity_descriptor: &mut EntityDescriptor, now: DateTime<Utc>) {
+        entity_descriptor
+            .role_descriptors_mut()
+            .retain(|role_descriptor| {
+                role_descriptor
+                    .role_descriptor_type()
+                    .valid_until()
+                    .is_none_or(|valid_until| *valid_until > now)
+            });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::Duration;
+
+    use crate::{
+        error::SAMLError,
+        metadata::{
+            entities_descriptor::EntitiesDescriptor,
+            test_fixtures::{aggregate, verifier},
+        },
+        util::InputStream,
+        xml::XmlObject,
+    };
+
+    use super::{MetadataVerifier, VerificationPolicy};
+
+    fn verify(verifier: &MetadataVerifier, xml: &str) -> Result<EntitiesDescriptor, SAMLError> {
+        let root = XmlObject::parse_xml(InputStream::new(xml.as_bytes().to_vec())).unwrap();
+        let entities_descriptor = verifier.verify_entities_descriptor(root.borrow());
+        entities_descriptor
+    }
+
+    #[test]
+    fn we_can_verify_signed_aggregate() {
+        let entities_descriptor = verify(&verifier(), &aggregate(Duration::days(1), true)).unwrap();
+        let entity_ids = entities_descriptor
+            .entity_descriptors()
+            .iter()
+            .map(|entity_descriptor| entity_descriptor.entity_id())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            entity_ids,
+            vec!["https://sp.example.org", "https://idp.example.org"]
+        );
+    }
+
+    #[test]
+    fn we_cannot_verify_tampered_aggregate() {
+        let xml = aggregate(Duration::days(1), true)
+            .replace("https://sp.example.org", "https://evil.example.org");
+        assert!(matches!(
+            verify(&verifier(), &xml),
+            Err(SAMLError::SignatureError(_))
+        ));
+
+        // a signature is checked even if unsigned metadata is acceptable
+        let mut policy = VerificationPolicy::default();
+        policy.set_allow_unsigned(true);
+        assert!(verify(&verifier().with_policy(policy), &xml).is_err());
+    }
+
+    #[test]
+    fn we_cannot_verify_expired_or_unsigned_aggregate_unless_allowed() {
+        let expired = aggregate(Duration::days(-1), true);
+        assert!(matches!(
+            verify(&verifier(), &expired),
+            Err(SAMLError::MetadataError(_))
+        ));
+        let unsigned = aggregate(Duration::days(1), false);
+        assert!(matches!(
+            verify(&verifier(), &unsigned),
+            Err(SAMLError::SignatureError(_))
+        ));
+
+        let mut policy = VerificationPolicy::default();
+        policy.set_allow_expired(true);
+        let entities_descriptor = verify(&verifier().with_policy(policy), &expired).unwrap();
+        assert_eq!(entities_descriptor.entity_descriptors().len(), 4);
+
+        let mut policy = VerificationPolicy::default();
+        policy.set_allow_unsigned(true);
+        let entities_descriptor = verify(&verifier().with_policy(policy), &unsigned).unwrap();
+        assert_eq!(entities_descriptor.entity_descriptors().len(), 2);
+    }
+}
@@ -1 +1,229 @@
+use std::str::FromStr;
 
+use chrono::Duration;
+
+use crate::{
+    error::SAMLError,
+    xml::{QName, XmlObject},
+};
+
+pub mod attribute_authority_descriptor;
+pub mod attribute_consuming_service;
+pub mod authn_authority_descriptor;
+pub mod contact_person;
+pub mod contact_type;
+pub mod endpoint;
+pub mod entities_descriptor;
+pub mod entity_descriptor;
+pub mod extensions;
+pub mod idp_sso_descriptor;
+pub mod indexed_endpoint;
+pub mod key_descriptor;
+pub mod key_type;
+pub mod mdq_resolver;
+pub mod metadata_builder;
+pub mod metadata_reader;
+pub mod metadata_resolver;
+pub mod metadata_verifier;
+pub mod organization;
+pub mod organization_display_name;
+pub mod organization_name;
+pub mod organization_url;
+pub mod pdp_descriptor;
+pub mod requested_attribute;
+pub mod role_descriptor;
+pub mod service_description;
+pub mod service_name;
+pub mod sp_sso_descriptor;
+pub mod sso_descriptor_type;
+pub mod unknown_role_descriptor;
+
+#[cfg(test)]
+mod test_fixtures;
+
+pub const NS_PREFIX: &str = "md";
+pub const NS_URI: &str = "urn:oasis:names:tc:SAML:2.0:metadata";
+
+/// the `xml:lang` attribute of the localized metadata elements
+const ATTRIB_XML_LANG: &str = "xml:lang";
+
+/// parse a xml string to a type that implements the fromStr trait
+fn parse_from_string<T: FromStr>(value: &str) -> Result<T, SAMLError> {
+    T::from_str(value).map_err(|_| SAMLError::UnmarshallingError("Invalid XML".to_string()))
+}
+
+/// an empty metadata element `local_name`
+fn metadata_element(local_name: &str) -> XmlObject {
+    let mut xml_object = XmlObject::new(
+        Some(NS_URI.to_string()),
+        local_name.to_string(),
+        Some(NS_PREFIX.to_string()),
+    );
+    xml_object.add_namespace(NS_PREFIX.to_string(), NS_URI.to_string());
+    xml_object
+}
+
+/// Put `xml_object` in the metadata namespace as `local_name`, for the metadata types whose
+/// element name depends on where they are used, e.g. the endpoints of a role.
+fn rename(mut xml_object: XmlObject, local_name: &str) -> XmlObject {
+    xml_object.set_q_name(QName::new(
+        Some(NS_URI.to_string()),
+        local_name.to_string(),
+        Some(NS_PREFIX.to_string()),
+    ));
+    xml_object.add_namespace(NS_PREFIX.to_string(), NS_URI.to_string());
+    xml_object
+}
+
+/// Parse an `xs:duration` such as the `cacheDuration` of metadata. Calendar units have no
+/// fixed length, a year counts 365 days and a month 30 days.
+pub fn parse_duration(value: &str) -> Result<Duration, SAMLError> {
+    let invalid = || SAMLError::UnmarshallingError(format!("Invalid xs:duration: {}", value));
+    let (negative, rest) = match value.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, value),
+    };
+    let rest = rest.strip_prefix('P').ok_or_else(invalid)?;
+    let (date, time) = match rest.split_once('T') {
+        Some((_, "")) => return Err(invalid()),
+        Some((date, time)) => (date, Some(time)),
+        None => (rest, None),
+    };
+    if date.is_empty() && time.is_none() {
+        return Err(invalid());
+    }
+
+    let mut milliseconds: i64 = 0;
+    let mut number = String::new();
+    for c in date.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'Y' | 'M' | 'D' => {
+                let days_per_unit = match c {
+                    'Y' => 365,
+                    'M' => 30,
+                    _ => 1,
+                };
+                let units: i64 = number.parse().map_err(|_| invalid())?;
+                milliseconds = units
+                    .checked_mul(days_per_unit * 86_400_000)
+                    .and_then(|unit_milliseconds| milliseconds.checked_add(unit_milliseconds))
+                    .ok_or_else(invalid)?;
+                number.clear();
+            }
+            _ => return Err(invalid()),
+        }
+    }
+    if !number.is_empty() {
+        return Err(invalid());
+    }
+    for c in time.unwrap_or_default().chars() {
+        match c {
+            '0'..='9' | '.' => number.push(c),
+            'H' | 'M' | 'S' => {
+                let seconds_per_unit = match c {
+                    'H' => 3600.0,
+                    'M' => 60.0,
+                    _ => 1.0,
+                };
+                if c != 'S' && number.contains('.') {
+                    return Err(invalid());
+                }
+                let units: f64 = number.parse().map_err(|_| invalid())?;
+                let unit_milliseconds = (units * seconds_per_unit * 1000.0).round();
+                // `as` would saturate, i64::MAX as f64 is already out of range
+                if unit_milliseconds >= i64::MAX as f64 {
+                    return Err(invalid());
+                }
+                milliseconds = milliseconds
+                    .checked_add(unit_milliseconds as i64)
+                    .ok_or_else(invalid)?;
+                number.clear();
+            }
+            _ => return Err(invalid()),
+        }
+    }
+    if !number.is_empty() {
+        return Err(invalid());
+    }
+    let duration = Duration::milliseconds(milliseconds);
+    Ok(if negative { -duration } else { duration })
+}
+
+/// Format `duration` as an `xs:duration` in days, hours, minutes and seconds.
+pub fn format_duration(duration: Duration) -> String {
+    let sign = if duration < Duration::zero() { "-" } else { "" };
+    let milliseconds = duration.num_milliseconds().abs();
+    let (days, rest) = (milliseconds / 86_400_000, milliseconds % 86_400_000);
+    let (hours, rest) = (rest / 3_600_000, rest % 3_600_000);
+    let (minutes, rest) = (rest / 60_000, rest % 60_000);
+    let (seconds, millis) = (rest / 1000, rest % 1000);
+
+    let mut value = format!("{}P", sign);
+    if days > 0 {
+        value.push_str(&format!("{}D", days));
+    }
+    if hours > 0 || minutes > 0 || seconds > 0 || millis > 0 || days == 0 {
+        value.push('T');
+        if hours > 0 {
+            value.push_str(&format!("{}H", hours));
+        }
+        if minutes > 0 {
+            value.push_str(&format!("{}M", minutes));
+        }
+        if millis > 0 {
+            value.push_str(&format!("{}.{:03}S", seconds, millis));
+        } else if seconds > 0 || (hours == 0 && minutes == 0) {
+            value.push_str(&format!("{}S", seconds));
+        }
+    }
+    value
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::Duration;
+
+    use super::{format_duration, parse_duration};
+
+    #[test]
+    fn we_can_parse_xs_duration() {
+        assert_eq!(parse_duration("PT6H").unwrap(), Duration::hours(6));
+        assert_eq!(parse_duration("P1D").unwrap(), Duration::days(1));
+        assert_eq!(
+            parse_duration("P1DT2H30M15.5S").unwrap(),
+            Duration::milliseconds(95_415_500)
+        );
+        assert_eq!(parse_duration("P1Y2M").unwrap(), Duration::days(425));
+        assert_eq!(parse_duration("-PT1M").unwrap(), Duration::minutes(-1));
+        for invalid in ["", "P", "PT", "6H", "PT6", "P1H", "PT1.5H", "P1DT"] {
+            assert!(parse_duration(invalid).is_err(), "{}", invalid);
+        }
+        // durations beyond the range of Duration
+        for invalid in [
+            "P100000000000Y",
+            "P100000000Y100000000Y100000000Y",
+            "PT9223372036854775807H",
+            "PT99999999999999999999.5S",
+        ] {
+            assert!(parse_duration(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn we_can_format_xs_duration() {
+        assert_eq!(format_duration(Duration::hours(6)), "PT6H");
+        assert_eq!(format_duration(Duration::days(7)), "P7D");
+        assert_eq!(format_duration(Duration::zero()), "PT0S");
+        assert_eq!(
+            format_duration(Duration::milliseconds(95_415_500)),
+            "P1DT2H30M15.500S"
+        );
+        for duration in [Duration::minutes(-90), Duration::seconds(86_401)] {
+            assert_eq!(
+                parse_duration(&format_duration(duration)).unwrap(),
+                duration
+            );
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use std::{
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
+
+use crate::{common::SAML2Obj, error::SAMLError, util::AttributeMap, xml::XmlObject};
+
+use super::{
+    extensions::Extensions, organization_display_name::OrganizationDisplayName,
+    organization_name::OrganizationName, organization_url::OrganizationURL,
+};
+
+/// `md:Organization`, the organization responsible for an entity or a role
+#[derive(Debug, Default)]
+pub struct Organization {
+    extensions: Option<Extensions>,
+    organization_names: Vec<OrganizationName>,
+    organization_display_names: Vec<OrganizationDisplayName>,
+    organization_urls: Vec<OrganizationURL>,
+    unknown_attributes: AttributeMap,
+}
+
+impl SAML2Obj for Organization {}
+
+impl Organization {
+    const CHILD_EXTENSIONS: &'static str = "Extensions";
+    const CHILD_ORGANIZATION_NAME: &'static str = "OrganizationName";
+    const CHILD_ORGANIZATION_DISPLAY_NAME: &'static str = "OrganizationDisplayName";
+    const CHILD_ORGANIZATION_URL: &'static str = "OrganizationURL";
+
+    pub const ELEMENT_NAME: &'static str = "Organization";
+    pub const NS_PREFIX: &'static str = "md";
+    pub const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:metadata";
+
+    #[inline]
+    pub fn extensions(&self) -> Option<&Extensions> {
+        self.extensions.as_ref()
+    }
+
+    #[inline]
+    pub fn set_extensions(&mut self, extensions: Option<Extensions>) {
+        self.extensions = extensions;
+    }
+
+    #[inline]
+    pub fn organization_names(&self) -> &Vec<OrganizationName> {
+        &self.organization_names
+    }
+
+    #[inline]
+    pub fn add_organization_name(&mut self, organization_name: OrganizationName) {
+        self.organization_names.push(organization_name);
+    }
+
+    #[inline]
+    pub fn organization_display_names(&self) -> &Vec<OrganizationDisplayName> {
+        &self.organization_display_names
+    }
+
+    #[inline]
+    pub fn add_organization_display_name(
+        &mut self,
+        organization_display_name: OrganizationDisplayName,
+    ) {
+        self.organization_display_names
+            .push(organization_display_name);
+    }
+
+    #[inline]
+    pub fn organization_urls(&self) -> &Vec<OrganizationURL> {
+        &self.organization_urls
+    }
+
+    #[inline]
+    pub fn add_organization_url(&mut self, organization_url: OrganizationURL) {
+        self.organization_urls.push(organization_url);
+    }
+
+    #[inline]
+    pub fn unknown_attributes(&self) -> &AttributeMap {
+        &self.unknown_attributes
+    }
+
+    #[inline]
+    pub fn unknown_attributes_mut(&mut self) -> &mut AttributeMap {
+        &mut self.unknown_attributes
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for Organization {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut organization = Organization::default();
+        for (key, value) in element.attributes() {
+            organization
+                .unknown_attributes
+                .add_attribute_of(&element, key, value);
+        }
+        for child in element.children() {
+            let child = child.borrow();
+            match child.q_name().local_name() {
+                Organization::CHILD_EXTENSIONS => {
+                    organization.set_extensions(Some(Extensions::try_from(child)?));
+                }
+                Organization::CHILD_ORGANIZATION_NAME => {
+                    organization.add_organization_name(OrganizationName::try_from(child)?);
+                }
+                Organization::CHILD_ORGANIZATION_DISPLAY_NAME => {
+                    organization
+                        .add_organization_display_name(OrganizationDisplayName::try_from(child)?);
+                }
+                Organization::CHILD_ORGANIZATION_URL => {
+                    organization.add_organization_url(OrganizationURL::try_from(child)?);
+                }
+                _ => {}
+            }
+        }
+        Ok(organization)
+    }
+}
+
+impl TryFrom<Organization> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(organization: Organization) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(Organization::NS_URI.to_string()),
+            Organization::ELEMENT_NAME.to_string(),
+            Some(Organization::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            Organization::NS_PREFIX.to_string(),
+            Organization::NS_URI.to_string(),
+        );
+        organization.unknown_attributes.write_to(&mut xml_object);
+        if let Some(extensions) = organization.extensions {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(extensions)?)));
+        }
+        for organization_name in organization.organization_names {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(
+                organization_name,
+            )?)));
+        }
+        for organization_display_name in organization.organization_display_names {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(
+                organization_display_name,
+            )?)));
+        }
+        for organization_url in organization.organization_urls {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(
+                organization_url,
+            )?)));
+        }
+        Ok(xml_object)
+    }
+}
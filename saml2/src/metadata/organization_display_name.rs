@@ -0,0 +1,82 @@
+use std::cell::Ref;
+
+use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+
+use super::ATTRIB_XML_LANG;
+
+/// `md:OrganizationDisplayName`, the name of the organization suitable for display in the language `xml:lang`
+#[derive(Debug, Default)]
+pub struct OrganizationDisplayName {
+    lang: String,
+    value: String,
+}
+
+impl SAML2Obj for OrganizationDisplayName {}
+
+impl OrganizationDisplayName {
+    pub const ELEMENT_NAME: &'static str = "OrganizationDisplayName";
+    pub const NS_PREFIX: &'static str = "md";
+    pub const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:metadata";
+
+    pub fn new(lang: String, value: String) -> Self {
+        OrganizationDisplayName { lang, value }
+    }
+
+    #[inline]
+    pub fn lang(&self) -> &str {
+        &self.lang
+    }
+
+    #[inline]
+    pub fn set_lang(&mut self, lang: String) {
+        self.lang = lang;
+    }
+
+    #[inline]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    #[inline]
+    pub fn set_value(&mut self, value: String) {
+        self.value = value;
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for OrganizationDisplayName {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let lang = element.attribute(ATTRIB_XML_LANG).ok_or_else(|| {
+            SAMLError::UnmarshallingError(
+                "OrganizationDisplayName must have an xml:lang".to_string(),
+            )
+        })?;
+        Ok(OrganizationDisplayName::new(
+            lang.to_string(),
+            element
+                .text()
+                .map(|text| text.trim().to_string())
+                .unwrap_or_default(),
+        ))
+    }
+}
+
+impl TryFrom<OrganizationDisplayName> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(organization_display_name: OrganizationDisplayName) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(OrganizationDisplayName::NS_URI.to_string()),
+            OrganizationDisplayName::ELEMENT_NAME.to_string(),
+            Some(OrganizationDisplayName::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            OrganizationDisplayName::NS_PREFIX.to_string(),
+            OrganizationDisplayName::NS_URI.to_string(),
+        );
+        xml_object.add_attribute(ATTRIB_XML_LANG.to_string(), organization_display_name.lang);
+        xml_object.set_text(Some(organization_display_name.value));
+        Ok(xml_object)
+    }
+}
@@ -0,0 +1,80 @@
+use std::cell::Ref;
+
+use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+
+use super::ATTRIB_XML_LANG;
+
+/// `md:OrganizationName`, the name of the organization in the language `xml:lang`
+#[derive(Debug, Default)]
+pub struct OrganizationName {
+    lang: String,
+    value: String,
+}
+
+impl SAML2Obj for OrganizationName {}
+
+impl OrganizationName {
+    pub const ELEMENT_NAME: &'static str = "OrganizationName";
+    pub const NS_PREFIX: &'static str = "md";
+    pub const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:metadata";
+
+    pub fn new(lang: String, value: String) -> Self {
+        OrganizationName { lang, value }
+    }
+
+    #[inline]
+    pub fn lang(&self) -> &str {
+        &self.lang
+    }
+
+    #[inline]
+    pub fn set_lang(&mut self, lang: String) {
+        self.lang = lang;
+    }
+
+    #[inline]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    #[inline]
+    pub fn set_value(&mut self, value: String) {
+        self.value = value;
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for OrganizationName {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let lang = element.attribute(ATTRIB_XML_LANG).ok_or_else(|| {
+            SAMLError::UnmarshallingError("OrganizationName must have an xml:lang".to_string())
+        })?;
+        Ok(OrganizationName::new(
+            lang.to_string(),
+            element
+                .text()
+                .map(|text| text.trim().to_string())
+                .unwrap_or_default(),
+        ))
+    }
+}
+
+impl TryFrom<OrganizationName> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(organization_name: OrganizationName) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(OrganizationName::NS_URI.to_string()),
+            OrganizationName::ELEMENT_NAME.to_string(),
+            Some(OrganizationName::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            OrganizationName::NS_PREFIX.to_string(),
+            OrganizationName::NS_URI.to_string(),
+        );
+        xml_object.add_attribute(ATTRIB_XML_LANG.to_string(), organization_name.lang);
+        xml_object.set_text(Some(organization_name.value));
+        Ok(xml_object)
+    }
+}
@@ -0,0 +1,80 @@
+use std::cell::Ref;
+
+use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+
+use super::ATTRIB_XML_LANG;
+
+/// `md:OrganizationURL`, the URL of the organization's web site in the language `xml:lang`
+#[derive(Debug, Default)]
+pub struct OrganizationURL {
+    lang: String,
+    value: String,
+}
+
+impl SAML2Obj for OrganizationURL {}
+
+impl OrganizationURL {
+    pub const ELEMENT_NAME: &'static str = "OrganizationURL";
+    pub const NS_PREFIX: &'static str = "md";
+    pub const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:metadata";
+
+    pub fn new(lang: String, value: String) -> Self {
+        OrganizationURL { lang, value }
+    }
+
+    #[inline]
+    pub fn lang(&self) -> &str {
+        &self.lang
+    }
+
+    #[inline]
+    pub fn set_lang(&mut self, lang: String) {
+        self.lang = lang;
+    }
+
+    #[inline]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    #[inline]
+    pub fn set_value(&mut self, value: String) {
+        self.value = value;
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for OrganizationURL {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let lang = element.attribute(ATTRIB_XML_LANG).ok_or_else(|| {
+            SAMLError::UnmarshallingError("OrganizationURL must have an xml:lang".to_string())
+        })?;
+        Ok(OrganizationURL::new(
+            lang.to_string(),
+            element
+                .text()
+                .map(|text| text.trim().to_string())
+                .unwrap_or_default(),
+        ))
+    }
+}
+
+impl TryFrom<OrganizationURL> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(organization_url: OrganizationURL) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(OrganizationURL::NS_URI.to_string()),
+            OrganizationURL::ELEMENT_NAME.to_string(),
+            Some(OrganizationURL::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            OrganizationURL::NS_PREFIX.to_string(),
+            OrganizationURL::NS_URI.to_string(),
+        );
+        xml_object.add_attribute(ATTRIB_XML_LANG.to_string(), organization_url.lang);
+        xml_object.set_text(Some(organization_url.value));
+        Ok(xml_object)
+    }
+}
@@ -0,0 +1,158 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
+
+use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+
+use super::{
+    endpoint::Endpoint,
+    metadata_element,
+    role_descriptor::{RoleDescriptor, RoleDescriptorType},
+};
+
+/// `md:PDPDescriptor`, the policy decision point role of an entity, answering `AuthzDecisionQuery`s
+#[derive(Debug, Default)]
+pub struct PDPDescriptor {
+    role_descriptor_type: RoleDescriptorType,
+    authz_services: Vec<Endpoint>,
+    assertion_id_request_services: Vec<Endpoint>,
+    name_id_formats: Vec<String>,
+}
+
+impl SAML2Obj for PDPDescriptor {}
+
+impl PDPDescriptor {
+    const CHILD_AUTHZ_SERVICE: &'static str = "AuthzService";
+    const CHILD_ASSERTION_ID_REQUEST_SERVICE: &'static str = "AssertionIDRequestService";
+    const CHILD_NAME_ID_FORMAT: &'static str = "NameIDFormat";
+
+    pub const ELEMENT_NAME: &'static str = "PDPDescriptor";
+    pub const NS_PREFIX: &'static str = "md";
+    pub const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:metadata";
+
+    pub fn new() -> Self {
+        PDPDescriptor {
+            role_descriptor_type: RoleDescriptorType::new(),
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    pub fn authz_services(&self) -> &Vec<Endpoint> {
+        &self.authz_services
+    }
+
+    #[inline]
+    pub fn add_authz_service(&mut self, authz_service: Endpoint) {
+        self.authz_services.push(authz_service);
+    }
+
+    /// the `AuthzService` speaking `binding`
+    pub fn authz_service(&self, binding: &str) -> Option<&Endpoint> {
+        self.authz_services
+            .iter()
+            .find(|endpoint| endpoint.binding() == binding)
+    }
+
+    #[inline]
+    pub fn assertion_id_request_services(&self) -> &Vec<Endpoint> {
+        &self.assertion_id_request_services
+    }
+
+    #[inline]
+    pub fn add_assertion_id_request_service(&mut self, assertion_id_request_service: Endpoint) {
+        self.assertion_id_request_services
+            .push(assertion_id_request_service);
+    }
+
+    #[inline]
+    pub fn name_id_formats(&self) -> &Vec<String> {
+        &self.name_id_formats
+    }
+
+    #[inline]
+    pub fn add_name_id_format(&mut self, name_id_format: String) {
+        self.name_id_formats.push(name_id_format);
+    }
+}
+
+impl RoleDescriptor for PDPDescriptor {
+    fn role_descriptor_type(&self) -> &RoleDescriptorType {
+        &self.role_descriptor_type
+    }
+
+    fn role_descriptor_type_mut(&mut self) -> &mut RoleDescriptorType {
+        &mut self.role_descriptor_type
+    }
+
+    fn into_xml_object(self: Box<Self>) -> Result<XmlObject, SAMLError> {
+        XmlObject::try_from(*self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for PDPDescriptor {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut pdp_descriptor = PDPDescriptor {
+            role_descriptor_type: RoleDescriptorType::from_element(&element, &[])?,
+            ..Default::default()
+        };
+        for child in element.children() {
+            let child = child.borrow();
+            match child.q_name().local_name() {
+                PDPDescriptor::CHILD_AUTHZ_SERVICE => {
+                    pdp_descriptor.add_authz_service(Endpoint::try_from(child)?);
+                }
+                PDPDescriptor::CHILD_ASSERTION_ID_REQUEST_SERVICE => {
+                    pdp_descriptor.add_assertion_id_request_service(Endpoint::try_from(child)?);
+                }
+                PDPDescriptor::CHILD_NAME_ID_FORMAT => {
+                    if let Some(name_id_format) = child.text() {
+                        pdp_descriptor.add_name_id_format(name_id_format.trim().to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+        if pdp_descriptor.authz_services.is_empty() {
+            return Err(SAMLError::UnmarshallingError(
+                "PDPDescriptor must have an AuthzService".to_string(),
+            ));
+        }
+        Ok(pdp_descriptor)
+    }
+}
+
+impl TryFrom<PDPDescriptor> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(pdp_descriptor: PDPDescriptor) -> Result<Self, Self::Error> {
+        let mut xml_object = pdp_descriptor
+            .role_descriptor_type
+            .into_xml_object(PDPDescriptor::ELEMENT_NAME)?;
+        for authz_service in pdp_descriptor.authz_services {
+            xml_object.add_child(Rc::new(RefCell::new(
+                authz_service.into_xml_object(PDPDescriptor::CHILD_AUTHZ_SERVICE)?,
+            )));
+        }
+        for assertion_id_request_service in pdp_descriptor.assertion_id_request_services {
+            xml_object.add_child(Rc::new(RefCell::new(
+                assertion_id_request_service
+                    .into_xml_object(PDPDescriptor::CHILD_ASSERTION_ID_REQUEST_SERVICE)?,
+            )));
+        }
+        for name_id_format in pdp_descriptor.name_id_formats {
+            let mut element = metadata_element(PDPDescriptor::CHILD_NAME_ID_FORMAT);
+            element.set_text(Some(name_id_format));
+            xml_object.add_child(Rc::new(RefCell::new(element)));
+        }
+        Ok(xml_object)
+    }
+}
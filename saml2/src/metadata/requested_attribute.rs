@@ -0,0 +1,84 @@
+use std::cell::Ref;
+
+use crate::{common::SAML2Obj, core::attribute::Attribute, error::SAMLError, xml::XmlObject};
+
+use super::{parse_from_string, rename};
+
+/// `md:RequestedAttribute`, an attribute a service asks for, with the values it accepts
+#[derive(Debug, Default)]
+pub struct RequestedAttribute {
+    attribute: Attribute,
+    is_required: Option<bool>,
+}
+
+impl SAML2Obj for RequestedAttribute {}
+
+impl RequestedAttribute {
+    const ATTRIB_IS_REQUIRED: &'static str = "isRequired";
+
+    pub const ELEMENT_NAME: &'static str = "RequestedAttribute";
+    pub const NS_PREFIX: &'static str = "md";
+    pub const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:metadata";
+
+    pub fn new(attribute: Attribute, is_required: Option<bool>) -> Self {
+        RequestedAttribute {
+            attribute,
+            is_required,
+        }
+    }
+
+    /// name, format and accepted values of the attribute
+    #[inline]
+    pub fn attribute(&self) -> &Attribute {
+        &self.attribute
+    }
+
+    #[inline]
+    pub fn attribute_mut(&mut self) -> &mut Attribute {
+        &mut self.attribute
+    }
+
+    #[inline]
+    pub fn is_required(&self) -> Option<bool> {
+        self.is_required
+    }
+
+    #[inline]
+    pub fn set_is_required(&mut self, is_required: Option<bool>) {
+        self.is_required = is_required;
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for RequestedAttribute {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut attribute = Attribute::try_from(element)?;
+        let is_required = match attribute
+            .unknown_attributes_mut()
+            .remove(RequestedAttribute::ATTRIB_IS_REQUIRED)
+        {
+            Some(is_required) => Some(parse_from_string(&is_required)?),
+            None => None,
+        };
+        Ok(RequestedAttribute::new(attribute, is_required))
+    }
+}
+
+impl TryFrom<RequestedAttribute> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(requested_attribute: RequestedAttribute) -> Result<Self, Self::Error> {
+        let mut xml_object = rename(
+            XmlObject::try_from(requested_attribute.attribute)?,
+            RequestedAttribute::ELEMENT_NAME,
+        );
+        if let Some(is_required) = requested_attribute.is_required {
+            xml_object.add_attribute(
+                RequestedAttribute::ATTRIB_IS_REQUIRED.to_string(),
+                is_required.to_string(),
+            );
+        }
+        Ok(xml_object)
+    }
+}
@@ -0,0 +1,318 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefCell},
+    fmt::Debug,
+    rc::Rc,
+};
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{
+    error::SAMLError, signature::signature::Signature, util::AttributeMap, xml::XmlObject,
+};
+
+use super::{
+    contact_person::ContactPerson, extensions::Extensions, format_duration,
+    key_descriptor::KeyDescriptor, key_type::KeyType, metadata_element, organization::Organization,
+    parse_duration, parse_from_string,
+};
+
+/// A role an entity plays, e.g. `IDPSSODescriptor`. Roles are stored type erased in an
+/// `EntityDescriptor`.
+pub trait RoleDescriptor: Debug {
+    /// the attributes and elements all roles have
+    fn role_descriptor_type(&self) -> &RoleDescriptorType;
+
+    fn role_descriptor_type_mut(&mut self) -> &mut RoleDescriptorType;
+
+    fn into_xml_object(self: Box<Self>) -> Result<XmlObject, SAMLError>;
+
+    /// give access to the concrete role, e.g. to downcast to an `SPSSODescriptor`
+    fn as_any(&self) -> &dyn Any;
+}
+
+/// `md:RoleDescriptorType`, what the role descriptors have in common
+#[derive(Debug, Default)]
+pub struct RoleDescriptorType {
+    id: Option<String>,
+    valid_until: Option<DateTime<Utc>>,
+    cache_duration: Option<Duration>,
+    protocol_support_enumeration: Vec<String>,
+    error_url: Option<String>,
+    signature: Option<Signature>,
+    extensions: Option<Extensions>,
+    key_descriptors: Vec<KeyDescriptor>,
+    organization: Option<Organization>,
+    contact_persons: Vec<ContactPerson>,
+    unknown_attributes: AttributeMap,
+}
+
+impl RoleDescriptorType {
+    const ATTRIB_ID: &'static str = "ID";
+    const ATTRIB_VALID_UNTIL: &'static str = "validUntil";
+    const ATTRIB_CACHE_DURATION: &'static str = "cacheDuration";
+    const ATTRIB_PROTOCOL_SUPPORT_ENUMERATION: &'static str = "protocolSupportEnumeration";
+    const ATTRIB_ERROR_URL: &'static str = "errorURL";
+
+    const CHILD_SIGNATURE: &'static str = "Signature";
+    const CHILD_EXTENSIONS: &'static str = "Extensions";
+    const CHILD_KEY_DESCRIPTOR: &'static str = "KeyDescriptor";
+    const CHILD_ORGANIZATION: &'static str = "Organization";
+    const CHILD_CONTACT_PERSON: &'static str = "ContactPerson";
+
+    /// the elements of the common part, anything after them belongs to the concrete role
+    pub(crate) const COMMON_CHILDREN: [&'static str; 5] = [
+        RoleDescriptorType::CHILD_SIGNATURE,
+        RoleDescriptorType::CHILD_EXTENSIONS,
+        RoleDescriptorType::CHILD_KEY_DESCRIPTOR,
+        RoleDescriptorType::CHILD_ORGANIZATION,
+        RoleDescriptorType::CHILD_CONTACT_PERSON,
+    ];
+
+    /// the `protocolSupportEnumeration` member of roles speaking SAML 2.0
+    pub const SAML20_PROTOCOL: &'static str = "urn:oasis:names:tc:SAML:2.0:protocol";
+
+    /// a role supporting SAML 2.0
+    pub fn new() -> Self {
+        RoleDescriptorType {
+            protocol_support_enumeration: vec![RoleDescriptorType::SAML20_PROTOCOL.to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    pub fn id(&self) -> Option<&String> {
+        self.id.as_ref()
+    }
+
+    #[inline]
+    pub fn set_id(&mut self, id: Option<String>) {
+        self.id = id;
+    }
+
+    #[inline]
+    pub fn valid_until(&self) -> Option<&DateTime<Utc>> {
+        self.valid_until.as_ref()
+    }
+
+    #[inline]
+    pub fn set_valid_until(&mut self, valid_until: Option<DateTime<Utc>>) {
+        self.valid_until = valid_until;
+    }
+
+    #[inline]
+    pub fn cache_duration(&self) -> Option<&Duration> {
+        self.cache_duration.as_ref()
+    }
+
+    #[inline]
+    pub fn set_cache_duration(&mut self, cache_duration: Option<Duration>) {
+        self.cache_duration = cache_duration;
+    }
+
+    /// the URIs of the protocols the role supports
+    #[inline]
+    pub fn protocol_support_enumeration(&self) -> &Vec<String> {
+        &self.protocol_support_enumeration
+    }
+
+    #[inline]
+    pub fn set_protocol_support_enumeration(&mut self, protocol_support_enumeration: Vec<String>) {
+        self.protocol_support_enumeration = protocol_support_enumeration;
+    }
+
+    #[inline]
+    pub fn supports_protocol(&self, protocol: &str) -> bool {
+        self.protocol_support_enumeration
+            .iter()
+            .any(|supported| supported == protocol)
+    }
+
+    #[inline]
+    pub fn error_url(&self) -> Option<&String> {
+        self.error_url.as_ref()
+    }
+
+    #[inline]
+    pub fn set_error_url(&mut self, error_url: Option<String>) {
+        self.error_url = error_url;
+    }
+
+    #[inline]
+    pub fn signature(&self) -> Option<&Signature> {
+        self.signature.as_ref()
+    }
+
+    #[inline]
+    pub fn set_signature(&mut self, signature: Option<Signature>) {
+        self.signature = signature;
+    }
+
+    #[inline]
+    pub fn extensions(&self) -> Option<&Extensions> {
+        self.extensions.as_ref()
+    }
+
+    #[inline]
+    pub fn set_extensions(&mut self, extensions: Option<Extensions>) {
+        self.extensions = extensions;
+    }
+
+    #[inline]
+    pub fn key_descriptors(&self) -> &Vec<KeyDescriptor> {
+        &self.key_descriptors
+    }
+
+    #[inline]
+    pub fn add_key_descriptor(&mut self, key_descriptor: KeyDescriptor) {
+        self.key_descriptors.push(key_descriptor);
+    }
+
+    /// the keys that may be used for `key_type`, including those without a `use`
+    pub fn key_descriptors_for(&self, key_type: KeyType) -> impl Iterator<Item = &KeyDescriptor> {
+        self.key_descriptors
+            .iter()
+            .filter(move |key_descriptor| key_descriptor.is_usable_for(key_type))
+    }
+
+    #[inline]
+    pub fn organization(&self) -> Option<&Organization> {
+        self.organization.as_ref()
+    }
+
+    #[inline]
+    pub fn set_organization(&mut self, organization: Option<Organization>) {
+        self.organization = organization;
+    }
+
+    #[inline]
+    pub fn contact_persons(&self) -> &Vec<ContactPerson> {
+        &self.contact_persons
+    }
+
+    #[inline]
+    pub fn add_contact_person(&mut self, contact_person: ContactPerson) {
+        self.contact_persons.push(contact_person);
+    }
+
+    #[inline]
+    pub fn unknown_attributes(&self) -> &AttributeMap {
+        &self.unknown_attributes
+    }
+
+    #[inline]
+    pub fn unknown_attributes_mut(&mut self) -> &mut AttributeMap {
+        &mut self.unknown_attributes
+    }
+
+    /// Parse the common part of the role descriptor `element`. The attributes in
+    /// `own_attributes` and the elements past `ContactPerson` are left to the caller.
+    pub(crate) fn from_element(
+        element: &Ref<'_, XmlObject>,
+        own_attributes: &[&str],
+    ) -> Result<Self, SAMLError> {
+        let mut role_descriptor_type = RoleDescriptorType::default();
+        let mut has_protocol_support_enumeration = false;
+        for (key, value) in element.attributes() {
+            match key.as_str() {
+                RoleDescriptorType::ATTRIB_ID => {
+                    role_descriptor_type.set_id(Some(value.to_string()));
+                }
+                RoleDescriptorType::ATTRIB_VALID_UNTIL => {
+                    role_descriptor_type.set_valid_until(Some(parse_from_string(value)?));
+                }
+                RoleDescriptorType::ATTRIB_CACHE_DURATION => {
+                    role_descriptor_type.set_cache_duration(Some(parse_duration(value)?));
+                }
+                RoleDescriptorType::ATTRIB_PROTOCOL_SUPPORT_ENUMERATION => {
+                    role_descriptor_type.set_protocol_support_enumeration(
+                        value.split_whitespace().map(str::to_string).collect(),
+                    );
+                    has_protocol_support_enumeration = true;
+                }
+                RoleDescriptorType::ATTRIB_ERROR_URL => {
+                    role_descriptor_type.set_error_url(Some(value.to_string()));
+                }
+                key if own_attributes.contains(&key) => {}
+                _ => {
+                    role_descriptor_type
+                        .unknown_attributes
+                        .add_attribute_of(element, key, value);
+                }
+            }
+        }
+        if !has_protocol_support_enumeration {
+            return Err(SAMLError::UnmarshallingError(format!(
+                "{} must have a protocolSupportEnumeration",
+                element.q_name().local_name()
+            )));
+        }
+        for child in element.children() {
+            let child = child.borrow();
+            match child.q_name().local_name() {
+                RoleDescriptorType::CHILD_SIGNATURE => {
+                    role_descriptor_type.set_signature(Some(Signature::try_from(child)?));
+                }
+                RoleDescriptorType::CHILD_EXTENSIONS => {
+                    role_descriptor_type.set_extensions(Some(Extensions::try_from(child)?));
+                }
+                RoleDescriptorType::CHILD_KEY_DESCRIPTOR => {
+                    role_descriptor_type.add_key_descriptor(KeyDescriptor::try_from(child)?);
+                }
+                RoleDescriptorType::CHILD_ORGANIZATION => {
+                    role_descriptor_type.set_organization(Some(Organization::try_from(child)?));
+                }
+                RoleDescriptorType::CHILD_CONTACT_PERSON => {
+                    role_descriptor_type.add_contact_person(ContactPerson::try_from(child)?);
+                }
+                _ => {}
+            }
+        }
+        Ok(role_descriptor_type)
+    }
+
+    /// Serialize the common part as the role descriptor `local_name`, the caller adds its
+    /// own attributes and elements.
+    pub(crate) fn into_xml_object(self, local_name: &str) -> Result<XmlObject, SAMLError> {
+        let mut xml_object = metadata_element(local_name);
+        if let Some(id) = self.id {
+            xml_object.add_attribute(RoleDescriptorType::ATTRIB_ID.to_string(), id);
+        }
+        if let Some(valid_until) = self.valid_until {
+            xml_object.add_attribute(
+                RoleDescriptorType::ATTRIB_VALID_UNTIL.to_string(),
+                valid_until.to_rfc3339(),
+            );
+        }
+        if let Some(cache_duration) = self.cache_duration {
+            xml_object.add_attribute(
+                RoleDescriptorType::ATTRIB_CACHE_DURATION.to_string(),
+                format_duration(cache_duration),
+            );
+        }
+        xml_object.add_attribute(
+            RoleDescriptorType::ATTRIB_PROTOCOL_SUPPORT_ENUMERATION.to_string(),
+            self.protocol_support_enumeration.join(" "),
+        );
+        if let Some(error_url) = self.error_url {
+            xml_object.add_attribute(RoleDescriptorType::ATTRIB_ERROR_URL.to_string(), error_url);
+        }
+        self.unknown_attributes.write_to(&mut xml_object);
+        if let Some(signature) = self.signature {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(signature)?)));
+        }
+        if let Some(extensions) = self.extensions {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(extensions)?)));
+        }
+        for key_descriptor in self.key_descriptors {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(key_descriptor)?)));
+        }
+        if let Some(organization) = self.organization {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(organization)?)));
+        }
+        for contact_person in self.contact_persons {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(contact_person)?)));
+        }
+        Ok(xml_object)
+    }
+}
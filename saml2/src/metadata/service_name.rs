@@ -0,0 +1,80 @@
+use std::cell::Ref;
+
+use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+
+use super::ATTRIB_XML_LANG;
+
+/// `md:ServiceName`, the name of the service in the language `xml:lang`
+#[derive(Debug, Default)]
+pub struct ServiceName {
+    lang: String,
+    value: String,
+}
+
+impl SAML2Obj for ServiceName {}
+
+impl ServiceName {
+    pub const ELEMENT_NAME: &'static str = "ServiceName";
+    pub const NS_PREFIX: &'static str = "md";
+    pub const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:metadata";
+
+    pub fn new(lang: String, value: String) -> Self {
+        ServiceName { lang, value }
+    }
+
+    #[inline]
+    pub fn lang(&self) -> &str {
+        &self.lang
+    }
+
+    #[inline]
+    pub fn set_lang(&mut self, lang: String) {
+        self.lang = lang;
+    }
+
+    #[inline]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    #[inline]
+    pub fn set_value(&mut self, value: String) {
+        self.value = value;
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for ServiceName {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let lang = element.attribute(ATTRIB_XML_LANG).ok_or_else(|| {
+            SAMLError::UnmarshallingError("ServiceName must have an xml:lang".to_string())
+        })?;
+        Ok(ServiceName::new(
+            lang.to_string(),
+            element
+                .text()
+                .map(|text| text.trim().to_string())
+                .unwrap_or_default(),
+        ))
+    }
+}
+
+impl TryFrom<ServiceName> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(service_name: ServiceName) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(ServiceName::NS_URI.to_string()),
+            ServiceName::ELEMENT_NAME.to_string(),
+            Some(ServiceName::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            ServiceName::NS_PREFIX.to_string(),
+            ServiceName::NS_URI.to_string(),
+        );
+        xml_object.add_attribute(ATTRIB_XML_LANG.to_string(), service_name.lang);
+        xml_object.set_text(Some(service_name.value));
+        Ok(xml_object)
+    }
+}
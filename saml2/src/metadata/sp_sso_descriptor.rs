@@ -0,0 +1,393 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
+
+use crate::{
+    common::SAML2Obj, core::authn_request::AuthnRequest, error::SAMLError, xml::XmlObject,
+};
+
+use super::{
+    attribute_consuming_service::AttributeConsumingService,
+    indexed_endpoint::{default_endpoint, IndexedEndpoint},
+    parse_from_string,
+    role_descriptor::{RoleDescriptor, RoleDescriptorType},
+    sso_descriptor_type::SSODescriptorType,
+};
+
+/// `md:SPSSODescriptor`, the service provider role of an entity
+#[derive(Debug, Default)]
+pub struct SPSSODescriptor {
+    sso_descriptor_type: SSODescriptorType,
+    authn_requests_signed: Option<bool>,
+    want_assertions_signed: Option<bool>,
+    assertion_consumer_services: Vec<IndexedEndpoint>,
+    attribute_consuming_services: Vec<AttributeConsumingService>,
+}
+
+impl SAML2Obj for SPSSODescriptor {}
+
+impl SPSSODescriptor {
+    const ATTRIB_AUTHN_REQUESTS_SIGNED: &'static str = "AuthnRequestsSigned";
+    const ATTRIB_WANT_ASSERTIONS_SIGNED: &'static str = "WantAssertionsSigned";
+
+    const CHILD_ASSERTION_CONSUMER_SERVICE: &'static str = "AssertionConsumerService";
+    const CHILD_ATTRIBUTE_CONSUMING_SERVICE: &'static str = "AttributeConsumingService";
+
+    pub const ELEMENT_NAME: &'static str = "SPSSODescriptor";
+    pub const NS_PREFIX: &'static str = "md";
+    pub const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:metadata";
+
+    pub fn new() -> Self {
+        SPSSODescriptor {
+            sso_descriptor_type: SSODescriptorType::new(),
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    pub fn sso_descriptor_type(&self) -> &SSODescriptorType {
+        &self.sso_descriptor_type
+    }
+
+    #[inline]
+    pub fn sso_descriptor_type_mut(&mut self) -> &mut SSODescriptorType {
+        &mut self.sso_descriptor_type
+    }
+
+    #[inline]
+    pub fn authn_requests_signed(&self) -> Option<bool> {
+        self.authn_requests_signed
+    }
+
+    #[inline]
+    pub fn set_authn_requests_signed(&mut self, authn_requests_signed: Option<bool>) {
+        self.authn_requests_signed = authn_requests_signed;
+    }
+
+    #[inline]
+    pub fn want_assertions_signed(&self) -> Option<bool> {
+        self.want_assertions_signed
+    }
+
+    #[inline]
+    pub fn set_want_assertions_signed(&mut self, want_assertions_signed: Option<bool>) {
+        self.want_assertions_signed = want_assertions_signed;
+    }
+
+    #[inline]
+    pub fn assertion_consumer_services(&self) -> &Vec<IndexedEndpoint> {
+        &self.assertion_consumer_services
+    }
+
+    #[inline]
+    pub fn add_assertion_consumer_service(&mut self, assertion_consumer_service: IndexedEndpoint) {
+        self.assertion_consumer_services
+            .push(assertion_consumer_service);
+    }
+
+    #[inline]
+    pub fn attribute_consuming_services(&self) -> &Vec<AttributeConsumingService> {
+        &self.attribute_consuming_services
+    }
+
+    #[inline]
+    pub fn add_attribute_consuming_service(
+        &mut self,
+        attribute_consuming_service: AttributeConsumingService,
+    ) {
+        self.attribute_consuming_services
+            .push(attribute_consuming_service);
+    }
+
+    /// the `AssertionConsumerService` used when a request names none
+    pub fn default_assertion_consumer_service(&self) -> Option<&IndexedEndpoint> {
+        default_endpoint(&self.assertion_consumer_services)
+    }
+
+    /// The `AttributeConsumingService` at `index`, or the default one when `index` is `None`:
+    /// the first marked `isDefault="true"`, else the first not marked `isDefault="false"`,
+    /// else the first.
+    pub fn attribute_consuming_service(
+        &self,
+        index: Option<usize>,
+    ) -> Option<&AttributeConsumingService> {
+        let services = &self.attribute_consuming_services;
+        match index {
+            Some(index) => services.iter().find(|service| service.index() == index),
+            None => services
+                .iter()
+                .find(|service| service.is_default() == Some(true))
+                .or_else(|| {
+                    services
+                        .iter()
+                        .find(|service| service.is_default().is_none())
+                })
+                .or_else(|| services.first()),
+        }
+    }
+
+    /// Find where the response to `authn_request` goes. An `AssertionConsumerServiceIndex`
+    /// selects the endpoint with that index and excludes `AssertionConsumerServiceURL` and
+    /// `ProtocolBinding`. A URL must be the `Location` of an endpoint speaking the
+    /// `ProtocolBinding`, if one is given. Without either the default endpoint among those
+    /// speaking the `ProtocolBinding` is used.
+    pub fn resolve_assertion_consumer_service(
+        &self,
+        authn_request: &AuthnRequest,
+    ) -> Result<&IndexedEndpoint, SAMLError> {
+        let services = &self.assertion_consumer_services;
+        let url = authn_request.assertion_consumer_service_url();
+        let binding = authn_request.protocol_binding();
+        if let Some(index) = authn_request.assertion_consumer_service_index() {
+            if url.is_some() || binding.is_some() {
+                return Err(SAMLError::MetadataError(
+                    "AssertionConsumerServiceIndex excludes AssertionConsumerServiceURL and ProtocolBinding"
+                        .to_string(),
+                ));
+            }
+            return services
+                .iter()
+                .find(|service| service.index() == index)
+                .ok_or_else(|| {
+                    SAMLError::MetadataError(format!(
+                        "no AssertionConsumerService with index {}",
+                        index
+                    ))
+                });
+        }
+        let mut candidates = services
+            .iter()
+            .filter(|service| binding.is_none_or(|binding| service.binding() == binding));
+        match url {
+            Some(url) => candidates
+                .find(|service| service.location() == url)
+                .ok_or_else(|| {
+                    SAMLError::MetadataError(format!(
+                        "{} is not an AssertionConsumerService of the SP",
+                        url
+                    ))
+                }),
+            None => default_endpoint(candidates).ok_or_else(|| {
+                SAMLError::MetadataError(
+                    "no AssertionConsumerService for the requested binding".to_string(),
+                )
+            }),
+        }
+    }
+}
+
+impl RoleDescriptor for SPSSODescriptor {
+    fn role_descriptor_type(&self) -> &RoleDescriptorType {
+        self.sso_descriptor_type.role_descriptor_type()
+    }
+
+    fn role_descriptor_type_mut(&mut self) -> &mut RoleDescriptorType {
+        self.sso_descriptor_type.role_descriptor_type_mut()
+    }
+
+    fn into_xml_object(self: Box<Self>) -> Result<XmlObject, SAMLError> {
+        XmlObject::try_from(*self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for SPSSODescriptor {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut sp_sso_descriptor = SPSSODescriptor {
+            sso_descriptor_type: SSODescriptorType::from_element(
+                &element,
+                &[
+                    SPSSODescriptor::ATTRIB_AUTHN_REQUESTS_SIGNED,
+                    SPSSODescriptor::ATTRIB_WANT_ASSERTIONS_SIGNED,
+                ],
+            )?,
+            ..Default::default()
+        };
+        if let Some(value) = element.attribute(SPSSODescriptor::ATTRIB_AUTHN_REQUESTS_SIGNED) {
+            sp_sso_descriptor.set_authn_requests_signed(Some(parse_from_string(value)?));
+        }
+        if let Some(value) = element.attribute(SPSSODescriptor::ATTRIB_WANT_ASSERTIONS_SIGNED) {
+            sp_sso_descriptor.set_want_assertions_signed(Some(parse_from_string(value)?));
+        }
+        for child in element.children() {
+            let child = child.borrow();
+            match child.q_name().local_name() {
+                SPSSODescriptor::CHILD_ASSERTION_CONSUMER_SERVICE => {
+                    sp_sso_descriptor
+                        .add_assertion_consumer_service(IndexedEndpoint::try_from(child)?);
+                }
+                SPSSODescriptor::CHILD_ATTRIBUTE_CONSUMING_SERVICE => {
+                    sp_sso_descriptor.add_attribute_consuming_service(
+                        AttributeConsumingService::try_from(child)?,
+                    );
+                }
+                _ => {}
+            }
+        }
+        if sp_sso_descriptor.assertion_consumer_services.is_empty() {
+            return Err(SAMLError::UnmarshallingError(
+                "SPSSODescriptor must have an AssertionConsumerService".to_string(),
+            ));
+        }
+        Ok(sp_sso_descriptor)
+    }
+}
+
+impl TryFrom<SPSSODescriptor> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(sp_sso_descriptor: SPSSODescriptor) -> Result<Self, Self::Error> {
+        let mut xml_object = sp_sso_descriptor
+            .sso_descriptor_type
+            .into_xml_object(SPSSODescriptor::ELEMENT_NAME)?;
+        if let Some(authn_requests_signed) = sp_sso_descriptor.authn_requests_signed {
+            xml_object.add_attribute(
+                SPSSODescriptor::ATTRIB_AUTHN_REQUESTS_SIGNED.to_string(),
+                authn_requests_signed.to_string(),
+            );
+        }
+        if let Some(want_assertions_signed) = sp_sso_descriptor.want_assertions_signed {
+            xml_object.add_attribute(
+                SPSSODescriptor::ATTRIB_WANT_ASSERTIONS_SIGNED.to_string(),
+                want_assertions_signed.to_string(),
+            );
+        }
+        for assertion_consumer_service in sp_sso_descriptor.assertion_consumer_services {
+            xml_object.add_child(Rc::new(RefCell::new(
+                assertion_consumer_service
+                    .into_xml_object(SPSSODescriptor::CHILD_ASSERTION_CONSUMER_SERVICE)?,
+            )));
+        }
+        for attribute_consuming_service in sp_sso_descriptor.attribute_consuming_services {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(
+                attribute_consuming_service,
+            )?)));
+        }
+        Ok(xml_object)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        core::authn_request::AuthnRequest,
+        error::SAMLError,
+        metadata::{entity_descriptor::EntityDescriptor, key_type::KeyType},
+        util::InputStream,
+        xml::XmlObject,
+    };
+
+    const SP_METADATA: &str = include_str!("../../tests/resources/sp_metadata.xml");
+    const POST: &str = "urn:oasis:names:tc:SAML:2.0:bindings:HTTP-POST";
+    const PAOS: &str = "urn:oasis:names:tc:SAML:2.0:bindings:PAOS";
+    const ECP_ACS: &str = "https://sp.example.org/SAML2/ECP";
+
+    fn parse(xml: &str) -> EntityDescriptor {
+        let root = XmlObject::parse_xml(InputStream::new(xml.as_bytes().to_vec())).unwrap();
+        let entity_descriptor = EntityDescriptor::try_from(root.borrow()).unwrap();
+        entity_descriptor
+    }
+
+    fn authn_request(
+        index: Option<usize>,
+        url: Option<&str>,
+        binding: Option<&str>,
+    ) -> AuthnRequest {
+        let mut authn_request = AuthnRequest::default();
+        authn_request.set_assertion_consumer_service_index(index);
+        authn_request.set_assertion_consumer_service_url(url.map(str::to_string));
+        authn_request.set_protocol_binding(binding.map(str::to_string));
+        authn_request
+    }
+
+    #[test]
+    fn we_can_resolve_assertion_consumer_service() {
+        let entity_descriptor = parse(SP_METADATA);
+        let sp = entity_descriptor.sp_sso_descriptors().next().unwrap();
+        let resolve = |index, url, binding| {
+            sp.resolve_assertion_consumer_service(&authn_request(index, url, binding))
+                .map(|service| service.index())
+        };
+
+        // index 1 is marked isDefault="false", index 2 is the first one left unmarked
+        assert_eq!(sp.default_assertion_consumer_service().unwrap().index(), 2);
+        assert_eq!(resolve(None, None, None).unwrap(), 2);
+        assert_eq!(resolve(None, None, Some(POST)).unwrap(), 1);
+        assert_eq!(resolve(Some(3), None, None).unwrap(), 3);
+        assert_eq!(resolve(None, Some(ECP_ACS), None).unwrap(), 3);
+        assert_eq!(resolve(None, Some(ECP_ACS), Some(PAOS)).unwrap(), 3);
+    }
+
+    #[test]
+    fn we_cannot_resolve_assertion_consumer_service() {
+        let entity_descriptor = parse(SP_METADATA);
+        let sp = entity_descriptor.sp_sso_descriptors().next().unwrap();
+        for (index, url, binding) in [
+            (Some(3), Some(ECP_ACS), None),
+            (Some(3), None, Some(PAOS)),
+            (Some(9), None, None),
+            (None, Some("https://attacker.example.org/ACS"), None),
+            (None, Some(ECP_ACS), Some(POST)),
+            (
+                None,
+                None,
+                Some("urn:oasis:names:tc:SAML:2.0:bindings:SOAP"),
+            ),
+        ] {
+            assert!(matches!(
+                sp.resolve_assertion_consumer_service(&authn_request(index, url, binding)),
+                Err(SAMLError::MetadataError(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn we_can_round_trip_sp_sso_descriptor() {
+        let xml_object = XmlObject::try_from(parse(SP_METADATA)).unwrap();
+        let xml = XmlObject::write_xml(Rc::new(RefCell::new(xml_object))).unwrap();
+        let entity_descriptor = parse(&xml);
+        let sp = entity_descriptor.sp_sso_descriptors().next().unwrap();
+        assert_eq!(sp.authn_requests_signed(), Some(true));
+        assert_eq!(sp.want_assertions_signed(), Some(true));
+        assert_eq!(sp.assertion_consumer_services().len(), 3);
+        assert_eq!(
+            sp.assertion_consumer_services()[0].is_default(),
+            Some(false)
+        );
+
+        let attribute_consuming_service = sp.attribute_consuming_service(None).unwrap();
+        assert_eq!(
+            attribute_consuming_service.service_names()[0].value(),
+            "Example Portal"
+        );
+        let requested = attribute_consuming_service.requested_attributes();
+        assert_eq!(requested[0].attribute().friendly_name().unwrap(), "mail");
+        assert_eq!(requested[0].is_required(), Some(true));
+        assert!(requested[0].attribute().unknown_attributes().is_empty());
+        assert_eq!(requested[1].is_required(), None);
+        assert!(sp.attribute_consuming_service(Some(1)).is_none());
+
+        let role = sp.sso_descriptor_type().role_descriptor_type();
+        let key_descriptor = role
+            .key_descriptors_for(KeyType::Encryption)
+            .next()
+            .unwrap();
+        assert!(key_descriptor.is_usable_for(KeyType::Signing));
+        assert_eq!(key_descriptor.credentials().unwrap().len(), 1);
+        assert_eq!(
+            key_descriptor.encryption_methods()[0].algorithm(),
+            "http://www.w3.org/2009/xmlenc11#aes256-gcm"
+        );
+        assert!(xml.contains("<md:EncryptionMethod"));
+        assert!(xml.contains("<md:RequestedAttribute"));
+    }
+}
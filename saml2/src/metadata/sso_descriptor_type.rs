@@ -0,0 +1,161 @@
+use std::{
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
+
+use crate::{error::SAMLError, xml::XmlObject};
+
+use super::{
+    endpoint::Endpoint, indexed_endpoint::IndexedEndpoint, metadata_element,
+    role_descriptor::RoleDescriptorType,
+};
+
+/// `md:SSODescriptorType`, what the IdP and SP roles have in common
+#[derive(Debug, Default)]
+pub struct SSODescriptorType {
+    role_descriptor_type: RoleDescriptorType,
+    artifact_resolution_services: Vec<IndexedEndpoint>,
+    single_logout_services: Vec<Endpoint>,
+    manage_name_id_services: Vec<Endpoint>,
+    name_id_formats: Vec<String>,
+}
+
+impl SSODescriptorType {
+    const CHILD_ARTIFACT_RESOLUTION_SERVICE: &'static str = "ArtifactResolutionService";
+    const CHILD_SINGLE_LOGOUT_SERVICE: &'static str = "SingleLogoutService";
+    const CHILD_MANAGE_NAME_ID_SERVICE: &'static str = "ManageNameIDService";
+    const CHILD_NAME_ID_FORMAT: &'static str = "NameIDFormat";
+
+    pub fn new() -> Self {
+        SSODescriptorType {
+            role_descriptor_type: RoleDescriptorType::new(),
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    pub fn role_descriptor_type(&self) -> &RoleDescriptorType {
+        &self.role_descriptor_type
+    }
+
+    #[inline]
+    pub fn role_descriptor_type_mut(&mut self) -> &mut RoleDescriptorType {
+        &mut self.role_descriptor_type
+    }
+
+    #[inline]
+    pub fn artifact_resolution_services(&self) -> &Vec<IndexedEndpoint> {
+        &self.artifact_resolution_services
+    }
+
+    #[inline]
+    pub fn add_artifact_resolution_service(
+        &mut self,
+        artifact_resolution_service: IndexedEndpoint,
+    ) {
+        self.artifact_resolution_services
+            .push(artifact_resolution_service);
+    }
+
+    #[inline]
+    pub fn single_logout_services(&self) -> &Vec<Endpoint> {
+        &self.single_logout_services
+    }
+
+    #[inline]
+    pub fn add_single_logout_service(&mut self, single_logout_service: Endpoint) {
+        self.single_logout_services.push(single_logout_service);
+    }
+
+    #[inline]
+    pub fn manage_name_id_services(&self) -> &Vec<Endpoint> {
+        &self.manage_name_id_services
+    }
+
+    #[inline]
+    pub fn add_manage_name_id_service(&mut self, manage_name_id_service: Endpoint) {
+        self.manage_name_id_services.push(manage_name_id_service);
+    }
+
+    /// the name identifier formats the role supports
+    #[inline]
+    pub fn name_id_formats(&self) -> &Vec<String> {
+        &self.name_id_formats
+    }
+
+    #[inline]
+    pub fn add_name_id_format(&mut self, name_id_format: String) {
+        self.name_id_formats.push(name_id_format);
+    }
+
+    /// the `SingleLogoutService` speaking `binding`
+    pub fn single_logout_service(&self, binding: &str) -> Option<&Endpoint> {
+        self.single_logout_services
+            .iter()
+            .find(|endpoint| endpoint.binding() == binding)
+    }
+
+    /// Parse the common part of the SSO descriptor `element`, see
+    /// [`RoleDescriptorType::from_element`].
+    pub(crate) fn from_element(
+        element: &Ref<'_, XmlObject>,
+        own_attributes: &[&str],
+    ) -> Result<Self, SAMLError> {
+        let mut sso_descriptor_type = SSODescriptorType {
+            role_descriptor_type: RoleDescriptorType::from_element(element, own_attributes)?,
+            ..Default::default()
+        };
+        for child in element.children() {
+            let child = child.borrow();
+            match child.q_name().local_name() {
+                SSODescriptorType::CHILD_ARTIFACT_RESOLUTION_SERVICE => {
+                    sso_descriptor_type
+                        .add_artifact_resolution_service(IndexedEndpoint::try_from(child)?);
+                }
+                SSODescriptorType::CHILD_SINGLE_LOGOUT_SERVICE => {
+                    sso_descriptor_type.add_single_logout_service(Endpoint::try_from(child)?);
+                }
+                SSODescriptorType::CHILD_MANAGE_NAME_ID_SERVICE => {
+                    sso_descriptor_type.add_manage_name_id_service(Endpoint::try_from(child)?);
+                }
+                SSODescriptorType::CHILD_NAME_ID_FORMAT => {
+                    if let Some(name_id_format) = child.text() {
+                        sso_descriptor_type.add_name_id_format(name_id_format.trim().to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(sso_descriptor_type)
+    }
+
+    /// Serialize the common part as the SSO descriptor `local_name`, the caller adds its
+    /// own attributes and elements.
+    pub(crate) fn into_xml_object(self, local_name: &str) -> Result<XmlObject, SAMLError> {
+        let mut xml_object = self.role_descriptor_type.into_xml_object(local_name)?;
+        for artifact_resolution_service in self.artifact_resolution_services {
+            xml_object.add_child(Rc::new(RefCell::new(
+                artifact_resolution_service
+                    .into_xml_object(SSODescriptorType::CHILD_ARTIFACT_RESOLUTION_SERVICE)?,
+            )));
+        }
+        for single_logout_service in self.single_logout_services {
+            xml_object.add_child(Rc::new(RefCell::new(
+                single_logout_service
+                    .into_xml_object(SSODescriptorType::CHILD_SINGLE_LOGOUT_SERVICE)?,
+            )));
+        }
+        for manage_name_id_service in self.manage_name_id_services {
+            xml_object.add_child(Rc::new(RefCell::new(
+                manage_name_id_service
+                    .into_xml_object(SSODescriptorType::CHILD_MANAGE_NAME_ID_SERVICE)?,
+            )));
+        }
+        for name_id_format in self.name_id_formats {
+            let mut element = metadata_element(SSODescriptorType::CHILD_NAME_ID_FORMAT);
+            element.set_text(Some(name_id_format));
+            xml_object.add_child(Rc::new(RefCell::new(element)));
+        }
+        Ok(xml_object)
+    }
+}
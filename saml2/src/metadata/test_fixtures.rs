@@ -0,0 +1,80 @@
+//! fixtures shared by the tests of the metadata module
+
+use std::{cell::RefCell, rc::Rc};
+
+use chrono::{Duration, Utc};
+
+use crate::{
+    binding::encoding::post::HTTP_POST_BINDING,
+    signature::{algorithm::SignatureAlgorithm, credential::Credential, signer::sign_enveloped},
+    xml::XmlObject,
+};
+
+use super::{
+    entities_descriptor::EntitiesDescriptor, entity_descriptor::EntityDescriptor,
+    metadata_builder::MetadataBuilder, metadata_verifier::MetadataVerifier,
+};
+
+pub(super) const PRIVATE_KEY: &str = include_str!("../../tests/resources/rsa_private_key.pem");
+pub(super) const CERTIFICATE: &str = include_str!("../../tests/resources/rsa_certificate.pem");
+
+/// the private key metadata is signed with, together with its certificate
+pub(super) fn signing_credential() -> Credential {
+    Credential::from_private_key_pem(PRIVATE_KEY)
+        .unwrap()
+        .with_certificate_pem(CERTIFICATE)
+        .unwrap()
+}
+
+/// a verifier trusting the certificate of [`signing_credential`]
+pub(super) fn verifier() -> MetadataVerifier {
+    MetadataVerifier::new(Credential::from_certificate_pem(CERTIFICATE).unwrap())
+}
+
+/// an SP with a single HTTP-POST `AssertionConsumerService`
+pub(super) fn sp(entity_id: &str) -> MetadataBuilder {
+    MetadataBuilder::new(entity_id.to_string()).with_assertion_consumer_service(
+        HTTP_POST_BINDING.to_string(),
+        format!("{}/acs", entity_id),
+    )
+}
+
+/// an SP valid for `valid_for`, expired if it is negative
+pub(super) fn entity(entity_id: &str, valid_for: Duration) -> EntityDescriptor {
+    sp(entity_id)
+        .with_valid_until(Utc::now() + valid_for)
+        .build()
+        .unwrap()
+}
+
+/// The aggregate `_aggregate` valid for `valid_for`. It holds `https://sp.example.org`,
+/// the expired `https://expired.example.org`, a group of `https://idp.example.org` and an
+/// expired group of `https://old.example.org`.
+pub(super) fn aggregate(valid_for: Duration, signed: bool) -> String {
+    let mut entities_descriptor = EntitiesDescriptor::default();
+    entities_descriptor.set_id(Some("_aggregate".to_string()));
+    entities_descriptor.set_name(Some("https://federation.example.org".to_string()));
+    entities_descriptor.set_valid_until(Some(Utc::now() + valid_for));
+    entities_descriptor.add_entity_descriptor(entity("https://sp.example.org", Duration::days(7)));
+    entities_descriptor
+        .add_entity_descriptor(entity("https://expired.example.org", Duration::days(-1)));
+
+    let mut group = EntitiesDescriptor::default();
+    group.add_entity_descriptor(entity("https://idp.example.org", Duration::days(7)));
+    entities_descriptor.add_entities_descriptor(group);
+    let mut expired_group = EntitiesDescriptor::default();
+    expired_group.set_valid_until(Some(Utc::now() - Duration::days(1)));
+    expired_group.add_entity_descriptor(entity("https://old.example.org", Duration::days(7)));
+    entities_descriptor.add_entities_descriptor(expired_group);
+
+    let mut element = XmlObject::try_from(entities_descriptor).unwrap();
+    if signed {
+        sign_enveloped(
+            &mut element,
+            &signing_credential(),
+            SignatureAlgorithm::RSA_SHA256,
+        )
+        .unwrap();
+    }
+    XmlObject::write_xml(Rc::new(RefCell::new(element))).unwrap()
+}
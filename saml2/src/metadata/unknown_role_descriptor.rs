@@ -0,0 +1,180 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
+
+use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+
+use super::role_descriptor::{RoleDescriptor, RoleDescriptorType};
+
+/// `md:RoleDescriptor` extended through `xsi:type` by another specification. The common
+/// part is understood, the elements the extension adds are kept as they are.
+#[derive(Debug, Default)]
+pub struct UnknownRoleDescriptor {
+    role_descriptor_type: RoleDescriptorType,
+    unknown_children: Vec<Rc<RefCell<XmlObject>>>,
+}
+
+impl SAML2Obj for UnknownRoleDescriptor {}
+
+impl UnknownRoleDescriptor {
+    const XSI_TYPE: &'static str = "type";
+    const XSI_NS_URI: &'static str = "http://www.w3.org/2001/XMLSchema-instance";
+
+    pub const ELEMENT_NAME: &'static str = "RoleDescriptor";
+    pub const NS_PREFIX: &'static str = "md";
+    pub const NS_URI: &'static str = "urn:oasis:names:tc:SAML:2.0:metadata";
+
+    /// the `xsi:type` naming the extension, RoleDescriptor itself is abstract
+    pub fn xsi_type(&self) -> Option<&str> {
+        let unknown_attributes = self.role_descriptor_type.unknown_attributes();
+        unknown_attributes.iter().find_map(|(name, value)| {
+            let (prefix, local_name) = name.split_once(':')?;
+            (local_name == UnknownRoleDescriptor::XSI_TYPE
+                && unknown_attributes
+                    .namespaces()
+                    .get(prefix)
+                    .map(|uri| uri.as_str())
+                    == Some(UnknownRoleDescriptor::XSI_NS_URI))
+            .then_some(value)
+        })
+    }
+
+    /// the elements the extension adds after the common part
+    #[inline]
+    pub fn unknown_children(&self) -> &Vec<Rc<RefCell<XmlObject>>> {
+        &self.unknown_children
+    }
+
+    #[inline]
+    pub fn add_unknown_child(&mut self, unknown_child: Rc<RefCell<XmlObject>>) {
+        self.unknown_children.push(unknown_child);
+    }
+}
+
+impl RoleDescriptor for UnknownRoleDescriptor {
+    fn role_descriptor_type(&self) -> &RoleDescriptorType {
+        &self.role_descriptor_type
+    }
+
+    fn role_descriptor_type_mut(&mut self) -> &mut RoleDescriptorType {
+        &mut self.role_descriptor_type
+    }
+
+    fn into_xml_object(self: Box<Self>) -> Result<XmlObject, SAMLError> {
+        XmlObject::try_from(*self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for UnknownRoleDescriptor {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut unknown_role_descriptor = UnknownRoleDescriptor {
+            role_descriptor_type: RoleDescriptorType::from_element(&element, &[])?,
+            ..Default::default()
+        };
+        // the prefix of the extension type name has to stay bound as well
+        let type_binding = unknown_role_descriptor
+            .xsi_type()
+            .and_then(|xsi_type| xsi_type.split_once(':'))
+            .and_then(|(prefix, _)| Some((prefix.to_string(), element.namespace().get(prefix)?)));
+        if let Some((prefix, uri)) = type_binding {
+            unknown_role_descriptor
+                .role_descriptor_type
+                .unknown_attributes_mut()
+                .add_namespace(prefix, uri.to_string());
+        }
+        for child in element.children() {
+            let is_common = {
+                let child = child.borrow();
+                child.q_name().namespace_uri() == Some(UnknownRoleDescriptor::NS_URI)
+                    && RoleDescriptorType::COMMON_CHILDREN.contains(&child.q_name().local_name())
+            };
+            if !is_common {
+                unknown_role_descriptor.add_unknown_child(Rc::clone(child));
+            }
+        }
+        Ok(unknown_role_descriptor)
+    }
+}
+
+impl TryFrom<UnknownRoleDescriptor> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(unknown_role_descriptor: UnknownRoleDescriptor) -> Result<Self, Self::Error> {
+        let mut xml_object = unknown_role_descriptor
+            .role_descriptor_type
+            .into_xml_object(UnknownRoleDescriptor::ELEMENT_NAME)?;
+        for unknown_child in unknown_role_descriptor.unknown_children {
+            xml_object.add_child(unknown_child);
+        }
+        Ok(xml_object)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        metadata::{entity_descriptor::EntityDescriptor, role_descriptor::RoleDescriptor},
+        util::InputStream,
+        xml::XmlObject,
+    };
+
+    const AUTHORITY_METADATA: &str = include_str!("../../tests/resources/authority_metadata.xml");
+    const FED_NS_URI: &str = "http://docs.oasis-open.org/wsfed/federation/200706";
+
+    fn parse(xml: &str) -> EntityDescriptor {
+        let root = XmlObject::parse_xml(InputStream::new(xml.as_bytes().to_vec())).unwrap();
+        let entity_descriptor = EntityDescriptor::try_from(root.borrow()).unwrap();
+        entity_descriptor
+    }
+
+    fn check(entity_descriptor: &EntityDescriptor) {
+        let role = entity_descriptor.unknown_role_descriptors().next().unwrap();
+        assert_eq!(role.xsi_type(), Some("fed:ApplicationServiceType"));
+        let common = role.role_descriptor_type();
+        assert!(common.supports_protocol(FED_NS_URI));
+        assert_eq!(
+            common.unknown_attributes().get("ServiceDisplayName"),
+            Some("Example Application")
+        );
+        assert_eq!(
+            common.contact_persons()[0].email_addresses()[0],
+            "mailto:fed@example.org"
+        );
+        assert_eq!(role.unknown_children().len(), 1);
+        let endpoint = role.unknown_children()[0].borrow();
+        assert_eq!(endpoint.q_name().namespace_uri(), Some(FED_NS_URI));
+        assert_eq!(endpoint.q_name().local_name(), "PassiveRequestorEndpoint");
+    }
+
+    #[test]
+    fn we_can_keep_unknown_role_descriptor() {
+        check(&parse(AUTHORITY_METADATA));
+    }
+
+    #[test]
+    fn we_can_round_trip_unknown_role_descriptor() {
+        let xml_object = XmlObject::try_from(parse(AUTHORITY_METADATA)).unwrap();
+        let xml = XmlObject::write_xml(Rc::new(RefCell::new(xml_object))).unwrap();
+        assert!(xml.contains("https://aa.example.org/wsfed"));
+        let entity_descriptor = parse(&xml);
+        check(&entity_descriptor);
+
+        // the prefix of the extension type stays bound to its namespace
+        let xml_object = XmlObject::try_from(entity_descriptor).unwrap();
+        let role = xml_object.children()[0].borrow();
+        assert_eq!(
+            role.namespace().get("fed").map(String::as_str),
+            Some(FED_NS_URI)
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use rsa::{Pkcs1v15Sign, RsaPrivateKey, RsaPublicKey};
+use sha1::Sha1;
+use sha2::{digest::DynDigest, Digest, Sha256, Sha512};
+
+use crate::error::SAMLError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigestAlgorithm {
+    Sha1,
+    Sha256,
+    Sha512,
+}
+
+impl DigestAlgorithm {
+    pub const SHA1: &'static str = "http://www.w3.org/2000/09/xmldsig#sha1";
+    pub const SHA256: &'static str = "http://www.w3.org/2001/04/xmlenc#sha256";
+    pub const SHA512: &'static str = "http://www.w3.org/2001/04/xmlenc#sha512";
+
+    pub fn from_uri(uri: &str) -> Result<Self, SAMLError> {
+        match uri {
+            Self::SHA1 => Ok(DigestAlgorithm::Sha1),
+            Self::SHA256 => Ok(DigestAlgorithm::Sha256),
+            Self::SHA512 => Ok(DigestAlgorithm::Sha512),
+            _ => Err(SAMLError::SignatureError(format!(
+                "unsupported digest algorithm {}",
+                uri
+            ))),
+        }
+    }
+
+    pub fn uri(&self) -> &'static str {
+        match self {
+            DigestAlgorithm::Sha1 => Self::SHA1,
+            DigestAlgorithm::Sha256 => Self::SHA256,
+            DigestAlgorithm::Sha512 => Self::SHA512,
+        }
+    }
+
+    pub fn digest(&self, data: &[u8]) -> Vec<u8> {
+        match self {
+            DigestAlgorithm::Sha1 => Sha1::digest(data).to_vec(),
+            DigestAlgorithm::Sha256 => Sha256::digest(data).to_vec(),
+            DigestAlgorithm::Sha512 => Sha512::digest(data).to_vec(),
+        }
+    }
+
+    /// a fresh hasher of the algorithm, as the RSA-OAEP padding takes it
+    pub(crate) fn dyn_digest(&self) -> Box<dyn DynDigest + Send + Sync> {
+        match self {
+            DigestAlgorithm::Sha1 => Box::new(Sha1::new()),
+            DigestAlgorithm::Sha256 => Box::new(Sha256::new()),
+            DigestAlgorithm::Sha512 => Box::new(Sha512::new()),
+        }
+    }
+
+    fn pkcs1v15_padding(&self) -> Pkcs1v15Sign {
+        match self {
+            DigestAlgorithm::Sha1 => Pkcs1v15Sign::new::<Sha1>(),
+            DigestAlgorithm::Sha256 => Pkcs1v15Sign::new::<Sha256>(),
+            DigestAlgorithm::Sha512 => Pkcs1v15Sign::new::<Sha512>(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureAlgorithm {
+    RsaSha1,
+    RsaSha256,
+    RsaSha512,
+}
+
+impl SignatureAlgorithm {
+    pub const RSA_SHA1: &'static str = "http://www.w3.org/2000/09/xmldsig#rsa-sha1";
+    pub const RSA_SHA256: &'static str = "http://www.w3.org/2001/04/xmldsig-more#rsa-sha256";
+    pub const RSA_SHA512: &'static str = "http://www.w3.org/2001/04/xmldsig-more#rsa-sha512";
+
+    pub fn from_uri(uri: &str) -> Result<Self, SAMLError> {
+        match uri {
+            Self::RSA_SHA1 => Ok(SignatureAlgorithm::RsaSha1),
+            Self::RSA_SHA256 => Ok(SignatureAlgorithm::RsaSha256),
+            Self::RSA_SHA512 => Ok(SignatureAlgorithm::RsaSha512),
+            _ => Err(SAMLError::SignatureError(format!(
+                "unsupported signature algorithm {}",
+                uri
+            ))),
+        }
+    }
+
+    pub fn uri(&self) -> &'static str {
+        match self {
+            SignatureAlgorithm::RsaSha1 => Self::RSA_SHA1,
+            SignatureAlgorithm::RsaSha256 => Self::RSA_SHA256,
+            SignatureAlgorithm::RsaSha512 => Self::RSA_SHA512,
+        }
+    }
+
+    pub fn digest_algorithm(&self) -> DigestAlgorithm {
+        match self {
+            SignatureAlgorithm::RsaSha1 => DigestAlgorithm::Sha1,
+            SignatureAlgorithm::RsaSha256 => DigestAlgorithm::Sha256,
+            SignatureAlgorithm::RsaSha512 => DigestAlgorithm::Sha512,
+        }
+    }
+
+    /// compute the PKCS#1 v1.5 signature over `data`
+    pub fn sign(&self, private_key: &RsaPrivateKey, data: &[u8]) -> Result<Vec<u8>, SAMLError> {
+        let digest_algorithm = self.digest_algorithm();
+        private_key
+            .sign(
+                digest_algorithm.pkcs1v15_padding(),
+                &digest_algorithm.digest(data),
+            )
+            .map_err(|e| SAMLError::SignatureError(format!("signing failed: {}", e)))
+    }
+
+    /// verify a PKCS#1 v1.5 `signature` over `data`
+    pub fn verify(
+        &self,
+        public_key: &RsaPublicKey,
+        data: &[u8],
+        signature: &[u8],
+    ) -> Result<(), SAMLError> {
+        let digest_algorithm = self.digest_algorithm();
+        public_key
+            .verify(
+                digest_algorithm.pkcs1v15_padding(),
+                &digest_algorithm.digest(data),
+                signature,
+            )
+            .map_err(|_| SAMLError::SignatureError("signature value mismatch".to_string()))
+    }
+}
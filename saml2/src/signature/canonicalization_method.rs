@@ -0,0 +1,103 @@
+use std::{
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
+
+use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+
+use super::inclusive_namespaces::InclusiveNamespaces;
+
+#[derive(Default, Debug)]
+pub struct CanonicalizationMethod {
+    algorithm: String,
+    inclusive_namespaces: Option<InclusiveNamespaces>,
+}
+
+impl SAML2Obj for CanonicalizationMethod {}
+
+impl CanonicalizationMethod {
+    const ATTRIB_ALGORITHM: &'static str = "Algorithm";
+
+    const CHILD_INCLUSIVE_NAMESPACES: &'static str = "InclusiveNamespaces";
+
+    const ELEMENT_NAME: &'static str = "CanonicalizationMethod";
+    const NS_PREFIX: &'static str = "ds";
+    const NS_URI: &'static str = "http://www.w3.org/2000/09/xmldsig#";
+
+    pub fn new(algorithm: String) -> Self {
+        CanonicalizationMethod {
+            algorithm,
+            inclusive_namespaces: None,
+        }
+    }
+
+    #[inline]
+    pub fn algorithm(&self) -> &str {
+        &self.algorithm
+    }
+
+    #[inline]
+    pub fn set_algorithm(&mut self, algorithm: String) {
+        self.algorithm = algorithm;
+    }
+
+    #[inline]
+    pub fn inclusive_namespaces(&self) -> Option<&InclusiveNamespaces> {
+        self.inclusive_namespaces.as_ref()
+    }
+
+    #[inline]
+    pub fn set_inclusive_namespaces(&mut self, inclusive_namespaces: Option<InclusiveNamespaces>) {
+        self.inclusive_namespaces = inclusive_namespaces;
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for CanonicalizationMethod {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut canonicalization_method =
+            match element.attribute(CanonicalizationMethod::ATTRIB_ALGORITHM) {
+                Some(algorithm) => CanonicalizationMethod::new(algorithm.to_string()),
+                None => {
+                    return Err(SAMLError::UnmarshallingError(
+                        "CanonicalizationMethod must have an Algorithm".to_string(),
+                    ))
+                }
+            };
+        for child in element.children() {
+            let child = child.borrow();
+            if child.q_name().local_name() == CanonicalizationMethod::CHILD_INCLUSIVE_NAMESPACES {
+                canonicalization_method
+                    .set_inclusive_namespaces(Some(InclusiveNamespaces::try_from(child)?));
+            }
+        }
+        Ok(canonicalization_method)
+    }
+}
+
+impl TryFrom<CanonicalizationMethod> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(canonicalization_method: CanonicalizationMethod) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(CanonicalizationMethod::NS_URI.to_string()),
+            CanonicalizationMethod::ELEMENT_NAME.to_string(),
+            Some(CanonicalizationMethod::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            CanonicalizationMethod::NS_PREFIX.to_string(),
+            CanonicalizationMethod::NS_URI.to_string(),
+        );
+        xml_object.add_attribute(
+            CanonicalizationMethod::ATTRIB_ALGORITHM.to_string(),
+            canonicalization_method.algorithm,
+        );
+        if let Some(inclusive_namespaces) = canonicalization_method.inclusive_namespaces {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(
+                inclusive_namespaces,
+            )?)));
+        }
+        Ok(xml_object)
+    }
+}
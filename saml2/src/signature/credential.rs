@@ -0,0 +1,108 @@
+use rsa::{
+    pkcs1::DecodeRsaPrivateKey,
+    pkcs8::{DecodePrivateKey, DecodePublicKey},
+    RsaPrivateKey, RsaPublicKey,
+};
+use x509_cert::{
+    der::{Decode, DecodePem, Encode},
+    Certificate,
+};
+
+use crate::error::SAMLError;
+
+/// key material of a peer or of ourselves, backed by an RSA key and
+/// optionally the X.509 certificate it was taken from. Credentials used for
+/// signing or decryption also carry the private key.
+#[derive(Debug, Clone)]
+pub struct Credential {
+    public_key: RsaPublicKey,
+    private_key: Option<RsaPrivateKey>,
+    certificate: Option<Vec<u8>>,
+}
+
+impl Credential {
+    pub fn from_public_key(public_key: RsaPublicKey) -> Self {
+        Credential {
+            public_key,
+            private_key: None,
+            certificate: None,
+        }
+    }
+
+    pub fn from_private_key(private_key: RsaPrivateKey) -> Self {
+        Credential {
+            public_key: private_key.to_public_key(),
+            private_key: Some(private_key),
+            certificate: None,
+        }
+    }
+
+    /// build a credential from a PEM encoded PKCS#8 or PKCS#1 RSA private key
+    pub fn from_private_key_pem(pem: &str) -> Result<Self, SAMLError> {
+        let private_key = RsaPrivateKey::from_pkcs8_pem(pem)
+            .or_else(|_| RsaPrivateKey::from_pkcs1_pem(pem))
+            .map_err(|e| SAMLError::CredentialError(format!("invalid private key: {}", e)))?;
+        Ok(Self::from_private_key(private_key))
+    }
+
+    /// attach the PEM encoded certificate of the private key, it is published in
+    /// the `KeyInfo` of signatures made with this credential
+    pub fn with_certificate_pem(mut self, pem: &str) -> Result<Self, SAMLError> {
+        let certificate = Self::from_certificate_pem(pem)?;
+        if certificate.public_key != self.public_key {
+            return Err(SAMLError::CredentialError(
+                "certificate does not match the private key".to_string(),
+            ));
+        }
+        self.certificate = certificate.certificate;
+        Ok(self)
+    }
+
+    /// build a credential from a DER encoded X.509 certificate
+    pub fn from_certificate_der(der: &[u8]) -> Result<Self, SAMLError> {
+        let certificate = Certificate::from_der(der)
+            .map_err(|e| SAMLError::CredentialError(format!("invalid certificate: {}", e)))?;
+        Self::from_certificate(certificate, der.to_vec())
+    }
+
+    /// build a credential from a PEM encoded X.509 certificate
+    pub fn from_certificate_pem(pem: &str) -> Result<Self, SAMLError> {
+        let certificate = Certificate::from_pem(pem)
+            .map_err(|e| SAMLError::CredentialError(format!("invalid certificate: {}", e)))?;
+        let der = certificate
+            .to_der()
+            .map_err(|e| SAMLError::CredentialError(format!("invalid certificate: {}", e)))?;
+        Self::from_certificate(certificate, der)
+    }
+
+    fn from_certificate(certificate: Certificate, der: Vec<u8>) -> Result<Self, SAMLError> {
+        let public_key_info = certificate
+            .tbs_certificate
+            .subject_public_key_info
+            .to_der()
+            .map_err(|e| SAMLError::CredentialError(format!("invalid public key: {}", e)))?;
+        let public_key = RsaPublicKey::from_public_key_der(&public_key_info)
+            .map_err(|e| SAMLError::CredentialError(format!("unsupported public key: {}", e)))?;
+        Ok(Credential {
+            public_key,
+            private_key: None,
+            certificate: Some(der),
+        })
+    }
+
+    #[inline]
+    pub fn public_key(&self) -> &RsaPublicKey {
+        &self.public_key
+    }
+
+    #[inline]
+    pub fn private_key(&self) -> Option<&RsaPrivateKey> {
+        self.private_key.as_ref()
+    }
+
+    /// DER encoding of the certificate, if the credential was built from one
+    #[inline]
+    pub fn certificate(&self) -> Option<&[u8]> {
+        self.certificate.as_deref()
+    }
+}
@@ -0,0 +1,66 @@
+use std::cell::Ref;
+
+use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+
+#[derive(Default, Debug)]
+pub struct DigestMethod {
+    algorithm: String,
+}
+
+impl SAML2Obj for DigestMethod {}
+
+impl DigestMethod {
+    const ATTRIB_ALGORITHM: &'static str = "Algorithm";
+
+    const ELEMENT_NAME: &'static str = "DigestMethod";
+    const NS_PREFIX: &'static str = "ds";
+    const NS_URI: &'static str = "http://www.w3.org/2000/09/xmldsig#";
+
+    pub fn new(algorithm: String) -> Self {
+        DigestMethod { algorithm }
+    }
+
+    #[inline]
+    pub fn algorithm(&self) -> &str {
+        &self.algorithm
+    }
+
+    #[inline]
+    pub fn set_algorithm(&mut self, algorithm: String) {
+        self.algorithm = algorithm;
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for DigestMethod {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        match element.attribute(DigestMethod::ATTRIB_ALGORITHM) {
+            Some(algorithm) => Ok(DigestMethod::new(algorithm.to_string())),
+            None => Err(SAMLError::UnmarshallingError(
+                "DigestMethod must have an Algorithm".to_string(),
+            )),
+        }
+    }
+}
+
+impl TryFrom<DigestMethod> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(digest_method: DigestMethod) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(DigestMethod::NS_URI.to_string()),
+            DigestMethod::ELEMENT_NAME.to_string(),
+            Some(DigestMethod::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            DigestMethod::NS_PREFIX.to_string(),
+            DigestMethod::NS_URI.to_string(),
+        );
+        xml_object.add_attribute(
+            DigestMethod::ATTRIB_ALGORITHM.to_string(),
+            digest_method.algorithm,
+        );
+        Ok(xml_object)
+    }
+}
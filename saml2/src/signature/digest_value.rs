@@ -0,0 +1,59 @@
+use std::cell::Ref;
+
+use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+
+#[derive(Default, Debug)]
+pub struct DigestValue {
+    value: String,
+}
+
+impl SAML2Obj for DigestValue {}
+
+impl DigestValue {
+    const ELEMENT_NAME: &'static str = "DigestValue";
+    const NS_PREFIX: &'static str = "ds";
+    const NS_URI: &'static str = "http://www.w3.org/2000/09/xmldsig#";
+
+    pub fn new(value: String) -> Self {
+        DigestValue { value }
+    }
+
+    #[inline]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    #[inline]
+    pub fn set_value(&mut self, value: String) {
+        self.value = value;
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for DigestValue {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        match element.text() {
+            Some(value) => Ok(DigestValue::new(value.to_string())),
+            None => Err(SAMLError::UnmarshallingError("Invalid XML".to_string())),
+        }
+    }
+}
+
+impl TryFrom<DigestValue> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(digest_value: DigestValue) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(DigestValue::NS_URI.to_string()),
+            DigestValue::ELEMENT_NAME.to_string(),
+            Some(DigestValue::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            DigestValue::NS_PREFIX.to_string(),
+            DigestValue::NS_URI.to_string(),
+        );
+        xml_object.set_text(Some(digest_value.value));
+        Ok(xml_object)
+    }
+}
@@ -0,0 +1,75 @@
+use std::cell::Ref;
+
+use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+
+/// `ec:InclusiveNamespaces` parameter of the exclusive canonicalization transforms
+#[derive(Default, Debug)]
+pub struct InclusiveNamespaces {
+    prefix_list: String,
+}
+
+impl SAML2Obj for InclusiveNamespaces {}
+
+impl InclusiveNamespaces {
+    const ATTRIB_PREFIX_LIST: &'static str = "PrefixList";
+
+    pub const ELEMENT_NAME: &'static str = "InclusiveNamespaces";
+    pub const NS_PREFIX: &'static str = "ec";
+    pub const NS_URI: &'static str = "http://www.w3.org/2001/10/xml-exc-c14n#";
+
+    pub fn new(prefix_list: String) -> Self {
+        InclusiveNamespaces { prefix_list }
+    }
+
+    #[inline]
+    pub fn prefix_list(&self) -> &str {
+        &self.prefix_list
+    }
+
+    #[inline]
+    pub fn set_prefix_list(&mut self, prefix_list: String) {
+        self.prefix_list = prefix_list;
+    }
+
+    /// the whitespace separated prefixes of the list, `#default` stands for the default namespace
+    pub fn prefixes(&self) -> Vec<String> {
+        self.prefix_list
+            .split_whitespace()
+            .map(|prefix| prefix.to_string())
+            .collect()
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for InclusiveNamespaces {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        match element.attribute(InclusiveNamespaces::ATTRIB_PREFIX_LIST) {
+            Some(prefix_list) => Ok(InclusiveNamespaces::new(prefix_list.to_string())),
+            None => Err(SAMLError::UnmarshallingError(
+                "InclusiveNamespaces must have a PrefixList".to_string(),
+            )),
+        }
+    }
+}
+
+impl TryFrom<InclusiveNamespaces> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(inclusive_namespaces: InclusiveNamespaces) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(InclusiveNamespaces::NS_URI.to_string()),
+            InclusiveNamespaces::ELEMENT_NAME.to_string(),
+            Some(InclusiveNamespaces::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            InclusiveNamespaces::NS_PREFIX.to_string(),
+            InclusiveNamespaces::NS_URI.to_string(),
+        );
+        xml_object.add_attribute(
+            InclusiveNamespaces::ATTRIB_PREFIX_LIST.to_string(),
+            inclusive_namespaces.prefix_list,
+        );
+        Ok(xml_object)
+    }
+}
@@ -1,10 +1,21 @@
-use std::cell::Ref;
+use std::{
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
 
-use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+use crate::{
+    common::SAML2Obj, encryption::encrypted_key::EncryptedKey, error::SAMLError, xml::XmlObject,
+};
+
+use super::{key_name::KeyName, retrieval_method::RetrievalMethod, x509_data::X509Data};
 
 #[derive(Default, Debug)]
 pub struct KeyInfo {
     id: Option<String>,
+    key_names: Vec<KeyName>,
+    x509_datas: Vec<X509Data>,
+    retrieval_methods: Vec<RetrievalMethod>,
+    encrypted_keys: Vec<EncryptedKey>,
     indexed_children: Vec<Box<dyn SAML2Obj>>,
 }
 
@@ -13,6 +24,11 @@ impl SAML2Obj for KeyInfo {}
 impl KeyInfo {
     const ATTRIBUTE_ID: &'static str = "Id";
 
+    const CHILD_KEY_NAME: &'static str = "KeyName";
+    const CHILD_X509_DATA: &'static str = "X509Data";
+    const CHILD_RETRIEVAL_METHOD: &'static str = "RetrievalMethod";
+    const CHILD_ENCRYPTED_KEY: &'static str = "EncryptedKey";
+
     const ELEMENT_NAME: &'static str = "KeyInfo";
     const NS_PREFIX: &'static str = "ds";
     const NS_URI: &'static str = "http://www.w3.org/2000/09/xmldsig#";
@@ -27,6 +43,47 @@ impl KeyInfo {
         self.id = id;
     }
 
+    #[inline]
+    pub fn key_names(&self) -> &Vec<KeyName> {
+        &self.key_names
+    }
+
+    #[inline]
+    pub fn add_key_name(&mut self, key_name: KeyName) {
+        self.key_names.push(key_name);
+    }
+
+    #[inline]
+    pub fn x509_datas(&self) -> &Vec<X509Data> {
+        &self.x509_datas
+    }
+
+    #[inline]
+    pub fn add_x509_data(&mut self, x509_data: X509Data) {
+        self.x509_datas.push(x509_data);
+    }
+
+    #[inline]
+    pub fn retrieval_methods(&self) -> &Vec<RetrievalMethod> {
+        &self.retrieval_methods
+    }
+
+    #[inline]
+    pub fn add_retrieval_method(&mut self, retrieval_method: RetrievalMethod) {
+        self.retrieval_methods.push(retrieval_method);
+    }
+
+    /// the `xenc:EncryptedKey` carrying the key of the enclosing `EncryptedData`
+    #[inline]
+    pub fn encrypted_keys(&self) -> &Vec<EncryptedKey> {
+        &self.encrypted_keys
+    }
+
+    #[inline]
+    pub fn add_encrypted_key(&mut self, encrypted_key: EncryptedKey) {
+        self.encrypted_keys.push(encrypted_key);
+    }
+
     #[inline]
     pub fn indexed_children(&self) -> &Vec<Box<dyn SAML2Obj>> {
         &self.indexed_children
@@ -43,17 +100,26 @@ impl TryFrom<Ref<'_, XmlObject>> for KeyInfo {
 
     fn try_from(value: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
         let mut key_info = KeyInfo::default();
-        for attr in value.attributes() {
-            match attr.0.as_str() {
-                KeyInfo::ATTRIBUTE_ID => {
-                    key_info.set_id(Some(attr.1.to_string()));
-                }
-                _ => {}
-            }
+        if let Some(id) = value.attribute(KeyInfo::ATTRIBUTE_ID) {
+            key_info.set_id(Some(id.to_string()));
         }
         for child in value.children() {
             let child = child.borrow();
-            todo!("Implement conversion from XmlObject to KeyInfo")
+            match child.q_name().local_name() {
+                KeyInfo::CHILD_KEY_NAME => {
+                    key_info.add_key_name(KeyName::try_from(child)?);
+                }
+                KeyInfo::CHILD_X509_DATA => {
+                    key_info.add_x509_data(X509Data::try_from(child)?);
+                }
+                KeyInfo::CHILD_RETRIEVAL_METHOD => {
+                    key_info.add_retrieval_method(RetrievalMethod::try_from(child)?);
+                }
+                KeyInfo::CHILD_ENCRYPTED_KEY => {
+                    key_info.add_encrypted_key(EncryptedKey::try_from(child)?);
+                }
+                _ => {}
+            }
         }
         Ok(key_info)
     }
@@ -64,16 +130,27 @@ impl TryFrom<KeyInfo> for XmlObject {
 
     fn try_from(key_info: KeyInfo) -> Result<Self, Self::Error> {
         let mut xml_object = XmlObject::new(
-            Some(KeyInfo::NS_PREFIX.to_string()),
-            KeyInfo::ELEMENT_NAME.to_string(),
             Some(KeyInfo::NS_URI.to_string()),
+            KeyInfo::ELEMENT_NAME.to_string(),
+            Some(KeyInfo::NS_PREFIX.to_string()),
         );
         xml_object.add_namespace(KeyInfo::NS_PREFIX.to_string(), KeyInfo::NS_URI.to_string());
         if let Some(id) = key_info.id {
             xml_object.add_attribute(KeyInfo::ATTRIBUTE_ID.to_string(), id);
         }
-        for child in key_info.indexed_children {
-            todo!("Implement conversion from KeyInfo to XmlObject")
+        for key_name in key_info.key_names {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(key_name)?)));
+        }
+        for x509_data in key_info.x509_datas {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(x509_data)?)));
+        }
+        for retrieval_method in key_info.retrieval_methods {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(
+                retrieval_method,
+            )?)));
+        }
+        for encrypted_key in key_info.encrypted_keys {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(encrypted_key)?)));
         }
         Ok(xml_object)
     }
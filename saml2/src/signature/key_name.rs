@@ -0,0 +1,56 @@
+use std::cell::Ref;
+
+use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+
+#[derive(Default, Debug)]
+pub struct KeyName {
+    value: String,
+}
+
+impl SAML2Obj for KeyName {}
+
+impl KeyName {
+    const ELEMENT_NAME: &'static str = "KeyName";
+    const NS_PREFIX: &'static str = "ds";
+    const NS_URI: &'static str = "http://www.w3.org/2000/09/xmldsig#";
+
+    pub fn new(value: String) -> Self {
+        KeyName { value }
+    }
+
+    #[inline]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    #[inline]
+    pub fn set_value(&mut self, value: String) {
+        self.value = value;
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for KeyName {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        match element.text() {
+            Some(value) => Ok(KeyName::new(value.to_string())),
+            None => Err(SAMLError::UnmarshallingError("Invalid XML".to_string())),
+        }
+    }
+}
+
+impl TryFrom<KeyName> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(key_name: KeyName) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(KeyName::NS_URI.to_string()),
+            KeyName::ELEMENT_NAME.to_string(),
+            Some(KeyName::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(KeyName::NS_PREFIX.to_string(), KeyName::NS_URI.to_string());
+        xml_object.set_text(Some(key_name.value));
+        Ok(xml_object)
+    }
+}
@@ -1,2 +1,20 @@
+pub mod algorithm;
+pub mod canonicalization_method;
+pub mod credential;
+pub mod digest_method;
+pub mod digest_value;
+pub mod inclusive_namespaces;
 pub mod key_info;
+pub mod key_name;
+pub mod reference;
+pub mod retrieval_method;
+#[allow(clippy::module_inception)]
+pub mod signature;
+pub mod signature_method;
+pub mod signature_value;
+pub mod signed_info;
+pub mod signer;
 pub mod transform;
+pub mod validator;
+pub mod x509_certificate;
+pub mod x509_data;
@@ -0,0 +1,169 @@
+use std::{
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
+
+use crate::{
+    common::SAML2Obj, encryption::transforms::Transforms, error::SAMLError, xml::XmlObject,
+};
+
+use super::{digest_method::DigestMethod, digest_value::DigestValue};
+
+#[derive(Default, Debug)]
+pub struct Reference {
+    id: Option<String>,
+    uri: Option<String>,
+    reference_type: Option<String>,
+    transforms: Option<Transforms>,
+    digest_method: DigestMethod,
+    digest_value: DigestValue,
+}
+
+impl SAML2Obj for Reference {}
+
+impl Reference {
+    const ATTRIB_ID: &'static str = "Id";
+    const ATTRIB_URI: &'static str = "URI";
+    const ATTRIB_TYPE: &'static str = "Type";
+
+    const CHILD_TRANSFORMS: &'static str = "Transforms";
+    const CHILD_DIGEST_METHOD: &'static str = "DigestMethod";
+    const CHILD_DIGEST_VALUE: &'static str = "DigestValue";
+
+    const ELEMENT_NAME: &'static str = "Reference";
+    const NS_PREFIX: &'static str = "ds";
+    const NS_URI: &'static str = "http://www.w3.org/2000/09/xmldsig#";
+
+    #[inline]
+    pub fn id(&self) -> Option<&String> {
+        self.id.as_ref()
+    }
+
+    #[inline]
+    pub fn set_id(&mut self, id: Option<String>) {
+        self.id = id;
+    }
+
+    #[inline]
+    pub fn uri(&self) -> Option<&String> {
+        self.uri.as_ref()
+    }
+
+    #[inline]
+    pub fn set_uri(&mut self, uri: Option<String>) {
+        self.uri = uri;
+    }
+
+    #[inline]
+    pub fn reference_type(&self) -> Option<&String> {
+        self.reference_type.as_ref()
+    }
+
+    #[inline]
+    pub fn set_reference_type(&mut self, reference_type: Option<String>) {
+        self.reference_type = reference_type;
+    }
+
+    #[inline]
+    pub fn transforms(&self) -> Option<&Transforms> {
+        self.transforms.as_ref()
+    }
+
+    #[inline]
+    pub fn set_transforms(&mut self, transforms: Option<Transforms>) {
+        self.transforms = transforms;
+    }
+
+    #[inline]
+    pub fn digest_method(&self) -> &DigestMethod {
+        &self.digest_method
+    }
+
+    #[inline]
+    pub fn set_digest_method(&mut self, digest_method: DigestMethod) {
+        self.digest_method = digest_method;
+    }
+
+    #[inline]
+    pub fn digest_value(&self) -> &DigestValue {
+        &self.digest_value
+    }
+
+    #[inline]
+    pub fn set_digest_value(&mut self, digest_value: DigestValue) {
+        self.digest_value = digest_value;
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for Reference {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut reference = Reference::default();
+        for attribute in element.attributes() {
+            match attribute.0.as_str() {
+                Reference::ATTRIB_ID => {
+                    reference.set_id(Some(attribute.1.to_string()));
+                }
+                Reference::ATTRIB_URI => {
+                    reference.set_uri(Some(attribute.1.to_string()));
+                }
+                Reference::ATTRIB_TYPE => {
+                    reference.set_reference_type(Some(attribute.1.to_string()));
+                }
+                _ => {}
+            }
+        }
+        for child in element.children() {
+            let child = child.borrow();
+            match child.q_name().local_name() {
+                Reference::CHILD_TRANSFORMS => {
+                    reference.set_transforms(Some(Transforms::try_from(child)?));
+                }
+                Reference::CHILD_DIGEST_METHOD => {
+                    reference.set_digest_method(DigestMethod::try_from(child)?);
+                }
+                Reference::CHILD_DIGEST_VALUE => {
+                    reference.set_digest_value(DigestValue::try_from(child)?);
+                }
+                _ => {}
+            }
+        }
+        Ok(reference)
+    }
+}
+
+impl TryFrom<Reference> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(reference: Reference) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(Reference::NS_URI.to_string()),
+            Reference::ELEMENT_NAME.to_string(),
+            Some(Reference::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            Reference::NS_PREFIX.to_string(),
+            Reference::NS_URI.to_string(),
+        );
+        if let Some(id) = reference.id {
+            xml_object.add_attribute(Reference::ATTRIB_ID.to_string(), id);
+        }
+        if let Some(uri) = reference.uri {
+            xml_object.add_attribute(Reference::ATTRIB_URI.to_string(), uri);
+        }
+        if let Some(reference_type) = reference.reference_type {
+            xml_object.add_attribute(Reference::ATTRIB_TYPE.to_string(), reference_type);
+        }
+        if let Some(transforms) = reference.transforms {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(transforms)?)));
+        }
+        xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(
+            reference.digest_method,
+        )?)));
+        xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(
+            reference.digest_value,
+        )?)));
+        Ok(xml_object)
+    }
+}
@@ -0,0 +1,121 @@
+use std::{
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
+
+use crate::{
+    common::SAML2Obj, encryption::transforms::Transforms, error::SAMLError, xml::XmlObject,
+};
+
+/// `ds:RetrievalMethod` referencing key information held elsewhere in the document
+#[derive(Default, Debug)]
+pub struct RetrievalMethod {
+    uri: String,
+    retrieval_type: Option<String>,
+    transforms: Option<Transforms>,
+}
+
+impl SAML2Obj for RetrievalMethod {}
+
+impl RetrievalMethod {
+    const ATTRIB_URI: &'static str = "URI";
+    const ATTRIB_TYPE: &'static str = "Type";
+
+    const CHILD_TRANSFORMS: &'static str = "Transforms";
+
+    pub const TYPE_ENCRYPTED_KEY: &'static str = "http://www.w3.org/2001/04/xmlenc#EncryptedKey";
+
+    const ELEMENT_NAME: &'static str = "RetrievalMethod";
+    const NS_PREFIX: &'static str = "ds";
+    const NS_URI: &'static str = "http://www.w3.org/2000/09/xmldsig#";
+
+    pub fn new(uri: String, retrieval_type: Option<String>) -> Self {
+        RetrievalMethod {
+            uri,
+            retrieval_type,
+            transforms: None,
+        }
+    }
+
+    #[inline]
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    #[inline]
+    pub fn set_uri(&mut self, uri: String) {
+        self.uri = uri;
+    }
+
+    #[inline]
+    pub fn retrieval_type(&self) -> Option<&String> {
+        self.retrieval_type.as_ref()
+    }
+
+    #[inline]
+    pub fn set_retrieval_type(&mut self, retrieval_type: Option<String>) {
+        self.retrieval_type = retrieval_type;
+    }
+
+    #[inline]
+    pub fn transforms(&self) -> Option<&Transforms> {
+        self.transforms.as_ref()
+    }
+
+    #[inline]
+    pub fn set_transforms(&mut self, transforms: Option<Transforms>) {
+        self.transforms = transforms;
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for RetrievalMethod {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let uri = element
+            .attribute(RetrievalMethod::ATTRIB_URI)
+            .ok_or_else(|| {
+                SAMLError::UnmarshallingError("RetrievalMethod must have a URI".to_string())
+            })?;
+        let mut retrieval_method = RetrievalMethod::new(
+            uri.to_string(),
+            element
+                .attribute(RetrievalMethod::ATTRIB_TYPE)
+                .map(|retrieval_type| retrieval_type.to_string()),
+        );
+        for child in element.children() {
+            let child = child.borrow();
+            if child.q_name().local_name() == RetrievalMethod::CHILD_TRANSFORMS {
+                retrieval_method.set_transforms(Some(Transforms::try_from(child)?));
+            }
+        }
+        Ok(retrieval_method)
+    }
+}
+
+impl TryFrom<RetrievalMethod> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(retrieval_method: RetrievalMethod) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(RetrievalMethod::NS_URI.to_string()),
+            RetrievalMethod::ELEMENT_NAME.to_string(),
+            Some(RetrievalMethod::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            RetrievalMethod::NS_PREFIX.to_string(),
+            RetrievalMethod::NS_URI.to_string(),
+        );
+        xml_object.add_attribute(
+            RetrievalMethod::ATTRIB_URI.to_string(),
+            retrieval_method.uri,
+        );
+        if let Some(retrieval_type) = retrieval_method.retrieval_type {
+            xml_object.add_attribute(RetrievalMethod::ATTRIB_TYPE.to_string(), retrieval_type);
+        }
+        if let Some(transforms) = retrieval_method.transforms {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(transforms)?)));
+        }
+        Ok(xml_object)
+    }
+}
@@ -0,0 +1,132 @@
+use std::{
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
+
+use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+
+use super::{key_info::KeyInfo, signature_value::SignatureValue, signed_info::SignedInfo};
+
+#[derive(Default, Debug)]
+pub struct Signature {
+    id: Option<String>,
+    signed_info: SignedInfo,
+    signature_value: SignatureValue,
+    key_info: Option<KeyInfo>,
+}
+
+impl SAML2Obj for Signature {}
+
+impl Signature {
+    const ATTRIB_ID: &'static str = "Id";
+
+    const CHILD_SIGNED_INFO: &'static str = "SignedInfo";
+    const CHILD_SIGNATURE_VALUE: &'static str = "SignatureValue";
+    const CHILD_KEY_INFO: &'static str = "KeyInfo";
+
+    pub const ELEMENT_NAME: &'static str = "Signature";
+    pub const NS_PREFIX: &'static str = "ds";
+    pub const NS_URI: &'static str = "http://www.w3.org/2000/09/xmldsig#";
+
+    #[inline]
+    pub fn id(&self) -> Option<&String> {
+        self.id.as_ref()
+    }
+
+    #[inline]
+    pub fn set_id(&mut self, id: Option<String>) {
+        self.id = id;
+    }
+
+    #[inline]
+    pub fn signed_info(&self) -> &SignedInfo {
+        &self.signed_info
+    }
+
+    #[inline]
+    pub fn set_signed_info(&mut self, signed_info: SignedInfo) {
+        self.signed_info = signed_info;
+    }
+
+    #[inline]
+    pub fn signature_value(&self) -> &SignatureValue {
+        &self.signature_value
+    }
+
+    #[inline]
+    pub fn set_signature_value(&mut self, signature_value: SignatureValue) {
+        self.signature_value = signature_value;
+    }
+
+    #[inline]
+    pub fn key_info(&self) -> Option<&KeyInfo> {
+        self.key_info.as_ref()
+    }
+
+    #[inline]
+    pub fn set_key_info(&mut self, key_info: Option<KeyInfo>) {
+        self.key_info = key_info;
+    }
+
+    /// whether `element` is a `ds:Signature`
+    pub fn is_signature_element(element: &XmlObject) -> bool {
+        element.q_name().local_name() == Signature::ELEMENT_NAME
+            && element.q_name().namespace_uri() == Some(Signature::NS_URI)
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for Signature {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut signature = Signature::default();
+        if let Some(id) = element.attribute(Signature::ATTRIB_ID) {
+            signature.set_id(Some(id.to_string()));
+        }
+        for child in element.children() {
+            let child = child.borrow();
+            match child.q_name().local_name() {
+                Signature::CHILD_SIGNED_INFO => {
+                    signature.set_signed_info(SignedInfo::try_from(child)?);
+                }
+                Signature::CHILD_SIGNATURE_VALUE => {
+                    signature.set_signature_value(SignatureValue::try_from(child)?);
+                }
+                Signature::CHILD_KEY_INFO => {
+                    signature.set_key_info(Some(KeyInfo::try_from(child)?));
+                }
+                _ => {}
+            }
+        }
+        Ok(signature)
+    }
+}
+
+impl TryFrom<Signature> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(signature: Signature) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(Signature::NS_URI.to_string()),
+            Signature::ELEMENT_NAME.to_string(),
+            Some(Signature::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            Signature::NS_PREFIX.to_string(),
+            Signature::NS_URI.to_string(),
+        );
+        if let Some(id) = signature.id {
+            xml_object.add_attribute(Signature::ATTRIB_ID.to_string(), id);
+        }
+        xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(
+            signature.signed_info,
+        )?)));
+        xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(
+            signature.signature_value,
+        )?)));
+        if let Some(key_info) = signature.key_info {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(key_info)?)));
+        }
+        Ok(xml_object)
+    }
+}
@@ -0,0 +1,66 @@
+use std::cell::Ref;
+
+use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+
+#[derive(Default, Debug)]
+pub struct SignatureMethod {
+    algorithm: String,
+}
+
+impl SAML2Obj for SignatureMethod {}
+
+impl SignatureMethod {
+    const ATTRIB_ALGORITHM: &'static str = "Algorithm";
+
+    const ELEMENT_NAME: &'static str = "SignatureMethod";
+    const NS_PREFIX: &'static str = "ds";
+    const NS_URI: &'static str = "http://www.w3.org/2000/09/xmldsig#";
+
+    pub fn new(algorithm: String) -> Self {
+        SignatureMethod { algorithm }
+    }
+
+    #[inline]
+    pub fn algorithm(&self) -> &str {
+        &self.algorithm
+    }
+
+    #[inline]
+    pub fn set_algorithm(&mut self, algorithm: String) {
+        self.algorithm = algorithm;
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for SignatureMethod {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        match element.attribute(SignatureMethod::ATTRIB_ALGORITHM) {
+            Some(algorithm) => Ok(SignatureMethod::new(algorithm.to_string())),
+            None => Err(SAMLError::UnmarshallingError(
+                "SignatureMethod must have an Algorithm".to_string(),
+            )),
+        }
+    }
+}
+
+impl TryFrom<SignatureMethod> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(signature_method: SignatureMethod) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(SignatureMethod::NS_URI.to_string()),
+            SignatureMethod::ELEMENT_NAME.to_string(),
+            Some(SignatureMethod::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            SignatureMethod::NS_PREFIX.to_string(),
+            SignatureMethod::NS_URI.to_string(),
+        );
+        xml_object.add_attribute(
+            SignatureMethod::ATTRIB_ALGORITHM.to_string(),
+            signature_method.algorithm,
+        );
+        Ok(xml_object)
+    }
+}
@@ -0,0 +1,79 @@
+use std::cell::Ref;
+
+use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+
+#[derive(Default, Debug)]
+pub struct SignatureValue {
+    id: Option<String>,
+    value: String,
+}
+
+impl SAML2Obj for SignatureValue {}
+
+impl SignatureValue {
+    const ATTRIB_ID: &'static str = "Id";
+
+    const ELEMENT_NAME: &'static str = "SignatureValue";
+    const NS_PREFIX: &'static str = "ds";
+    const NS_URI: &'static str = "http://www.w3.org/2000/09/xmldsig#";
+
+    pub fn new(value: String) -> Self {
+        SignatureValue { id: None, value }
+    }
+
+    #[inline]
+    pub fn id(&self) -> Option<&String> {
+        self.id.as_ref()
+    }
+
+    #[inline]
+    pub fn set_id(&mut self, id: Option<String>) {
+        self.id = id;
+    }
+
+    #[inline]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    #[inline]
+    pub fn set_value(&mut self, value: String) {
+        self.value = value;
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for SignatureValue {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut signature_value = match element.text() {
+            Some(value) => SignatureValue::new(value.to_string()),
+            None => return Err(SAMLError::UnmarshallingError("Invalid XML".to_string())),
+        };
+        if let Some(id) = element.attribute(SignatureValue::ATTRIB_ID) {
+            signature_value.set_id(Some(id.to_string()));
+        }
+        Ok(signature_value)
+    }
+}
+
+impl TryFrom<SignatureValue> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(signature_value: SignatureValue) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(SignatureValue::NS_URI.to_string()),
+            SignatureValue::ELEMENT_NAME.to_string(),
+            Some(SignatureValue::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            SignatureValue::NS_PREFIX.to_string(),
+            SignatureValue::NS_URI.to_string(),
+        );
+        if let Some(id) = signature_value.id {
+            xml_object.add_attribute(SignatureValue::ATTRIB_ID.to_string(), id);
+        }
+        xml_object.set_text(Some(signature_value.value));
+        Ok(xml_object)
+    }
+}
@@ -0,0 +1,130 @@
+use std::{
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
+
+use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+
+use super::{
+    canonicalization_method::CanonicalizationMethod, reference::Reference,
+    signature_method::SignatureMethod,
+};
+
+#[derive(Default, Debug)]
+pub struct SignedInfo {
+    id: Option<String>,
+    canonicalization_method: CanonicalizationMethod,
+    signature_method: SignatureMethod,
+    references: Vec<Reference>,
+}
+
+impl SAML2Obj for SignedInfo {}
+
+impl SignedInfo {
+    const ATTRIB_ID: &'static str = "Id";
+
+    const CHILD_CANONICALIZATION_METHOD: &'static str = "CanonicalizationMethod";
+    const CHILD_SIGNATURE_METHOD: &'static str = "SignatureMethod";
+    const CHILD_REFERENCE: &'static str = "Reference";
+
+    pub const ELEMENT_NAME: &'static str = "SignedInfo";
+    const NS_PREFIX: &'static str = "ds";
+    const NS_URI: &'static str = "http://www.w3.org/2000/09/xmldsig#";
+
+    #[inline]
+    pub fn id(&self) -> Option<&String> {
+        self.id.as_ref()
+    }
+
+    #[inline]
+    pub fn set_id(&mut self, id: Option<String>) {
+        self.id = id;
+    }
+
+    #[inline]
+    pub fn canonicalization_method(&self) -> &CanonicalizationMethod {
+        &self.canonicalization_method
+    }
+
+    #[inline]
+    pub fn set_canonicalization_method(&mut self, canonicalization_method: CanonicalizationMethod) {
+        self.canonicalization_method = canonicalization_method;
+    }
+
+    #[inline]
+    pub fn signature_method(&self) -> &SignatureMethod {
+        &self.signature_method
+    }
+
+    #[inline]
+    pub fn set_signature_method(&mut self, signature_method: SignatureMethod) {
+        self.signature_method = signature_method;
+    }
+
+    #[inline]
+    pub fn references(&self) -> &Vec<Reference> {
+        &self.references
+    }
+
+    #[inline]
+    pub fn add_reference(&mut self, reference: Reference) {
+        self.references.push(reference);
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for SignedInfo {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut signed_info = SignedInfo::default();
+        if let Some(id) = element.attribute(SignedInfo::ATTRIB_ID) {
+            signed_info.set_id(Some(id.to_string()));
+        }
+        for child in element.children() {
+            let child = child.borrow();
+            match child.q_name().local_name() {
+                SignedInfo::CHILD_CANONICALIZATION_METHOD => {
+                    signed_info
+                        .set_canonicalization_method(CanonicalizationMethod::try_from(child)?);
+                }
+                SignedInfo::CHILD_SIGNATURE_METHOD => {
+                    signed_info.set_signature_method(SignatureMethod::try_from(child)?);
+                }
+                SignedInfo::CHILD_REFERENCE => {
+                    signed_info.add_reference(Reference::try_from(child)?);
+                }
+                _ => {}
+            }
+        }
+        Ok(signed_info)
+    }
+}
+
+impl TryFrom<SignedInfo> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(signed_info: SignedInfo) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(SignedInfo::NS_URI.to_string()),
+            SignedInfo::ELEMENT_NAME.to_string(),
+            Some(SignedInfo::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            SignedInfo::NS_PREFIX.to_string(),
+            SignedInfo::NS_URI.to_string(),
+        );
+        if let Some(id) = signed_info.id {
+            xml_object.add_attribute(SignedInfo::ATTRIB_ID.to_string(), id);
+        }
+        xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(
+            signed_info.canonicalization_method,
+        )?)));
+        xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(
+            signed_info.signature_method,
+        )?)));
+        for reference in signed_info.references {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(reference)?)));
+        }
+        Ok(xml_object)
+    }
+}
@@ -0,0 +1,221 @@
+use std::{cell::RefCell, rc::Rc};
+
+use base64::{prelude::BASE64_STANDARD, Engine};
+
+use crate::{
+    error::SAMLError,
+    xml::{
+        c14n::{CanonicalizationAlgorithm, Canonicalizer},
+        XmlObject,
+    },
+};
+
+use super::{
+    algorithm::SignatureAlgorithm, canonicalization_method::CanonicalizationMethod,
+    credential::Credential, digest_method::DigestMethod, digest_value::DigestValue,
+    key_info::KeyInfo, reference::Reference, signature::Signature,
+    signature_method::SignatureMethod, signed_info::SignedInfo, transform::Transform,
+    validator::ID_ATTRIBUTES, x509_certificate::X509Certificate, x509_data::X509Data,
+};
+use crate::encryption::transforms::Transforms;
+
+const CHILD_ISSUER: &str = "Issuer";
+
+/// Sign `element` with an enveloped `ds:Signature` using the private key of `credential`.
+///
+/// The signature references the element by its ID, applies the enveloped signature and
+/// exclusive canonicalization transforms and is inserted right after the `Issuer` child,
+/// as the SAML schema requires. The certificate of the credential, if any, is published
+/// in the `KeyInfo`.
+pub fn sign_enveloped(
+    element: &mut XmlObject,
+    credential: &Credential,
+    algorithm: &str,
+) -> Result<(), SAMLError> {
+    let signature_algorithm = SignatureAlgorithm::from_uri(algorithm)?;
+    let private_key = credential
+        .private_key()
+        .ok_or_else(|| SAMLError::CredentialError("signing requires a private key".to_string()))?;
+    if element
+        .children()
+        .iter()
+        .any(|child| Signature::is_signature_element(&child.borrow()))
+    {
+        return Err(SAMLError::SignatureError(
+            "element is already signed".to_string(),
+        ));
+    }
+    let element_id = ID_ATTRIBUTES
+        .iter()
+        .find_map(|key| element.attribute(key))
+        .ok_or_else(|| SAMLError::SignatureError("element to sign has no ID".to_string()))?
+        .to_string();
+
+    let digest_algorithm = signature_algorithm.digest_algorithm();
+    let signed_content =
+        Canonicalizer::new(CanonicalizationAlgorithm::Exclusive).canonicalize(element);
+    let digest_value = BASE64_STANDARD.encode(digest_algorithm.digest(signed_content.as_bytes()));
+
+    let mut transforms = Transforms::default();
+    transforms.add_transform(Transform::new(Transform::ENVELOPED_SIGNATURE.to_string()));
+    transforms.add_transform(Transform::new(
+        CanonicalizationAlgorithm::EXCLUSIVE.to_string(),
+    ));
+    let mut reference = Reference::default();
+    reference.set_uri(Some(format!("#{}", element_id)));
+    reference.set_transforms(Some(transforms));
+    reference.set_digest_method(DigestMethod::new(digest_algorithm.uri().to_string()));
+    reference.set_digest_value(DigestValue::new(digest_value));
+
+    let mut signed_info = SignedInfo::default();
+    signed_info.set_canonicalization_method(CanonicalizationMethod::new(
+        CanonicalizationAlgorithm::EXCLUSIVE.to_string(),
+    ));
+    signed_info.set_signature_method(SignatureMethod::new(algorithm.to_string()));
+    signed_info.add_reference(reference);
+
+    let mut signature = Signature::default();
+    signature.set_signed_info(signed_info);
+    if let Some(certificate) = credential.certificate() {
+        let mut x509_data = X509Data::default();
+        x509_data.add_x509_certificate(X509Certificate::new(BASE64_STANDARD.encode(certificate)));
+        let mut key_info = KeyInfo::default();
+        key_info.add_x509_data(x509_data);
+        signature.set_key_info(Some(key_info));
+    }
+
+    // the signature value is filled in once SignedInfo is in its final form
+    let signature_object = XmlObject::try_from(signature)?;
+    let signature_value = {
+        let signed_info_object = signature_object.children()[0].borrow();
+        let canonical_signed_info = Canonicalizer::new(CanonicalizationAlgorithm::Exclusive)
+            .canonicalize(&signed_info_object);
+        signature_algorithm.sign(private_key, canonical_signed_info.as_bytes())?
+    };
+    signature_object.children()[1]
+        .borrow_mut()
+        .set_text(Some(BASE64_STANDARD.encode(signature_value)));
+
+    let position = element
+        .children()
+        .iter()
+        .position(|child| child.borrow().q_name().local_name() == CHILD_ISSUER)
+        .map_or(0, |index| index + 1);
+    element.insert_child(position, Rc::new(RefCell::new(signature_object)));
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        core::{
+            assertion::Assertion, authn_request::AuthnRequest, issuer::Issuer,
+            logout_request::LogoutRequest, name_id::NameID,
+            request_abstract_type::RequestAbstractType, response::Response,
+            saml_version::SAMLVersion, session_index::SessionIndex,
+            status_response_type::StatusResponseType,
+        },
+        signature::{
+            algorithm::SignatureAlgorithm, signature::Signature,
+            validator::validate_enveloped_signature,
+        },
+        util::InputStream,
+        xml::XmlObject,
+    };
+
+    use super::{sign_enveloped, Credential};
+
+    const PRIVATE_KEY: &str = include_str!("../../tests/resources/rsa_private_key.pem");
+    const CERTIFICATE: &str = include_str!("../../tests/resources/rsa_certificate.pem");
+
+    #[test]
+    fn we_can_sign_authn_request_after_issuer() {
+        let credential = Credential::from_private_key_pem(PRIVATE_KEY)
+            .unwrap()
+            .with_certificate_pem(CERTIFICATE)
+            .unwrap();
+        let mut issuer = Issuer::new();
+        issuer.set_value(Some("https://sp.example.org".to_string()));
+        let mut authn_request = AuthnRequest::default();
+        authn_request.set_id("_a3b5f1d0e7".to_string());
+        authn_request.set_version(SAMLVersion::new(2, 0));
+        authn_request.set_issuer(Some(issuer));
+        authn_request
+            .set_assertion_consumer_service_url(Some("https://sp.example.org/acs".to_string()));
+
+        let mut element = XmlObject::try_from(authn_request).unwrap();
+        sign_enveloped(&mut element, &credential, SignatureAlgorithm::RSA_SHA256).unwrap();
+        assert!(Signature::is_signature_element(
+            &element.children()[1].borrow()
+        ));
+
+        let xml = XmlObject::write_xml(Rc::new(RefCell::new(element))).unwrap();
+        let root = XmlObject::parse_xml(InputStream::new(xml.into_bytes())).unwrap();
+        let verifier = Credential::from_certificate_pem(CERTIFICATE).unwrap();
+        validate_enveloped_signature(&root.borrow(), &verifier).unwrap();
+        let authn_request = AuthnRequest::try_from(root.borrow()).unwrap();
+        assert!(authn_request.signature().is_some());
+    }
+
+    #[test]
+    fn we_can_sign_response_without_certificate() {
+        let credential = Credential::from_private_key_pem(PRIVATE_KEY).unwrap();
+        let mut assertion = Assertion::default();
+        assertion.set_id("_assertion".to_string());
+        assertion.set_version(SAMLVersion::new(2, 0));
+        let mut issuer = Issuer::new();
+        issuer.set_value(Some("https://idp.example.org".to_string()));
+        let mut response = Response::default();
+        response.set_id("_response".to_string());
+        response.set_version(SAMLVersion::new(2, 0));
+        response.set_issuer(Some(issuer));
+        response.add_assertion(assertion);
+
+        let mut element = XmlObject::try_from(response).unwrap();
+        sign_enveloped(&mut element, &credential, SignatureAlgorithm::RSA_SHA1).unwrap();
+        let xml = XmlObject::write_xml(Rc::new(RefCell::new(element))).unwrap();
+        let root = XmlObject::parse_xml(InputStream::new(xml.into_bytes())).unwrap();
+        let verifier = Credential::from_certificate_pem(CERTIFICATE).unwrap();
+        let signature = validate_enveloped_signature(&root.borrow(), &verifier).unwrap();
+        assert!(signature.key_info().is_none());
+        let response = Response::try_from(root.borrow()).unwrap();
+        assert_eq!(response.assertions().len(), 1);
+    }
+
+    #[test]
+    fn we_can_sign_logout_request() {
+        let credential = Credential::from_private_key_pem(PRIVATE_KEY)
+            .unwrap()
+            .with_certificate_pem(CERTIFICATE)
+            .unwrap();
+        let mut issuer = Issuer::new();
+        issuer.set_value(Some("https://sp.example.org".to_string()));
+        let mut name_id = NameID::default();
+        name_id.set_value(Some("alice".to_string()));
+        let mut session_index = SessionIndex::default();
+        session_index.set_value("_session".to_string());
+        let mut logout_request = LogoutRequest::default();
+        logout_request.set_id("_c4d2e8f1a9".to_string());
+        logout_request.set_version(SAMLVersion::new(2, 0));
+        logout_request.set_issuer(Some(issuer));
+        logout_request.set_name_id(Some(name_id));
+        logout_request.add_session_index(session_index);
+
+        let mut element = XmlObject::try_from(logout_request).unwrap();
+        sign_enveloped(&mut element, &credential, SignatureAlgorithm::RSA_SHA256).unwrap();
+        assert!(Signature::is_signature_element(
+            &element.children()[1].borrow()
+        ));
+
+        let xml = XmlObject::write_xml(Rc::new(RefCell::new(element))).unwrap();
+        let root = XmlObject::parse_xml(InputStream::new(xml.into_bytes())).unwrap();
+        let verifier = Credential::from_certificate_pem(CERTIFICATE).unwrap();
+        validate_enveloped_signature(&root.borrow(), &verifier).unwrap();
+        let logout_request = LogoutRequest::try_from(root.borrow()).unwrap();
+        assert!(logout_request.signature().is_some());
+        assert_eq!(logout_request.name_id().unwrap().value().unwrap(), "alice");
+        assert_eq!(logout_request.session_indexes().len(), 1);
+    }
+}
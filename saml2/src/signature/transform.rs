@@ -1,9 +1,108 @@
-use crate::common::SAML2Obj;
+use std::{
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
+
+use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+
+use super::inclusive_namespaces::InclusiveNamespaces;
 
 #[derive(Default, Debug)]
 pub struct Transform {
     algorithm: String,
+    inclusive_namespaces: Option<InclusiveNamespaces>,
     indexed_children: Vec<Box<dyn SAML2Obj>>,
 }
 
 impl SAML2Obj for Transform {}
+
+impl Transform {
+    pub const ENVELOPED_SIGNATURE: &'static str =
+        "http://www.w3.org/2000/09/xmldsig#enveloped-signature";
+
+    const ATTRIB_ALGORITHM: &'static str = "Algorithm";
+
+    const CHILD_INCLUSIVE_NAMESPACES: &'static str = "InclusiveNamespaces";
+
+    const ELEMENT_NAME: &'static str = "Transform";
+    const NS_PREFIX: &'static str = "ds";
+    const NS_URI: &'static str = "http://www.w3.org/2000/09/xmldsig#";
+
+    pub fn new(algorithm: String) -> Self {
+        Transform {
+            algorithm,
+            inclusive_namespaces: None,
+            indexed_children: Vec::new(),
+        }
+    }
+
+    #[inline]
+    pub fn algorithm(&self) -> &str {
+        &self.algorithm
+    }
+
+    #[inline]
+    pub fn set_algorithm(&mut self, algorithm: String) {
+        self.algorithm = algorithm;
+    }
+
+    #[inline]
+    pub fn inclusive_namespaces(&self) -> Option<&InclusiveNamespaces> {
+        self.inclusive_namespaces.as_ref()
+    }
+
+    #[inline]
+    pub fn set_inclusive_namespaces(&mut self, inclusive_namespaces: Option<InclusiveNamespaces>) {
+        self.inclusive_namespaces = inclusive_namespaces;
+    }
+
+    #[inline]
+    pub fn indexed_children(&self) -> &Vec<Box<dyn SAML2Obj>> {
+        &self.indexed_children
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for Transform {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut transform = match element.attribute(Transform::ATTRIB_ALGORITHM) {
+            Some(algorithm) => Transform::new(algorithm.to_string()),
+            None => {
+                return Err(SAMLError::UnmarshallingError(
+                    "Transform must have an Algorithm".to_string(),
+                ))
+            }
+        };
+        for child in element.children() {
+            let child = child.borrow();
+            if child.q_name().local_name() == Transform::CHILD_INCLUSIVE_NAMESPACES {
+                transform.set_inclusive_namespaces(Some(InclusiveNamespaces::try_from(child)?));
+            }
+        }
+        Ok(transform)
+    }
+}
+
+impl TryFrom<Transform> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(transform: Transform) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(Transform::NS_URI.to_string()),
+            Transform::ELEMENT_NAME.to_string(),
+            Some(Transform::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            Transform::NS_PREFIX.to_string(),
+            Transform::NS_URI.to_string(),
+        );
+        xml_object.add_attribute(Transform::ATTRIB_ALGORITHM.to_string(), transform.algorithm);
+        if let Some(inclusive_namespaces) = transform.inclusive_namespaces {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(
+                inclusive_namespaces,
+            )?)));
+        }
+        Ok(xml_object)
+    }
+}
@@ -0,0 +1,223 @@
+use std::cell::Ref;
+
+use base64::{prelude::BASE64_STANDARD, Engine};
+
+use crate::{
+    error::SAMLError,
+    xml::{
+        c14n::{CanonicalizationAlgorithm, Canonicalizer},
+        XmlObject,
+    },
+};
+
+use super::{
+    algorithm::{DigestAlgorithm, SignatureAlgorithm},
+    credential::Credential,
+    signature::Signature,
+    signed_info::SignedInfo,
+    transform::Transform,
+};
+
+pub(crate) const ID_ATTRIBUTES: [&str; 3] = ["ID", "Id", "id"];
+
+/// Verify the enveloped `ds:Signature` of `element` against a trusted `credential`.
+///
+/// The signature has to be a direct child of `element` and its single `Reference`
+/// must point at the ID of `element` itself, so a valid signature over some other
+/// part of the document can not be passed off for this element.
+pub fn validate_enveloped_signature(
+    element: &XmlObject,
+    credential: &Credential,
+) -> Result<Signature, SAMLError> {
+    let signature_element = element
+        .children()
+        .iter()
+        .find(|child| Signature::is_signature_element(&child.borrow()))
+        .ok_or_else(|| SAMLError::SignatureError("element is not signed".to_string()))?;
+    let signature_object = signature_element.borrow();
+    let signature = Signature::try_from(signature_element.borrow())?;
+
+    let element_id = ID_ATTRIBUTES
+        .iter()
+        .find_map(|key| element.attribute(key))
+        .ok_or_else(|| SAMLError::SignatureError("signed element has no ID".to_string()))?;
+    let reference = EnvelopedReference::of(&signature, element_id)?;
+    let signed_content = Canonicalizer::new(reference.c14n_algorithm)
+        .exclude(&signature_object)
+        .inclusive_prefixes(reference.inclusive_prefixes.clone())
+        .canonicalize(element);
+    reference.check_digest(&reference.digest_algorithm.digest(signed_content.as_bytes()))?;
+
+    verify_signed_info(element, &signature_object, &signature, credential)?;
+    Ok(signature)
+}
+
+/// how the single `Reference` of an enveloped signature digests the signed element
+pub(crate) struct EnvelopedReference {
+    pub(crate) c14n_algorithm: CanonicalizationAlgorithm,
+    pub(crate) inclusive_prefixes: Vec<String>,
+    pub(crate) digest_algorithm: DigestAlgorithm,
+    digest_value: Vec<u8>,
+}
+
+impl EnvelopedReference {
+    /// The reference of `signature`, which must point at `element_id` and apply the
+    /// enveloped signature and a canonicalization transform.
+    pub(crate) fn of(signature: &Signature, element_id: &str) -> Result<Self, SAMLError> {
+        let reference = match signature.signed_info().references().as_slice() {
+            [reference] => reference,
+            _ => {
+                return Err(SAMLError::SignatureError(
+                    "signature must contain exactly one reference".to_string(),
+                ))
+            }
+        };
+        match reference.uri().and_then(|uri| uri.strip_prefix('#')) {
+            Some(uri) if uri == element_id => {}
+            _ => {
+                return Err(SAMLError::SignatureError(
+                    "reference does not point at the signed element".to_string(),
+                ))
+            }
+        }
+
+        let mut enveloped = false;
+        let mut c14n_algorithm = None;
+        let mut inclusive_prefixes = Vec::new();
+        if let Some(transforms) = reference.transforms() {
+            for transform in transforms.transforms() {
+                match transform.algorithm() {
+                    Transform::ENVELOPED_SIGNATURE => enveloped = true,
+                    algorithm => {
+                        // a bare ID reference drops comments whatever the transform says
+                        c14n_algorithm =
+                            Some(canonicalization_algorithm(algorithm)?.without_comments());
+                        inclusive_prefixes = transform
+                            .inclusive_namespaces()
+                            .map(|inclusive_namespaces| inclusive_namespaces.prefixes())
+                            .unwrap_or_default();
+                    }
+                }
+            }
+        }
+        if !enveloped {
+            return Err(SAMLError::SignatureError(
+                "reference is missing the enveloped signature transform".to_string(),
+            ));
+        }
+        let c14n_algorithm = c14n_algorithm.ok_or_else(|| {
+            SAMLError::SignatureError(
+                "reference is missing a canonicalization transform".to_string(),
+            )
+        })?;
+        Ok(EnvelopedReference {
+            c14n_algorithm,
+            inclusive_prefixes,
+            digest_algorithm: DigestAlgorithm::from_uri(reference.digest_method().algorithm())?,
+            digest_value: decode_base64(reference.digest_value().value())?,
+        })
+    }
+
+    /// compare the digest of the signed content with the one of the reference
+    pub(crate) fn check_digest(&self, digest: &[u8]) -> Result<(), SAMLError> {
+        if digest != self.digest_value {
+            return Err(SAMLError::SignatureError(
+                "digest value mismatch".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Verify the `SignatureValue` of `signature` over its canonical `SignedInfo`, `document`
+/// is an element containing the signature.
+pub(crate) fn verify_signed_info(
+    document: &XmlObject,
+    signature_object: &XmlObject,
+    signature: &Signature,
+    credential: &Credential,
+) -> Result<(), SAMLError> {
+    let signed_info = signature.signed_info();
+    let signed_info_object = signature_object
+        .children()
+        .iter()
+        .find(|child| child.borrow().q_name().local_name() == SignedInfo::ELEMENT_NAME)
+        .map(|child| child.borrow())
+        .ok_or_else(|| SAMLError::SignatureError("signature has no SignedInfo".to_string()))?;
+    let canonical_signed_info =
+        canonicalize_signed_info(document, &signed_info_object, signed_info)?;
+    let signature_algorithm =
+        SignatureAlgorithm::from_uri(signed_info.signature_method().algorithm())?;
+    signature_algorithm.verify(
+        credential.public_key(),
+        canonical_signed_info.as_bytes(),
+        &decode_base64(signature.signature_value().value())?,
+    )
+}
+
+fn canonicalize_signed_info(
+    element: &XmlObject,
+    signed_info_object: &Ref<'_, XmlObject>,
+    signed_info: &SignedInfo,
+) -> Result<String, SAMLError> {
+    let canonicalization_method = signed_info.canonicalization_method();
+    let algorithm = canonicalization_algorithm(canonicalization_method.algorithm())?;
+    let inclusive_prefixes = canonicalization_method
+        .inclusive_namespaces()
+        .map(|inclusive_namespaces| inclusive_namespaces.prefixes())
+        .unwrap_or_default();
+    Canonicalizer::new(algorithm)
+        .inclusive_prefixes(inclusive_prefixes)
+        .canonicalize_subtree(element, signed_info_object)
+        .ok_or_else(|| {
+            SAMLError::SignatureError("SignedInfo is not part of the element".to_string())
+        })
+}
+
+fn canonicalization_algorithm(uri: &str) -> Result<CanonicalizationAlgorithm, SAMLError> {
+    CanonicalizationAlgorithm::from_uri(uri).ok_or_else(|| {
+        SAMLError::SignatureError(format!("unsupported canonicalization algorithm {}", uri))
+    })
+}
+
+/// base64 values in signatures are commonly wrapped over several lines
+fn decode_base64(value: &str) -> Result<Vec<u8>, SAMLError> {
+    let value: String = value.chars().filter(|c| !c.is_whitespace()).collect();
+    BASE64_STANDARD
+        .decode(value)
+        .map_err(|_| SAMLError::SignatureError("invalid base64 value".to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{core::assertion::Assertion, util::InputStream, xml::XmlObject};
+
+    use super::{validate_enveloped_signature, Credential};
+
+    const SIGNED_ASSERTION: &str = include_str!("../../tests/resources/signed_assertion.xml");
+    const CERTIFICATE: &str = include_str!("../../tests/resources/rsa_certificate.pem");
+
+    #[test]
+    fn we_can_validate_enveloped_signature_of_assertion() {
+        let credential = Credential::from_certificate_pem(CERTIFICATE).unwrap();
+        let root =
+            XmlObject::parse_xml(InputStream::new(SIGNED_ASSERTION.as_bytes().to_vec())).unwrap();
+        let signature = validate_enveloped_signature(&root.borrow(), &credential).unwrap();
+        assert_eq!(
+            signature.signed_info().references()[0].uri().unwrap(),
+            "#_d71a3a8e9fcc45c9e9d248ef7049393fc8f04e5f75"
+        );
+
+        let assertion = Assertion::try_from(root.borrow()).unwrap();
+        let key_info = assertion.signature().unwrap().key_info().unwrap();
+        assert_eq!(key_info.x509_datas()[0].x509_certificates().len(), 1);
+    }
+
+    #[test]
+    fn we_reject_tampered_assertion() {
+        let credential = Credential::from_certificate_pem(CERTIFICATE).unwrap();
+        let forged = SIGNED_ASSERTION.replace("alice@example.org", "mallory@example.org");
+        let root = XmlObject::parse_xml(InputStream::new(forged.into_bytes())).unwrap();
+        assert!(validate_enveloped_signature(&root.borrow(), &credential).is_err());
+    }
+}
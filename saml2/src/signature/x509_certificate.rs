@@ -0,0 +1,59 @@
+use std::cell::Ref;
+
+use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+
+#[derive(Default, Debug)]
+pub struct X509Certificate {
+    value: String,
+}
+
+impl SAML2Obj for X509Certificate {}
+
+impl X509Certificate {
+    const ELEMENT_NAME: &'static str = "X509Certificate";
+    const NS_PREFIX: &'static str = "ds";
+    const NS_URI: &'static str = "http://www.w3.org/2000/09/xmldsig#";
+
+    pub fn new(value: String) -> Self {
+        X509Certificate { value }
+    }
+
+    #[inline]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    #[inline]
+    pub fn set_value(&mut self, value: String) {
+        self.value = value;
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for X509Certificate {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        match element.text() {
+            Some(value) => Ok(X509Certificate::new(value.to_string())),
+            None => Err(SAMLError::UnmarshallingError("Invalid XML".to_string())),
+        }
+    }
+}
+
+impl TryFrom<X509Certificate> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(x509_certificate: X509Certificate) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(X509Certificate::NS_URI.to_string()),
+            X509Certificate::ELEMENT_NAME.to_string(),
+            Some(X509Certificate::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            X509Certificate::NS_PREFIX.to_string(),
+            X509Certificate::NS_URI.to_string(),
+        );
+        xml_object.set_text(Some(x509_certificate.value));
+        Ok(xml_object)
+    }
+}
@@ -0,0 +1,70 @@
+use std::{
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
+
+use crate::{common::SAML2Obj, error::SAMLError, xml::XmlObject};
+
+use super::x509_certificate::X509Certificate;
+
+#[derive(Default, Debug)]
+pub struct X509Data {
+    x509_certificates: Vec<X509Certificate>,
+}
+
+impl SAML2Obj for X509Data {}
+
+impl X509Data {
+    const CHILD_X509_CERTIFICATE: &'static str = "X509Certificate";
+
+    const ELEMENT_NAME: &'static str = "X509Data";
+    const NS_PREFIX: &'static str = "ds";
+    const NS_URI: &'static str = "http://www.w3.org/2000/09/xmldsig#";
+
+    #[inline]
+    pub fn x509_certificates(&self) -> &Vec<X509Certificate> {
+        &self.x509_certificates
+    }
+
+    #[inline]
+    pub fn add_x509_certificate(&mut self, x509_certificate: X509Certificate) {
+        self.x509_certificates.push(x509_certificate);
+    }
+}
+
+impl TryFrom<Ref<'_, XmlObject>> for X509Data {
+    type Error = SAMLError;
+
+    fn try_from(element: Ref<'_, XmlObject>) -> Result<Self, Self::Error> {
+        let mut x509_data = X509Data::default();
+        for child in element.children() {
+            let child = child.borrow();
+            if child.q_name().local_name() == X509Data::CHILD_X509_CERTIFICATE {
+                x509_data.add_x509_certificate(X509Certificate::try_from(child)?);
+            }
+        }
+        Ok(x509_data)
+    }
+}
+
+impl TryFrom<X509Data> for XmlObject {
+    type Error = SAMLError;
+
+    fn try_from(x509_data: X509Data) -> Result<Self, Self::Error> {
+        let mut xml_object = XmlObject::new(
+            Some(X509Data::NS_URI.to_string()),
+            X509Data::ELEMENT_NAME.to_string(),
+            Some(X509Data::NS_PREFIX.to_string()),
+        );
+        xml_object.add_namespace(
+            X509Data::NS_PREFIX.to_string(),
+            X509Data::NS_URI.to_string(),
+        );
+        for x509_certificate in x509_data.x509_certificates {
+            xml_object.add_child(Rc::new(RefCell::new(XmlObject::try_from(
+                x509_certificate,
+            )?)));
+        }
+        Ok(xml_object)
+    }
+}
@@ -1,4 +1,8 @@
-use std::io::Read;
+use std::{collections::BTreeMap, io::Read};
+
+use aes_gcm::aead::{rand_core::RngCore, OsRng};
+
+use crate::xml::XmlObject;
 
 pub struct InputStream {
     buf: Vec<u8>,
@@ -18,7 +22,7 @@ impl InputStream {
 
 impl Read for InputStream {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
-        if buf.len() == 0 || self.reach_end() {
+        if buf.is_empty() || self.reach_end() {
             return std::io::Result::Ok(0);
         }
         let (buf_len, pos_end) = (buf.len(), self.buf.len());
@@ -26,13 +30,98 @@ impl Read for InputStream {
             true => buf_len,
             false => pos_end - self.pos,
         };
-        for i in 0..r_len {
-            buf[i] = self.buf[self.pos + i];
-        }
+        buf[..r_len].copy_from_slice(&self.buf[self.pos..self.pos + r_len]);
         self.pos += r_len;
         std::io::Result::Ok(r_len)
     }
 }
 
-#[derive(Default, Debug)]
-pub struct AttributeMap {}
+/// a random `xs:ID`, 160 bits of randomness prefixed with an underscore
+pub fn generate_id() -> String {
+    let mut octets = [0u8; 20];
+    OsRng.fill_bytes(&mut octets);
+    let hex: String = octets
+        .iter()
+        .map(|octet| format!("{:02x}", octet))
+        .collect();
+    format!("_{}", hex)
+}
+
+/// xml attributes of an element which are not modelled by its type, e.g. those allowed by
+/// `xs:anyAttribute`. Attributes are keyed by their qualified name, the bindings of the
+/// prefixes they use are kept alongside.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct AttributeMap {
+    attributes: Vec<(String, String)>,
+    namespaces: BTreeMap<String, String>,
+}
+
+impl AttributeMap {
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// set the attribute `name`, replacing its former value
+    pub fn insert(&mut self, name: String, value: String) {
+        match self.attributes.iter_mut().find(|(key, _)| *key == name) {
+            Some(attribute) => attribute.1 = value,
+            None => self.attributes.push((name, value)),
+        }
+    }
+
+    /// take the attribute `name` out of the map
+    pub fn remove(&mut self, name: &str) -> Option<String> {
+        let index = self.attributes.iter().position(|(key, _)| key == name)?;
+        Some(self.attributes.remove(index).1)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.attributes
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.attributes.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty()
+    }
+
+    /// the namespace bindings of the prefixes used by the attribute names
+    #[inline]
+    pub fn namespaces(&self) -> &BTreeMap<String, String> {
+        &self.namespaces
+    }
+
+    #[inline]
+    pub fn add_namespace(&mut self, prefix: String, uri: String) {
+        self.namespaces.insert(prefix, uri);
+    }
+
+    /// keep the attribute `name` of `element` together with the binding of its prefix
+    pub(crate) fn add_attribute_of(&mut self, element: &XmlObject, name: &str, value: &str) {
+        if let Some((prefix, _)) = name.split_once(':') {
+            if let Some(uri) = element.namespace().get(prefix) {
+                self.add_namespace(prefix.to_string(), uri.to_string());
+            }
+        }
+        self.insert(name.to_string(), value.to_string());
+    }
+
+    /// add the attributes and the bindings of their prefixes to `element`
+    pub(crate) fn write_to(self, element: &mut XmlObject) {
+        for (prefix, uri) in self.namespaces {
+            element.add_namespace(prefix, uri);
+        }
+        for (name, value) in self.attributes {
+            element.add_attribute(name, value);
+        }
+    }
+}
@@ -0,0 +1,665 @@
+use std::collections::BTreeMap;
+
+use super::{split_at_comments, CharacterData, XmlObject};
+
+const NS_XML_PREFIX: &str = "xml";
+const NS_XML_URI: &str = "http://www.w3.org/XML/1998/namespace";
+const NS_XMLNS_PREFIX: &str = "xmlns";
+const NS_DEFAULT_PREFIX: &str = "";
+const PREFIX_LIST_DEFAULT: &str = "#default";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CanonicalizationAlgorithm {
+    Inclusive,
+    InclusiveWithComments,
+    Inclusive11,
+    Inclusive11WithComments,
+    Exclusive,
+    ExclusiveWithComments,
+}
+
+impl CanonicalizationAlgorithm {
+    pub const INCLUSIVE: &'static str = "http://www.w3.org/TR/2001/REC-xml-c14n-20010315";
+    pub const INCLUSIVE_WITH_COMMENTS: &'static str =
+        "http://www.w3.org/TR/2001/REC-xml-c14n-20010315#WithComments";
+    pub const INCLUSIVE_11: &'static str = "http://www.w3.org/2006/12/xml-c14n11";
+    pub const INCLUSIVE_11_WITH_COMMENTS: &'static str =
+        "http://www.w3.org/2006/12/xml-c14n11#WithComments";
+    pub const EXCLUSIVE: &'static str = "http://www.w3.org/2001/10/xml-exc-c14n#";
+    pub const EXCLUSIVE_WITH_COMMENTS: &'static str =
+        "http://www.w3.org/2001/10/xml-exc-c14n#WithComments";
+
+    pub fn from_uri(uri: &str) -> Option<Self> {
+        match uri {
+            Self::INCLUSIVE => Some(CanonicalizationAlgorithm::Inclusive),
+            Self::INCLUSIVE_WITH_COMMENTS => Some(CanonicalizationAlgorithm::InclusiveWithComments),
+            Self::INCLUSIVE_11 => Some(CanonicalizationAlgorithm::Inclusive11),
+            Self::INCLUSIVE_11_WITH_COMMENTS => {
+                Some(CanonicalizationAlgorithm::Inclusive11WithComments)
+            }
+            Self::EXCLUSIVE => Some(CanonicalizationAlgorithm::Exclusive),
+            Self::EXCLUSIVE_WITH_COMMENTS => Some(CanonicalizationAlgorithm::ExclusiveWithComments),
+            _ => None,
+        }
+    }
+
+    pub fn uri(&self) -> &'static str {
+        match self {
+            CanonicalizationAlgorithm::Inclusive => Self::INCLUSIVE,
+            CanonicalizationAlgorithm::InclusiveWithComments => Self::INCLUSIVE_WITH_COMMENTS,
+            CanonicalizationAlgorithm::Inclusive11 => Self::INCLUSIVE_11,
+            CanonicalizationAlgorithm::Inclusive11WithComments => Self::INCLUSIVE_11_WITH_COMMENTS,
+            CanonicalizationAlgorithm::Exclusive => Self::EXCLUSIVE,
+            CanonicalizationAlgorithm::ExclusiveWithComments => Self::EXCLUSIVE_WITH_COMMENTS,
+        }
+    }
+
+    pub fn is_exclusive(&self) -> bool {
+        matches!(
+            self,
+            CanonicalizationAlgorithm::Exclusive | CanonicalizationAlgorithm::ExclusiveWithComments
+        )
+    }
+
+    pub fn with_comments(&self) -> bool {
+        matches!(
+            self,
+            CanonicalizationAlgorithm::InclusiveWithComments
+                | CanonicalizationAlgorithm::Inclusive11WithComments
+                | CanonicalizationAlgorithm::ExclusiveWithComments
+        )
+    }
+
+    /// the same algorithm leaving comments out, a same document reference by ID
+    /// drops comments whatever the transform says
+    pub fn without_comments(&self) -> Self {
+        match self {
+            CanonicalizationAlgorithm::InclusiveWithComments => {
+                CanonicalizationAlgorithm::Inclusive
+            }
+            CanonicalizationAlgorithm::Inclusive11WithComments => {
+                CanonicalizationAlgorithm::Inclusive11
+            }
+            CanonicalizationAlgorithm::ExclusiveWithComments => {
+                CanonicalizationAlgorithm::Exclusive
+            }
+            algorithm => *algorithm,
+        }
+    }
+
+    /// the `xml:` attributes an apex element inherits from its ancestors
+    fn inherited_xml_attributes(&self) -> &'static [&'static str] {
+        match self {
+            CanonicalizationAlgorithm::Inclusive
+            | CanonicalizationAlgorithm::InclusiveWithComments => {
+                &["xml:base", "xml:id", "xml:lang", "xml:space"]
+            }
+            CanonicalizationAlgorithm::Inclusive11
+            | CanonicalizationAlgorithm::Inclusive11WithComments => {
+                &["xml:base", "xml:lang", "xml:space"]
+            }
+            _ => &[],
+        }
+    }
+}
+
+/// serializes a `XmlObject` subtree into its canonical form
+pub struct Canonicalizer<'a> {
+    algorithm: CanonicalizationAlgorithm,
+    excluded: Option<&'a XmlObject>,
+    inclusive_prefixes: Vec<String>,
+}
+
+impl<'a> Canonicalizer<'a> {
+    pub fn new(algorithm: CanonicalizationAlgorithm) -> Self {
+        Canonicalizer {
+            algorithm,
+            excluded: None,
+            inclusive_prefixes: Vec::new(),
+        }
+    }
+
+    #[inline]
+    pub fn algorithm(&self) -> CanonicalizationAlgorithm {
+        self.algorithm
+    }
+
+    /// leave the given element (and its subtree) out of the output, this is how the
+    /// enveloped signature transform removes the `ds:Signature` element
+    pub fn exclude(mut self, element: &'a XmlObject) -> Self {
+        self.excluded = Some(element);
+        self
+    }
+
+    /// the `InclusiveNamespaces` PrefixList of exclusive canonicalization, these prefixes
+    /// are rendered the way inclusive canonicalization does. `#default` is the default namespace
+    pub fn inclusive_prefixes(mut self, prefixes: Vec<String>) -> Self {
+        self.inclusive_prefixes = prefixes
+            .into_iter()
+            .map(|prefix| match prefix.as_str() {
+                PREFIX_LIST_DEFAULT => NS_DEFAULT_PREFIX.to_string(),
+                _ => prefix,
+            })
+            .collect();
+        self
+    }
+
+    pub fn canonicalize(&self, element: &XmlObject) -> String {
+        let mut output = String::new();
+        self.write_element(
+            element,
+            &BTreeMap::new(),
+            &BTreeMap::new(),
+            &[],
+            &mut output,
+        );
+        output
+    }
+
+    /// canonicalize `element` as a subtree of `document`, with inclusive canonicalization
+    /// the apex inherits the `xml:` attributes of its ancestors. `None` if `element` is not
+    /// part of `document`
+    pub fn canonicalize_subtree(
+        &self,
+        document: &XmlObject,
+        element: &XmlObject,
+    ) -> Option<String> {
+        let mut inherited = Vec::new();
+        if !std::ptr::eq(document, element)
+            && !collect_inherited_attributes(
+                document,
+                element,
+                self.algorithm.inherited_xml_attributes(),
+                &mut inherited,
+            )
+        {
+            return None;
+        }
+        let mut output = String::new();
+        self.write_element(
+            element,
+            &BTreeMap::new(),
+            &BTreeMap::new(),
+            &inherited,
+            &mut output,
+        );
+        Some(output)
+    }
+
+    fn write_element(
+        &self,
+        element: &XmlObject,
+        parent_scope: &BTreeMap<String, String>,
+        rendered: &BTreeMap<String, String>,
+        inherited: &[(String, String)],
+        output: &mut String,
+    ) {
+        let (scope, rendered_here) =
+            self.write_start_tag(element, parent_scope, rendered, inherited, output);
+
+        self.write_character_data(element.text(), element, 0, output);
+        for (index, child) in element.children().iter().enumerate() {
+            let child = child.borrow();
+            let is_excluded = self
+                .excluded
+                .filter(|excluded| std::ptr::eq(*excluded, &*child))
+                .is_some();
+            if !is_excluded {
+                self.write_element(&child, &scope, &rendered_here, &[], output);
+            }
+            self.write_character_data(child.tail(), element, index + 1, output);
+        }
+
+        write_end_tag(&element.q_name().to_string(), output);
+    }
+
+    /// write the start tag of `element`, it returns the namespaces in scope at the element
+    /// and those rendered by it or its ancestors
+    fn write_start_tag(
+        &self,
+        element: &XmlObject,
+        parent_scope: &BTreeMap<String, String>,
+        rendered: &BTreeMap<String, String>,
+        inherited: &[(String, String)],
+        output: &mut String,
+    ) -> (BTreeMap<String, String>, BTreeMap<String, String>) {
+        let scope = in_scope_namespaces(element, parent_scope);
+
+        let mut prefixes: Vec<String> = if self.algorithm.is_exclusive() {
+            let mut prefixes = vec![element
+                .q_name()
+                .prefix()
+                .unwrap_or(NS_DEFAULT_PREFIX)
+                .to_string()];
+            for (key, _) in element.attributes() {
+                if let Some((prefix, _)) = key.split_once(':') {
+                    if prefix != NS_XML_PREFIX {
+                        prefixes.push(prefix.to_string());
+                    }
+                }
+            }
+            prefixes.extend(
+                self.inclusive_prefixes
+                    .iter()
+                    .filter(|prefix| scope.contains_key(*prefix))
+                    .cloned(),
+            );
+            prefixes
+        } else {
+            scope.keys().cloned().collect()
+        };
+        prefixes.sort();
+        prefixes.dedup();
+
+        let mut rendered_here = rendered.clone();
+        let mut namespace_decls = Vec::new();
+        for prefix in &prefixes {
+            let uri = scope.get(prefix).map(|s| s.as_str()).unwrap_or("");
+            let already = rendered.get(prefix).map(|s| s.as_str());
+            let emit = if prefix == NS_DEFAULT_PREFIX {
+                match uri {
+                    "" => already.filter(|u| !u.is_empty()).is_some(),
+                    _ => already != Some(uri),
+                }
+            } else {
+                !uri.is_empty() && already != Some(uri)
+            };
+            if emit {
+                namespace_decls.push((prefix.clone(), uri.to_string()));
+                rendered_here.insert(prefix.clone(), uri.to_string());
+            }
+        }
+
+        let mut own_attributes: Vec<(&str, &str)> = element
+            .attributes()
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect();
+        for (key, value) in inherited {
+            if element.attribute(key).is_none() {
+                own_attributes.push((key.as_str(), value.as_str()));
+            }
+        }
+        let mut attributes: Vec<(&str, &str, &str, &str)> = own_attributes
+            .into_iter()
+            .map(|(key, value)| {
+                let (ns_uri, local_name) = match key.split_once(':') {
+                    Some((NS_XML_PREFIX, local_name)) => (NS_XML_URI, local_name),
+                    Some((prefix, local_name)) => (
+                        scope.get(prefix).map(|s| s.as_str()).unwrap_or(""),
+                        local_name,
+                    ),
+                    None => ("", key),
+                };
+                (ns_uri, local_name, key, value)
+            })
+            .collect();
+        attributes.sort_by(|a, b| (a.0, a.1).cmp(&(b.0, b.1)));
+
+        let qualified_name = element.q_name().to_string();
+        output.push('<');
+        output.push_str(&qualified_name);
+        for (prefix, uri) in &namespace_decls {
+            if prefix.is_empty() {
+                output.push_str(" xmlns=\"");
+            } else {
+                output.push_str(" xmlns:");
+                output.push_str(prefix);
+                output.push_str("=\"");
+            }
+            escape_attribute_value(uri, output);
+            output.push('"');
+        }
+        for (_, _, key, value) in &attributes {
+            output.push(' ');
+            output.push_str(key);
+            output.push_str("=\"");
+            escape_attribute_value(value, output);
+            output.push('"');
+        }
+        output.push('>');
+        (scope, rendered_here)
+    }
+
+    fn write_character_data(
+        &self,
+        data: Option<&String>,
+        element: &XmlObject,
+        segment: usize,
+        output: &mut String,
+    ) {
+        for part in split_at_comments(data, element.comments_in(segment)) {
+            match part {
+                CharacterData::Text(text) => escape_text(text, output),
+                CharacterData::Comment(comment) if self.algorithm.with_comments() => {
+                    output.push_str("<!--");
+                    output.push_str(comment);
+                    output.push_str("-->");
+                }
+                CharacterData::Comment(_) => {}
+            }
+        }
+    }
+}
+
+/// One element canonicalized from the events of a streaming parse, without holding the
+/// element in memory. The output of each event is appended to the given buffer.
+///
+/// Elements are given without their content, as `XmlObject::from_start_element` builds
+/// them. Comments and processing instructions are not part of the output, as a same
+/// document reference drops them.
+pub struct StreamCanonicalizer<'a> {
+    canonicalizer: Canonicalizer<'a>,
+    open_elements: Vec<OpenElement>,
+}
+
+/// an element whose start tag is written and the end tag is not
+struct OpenElement {
+    qualified_name: String,
+    scope: BTreeMap<String, String>,
+    rendered: BTreeMap<String, String>,
+}
+
+impl<'a> StreamCanonicalizer<'a> {
+    pub fn new(canonicalizer: Canonicalizer<'a>) -> Self {
+        StreamCanonicalizer {
+            canonicalizer,
+            open_elements: Vec::new(),
+        }
+    }
+
+    pub fn start_element(&mut self, element: &XmlObject, output: &mut String) {
+        let empty = BTreeMap::new();
+        let (parent_scope, rendered) = match self.open_elements.last() {
+            Some(parent) => (&parent.scope, &parent.rendered),
+            None => (&empty, &empty),
+        };
+        let (scope, rendered) =
+            self.canonicalizer
+                .write_start_tag(element, parent_scope, rendered, &[], output);
+        self.open_elements.push(OpenElement {
+            qualified_name: element.q_name().to_string(),
+            scope,
+            rendered,
+        });
+    }
+
+    /// character data of the innermost open element, outside of the apex it is dropped
+    pub fn characters(&mut self, data: &str, output: &mut String) {
+        if !self.open_elements.is_empty() {
+            escape_text(data, output);
+        }
+    }
+
+    pub fn end_element(&mut self, output: &mut String) {
+        if let Some(element) = self.open_elements.pop() {
+            write_end_tag(&element.qualified_name, output);
+        }
+    }
+}
+
+fn write_end_tag(qualified_name: &str, output: &mut String) {
+    output.push_str("</");
+    output.push_str(qualified_name);
+    output.push('>');
+}
+
+/// walk down from `node` to `target` and collect the given `xml:` attributes of the
+/// ancestors of `target`, the nearest ancestor wins. xml:base values are taken as they
+/// are, without resolving them against each other
+fn collect_inherited_attributes(
+    node: &XmlObject,
+    target: &XmlObject,
+    keys: &[&str],
+    inherited: &mut Vec<(String, String)>,
+) -> bool {
+    let found = node.children().iter().any(|child| {
+        let child = child.borrow();
+        std::ptr::eq(&*child, target)
+            || collect_inherited_attributes(&child, target, keys, inherited)
+    });
+    if found {
+        for key in keys {
+            if let Some(value) = node.attribute(key) {
+                if !inherited.iter().any(|(k, _)| k == key) {
+                    inherited.push((key.to_string(), value.to_string()));
+                }
+            }
+        }
+    }
+    found
+}
+
+/// the namespace bindings visible at `element`, parsed elements carry their whole in scope
+/// mapping while built ones only know the bindings added to them and their own name
+fn in_scope_namespaces(
+    element: &XmlObject,
+    parent_scope: &BTreeMap<String, String>,
+) -> BTreeMap<String, String> {
+    let mut scope = parent_scope.clone();
+    for (prefix, uri) in element.namespace() {
+        if prefix != NS_XML_PREFIX && prefix != NS_XMLNS_PREFIX {
+            scope.insert(prefix.clone(), uri.clone());
+        }
+    }
+    let prefix = element.q_name().prefix().unwrap_or(NS_DEFAULT_PREFIX);
+    match element.q_name().namespace_uri() {
+        Some(uri) => {
+            scope.insert(prefix.to_string(), uri.to_string());
+        }
+        None if prefix.is_empty() => {
+            scope.insert(prefix.to_string(), String::new());
+        }
+        None => {}
+    }
+    scope
+}
+
+fn escape_text(text: &str, output: &mut String) {
+    for c in text.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '\r' => output.push_str("&#xD;"),
+            _ => output.push(c),
+        }
+    }
+}
+
+fn escape_attribute_value(value: &str, output: &mut String) {
+    for c in value.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '"' => output.push_str("&quot;"),
+            '\t' => output.push_str("&#x9;"),
+            '\n' => output.push_str("&#xA;"),
+            '\r' => output.push_str("&#xD;"),
+            _ => output.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use xml::{
+        reader::{ParserConfig, XmlEvent},
+        EventReader,
+    };
+
+    use crate::{util::InputStream, xml::XmlObject};
+
+    use super::{CanonicalizationAlgorithm, Canonicalizer, StreamCanonicalizer};
+
+    const NAMESPACES_IN_SUBTREE: &str = r#"<n0:local xmlns:n0="foo:bar" xmlns:n3="ftp://example.org"><n1:elem2 xmlns:n1="http://example.net" xml:lang="en"><n3:stuff xmlns:n3="ftp://example.org"/></n1:elem2></n0:local>"#;
+
+    fn parse(xml: &str) -> std::rc::Rc<std::cell::RefCell<XmlObject>> {
+        XmlObject::parse_xml(InputStream::new(xml.as_bytes().to_vec())).unwrap()
+    }
+
+    #[test]
+    fn we_can_exclusive_canonicalize_a_subtree() {
+        let xml = r#"<n0:local xmlns:n0="foo:bar" xmlns:n3="ftp://example.org"><n1:elem2 xmlns:n1="http://example.net" xml:lang="en" b="2" a="1">
+  <n3:stuff xmlns:n3="ftp://example.org"/>
+</n1:elem2></n0:local>"#;
+        let root = parse(xml);
+        let root = root.borrow();
+        let elem2 = root.children()[0].borrow();
+        let canonical =
+            Canonicalizer::new(CanonicalizationAlgorithm::Exclusive).canonicalize(&elem2);
+        assert_eq!(
+            canonical,
+            "<n1:elem2 xmlns:n1=\"http://example.net\" a=\"1\" b=\"2\" xml:lang=\"en\">\n  <n3:stuff xmlns:n3=\"ftp://example.org\"></n3:stuff>\n</n1:elem2>"
+        );
+    }
+
+    #[test]
+    fn we_can_inclusive_canonicalize_a_subtree() {
+        // example of section 2.2 of the exclusive canonicalization recommendation
+        let root = parse(NAMESPACES_IN_SUBTREE);
+        let root = root.borrow();
+        let elem2 = root.children()[0].borrow();
+        let canonical = Canonicalizer::new(CanonicalizationAlgorithm::Inclusive)
+            .canonicalize_subtree(&root, &elem2)
+            .unwrap();
+        assert_eq!(
+            canonical,
+            r#"<n1:elem2 xmlns:n0="foo:bar" xmlns:n1="http://example.net" xmlns:n3="ftp://example.org" xml:lang="en"><n3:stuff></n3:stuff></n1:elem2>"#
+        );
+
+        let xml = r#"<n2:pdu xmlns:n1="http://example.com" xmlns:n2="http://foo.example" xml:lang="fr" xml:space="retain"><n1:elem2 xmlns:n1="http://example.net" xml:lang="en"><n3:stuff xmlns:n3="ftp://example.org"/></n1:elem2></n2:pdu>"#;
+        let root = parse(xml);
+        let root = root.borrow();
+        let elem2 = root.children()[0].borrow();
+        let canonical = Canonicalizer::new(CanonicalizationAlgorithm::Inclusive11)
+            .canonicalize_subtree(&root, &elem2)
+            .unwrap();
+        assert_eq!(
+            canonical,
+            r#"<n1:elem2 xmlns:n1="http://example.net" xmlns:n2="http://foo.example" xml:lang="en" xml:space="retain"><n3:stuff xmlns:n3="ftp://example.org"></n3:stuff></n1:elem2>"#
+        );
+        let canonical = Canonicalizer::new(CanonicalizationAlgorithm::Exclusive)
+            .canonicalize_subtree(&root, &elem2)
+            .unwrap();
+        assert_eq!(
+            canonical,
+            r#"<n1:elem2 xmlns:n1="http://example.net" xml:lang="en"><n3:stuff xmlns:n3="ftp://example.org"></n3:stuff></n1:elem2>"#
+        );
+    }
+
+    #[test]
+    fn we_can_canonicalize_start_and_end_tags() {
+        // example 3.3 of the canonical XML recommendation, without the DTD default attribute
+        let xml = r#"<doc>
+   <e1   />
+   <e2   ></e2>
+   <e3   name = "elem3"   id="elem3"   />
+   <e4   name="elem4"   id="elem4"   ></e4>
+   <e5 a:attr="out" b:attr="sorted" attr2="all" attr="I'm"
+      xmlns:b="http://www.ietf.org"
+      xmlns:a="http://www.w3.org"
+      xmlns="http://example.org"/>
+   <e6 xmlns="" xmlns:a="http://www.w3.org">
+      <e7 xmlns="http://www.ietf.org">
+         <e8 xmlns="" xmlns:a="http://www.w3.org">
+            <e9 xmlns="" xmlns:a="http://www.ietf.org"/>
+         </e8>
+      </e7>
+   </e6>
+</doc>"#;
+        let expected = r#"<doc>
+   <e1></e1>
+   <e2></e2>
+   <e3 id="elem3" name="elem3"></e3>
+   <e4 id="elem4" name="elem4"></e4>
+   <e5 xmlns="http://example.org" xmlns:a="http://www.w3.org" xmlns:b="http://www.ietf.org" attr="I'm" attr2="all" b:attr="sorted" a:attr="out"></e5>
+   <e6 xmlns:a="http://www.w3.org">
+      <e7 xmlns="http://www.ietf.org">
+         <e8 xmlns="">
+            <e9 xmlns:a="http://www.ietf.org"></e9>
+         </e8>
+      </e7>
+   </e6>
+</doc>"#;
+        let root = parse(xml);
+        let canonical =
+            Canonicalizer::new(CanonicalizationAlgorithm::Inclusive).canonicalize(&root.borrow());
+        assert_eq!(canonical, expected);
+    }
+
+    #[test]
+    fn we_can_canonicalize_with_and_without_comments() {
+        let xml = "<doc>\n<!-- c1 -->text<!--c2--><e a=\"1\"/>tail &amp; more<!--c3-->\n</doc>";
+        let root = parse(xml);
+        let root = root.borrow();
+        assert_eq!(
+            Canonicalizer::new(CanonicalizationAlgorithm::ExclusiveWithComments)
+                .canonicalize(&root),
+            "<doc>\n<!-- c1 -->text<!--c2--><e a=\"1\"></e>tail &amp; more<!--c3-->\n</doc>"
+        );
+        assert_eq!(
+            Canonicalizer::new(CanonicalizationAlgorithm::Exclusive).canonicalize(&root),
+            "<doc>\ntext<e a=\"1\"></e>tail &amp; more\n</doc>"
+        );
+    }
+
+    #[test]
+    fn we_can_exclusive_canonicalize_with_inclusive_prefixes() {
+        let xml = r#"<saml2:Assertion xmlns:saml2="urn:oasis:names:tc:SAML:2.0:assertion" xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"><saml2:AttributeValue xsi:type="xs:string">a</saml2:AttributeValue></saml2:Assertion>"#;
+        let root = parse(xml);
+        let root = root.borrow();
+        let value = root.children()[0].borrow();
+        assert_eq!(
+            Canonicalizer::new(CanonicalizationAlgorithm::Exclusive).canonicalize(&value),
+            r#"<saml2:AttributeValue xmlns:saml2="urn:oasis:names:tc:SAML:2.0:assertion" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:type="xs:string">a</saml2:AttributeValue>"#
+        );
+        assert_eq!(
+            Canonicalizer::new(CanonicalizationAlgorithm::Exclusive)
+                .inclusive_prefixes(vec!["xs".to_string(), "#default".to_string()])
+                .canonicalize(&value),
+            r#"<saml2:AttributeValue xmlns:saml2="urn:oasis:names:tc:SAML:2.0:assertion" xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:type="xs:string">a</saml2:AttributeValue>"#
+        );
+    }
+
+    #[test]
+    fn we_can_canonicalize_a_stream_like_its_tree() {
+        let xml = r#"<n0:local xmlns:n0="foo:bar" xmlns:n3="ftp://example.org" a="x&amp;y"><!-- note -->
+  <n1:elem2 xmlns:n1="http://example.net" xml:lang="en" b="2" a="1">a &lt; b<![CDATA[ & c]]></n1:elem2>
+  <n3:stuff xmlns="urn:default"><leaf/></n3:stuff>
+</n0:local>"#;
+        for algorithm in [
+            CanonicalizationAlgorithm::Exclusive,
+            CanonicalizationAlgorithm::Inclusive,
+        ] {
+            let mut canonicalizer = StreamCanonicalizer::new(Canonicalizer::new(algorithm));
+            let mut output = String::new();
+            let reader = EventReader::new_with_config(
+                xml.as_bytes(),
+                ParserConfig::new().ignore_comments(false),
+            );
+            for event in reader {
+                match event.unwrap() {
+                    XmlEvent::StartElement {
+                        name,
+                        attributes,
+                        namespace,
+                    } => canonicalizer.start_element(
+                        &XmlObject::from_start_element(&name, &attributes, &namespace),
+                        &mut output,
+                    ),
+                    XmlEvent::EndElement { .. } => canonicalizer.end_element(&mut output),
+                    XmlEvent::Characters(s) | XmlEvent::Whitespace(s) | XmlEvent::CData(s) => {
+                        canonicalizer.characters(&s, &mut output)
+                    }
+                    _ => {}
+                }
+            }
+            let root = parse(xml);
+            assert_eq!(
+                output,
+                Canonicalizer::new(algorithm).canonicalize(&root.borrow())
+            );
+        }
+    }
+}
@@ -2,22 +2,94 @@ use std::{
     cell::RefCell,
     collections::BTreeMap,
     fmt,
-    io::Read,
-    rc::{Rc, Weak},
+    io::{Read, Write},
+    rc::Rc,
 };
 
-use xml::{reader::XmlEvent, EventReader, EventWriter};
+use xml::{
+    attribute::OwnedAttribute,
+    name::OwnedName,
+    namespace::Namespace,
+    reader::{ParserConfig, XmlEvent},
+    EventReader, EventWriter,
+};
 
-use self::q_name::QName;
+pub use self::q_name::QName;
 
+pub mod c14n;
 mod q_name;
 
+#[derive(Debug)]
 pub struct XmlObject {
     children: Vec<Rc<RefCell<XmlObject>>>,
     attributes: Vec<(String, String)>,
     q_name: QName,
     namespace: BTreeMap<String, String>,
     text: Option<String>,
+    tail: Option<String>,
+    comments: Vec<XmlComment>,
+}
+
+/// a comment inside an element. `segment` is the character data it interrupts, 0 is the
+/// element text and `i` the tail of child `i - 1`, `offset` is the byte offset into it
+#[derive(Debug, Clone)]
+pub struct XmlComment {
+    segment: usize,
+    offset: usize,
+    value: String,
+}
+
+/// a piece of character data, either text or the value of a comment
+pub(crate) enum CharacterData<'a> {
+    Text(&'a str),
+    Comment(&'a str),
+}
+
+/// split the character `data` of a segment at the `comments` inside it
+pub(crate) fn split_at_comments<'a>(
+    data: Option<&'a String>,
+    comments: impl Iterator<Item = &'a XmlComment>,
+) -> Vec<CharacterData<'a>> {
+    let data = data.map(|s| s.as_str()).unwrap_or("");
+    let mut parts = Vec::new();
+    let mut start = 0;
+    for comment in comments {
+        let offset = comment.offset.clamp(start, data.len());
+        if offset > start {
+            parts.push(CharacterData::Text(&data[start..offset]));
+        }
+        parts.push(CharacterData::Comment(comment.value()));
+        start = offset;
+    }
+    if start < data.len() {
+        parts.push(CharacterData::Text(&data[start..]));
+    }
+    parts
+}
+
+impl XmlComment {
+    pub fn new(segment: usize, offset: usize, value: String) -> Self {
+        XmlComment {
+            segment,
+            offset,
+            value,
+        }
+    }
+
+    #[inline]
+    pub fn segment(&self) -> usize {
+        self.segment
+    }
+
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    #[inline]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
 }
 
 pub struct XmlError {
@@ -54,6 +126,8 @@ impl XmlObject {
             namespace: BTreeMap::new(),
             q_name: QName::new(namespace_uri, local_name, prefix),
             text: None,
+            tail: None,
+            comments: Vec::new(),
         }
     }
 
@@ -77,11 +151,45 @@ impl XmlObject {
         self.text = text;
     }
 
+    /// character data following the end tag of this element, up to the next sibling
+    #[inline]
+    pub fn tail(&self) -> Option<&String> {
+        self.tail.as_ref()
+    }
+
+    #[inline]
+    pub fn set_tail(&mut self, tail: Option<String>) {
+        self.tail = tail;
+    }
+
+    #[inline]
+    pub fn comments(&self) -> &Vec<XmlComment> {
+        &self.comments
+    }
+
+    #[inline]
+    pub fn add_comment(&mut self, comment: XmlComment) {
+        self.comments.push(comment);
+    }
+
+    /// the comments interrupting the character data of `segment`, see `XmlComment`
+    pub fn comments_in(&self, segment: usize) -> impl Iterator<Item = &XmlComment> {
+        self.comments.iter().filter(move |c| c.segment == segment)
+    }
+
     #[inline]
     pub fn attributes(&self) -> &Vec<(String, String)> {
         &self.attributes
     }
 
+    /// look up an attribute by its qualified name, e.g. `ID` or `xsi:type`
+    pub fn attribute(&self, key: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
     #[inline]
     pub fn add_attribute(&mut self, key: String, value: String) {
         self.attributes.push((key, value));
@@ -97,6 +205,11 @@ impl XmlObject {
         self.children.push(child);
     }
 
+    #[inline]
+    pub fn insert_child(&mut self, index: usize, child: Rc<RefCell<XmlObject>>) {
+        self.children.insert(index, child);
+    }
+
     #[inline]
     pub fn namespace(&self) -> &BTreeMap<String, String> {
         &self.namespace
@@ -108,54 +221,13 @@ impl XmlObject {
     }
 
     pub fn parse_xml<I: Read>(input: I) -> Result<Rc<RefCell<XmlObject>>, XmlError> {
-        let reader = EventReader::new(input);
-        let mut xml_objects: Vec<Rc<RefCell<XmlObject>>> = Vec::new();
+        let reader =
+            EventReader::new_with_config(input, ParserConfig::new().ignore_comments(false));
+        let mut builder = XmlTreeBuilder::default();
         for e in reader {
-            match e {
-                Ok(XmlEvent::StartElement {
-                    name,
-                    attributes,
-                    namespace,
-                }) => {
-                    let mut object = XmlObject::new(
-                        name.namespace.clone(),
-                        name.local_name.clone(),
-                        name.prefix.clone(),
-                    );
-                    for (prefix, uri) in &namespace.borrow().0 {
-                        object.add_namespace(prefix.clone(), uri.clone());
-                    }
-                    for attribute in &attributes {
-                        object.add_attribute(
-                            attribute.name.local_name.clone(),
-                            attribute.value.clone(),
-                        );
-                    }
-                    xml_objects.push(Rc::new(RefCell::new(object)));
-                }
-                Ok(XmlEvent::EndElement { .. }) => {
-                    if xml_objects.is_empty() {
-                        return Err(XmlError {
-                            message: String::from("invalid xml document"),
-                        });
-                    }
-                    let child = xml_objects.pop().unwrap();
-                    if let Some(parent) = xml_objects.last() {
-                        parent.borrow_mut().children.push(child);
-                    } else {
-                        return Ok(child);
-                    }
-                }
-                Ok(XmlEvent::Characters(s)) => {
-                    if xml_objects.is_empty() {
-                        return Err(XmlError {
-                            message: String::from("invalid xml document"),
-                        });
-                    }
-                    let parent = xml_objects.last().unwrap();
-                    parent.borrow_mut().text = Some(s);
-                }
-                _ => {}
+            let event = e.map_err(|e| XmlError::new(e.to_string()))?;
+            if let Some(root) = builder.push(event)? {
+                return Ok(root);
             }
         }
         Err(XmlError {
@@ -163,6 +235,53 @@ impl XmlObject {
         })
     }
 
+    /// the element opened by a `StartElement` event, without content
+    pub(crate) fn from_start_element(
+        name: &OwnedName,
+        attributes: &[OwnedAttribute],
+        namespace: &Namespace,
+    ) -> Self {
+        let mut object = XmlObject::new(
+            name.namespace.clone(),
+            name.local_name.clone(),
+            name.prefix.clone(),
+        );
+        for (prefix, uri) in &namespace.0 {
+            object.add_namespace(prefix.clone(), uri.clone());
+        }
+        for attribute in attributes {
+            let key = match attribute.name.prefix {
+                Some(ref prefix) => format!("{}:{}", prefix, attribute.name.local_name),
+                None => attribute.name.local_name.clone(),
+            };
+            object.add_attribute(key, attribute.value.clone());
+        }
+        object
+    }
+
+    /// character data goes to the element text until the first child element is seen,
+    /// after that it belongs to the tail of the last child
+    fn append_character_data(&mut self, data: &str) {
+        match self.children.last() {
+            Some(last_child) => last_child
+                .borrow_mut()
+                .tail
+                .get_or_insert_with(String::new)
+                .push_str(data),
+            None => self.text.get_or_insert_with(String::new).push_str(data),
+        }
+    }
+
+    /// comments are positioned in the character data seen so far, like `append_character_data`
+    fn append_comment(&mut self, value: String) {
+        let segment = self.children.len();
+        let offset = match self.children.last() {
+            Some(last_child) => last_child.borrow().tail().map_or(0, |tail| tail.len()),
+            None => self.text().map_or(0, |text| text.len()),
+        };
+        self.comments.push(XmlComment::new(segment, offset, value));
+    }
+
     pub fn write_xml(root: Rc<RefCell<XmlObject>>) -> Result<String, XmlError> {
         let mut output: Vec<u8> = Vec::new();
         let mut writer = EventWriter::new(&mut output);
@@ -180,6 +299,24 @@ impl XmlObject {
                 writer
                     .write(event)
                     .map_err(|_| XmlError::new(String::from("invalid xml document")))?;
+                // the parent is the closest element on the stack whose start tag is written
+                let parent = xml_objects
+                    .iter()
+                    .rev()
+                    .find(|(_, is_visited)| *is_visited)
+                    .map(|(parent, _)| Rc::clone(parent));
+                if let Some(parent) = parent {
+                    let parent = parent.borrow();
+                    let segment = parent
+                        .children()
+                        .iter()
+                        .position(|child| Rc::ptr_eq(child, &xml_object))
+                        .map_or(0, |index| index + 1);
+                    write_character_data(
+                        &mut writer,
+                        split_at_comments(borrow_object.tail(), parent.comments_in(segment)),
+                    )?;
+                }
             } else {
                 let name = xml::name::Name {
                     local_name: borrow_object.q_name().local_name(),
@@ -187,7 +324,16 @@ impl XmlObject {
                     prefix: borrow_object.q_name().prefix(),
                 };
                 let mut start_element = xml::writer::XmlEvent::start_element(name);
-                for ns in borrow_object.namespace() {
+                // the binding of the element's own prefix is always declared when no
+                // ancestor did, built objects do not necessarily add it themselves
+                let mut namespace = borrow_object.namespace().clone();
+                if let Some(uri) = borrow_object.q_name().namespace_uri() {
+                    let prefix = borrow_object.q_name().prefix().unwrap_or("");
+                    namespace
+                        .entry(prefix.to_string())
+                        .or_insert_with(|| uri.to_string());
+                }
+                for ns in &namespace {
                     let mut is_exist = false;
                     for (xml_object, v) in &xml_objects {
                         if !v {
@@ -214,13 +360,12 @@ impl XmlObject {
                 writer
                     .write(xml::writer::XmlEvent::from(start_element))
                     .map_err(|_| XmlError::new(String::from("invalid xml document")))?;
-                if let Some(text) = borrow_object.text() {
-                    writer
-                        .write(xml::writer::XmlEvent::characters(text.as_str()))
-                        .map_err(|_| XmlError::new(String::from("invalid xml document")))?;
-                }
+                write_character_data(
+                    &mut writer,
+                    split_at_comments(borrow_object.text(), borrow_object.comments_in(0)),
+                )?;
                 xml_objects.push((Rc::clone(&xml_object), true));
-                for child in borrow_object.children() {
+                for child in borrow_object.children().iter().rev() {
                     xml_objects.push((Rc::clone(child), false));
                 }
             }
@@ -231,6 +376,91 @@ impl XmlObject {
     }
 }
 
+/// assembles a `XmlObject` tree from the events of a streaming parse
+#[derive(Default)]
+pub(crate) struct XmlTreeBuilder {
+    xml_objects: Vec<Rc<RefCell<XmlObject>>>,
+}
+
+impl XmlTreeBuilder {
+    /// feed `event`, the root is returned once its end tag is seen
+    pub(crate) fn push(
+        &mut self,
+        event: XmlEvent,
+    ) -> Result<Option<Rc<RefCell<XmlObject>>>, XmlError> {
+        match event {
+            XmlEvent::StartElement {
+                name,
+                attributes,
+                namespace,
+            } => self.start(XmlObject::from_start_element(
+                &name,
+                &attributes,
+                &namespace,
+            )),
+            XmlEvent::EndElement { .. } => return self.end(),
+            XmlEvent::Characters(s) | XmlEvent::Whitespace(s) | XmlEvent::CData(s) => {
+                self.characters(&s)?;
+            }
+            XmlEvent::Comment(s) => self.comment(s),
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    pub(crate) fn start(&mut self, element: XmlObject) {
+        self.xml_objects.push(Rc::new(RefCell::new(element)));
+    }
+
+    /// close the innermost open element, it is returned if it is the root
+    pub(crate) fn end(&mut self) -> Result<Option<Rc<RefCell<XmlObject>>>, XmlError> {
+        let child = self.xml_objects.pop().ok_or_else(|| XmlError {
+            message: String::from("invalid xml document"),
+        })?;
+        match self.xml_objects.last() {
+            Some(parent) => {
+                parent.borrow_mut().children.push(child);
+                Ok(None)
+            }
+            None => Ok(Some(child)),
+        }
+    }
+
+    pub(crate) fn characters(&mut self, data: &str) -> Result<(), XmlError> {
+        if let Some(parent) = self.xml_objects.last() {
+            parent.borrow_mut().append_character_data(data);
+        } else if !data.trim().is_empty() {
+            return Err(XmlError {
+                message: String::from("invalid xml document"),
+            });
+        }
+        Ok(())
+    }
+
+    pub(crate) fn comment(&mut self, value: String) {
+        // comments outside of the document element are not kept
+        if let Some(parent) = self.xml_objects.last() {
+            parent.borrow_mut().append_comment(value);
+        }
+    }
+}
+
+fn write_character_data<W: Write>(
+    writer: &mut EventWriter<W>,
+    parts: Vec<CharacterData<'_>>,
+) -> Result<(), XmlError> {
+    for part in parts {
+        let event = match part {
+            CharacterData::Text(text) => xml::writer::XmlEvent::characters(text),
+            CharacterData::Comment(comment) => xml::writer::XmlEvent::comment(comment),
+        };
+        writer
+            .write(event)
+            .map_err(|_| XmlError::new(String::from("invalid xml document")))?;
+    }
+    Ok(())
+}
+
 impl fmt::Display for XmlObject {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "local name: {}", self.q_name().local_name())?;
@@ -240,7 +470,7 @@ impl fmt::Display for XmlObject {
         if let Some(namespace_uri) = self.q_name().namespace_uri() {
             write!(f, "namespace uri: {}", namespace_uri)?;
         }
-        writeln!(f, "")?;
+        writeln!(f)?;
         for (key, value) in self.attributes() {
             writeln!(f, "attribute: {}={}", key, value)?;
         }
@@ -18,7 +18,7 @@ impl QName {
 
     #[inline]
     pub fn namespace_uri(&self) -> Option<&str> {
-        self.namespace_uri.as_ref().map(|s| s.as_str())
+        self.namespace_uri.as_deref()
     }
 
     #[inline]
@@ -28,7 +28,7 @@ impl QName {
 
     #[inline]
     pub fn prefix(&self) -> Option<&str> {
-        self.prefix.as_ref().map(|s| s.as_str())
+        self.prefix.as_deref()
     }
 
     #[inline]